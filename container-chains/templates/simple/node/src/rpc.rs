@@ -24,9 +24,14 @@
 pub use sc_rpc::{DenyUnsafe, SubscriptionTaskExecutor};
 
 use {
-    container_chain_template_simple_runtime::{opaque::Block, AccountId, Hash, Index as Nonce},
+    container_chain_template_simple_runtime::{
+        opaque::Block, AccountId, Balance, CarbonCreditsBatchGroup, CarbonCreditsProjectDetail,
+        CarbonCreditsRetirement, DexPendingBuyOrder, DexSellOrder, Hash, Index as Nonce,
+    },
     cumulus_primitives_core::ParaId,
     manual_xcm_rpc::{ManualXcm, ManualXcmApiServer as _},
+    pallet_carbon_credits_rpc::{CarbonCredits, CarbonCreditsApiServer as _},
+    pallet_dex_rpc::{Dex, DexApiServer as _},
     sc_client_api::AuxStore,
     sc_consensus_manual_seal::{
         rpc::{ManualSeal, ManualSealApiServer as _},
@@ -70,6 +75,28 @@ where
         + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: BlockBuilder<Block>,
+    C::Api: pallet_carbon_credits_rpc::CarbonCreditsRuntimeApi<
+        Block,
+        AccountId,
+        u32,
+        u32,
+        u32,
+        u32,
+        CarbonCreditsProjectDetail,
+        CarbonCreditsBatchGroup,
+        CarbonCreditsRetirement,
+    >,
+    C::Api: pallet_dex_rpc::DexRuntimeApi<
+        Block,
+        AccountId,
+        u32,
+        u32,
+        u128,
+        Balance,
+        Balance,
+        DexSellOrder,
+        DexPendingBuyOrder,
+    >,
     P: TransactionPool + Sync + Send + 'static,
 {
     use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -83,7 +110,9 @@ where
         xcm_senders,
     } = deps;
 
-    module.merge(System::new(client, pool, deny_unsafe).into_rpc())?;
+    module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
+    module.merge(CarbonCredits::new(client.clone()).into_rpc())?;
+    module.merge(Dex::new(client).into_rpc())?;
 
     // Manual seal
     if let Some(command_sink) = command_sink {
@@ -71,8 +71,8 @@ use {
     sp_runtime::{
         create_runtime_str, generic, impl_opaque_keys,
         traits::{AccountIdLookup, AccountIdConversion, BlakeTwo256, Block as BlockT, IdentifyAccount, Verify, One},
-        transaction_validity::{TransactionSource, TransactionValidity},
-        ApplyExtrinsicResult, MultiSignature, DispatchResultWithInfo, Percent
+        transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+        ApplyExtrinsicResult, DispatchError, MultiSignature, DispatchResultWithInfo, Percent
     },
     sp_std::prelude::*,
     sp_version::RuntimeVersion,
@@ -82,6 +82,7 @@ use orml_traits::parameter_type_with_key;
 use pallet_acurast_fulfillment_receiver::Fulfillment;
 
 
+pub mod migrations;
 pub mod xcm_config;
 
 // Polkadot imports
@@ -417,6 +418,21 @@ parameter_types! {
 	pub const MaxPayoutsToStore : u32 = 1000;
 	#[derive(Clone, scale_info::TypeInfo, Debug, PartialEq)]
 	pub const MaxOpenOrdersPerUser : u32 = 10;
+	pub DexFeeAccount : AccountId = PalletId(*b"bitg/fee").into_account_truncating();
+	pub const MaxPriceLevels : u32 = 100;
+	pub const MaxOrdersPerPriceLevel : u32 = 50;
+	pub const MaxMatchesPerOrder : u32 = 20;
+}
+
+/// Currencies that can be used to pay for on-chain settled dex orders
+pub struct DexSettlementCurrencies;
+impl Contains<primitives::CurrencyId> for DexSettlementCurrencies {
+	fn contains(currency_id: &primitives::CurrencyId) -> bool {
+		matches!(
+			currency_id,
+			primitives::CurrencyId::USDT | primitives::CurrencyId::USDC | primitives::CurrencyId::DOT
+		)
+	}
 }
 
 impl pallet_dex::Config for Runtime {
@@ -440,9 +456,20 @@ impl pallet_dex::Config for Runtime {
 	type MaxOrderIds = MaxOrderIds;
 	type MaxPayoutsToStore = MaxPayoutsToStore;
 	type MaxOpenOrdersPerUser = MaxOpenOrdersPerUser;
+	type FeeAccount = DexFeeAccount;
+	type SettlementCurrencies = DexSettlementCurrencies;
+	type MaxPriceLevels = MaxPriceLevels;
+	type MaxOrdersPerPriceLevel = MaxOrdersPerPriceLevel;
+	type MaxMatchesPerOrder = MaxMatchesPerOrder;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
 	type WeightInfo = ();
 }
 
+/// Sell order returned by the dex runtime api
+pub type DexSellOrder = pallet_dex::OrderInfoOf<Runtime>;
+/// Pending buy order returned by the dex runtime api
+pub type DexPendingBuyOrder = pallet_dex::PendingBuyOrderOf<Runtime>;
+
 
 // New Pallets impl added to template
 
@@ -501,6 +528,8 @@ impl pallet_assets::Config for Runtime {
 
 parameter_types! {
 	pub const KYCPalletId: PalletId = PalletId(*b"bitg/kyc");
+	pub const KYCApplicationDeposit: Balance = DOLLARS;
+	pub const KYCMembershipPeriod: BlockNumber = 365 * DAYS;
 }
 
 impl pallet_kyc::Config for Runtime {
@@ -509,8 +538,9 @@ impl pallet_kyc::Config for Runtime {
 	type Currency = Balances;
 	type PalletId = KYCPalletId;
 	type MaxAuthorizedAccountCount = ConstU32<100>;
-    type MaxStringLength = ConstU32<512>;
     type MaxQueueLength = ConstU32<100>;
+	type ApplicationDeposit = KYCApplicationDeposit;
+	type MembershipPeriod = KYCMembershipPeriod;
 	type WeightInfo = ();
 }
 
@@ -527,6 +557,8 @@ parameter_types! {
   #[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
   pub const MaxGroupSize : u32 = 10;
   pub const MaxCoordinatesLength : u32 = 10;
+  pub const MinKycLevelToCreateProject : primitives::UserLevel = primitives::UserLevel::KYCLevel1;
+  pub const MinKycLevelToRetire : primitives::UserLevel = primitives::UserLevel::KYCLevel1;
 }
 
 impl pallet_carbon_credits::Config for Runtime {
@@ -539,7 +571,10 @@ impl pallet_carbon_credits::Config for Runtime {
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type ItemId = u32;
 	type KYCProvider = KYCPallet;
+	type MinKycLevelToCreate = MinKycLevelToCreateProject;
+	type MinKycLevelToRetire = MinKycLevelToRetire;
 	type MarketplaceEscrow = MarketplaceEscrowAccount;
+	type Marketplace = Dex;
 	type MaxAuthorizedAccountCount = MaxAuthorizedAccountCount;
 	type MaxDocumentCount = MaxDocumentCount;
 	type MaxGroupSize = MaxGroupSize;
@@ -548,12 +583,19 @@ impl pallet_carbon_credits::Config for Runtime {
 	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
 	type MaxShortStringLength = MaxShortStringLength;
 	type MinProjectId = MinProjectId;
-	type NFTHandler = Uniques;
+	type NFTHandler = pallet_carbon_credits::UniquesCertificateHandler<Runtime>;
 	type PalletId = CarbonCreditsPalletId;
 	type MaxCoordinatesLength = MaxCoordinatesLength;
 	type WeightInfo = ();
 }
 
+/// Project details returned by the carbon credits runtime api
+pub type CarbonCreditsProjectDetail = pallet_carbon_credits::ProjectDetail<Runtime>;
+/// Batch group returned by the carbon credits runtime api
+pub type CarbonCreditsBatchGroup = pallet_carbon_credits::BatchGroupOf<Runtime>;
+/// Retirement record returned by the carbon credits runtime api
+pub type CarbonCreditsRetirement = pallet_carbon_credits::RetiredCarbonCreditsData<Runtime>;
+
 parameter_types! {
 	pub const CarbonCreditsPoolPalletId: PalletId = PalletId(*b"bit/vcup");
 	pub const MaxAssetSymbolLength : u32 = 10;
@@ -561,6 +603,8 @@ parameter_types! {
 	pub const MaxProjectIdList : u32 = 100;
 	pub const MaxRegistryListCount : u32 = 10;
 	pub const MinPoolId : u32 = 10000;
+	pub const MinKycLevelToDeposit : primitives::UserLevel = primitives::UserLevel::KYCLevel1;
+	pub const MaxRedemptionFee : Percent = Percent::from_percent(10);
 }
 
 impl pallet_carbon_credits_pool::Config for Runtime {
@@ -572,6 +616,8 @@ impl pallet_carbon_credits_pool::Config for Runtime {
 	type MaxProjectIdList = MaxProjectIdList;
 	type MaxRegistryListCount = MaxRegistryListCount;
 	type MinPoolId = MinPoolId;
+	type MinKycLevelToDeposit = MinKycLevelToDeposit;
+	type MaxRedemptionFee = MaxRedemptionFee;
 	type PalletId = CarbonCreditsPoolPalletId;
 	type PoolId = u32;
 	type WeightInfo = ();
@@ -593,7 +639,8 @@ impl pallet_uniques::Config for Runtime {
 	type Locker = ();
 	type MetadataDepositBase = ConstU128<1>;
 	type StringLimit = ConstU32<50>;
-	type ValueLimit = ConstU32<50>;
+	// retirement certificates store batch uuids and retirement reasons as attributes
+	type ValueLimit = MaxShortStringLength;
     //type Helper = ();
 	type WeightInfo = ();
 }
@@ -603,6 +650,8 @@ parameter_types! {
 	pub const MaxKeyLength : u32 = 1024;
 	pub const MaxValueLength : u32 = 64000;
 	pub const DepositPerByte : Balance = DOLLARS / 10;
+	pub const MaxGrantsPerAccount : u32 = 20;
+	pub const MaxEntriesPerCall : u32 = 100;
 }
 
 impl pallet_general_storage::Config for Runtime {
@@ -611,6 +660,9 @@ impl pallet_general_storage::Config for Runtime {
 	type MaxKeyLength = MaxKeyLength;
 	type MaxValueLength = MaxValueLength;
 	type DepositPerByte = DepositPerByte;
+	type MaxGrantsPerAccount = MaxGrantsPerAccount;
+	type MaxEntriesPerCall = MaxEntriesPerCall;
+	type WeightInfo = pallet_general_storage::weights::SubstrateWeight<Runtime>;
 }
 
 // orml pallets
@@ -648,6 +700,9 @@ parameter_types! {
     pub const MaxVotesPerBlock: u32 = 16;
     pub const VoteDuration: BlockNumber = 200;
     pub const MaxPPC: u32 = 100;
+    pub const MaxProposalLength: u32 = 1024;
+    pub MaxProposalWeight: Weight = Perbill::from_percent(20) * RuntimeBlockWeights::get().max_block;
+    pub const MaxVoteLocks: u32 = 16;
 }
 
 impl pallet_foresta_collectives::Config for Runtime {
@@ -664,6 +719,13 @@ impl pallet_foresta_collectives::Config for Runtime {
     type MaxProjectsPerCollective = MaxPPC;
     type VotingDuration = VoteDuration;
     type ForceOrigin = EnsureRoot<AccountId>;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type MaxProposalLength = MaxProposalLength;
+    type MaxProposalWeight = MaxProposalWeight;
+    type MaxVoteLocks = MaxVoteLocks;
+    type NativeCurrency = Balances;
+    type VoteWeight = pallet_foresta_collectives::NativeBalanceAndRetirements<Runtime>;
 }
 
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
@@ -671,8 +733,14 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 // Configure the pallet template in pallets/template.
 parameter_types! {
 	pub const GracePeriod: BlockNumber = 3;
-	pub const MaxP: u32 = 64;
+	pub const MaxFeeds: u32 = 32;
+	pub const MaxUrlLength: u32 = 256;
+	pub const MaxJsonPathLength: u32 = 64;
 	pub const MaxA: u32 = 64;
+	pub const PriceSubmission: pallet_foresta_ocw::SubmissionMode =
+		pallet_foresta_ocw::SubmissionMode::UnsignedSignedPayload;
+	pub const OcwUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	pub const OcwMinSubmissions: u32 = 3;
 }
 
 //Pallet OCW
@@ -680,8 +748,15 @@ impl pallet_foresta_ocw::Config for Runtime {
 	type AuthorityId = pallet_foresta_ocw::crypto::TestAuthId;
 	type RuntimeEvent = RuntimeEvent;
 	type GracePeriod = GracePeriod;
-	type MaxPrices = MaxP;
+	type FeedId = u32;
+	type PriceSubmission = PriceSubmission;
+	type UnsignedPriority = OcwUnsignedPriority;
+	type MaxFeeds = MaxFeeds;
+	type MaxUrlLength = MaxUrlLength;
+	type MaxJsonPathLength = MaxJsonPathLength;
 	type MaxAuthorities = MaxA;
+	type MinSubmissions = OcwMinSubmissions;
+	type WeightInfo = pallet_foresta_ocw::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_membership::Config for Runtime {
@@ -917,7 +992,7 @@ impl xcm_primitives::PauseXcmExecution for XcmExecutionManager {
 
 impl pallet_migrations::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type MigrationsList = ();
+    type MigrationsList = (migrations::TemplateMigrations<Runtime>,);
     type XcmExecutionManager = XcmExecutionManager;
 }
 
@@ -1029,16 +1104,154 @@ impl OffchainWorker<BlockNumber> for MaintenanceHooks {
 impl pallet_acurast_fulfillment_receiver::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnFulfillment = OnAcurastFulfillment;
-    type WeightInfo = ();
+    type WeightInfo = AcurastFulfillmentWeight;
+}
+
+/// Charges the largest fulfillment the Foresta handlers can process on top of the receiver weight
+pub struct AcurastFulfillmentWeight;
+impl pallet_acurast_fulfillment_receiver::traits::WeightInfo for AcurastFulfillmentWeight {
+	fn fulfill() -> Weight {
+		<() as pallet_acurast_fulfillment_receiver::traits::WeightInfo>::fulfill()
+			.saturating_add(ForestaFulfillment::max_fulfillment_weight())
+	}
 }
 
 pub struct OnAcurastFulfillment;
 impl pallet_acurast_fulfillment_receiver::traits::OnFulfillment<Runtime> for OnAcurastFulfillment {
 	fn on_fulfillment(
-		_from: <Runtime as frame_system::Config>::AccountId,
-		_fulfillment: Fulfillment,
+		from: <Runtime as frame_system::Config>::AccountId,
+		fulfillment: Fulfillment,
 	) -> DispatchResultWithInfo<PostDispatchInfo> {
-		Ok(().into())
+		// failures are reported as events by the fulfillment pallet, the weight they did not use is
+		// refunded
+		let weight = ForestaFulfillment::handle_fulfillment(from, &fulfillment.payload);
+		Ok(Some(
+			<() as pallet_acurast_fulfillment_receiver::traits::WeightInfo>::fulfill()
+				.saturating_add(weight),
+		)
+		.into())
+	}
+}
+
+parameter_types! {
+	pub const MaxFulfillmentReadingLength: u32 = 4096;
+	pub const MaxFulfillmentProcessors: u32 = 20;
+	/// Prefix of the general storage keys of the MRV readings
+	pub const MrvKeyPrefix: &'static [u8] = b"mrv/";
+}
+
+impl pallet_foresta_fulfillment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type FeedId = u32;
+	type ProjectId = u32;
+	type MaxPrices = MaxFeeds;
+	type MaxReadingLength = MaxFulfillmentReadingLength;
+	type MaxProofLength = MaxTxHashLen;
+	type MaxProcessors = MaxFulfillmentProcessors;
+	type PriceHandler = FulfilledPrices;
+	type MrvHandler = FulfilledMrvReadings;
+	type PaymentHandler = FulfilledPaymentProofs;
+	type WeightInfo = pallet_foresta_fulfillment::weights::SubstrateWeight<Runtime>;
+}
+
+/// Submits the fulfilled prices to the oracle, the processor must be an oracle authority
+pub struct FulfilledPrices;
+impl FulfilledPrices {
+	/// The oracle calls are not benchmarked, the weight of `count` prices counts their storage
+	/// accesses: the authorities, then the feed, authorities, submissions and aggregated price of
+	/// every price
+	fn weight(count: u32) -> Weight {
+		let count = count as u64;
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(1 + 3 * count, 2 * count)
+	}
+}
+impl pallet_foresta_fulfillment::PriceUpdateHandler<AccountId, u32> for FulfilledPrices {
+	fn handle_price_update(
+		processor: &AccountId,
+		prices: Vec<(u32, u128)>,
+	) -> Result<Weight, DispatchError> {
+		let weight = Self::weight(prices.len() as u32);
+		let prices =
+			prices.try_into().map_err(|_| pallet_foresta_ocw::Error::<Runtime>::TooManyFeeds)?;
+		ForestaOCW::submit_prices(RuntimeOrigin::signed(processor.clone()), prices)
+			.map(|_| weight)
+			.map_err(|e| e.error)
+	}
+
+	fn max_weight() -> Weight {
+		Self::weight(MaxFeeds::get())
+	}
+}
+
+/// Stores the fulfilled MRV readings in the general storage namespace of the project originator,
+/// under the key `mrv/{project_id}/{timestamp}` with both numbers big endian encoded so that the
+/// readings of a project are sorted by time. The originator grants the processor write access to
+/// the keys of its project with `grant_access`, the processor pays the deposit.
+pub struct FulfilledMrvReadings;
+impl FulfilledMrvReadings {
+	/// The weight declared by `store_data_for` and the read of the project
+	fn weight() -> Weight {
+		<<Runtime as pallet_general_storage::Config>::WeightInfo as pallet_general_storage::WeightInfo>::store_data_for()
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+	}
+}
+impl pallet_foresta_fulfillment::MrvReadingHandler<AccountId, u32> for FulfilledMrvReadings {
+	fn handle_mrv_reading(
+		processor: &AccountId,
+		project_id: u32,
+		timestamp: u64,
+		reading: Vec<u8>,
+	) -> Result<Weight, DispatchError> {
+		let project = CarbonCredits::projects(project_id)
+			.ok_or(pallet_carbon_credits::Error::<Runtime>::ProjectNotFound)?;
+		let mut key = MrvKeyPrefix::get().to_vec();
+		key.extend_from_slice(&project_id.to_be_bytes());
+		key.push(b'/');
+		key.extend_from_slice(&timestamp.to_be_bytes());
+		GeneralStorage::store_data_for(
+			RuntimeOrigin::signed(processor.clone()),
+			project.originator,
+			key.try_into().map_err(|_| DispatchError::Other("MRV key too long"))?,
+			reading.try_into().map_err(|_| DispatchError::Other("MRV reading too long"))?,
+		)?;
+		Ok(Self::weight())
+	}
+
+	fn max_weight() -> Weight {
+		Self::weight()
+	}
+}
+
+/// Validates the fulfilled payments of dex buy orders, the processor must be a dex validator
+pub struct FulfilledPaymentProofs;
+impl FulfilledPaymentProofs {
+	/// The weight declared by `validate_buy_order`
+	fn weight() -> Weight {
+		<<Runtime as pallet_dex::Config>::WeightInfo as pallet_dex::WeightInfo>::buy_order(
+			MaxRoyaltyRecipients::get(),
+		)
+	}
+}
+impl pallet_foresta_fulfillment::PaymentProofHandler<AccountId> for FulfilledPaymentProofs {
+	fn handle_payment_proof(
+		processor: &AccountId,
+		order_id: u128,
+		chain_id: u32,
+		tx_proof: Vec<u8>,
+	) -> Result<Weight, DispatchError> {
+		Dex::validate_buy_order(
+			RuntimeOrigin::signed(processor.clone()),
+			order_id,
+			chain_id,
+			tx_proof.try_into().map_err(|_| DispatchError::Other("Payment proof too long"))?,
+			None,
+		)?;
+		Ok(Self::weight())
+	}
+
+	fn max_weight() -> Weight {
+		Self::weight()
 	}
 }
 
@@ -1156,6 +1369,7 @@ construct_runtime!(
         // Governance
         ForestaCollectives: pallet_foresta_collectives = 91,
         ForestaOCW: pallet_foresta_ocw = 92,
+        ForestaFulfillment: pallet_foresta_fulfillment = 93,
 
     }
 );
@@ -1244,6 +1458,69 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_carbon_credits_runtime_api::CarbonCreditsApi<
+        Block,
+        AccountId,
+        u32,
+        u32,
+        u32,
+        u32,
+        CarbonCreditsProjectDetail,
+        CarbonCreditsBatchGroup,
+        CarbonCreditsRetirement,
+    > for Runtime {
+        fn project_details(project_id: u32) -> Option<CarbonCreditsProjectDetail> {
+            CarbonCredits::get_project_details(project_id)
+        }
+
+        fn project_groups(project_id: u32) -> Vec<(u32, CarbonCreditsBatchGroup)> {
+            CarbonCredits::get_project_groups(project_id)
+        }
+
+        fn asset_project(asset_id: u32) -> Option<(u32, u32)> {
+            CarbonCredits::get_asset_project(asset_id)
+        }
+
+        fn retirements_by_account(account: AccountId) -> Vec<(u32, u32, CarbonCreditsRetirement)> {
+            CarbonCredits::get_retirements_by_account(&account)
+        }
+
+        fn retirement(asset_id: u32, item_id: u32) -> Option<CarbonCreditsRetirement> {
+            CarbonCredits::get_retirement(asset_id, item_id)
+        }
+    }
+
+    impl pallet_dex_runtime_api::DexApi<
+        Block,
+        AccountId,
+        u32,
+        u32,
+        pallet_dex::OrderId,
+        u128,
+        u128,
+        DexSellOrder,
+        DexPendingBuyOrder,
+    > for Runtime {
+        fn sell_orders(asset_id: Option<u32>, project_id: Option<u32>) -> Vec<(pallet_dex::OrderId, DexSellOrder)> {
+            Dex::open_sell_orders(asset_id, project_id)
+        }
+
+        fn pending_buy_orders(buyer: Option<AccountId>) -> Vec<DexPendingBuyOrder> {
+            Dex::pending_buy_orders(buyer)
+        }
+
+        fn quote_buy_order(
+            order_id: pallet_dex::OrderId,
+            units: u128,
+        ) -> Result<(u128, u128, u128), sp_runtime::DispatchError> {
+            Dex::quote_buy_order(order_id, units)
+        }
+
+        fn seller_receivables(seller: AccountId) -> u128 {
+            Dex::seller_receivables(seller).unwrap_or_default()
+        }
+    }
+
     impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
         fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
             ParachainSystem::collect_collation_info(header)
@@ -1273,6 +1550,9 @@ impl_runtime_apis! {
                 PalletAuthoritiesNotingBench::<Runtime>
             );
             list_benchmark!(list, extra, pallet_foresta_collectives, ForestaCollectives);
+            list_benchmark!(list, extra, pallet_general_storage, GeneralStorage);
+            list_benchmark!(list, extra, pallet_foresta_ocw, ForestaOCW);
+            list_benchmark!(list, extra, pallet_foresta_fulfillment, ForestaFulfillment);
 
             let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -1327,6 +1607,9 @@ impl_runtime_apis! {
                 PalletAuthoritiesNotingBench::<Runtime>
             );
             add_benchmark!(params, batches, pallet_foresta_collectives, ForestaCollectives);
+            add_benchmark!(params, batches, pallet_general_storage, GeneralStorage);
+            add_benchmark!(params, batches, pallet_foresta_ocw, ForestaOCW);
+            add_benchmark!(params, batches, pallet_foresta_fulfillment, ForestaFulfillment);
             if batches.is_empty() {
                 return Err("Benchmark not found for this pallet.".into());
             }
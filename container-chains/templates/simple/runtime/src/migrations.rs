@@ -0,0 +1,207 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! # Migrations
+//!
+//! This module acts as a registry where each migration is defined. Each migration should implement
+//! the "Migration" trait declared in the pallet-migrations crate.
+
+use {
+    frame_support::{traits::OnRuntimeUpgrade, weights::Weight},
+    pallet_migrations::{GetMigrations, Migration},
+    sp_std::{marker::PhantomData, prelude::*},
+};
+
+pub struct MigrateGeneralStorageDeposits<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateGeneralStorageDeposits<T>
+where
+    T: pallet_general_storage::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateGeneralStorageDeposits"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Start migrating the deposits of the existing entries, continued on idle
+        pallet_general_storage::migration::v1::MigrateToV1::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateKycApplicationsAndExpiries<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateKycApplicationsAndExpiries<T>
+where
+    T: pallet_kyc::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateKycApplicationsAndExpiries"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Drop the plaintext applications and schedule the member expiries
+        pallet_kyc::migration::v5::MigrateToV5::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateDexOrderBook<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateDexOrderBook<T>
+where
+    T: pallet_dex::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateDexOrderBook"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Index the open sell orders into the order book so they are matched with bids
+        pallet_dex::migration::v1::MigrateToV1::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateCarbonCreditsPoolConfig<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateCarbonCreditsPoolConfig<T>
+where
+    T: pallet_carbon_credits_pool::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateCarbonCreditsPoolConfig"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Add the issuance year, project type and SDG rules to the existing pool configs
+        pallet_carbon_credits_pool::migration::v1::MigrateToV1::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateCarbonCreditsRetirements<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateCarbonCreditsRetirements<T>
+where
+    T: pallet_carbon_credits::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateCarbonCreditsRetirements"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Start adding the beneficiary to the retirements and making the certificate collections
+        // free holding, continued on idle
+        pallet_carbon_credits::migration::v4::MigrateToV4::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateForestaCollectivesVotes<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateForestaCollectivesVotes<T>
+where
+    T: pallet_foresta_collectives::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateForestaCollectivesVotes"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Add the weight of the votes cast to the existing votes
+        pallet_foresta_collectives::migration::v1::MigrateToV1::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateForestaCollectivesVoteRecords<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateForestaCollectivesVoteRecords<T>
+where
+    T: pallet_foresta_collectives::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateForestaCollectivesVoteRecords"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Key the vote records by vote so they are cleared once the vote ends
+        pallet_foresta_collectives::migration::v2::MigrateToV2::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateForestaCollectivesManagers<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateForestaCollectivesManagers<T>
+where
+    T: pallet_foresta_collectives::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateForestaCollectivesManagers"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Count the collectives of each manager so they are deauthorized once they manage none
+        pallet_foresta_collectives::migration::v3::MigrateToV3::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct MigrateForestaOcwPrices<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateForestaOcwPrices<T>
+where
+    T: pallet_foresta_ocw::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateForestaOcwPrices"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        // Remove the BTC prices replaced by the keyed price feeds
+        pallet_foresta_ocw::migration::v1::MigrateToV1::<T>::on_runtime_upgrade()
+    }
+}
+
+pub struct TemplateMigrations<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> GetMigrations for TemplateMigrations<Runtime>
+where
+    Runtime: pallet_general_storage::Config,
+    Runtime: pallet_kyc::Config,
+    Runtime: pallet_dex::Config,
+    Runtime: pallet_carbon_credits_pool::Config,
+    Runtime: pallet_carbon_credits::Config
+        + pallet_uniques::Config<CollectionId = <Runtime as pallet_carbon_credits::Config>::AssetId>,
+    Runtime: pallet_foresta_collectives::Config,
+    Runtime: pallet_foresta_ocw::Config,
+{
+    fn get_migrations() -> Vec<Box<dyn Migration>> {
+        let migrate_general_storage_deposits =
+            MigrateGeneralStorageDeposits::<Runtime>(Default::default());
+        let migrate_kyc_applications_and_expiries =
+            MigrateKycApplicationsAndExpiries::<Runtime>(Default::default());
+        let migrate_dex_order_book = MigrateDexOrderBook::<Runtime>(Default::default());
+        let migrate_carbon_credits_pool_config =
+            MigrateCarbonCreditsPoolConfig::<Runtime>(Default::default());
+        let migrate_carbon_credits_retirements =
+            MigrateCarbonCreditsRetirements::<Runtime>(Default::default());
+        let migrate_foresta_collectives_votes =
+            MigrateForestaCollectivesVotes::<Runtime>(Default::default());
+        let migrate_foresta_collectives_vote_records =
+            MigrateForestaCollectivesVoteRecords::<Runtime>(Default::default());
+        let migrate_foresta_collectives_managers =
+            MigrateForestaCollectivesManagers::<Runtime>(Default::default());
+        let migrate_foresta_ocw_prices = MigrateForestaOcwPrices::<Runtime>(Default::default());
+
+        vec![
+            Box::new(migrate_general_storage_deposits),
+            Box::new(migrate_kyc_applications_and_expiries),
+            Box::new(migrate_dex_order_book),
+            Box::new(migrate_carbon_credits_pool_config),
+            Box::new(migrate_carbon_credits_retirements),
+            Box::new(migrate_foresta_collectives_votes),
+            Box::new(migrate_foresta_collectives_vote_records),
+            Box::new(migrate_foresta_collectives_managers),
+            Box::new(migrate_foresta_ocw_prices),
+        ]
+    }
+}
@@ -91,6 +91,8 @@ pub fn development_config(
                         min_orchestrator_collators: 1u32,
                         max_orchestrator_collators: 1u32,
                         collators_per_container: 2u32,
+                        min_collators_per_container: 1u32,
+                        max_collators_per_container: 5u32,
                         full_rotation_period: 0,
                     },
                     ..Default::default()
@@ -156,6 +158,8 @@ pub fn local_flashbox_config(
                         min_orchestrator_collators: 2u32,
                         max_orchestrator_collators: 5u32,
                         collators_per_container: 2u32,
+                        min_collators_per_container: 1u32,
+                        max_collators_per_container: 5u32,
                         full_rotation_period: 0,
                     },
                     ..Default::default()
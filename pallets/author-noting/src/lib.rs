@@ -27,6 +27,9 @@
 //!  
 //! Using those two requirements we can select who the author was based on the collators assigned
 //! to that containerChain, by simply assigning the slot position.
+//!
+//! The hash of the current code of each containerChain is also read from the relayChain, when the
+//! proof includes it, and passed to the ContainerChainCodeHook.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -34,7 +37,7 @@ pub use dp_chain_state_snapshot::*;
 use {
     cumulus_pallet_parachain_system::RelaychainStateProvider,
     cumulus_primitives_core::{
-        relay_chain::{BlakeTwo256, BlockNumber, HeadData},
+        relay_chain::{BlakeTwo256, BlockNumber, HeadData, ValidationCodeHash},
         ParaId,
     },
     dp_core::well_known_keys::PARAS_HEADS_INDEX,
@@ -44,8 +47,11 @@ use {
     sp_consensus_aura::{inherents::InherentType, AURA_ENGINE_ID},
     sp_inherents::{InherentIdentifier, IsFatalError},
     sp_runtime::{traits::Header, DigestItem, DispatchResult, RuntimeString},
-    tp_author_noting_inherent::INHERENT_IDENTIFIER,
-    tp_traits::{AuthorNotingHook, GetContainerChainAuthor, GetCurrentContainerChains},
+    tp_author_noting_inherent::{para_id_current_code_hash, INHERENT_IDENTIFIER},
+    tp_traits::{
+        AuthorNotingHook, ContainerChainCodeHook, GetContainerChainAuthor,
+        GetCurrentContainerChains, GetLatestAuthor,
+    },
 };
 
 #[cfg(test)]
@@ -86,6 +92,12 @@ pub mod pallet {
         /// Typically, this can be a hook to reward block authors.
         type AuthorNotingHook: AuthorNotingHook<Self::AccountId>;
 
+        /// An entry-point for higher-level logic to react to the current code of container chains.
+        ///
+        /// Typically, this can be a hook to clear the code announced for a container chain once
+        /// it has upgraded to it.
+        type ContainerChainCodeHook: ContainerChainCodeHook;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -205,6 +217,14 @@ pub mod pallet {
                             u32::from(para_id)
                         ),
                     }
+
+                    if let Some(code_hash) =
+                        Self::fetch_code_hash_from_proof(&relay_storage_rooted_proof, para_id)
+                    {
+                        total_weight = total_weight.saturating_add(
+                            T::ContainerChainCodeHook::on_container_code_noted(para_id, &code_hash),
+                        );
+                    }
                 }
             }
 
@@ -360,6 +380,18 @@ impl<T: Config> Pallet<T> {
         Err(first_error.unwrap_or(Error::<T>::AuraDigestFirstItem))
     }
 
+    /// Fetch the hash of the current code of a container chain from a proof.
+    /// Proofs that do not include it, such as the mocked ones, are ignored
+    fn fetch_code_hash_from_proof(
+        relay_state_proof: &GenericStateProof<cumulus_primitives_core::relay_chain::Block>,
+        para_id: ParaId,
+    ) -> Option<ValidationCodeHash> {
+        relay_state_proof
+            .read_optional_entry::<ValidationCodeHash>(&para_id_current_code_hash(para_id))
+            .ok()
+            .flatten()
+    }
+
     /// Get block author from aura digest
     fn author_from_log(
         aura_digest: &DigestItem,
@@ -390,6 +422,12 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+impl<T: Config> GetLatestAuthor<T::AccountId> for Pallet<T> {
+    fn latest_author(para_id: ParaId) -> Option<T::AccountId> {
+        LatestAuthor::<T>::get(para_id).map(|info| info.author)
+    }
+}
+
 #[derive(Encode)]
 #[cfg_attr(feature = "std", derive(Debug, Decode))]
 pub enum InherentError {
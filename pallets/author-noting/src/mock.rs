@@ -186,6 +186,7 @@ impl Config for Test {
     type SelfParaId = ParachainId;
     type ContainerChains = MockContainerChainGetter;
     type AuthorNotingHook = ();
+    type ContainerChainCodeHook = ();
     type RelayChainStateProvider = MockRelayStateProvider;
 }
 
@@ -128,7 +128,7 @@ benchmarks! {
 		pallet_carbon_credits::Pallet::<T>::force_add_authorized_account(RawOrigin::Root.into(), caller.clone().into())?;
 		pallet_carbon_credits::Pallet::<T>::create(RawOrigin::Signed(caller.clone()).into(), creation_params)?;
 		pallet_carbon_credits::Pallet::<T>::approve_project(RawOrigin::Signed(caller.clone()).into(), project_id, true)?;
-		pallet_carbon_credits::Pallet::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, 100_u32.into(), false)?;
+		pallet_carbon_credits::Pallet::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, 100_u32.into(), false, None)?;
 
 		// create a pool
 		let pool_id = 10_001_u32.into();
@@ -153,7 +153,7 @@ benchmarks! {
 		pallet_carbon_credits::Pallet::<T>::force_add_authorized_account(RawOrigin::Root.into(), caller.clone().into())?;
 		pallet_carbon_credits::Pallet::<T>::create(RawOrigin::Signed(caller.clone()).into(), creation_params)?;
 		pallet_carbon_credits::Pallet::<T>::approve_project(RawOrigin::Signed(caller.clone()).into(), project_id, true)?;
-		pallet_carbon_credits::Pallet::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, 100_u32.into(), false)?;
+		pallet_carbon_credits::Pallet::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, 100_u32.into(), false, None)?;
 
 		// create a pool and deposit tokens
 		let pool_id = 10_001_u32.into();
@@ -165,5 +165,32 @@ benchmarks! {
 		assert_last_event::<T>(Event::Retired { pool_id, who : caller, amount : 1_u32.into() }.into());
 	}
 
+	redeem {
+		let caller : T::AccountId = account("account_id", 0, 0);
+		let owner : T::AccountId = account("owner", 0, 1);
+		// create a project and mint tokens
+		let project_id : T::ProjectId = 0_u32.into();
+		let group_id  : T::GroupId = 0_u32.into();
+		let asset_id : T::AssetId = 0_u32.into();
+		let creation_params = get_default_creation_params::<T>();
+		let caller_lookup = <T::Lookup as sp_runtime::traits::StaticLookup>::unlookup(caller.clone());
+		pallet_membership::Pallet::<T>::add_member(RawOrigin::Root.into(), caller_lookup)?;
+
+		pallet_carbon_credits::Pallet::<T>::force_add_authorized_account(RawOrigin::Root.into(), caller.clone().into())?;
+		pallet_carbon_credits::Pallet::<T>::create(RawOrigin::Signed(caller.clone()).into(), creation_params)?;
+		pallet_carbon_credits::Pallet::<T>::approve_project(RawOrigin::Signed(caller.clone()).into(), project_id, true)?;
+		pallet_carbon_credits::Pallet::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, 100_u32.into(), false, None)?;
+
+		// create a pool with a redemption fee and deposit tokens
+		let pool_id = 10_001_u32.into();
+		let asset_symbol =  "pool_xyz".as_bytes().to_vec().try_into().unwrap();
+		CarbonCreditPools::<T>::create(RawOrigin::Signed(caller.clone()).into(), pool_id, owner, Default::default(), None, asset_symbol).unwrap();
+		CarbonCreditPools::<T>::force_set_redemption_fee(RawOrigin::Root.into(), pool_id, T::MaxRedemptionFee::get()).unwrap();
+		CarbonCreditPools::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), pool_id, asset_id, 10_u32.into()).unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), pool_id, asset_id, 2020, 5_u32.into())
+	verify {
+		assert!(CarbonCreditPools::<T>::pools(pool_id).unwrap().credits.get(&2020).is_some());
+	}
+
 	impl_benchmark_test_suite!(CarbonCreditPools, crate::mock::new_test_ext(), crate::mock::Test);
 }
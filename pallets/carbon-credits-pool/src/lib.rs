@@ -12,21 +12,29 @@
 //!
 //! ### Pool Config
 //! A pool creator can setup configs, these configs determine which type of tokens are accepted into
-//! the pool. Currently the owner can setup two configs for a pool 1. Registry List : This limits
-//! the pool to accept CarbonCredits's issued by the given registry's only 2. Project List : This
-//! limits the pool to accepts CarbonCredits's issued by specific project's only
+//! the pool. Currently the owner can setup the following configs for a pool 1. Registry List : This
+//! limits the pool to accept CarbonCredits's issued by the given registry's only 2. Project List :
+//! This limits the pool to accepts CarbonCredits's issued by specific project's only 3. Issuance
+//! Years : This limits the pool to accept CarbonCredits's issued within the given years only 4.
+//! Project Types : This limits the pool to accept CarbonCredits's of the given project types only
+//! 5. Required SDGs : This limits the pool to accept CarbonCredits's of projects addressing all the
+//! given SDGs only
 //!
 //! ## Interface
 //!
 //! ### Permissionless Functions
 //!
 //! * `create`: Creates a new pool with given config
-//! * `deposit`: Deposit some CarbonCredits tokens to generate pool tokens
+//! * `deposit`: Deposit some CarbonCredits tokens to generate pool tokens, the depositor must meet
+//!   the `MinKycLevelToDeposit` KYC level
 //! * `retire`: Burn a specified amount of pool tokens
+//! * `redeem`: Burn pool tokens and receive the CarbonCredits tokens of a chosen project and
+//!   issuance year from the pool, minus the redemption fee of the pool
 //!
 //! ### Permissioned Functions
 //!
 //! * `force_set_pool_storage`: Set the pool storage
+//! * `force_set_redemption_fee`: Set the redemption fee of a pool
 #![cfg_attr(not(feature = "std"), no_std)]
 pub use pallet::*;
 
@@ -39,6 +47,8 @@ mod tests;
 // #[cfg(feature = "runtime-benchmarks")]
 // mod benchmarking;
 
+pub mod migration;
+
 mod types;
 pub use types::*;
 
@@ -55,12 +65,16 @@ pub mod pallet {
 			fungibles::{metadata::Mutate as MetadataMutate, Create, Mutate},
 			Fortitude::Polite,
 			Precision::Exact,
-			Preservation::Protect,
+			Preservation::{Expendable, Protect},
 		},
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{AccountIdConversion, CheckedAdd, CheckedSub, Zero};
+	use primitives::{IssuanceYear, UserLevel};
+	use sp_runtime::{
+		traits::{AccountIdConversion, CheckedAdd, CheckedSub, Zero},
+		Percent,
+	};
 	use sp_std::convert::{TryFrom, TryInto};
 
 	use super::*;
@@ -100,6 +114,12 @@ pub mod pallet {
 		type MaxAssetSymbolLength: Get<u32>;
 		/// Min permitted value for PoolId
 		type MinPoolId: Get<Self::PoolId>;
+		/// Minimum KYC level required to deposit credits into a pool
+		#[pallet::constant]
+		type MinKycLevelToDeposit: Get<UserLevel>;
+		/// The maximum redemption fee that can be set for a pool
+		#[pallet::constant]
+		type MaxRedemptionFee: Get<Percent>;
 		/// The CarbonCredits-pools pallet id
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -107,8 +127,11 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
-	#[pallet::pallet]
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -119,6 +142,12 @@ pub mod pallet {
 	#[pallet::getter(fn pool_credits)]
 	pub type PoolCredits<T: Config> = StorageMap<_, Blake2_128Concat, T::PoolId, PoolOf<T>>;
 
+	// The share of redeemed pool tokens paid to the pool admin
+	#[pallet::storage]
+	#[pallet::getter(fn redemption_fee)]
+	pub type RedemptionFees<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, Percent, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -128,6 +157,17 @@ pub mod pallet {
 		Deposit { who: T::AccountId, pool_id: T::PoolId, asset_id: T::AssetId, amount: T::Balance },
 		/// Pool tokens were retired
 		Retired { who: T::AccountId, pool_id: T::PoolId, amount: T::Balance },
+		/// Pool tokens were redeemed for CarbonCredits tokens in the pool
+		Redeemed {
+			who: T::AccountId,
+			pool_id: T::PoolId,
+			asset_id: T::AssetId,
+			issuance_year: IssuanceYear,
+			amount: T::Balance,
+			fee: T::Balance,
+		},
+		/// The redemption fee of a pool was set
+		RedemptionFeeSet { pool_id: T::PoolId, fee: Percent },
 	}
 
 	// Errors inform users that something went wrong.
@@ -153,6 +193,20 @@ pub mod pallet {
 		ProjectIssuanceYearError,
 		/// User entered an invalid amount
 		InvalidAmount,
+		/// The minimum issuance year of the pool config is after the maximum
+		InvalidIssuanceYearRange,
+		/// The pool does not allow credits of this issuance year
+		IssuanceYearNotPermitted,
+		/// The pool does not allow this project type
+		ProjectTypeNotPermitted,
+		/// The project does not address the SDGs required by the pool
+		RequiredSdgMissing,
+		/// The pool does not hold credits of the asset for the issuance year
+		CreditsNotInPool,
+		/// The pool does not hold enough credits of the asset for the issuance year
+		InsufficientPoolCredits,
+		/// Cannot set more than the maximum redemption fee
+		CannotSetMoreThanMaxRedemptionFee,
 	}
 
 	#[pallet::call]
@@ -180,6 +234,12 @@ pub mod pallet {
 
 			ensure!(!Pools::<T>::contains_key(id), Error::<T>::PoolIdInUse);
 
+			if let (Some(min_year), Some(max_year)) =
+				(config.min_issuance_year, config.max_issuance_year)
+			{
+				ensure!(min_year <= max_year, Error::<T>::InvalidIssuanceYearRange);
+			}
+
 			// use default limit if limit not given by project owner
 			let actual_max_limit = match max_limit {
 				Some(limit) => {
@@ -241,6 +301,10 @@ pub mod pallet {
 			amount: T::Balance,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			pallet_carbon_credits::Pallet::<T>::check_kyc_level(
+				&who,
+				<T as pallet::Config>::MinKycLevelToDeposit::get(),
+			)?;
 
 			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
 
@@ -277,6 +341,27 @@ pub mod pallet {
 					)
 				}
 
+				if let Some(project_type_list) = &pool.config.project_type_list {
+					// projects without a type are not accepted
+					let project_type = project_details
+						.project_type
+						.as_ref()
+						.ok_or(Error::<T>::ProjectTypeNotPermitted)?;
+					ensure!(
+						project_type_list.contains(project_type),
+						Error::<T>::ProjectTypeNotPermitted
+					);
+				}
+
+				if let Some(required_sdg_list) = &pool.config.required_sdg_list {
+					for required_sdg in required_sdg_list.iter() {
+						ensure!(
+							project_details.sdg_details.iter().any(|x| &x.sdg_type == required_sdg),
+							Error::<T>::RequiredSdgMissing
+						);
+					}
+				}
+
 				// calculate the issuance year for the project
 				let project_issuance_year = pallet_carbon_credits::Pallet::calculate_issuance_year(
 					project_details,
@@ -284,6 +369,20 @@ pub mod pallet {
 				)
 				.ok_or(Error::<T>::ProjectIssuanceYearError)?;
 
+				if let Some(min_year) = pool.config.min_issuance_year {
+					ensure!(
+						project_issuance_year >= min_year,
+						Error::<T>::IssuanceYearNotPermitted
+					);
+				}
+
+				if let Some(max_year) = pool.config.max_issuance_year {
+					ensure!(
+						project_issuance_year <= max_year,
+						Error::<T>::IssuanceYearNotPermitted
+					);
+				}
+
 				// transfer the tokens to pallet account
 				<T as pallet::Config>::AssetHandler::transfer(
 					asset_id,
@@ -412,6 +511,7 @@ pub mod pallet {
 							group_id,
 							actual,
 							None,
+							None,
 						)?;
 
 						// Update value in storage
@@ -451,6 +551,111 @@ pub mod pallet {
 			Pools::<T>::insert(pool_id, data);
 			Ok(())
 		}
+
+		/// Redeem Pool Tokens - A user can burn pool tokens to receive the CarbonCredits tokens
+		/// of a chosen project and issuance year held by the pool. The redemption fee of the pool
+		/// is paid in pool tokens to the pool admin, the caller receives CarbonCredits tokens for
+		/// the remaining amount.
+		///
+		/// Params:
+		/// pool_id : Id of the pool tokens to redeem
+		/// asset_id : The CarbonCredits token to receive
+		/// issuance_year : The issuance year of the CarbonCredits tokens in the pool
+		/// amount: The amount of pool tokens to redeem
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::redeem())]
+		pub fn redeem(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			asset_id: T::AssetId,
+			issuance_year: IssuanceYear,
+			amount: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			Pools::<T>::try_mutate(pool_id, |pool| -> DispatchResultWithPostInfo {
+				let pool = pool.as_mut().ok_or(Error::<T>::InvalidPoolId)?;
+
+				let fee = Self::redemption_fee(pool_id).mul_floor(amount);
+				let redeemed = amount.checked_sub(&fee).ok_or(Error::<T>::UnexpectedOverflow)?;
+
+				// reduce the credits of the asset in the pool, empty entries are removed
+				let project_map =
+					pool.credits.get_mut(&issuance_year).ok_or(Error::<T>::CreditsNotInPool)?;
+				let available_amount =
+					project_map.get_mut(&asset_id).ok_or(Error::<T>::CreditsNotInPool)?;
+				*available_amount = available_amount
+					.checked_sub(&redeemed)
+					.ok_or(Error::<T>::InsufficientPoolCredits)?;
+				if available_amount.is_zero() {
+					project_map.remove(&asset_id);
+				}
+				if project_map.is_empty() {
+					pool.credits.remove(&issuance_year);
+				}
+
+				// Pay the fee to the pool admin and burn the rest of the pool tokens
+				if !fee.is_zero() {
+					<T as pallet::Config>::AssetHandler::transfer(
+						pool_id.into(),
+						&who,
+						&pool.admin,
+						fee,
+						Expendable,
+					)?;
+				}
+				<T as pallet::Config>::AssetHandler::burn_from(
+					pool_id.into(),
+					&who,
+					redeemed,
+					Exact,
+					Polite,
+				)?;
+
+				// transfer the CarbonCredits tokens to caller
+				<T as pallet::Config>::AssetHandler::transfer(
+					asset_id,
+					&Self::account_id(),
+					&who,
+					redeemed,
+					Expendable,
+				)?;
+
+				// Emit an event.
+				Self::deposit_event(Event::Redeemed {
+					who,
+					pool_id,
+					asset_id,
+					issuance_year,
+					amount: redeemed,
+					fee,
+				});
+
+				Ok(().into())
+			})
+		}
+
+		/// Set the redemption fee of the pool with `pool_id`
+		/// Can only be called by ForceOrigin
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::force_set_redemption_fee())]
+		pub fn force_set_redemption_fee(
+			origin: OriginFor<T>,
+			pool_id: T::PoolId,
+			fee: Percent,
+		) -> DispatchResult {
+			<T as pallet::Config>::ForceOrigin::ensure_origin(origin)?;
+			ensure!(Pools::<T>::contains_key(pool_id), Error::<T>::InvalidPoolId);
+			ensure!(
+				fee <= T::MaxRedemptionFee::get(),
+				Error::<T>::CannotSetMoreThanMaxRedemptionFee
+			);
+			RedemptionFees::<T>::insert(pool_id, fee);
+			Self::deposit_event(Event::RedemptionFeeSet { pool_id, fee });
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
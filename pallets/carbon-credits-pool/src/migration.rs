@@ -0,0 +1,65 @@
+use super::*;
+
+pub mod v1 {
+	use super::*;
+
+	use codec::{Decode, Encode};
+	use frame_support::{
+		pallet_prelude::*,
+		sp_std::marker::PhantomData,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	/// The pool config before version 1, with the registry and project lists only
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, Default)]
+	pub struct OldPoolConfig<RegistryList, MaxProjectIdList> {
+		pub registry_list: Option<RegistryList>,
+		pub project_id_list: Option<MaxProjectIdList>,
+	}
+
+	/// The pool before version 1, the credits keep their bound
+	pub type OldPoolOf<T> = Pool<
+		<T as frame_system::Config>::AccountId,
+		OldPoolConfig<RegistryNameList<T>, MaxProjectIdList<T>>,
+		CreditsMap<T>,
+	>;
+
+	/// Add the issuance year, project type and SDG rules to the config of the existing pools.
+	///
+	/// The existing pools accept credits of any issuance year, project type and SDGs. The credits
+	/// are moved as they are, so every pool token stays backed.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 1 {
+				log::info!("carbon-credits-pool migration to v1 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut pools = 0_u64;
+			Pools::<T>::translate::<OldPoolOf<T>, _>(|_pool_id, old| {
+				pools += 1;
+
+				Some(Pool {
+					admin: old.admin,
+					config: PoolConfig {
+						registry_list: old.config.registry_list,
+						project_id_list: old.config.project_id_list,
+						min_issuance_year: None,
+						max_issuance_year: None,
+						project_type_list: None,
+						required_sdg_list: None,
+					},
+					max_limit: old.max_limit,
+					credits: old.credits,
+				})
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!("carbon-credits-pool migration to v1 done, {} pools", pools);
+
+			T::DbWeight::get().reads_writes(pools + 1, pools + 1)
+		}
+	}
+}
@@ -5,15 +5,16 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32},
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, GetDefault},
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSigned};
+use primitives::{KycLevelOfMembers, UserLevel};
 use scale_info::TypeInfo;
 use sp_core::{ConstU16, ConstU64, H256};
 use sp_runtime::{
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	BuildStorage, Percent,
 };
 use sp_std::convert::{TryFrom, TryInto};
 
@@ -139,8 +140,11 @@ impl pallet_carbon_credits::Config for Test {
 	type ProjectId = u32;
 	type MaxCoordinatesLength = ConstU32<8>;
 	type GroupId = u32;
-	type KYCProvider = KYCMembership;
+	type KYCProvider = KycLevelOfMembers<KYCMembership, GetDefault>;
+	type MinKycLevelToCreate = GetDefault;
+	type MinKycLevelToRetire = GetDefault;
 	type MarketplaceEscrow = MarketplaceEscrowAccount;
+	type Marketplace = ();
 	type MaxAuthorizedAccountCount = ConstU32<2>;
 	type MaxDocumentCount = ConstU32<2>;
 	type MaxGroupSize = MaxGroupSize;
@@ -149,7 +153,7 @@ impl pallet_carbon_credits::Config for Test {
 	type MaxRoyaltyRecipients = ConstU32<5>;
 	type MaxShortStringLength = ConstU32<20>;
 	type MinProjectId = ConstU32<1000>;
-	type NFTHandler = Uniques;
+	type NFTHandler = pallet_carbon_credits::UniquesCertificateHandler<Test>;
 	type PalletId = CarbonCreditsPalletId;
 	type WeightInfo = ();
 }
@@ -188,6 +192,8 @@ impl pallet_membership::Config for Test {
 
 parameter_types! {
 	pub const CarbonCreditPoolsPalletId: PalletId = PalletId(*b"bit/ccpp");
+	pub static MinKycLevelToDeposit: UserLevel = UserLevel::KYCLevel1;
+	pub const MaxRedemptionFee: Percent = Percent::from_percent(10);
 }
 
 impl pallet_carbon_credits_pool::Config for Test {
@@ -199,6 +205,8 @@ impl pallet_carbon_credits_pool::Config for Test {
 	type MaxProjectIdList = ConstU32<100>;
 	type MaxRegistryListCount = ConstU32<2>;
 	type MinPoolId = ConstU32<10000>;
+	type MinKycLevelToDeposit = MinKycLevelToDeposit;
+	type MaxRedemptionFee = MaxRedemptionFee;
 	type PalletId = CarbonCreditPoolsPalletId;
 	type PoolId = u32;
 	type WeightInfo = ();
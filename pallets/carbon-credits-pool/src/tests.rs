@@ -3,19 +3,25 @@
 // This code is licensed under MIT license (see LICENSE.txt for details)
 //
 //! Tests for CarbonCredits pool pallet
-use crate::{mock::*, Config, Error, Pools};
+use crate::{mock::*, Config, Error, PoolConfigOf, Pools};
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::tokens::fungibles::{metadata::Inspect as MetadataInspect, Inspect},
+	storage::unhashed,
+	traits::{
+		tokens::fungibles::{metadata::Inspect as MetadataInspect, Inspect},
+		GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
+	},
 	BoundedVec,
 };
 use frame_system::RawOrigin;
 use pallet_carbon_credits::{
 	BatchGroupListOf, BatchGroupOf, BatchOf, ProjectCreateParams, RegistryListOf, SDGTypesListOf,
 };
-use primitives::{Batch, RegistryDetails, RegistryName, Royalty, SDGDetails, SdgType};
+use primitives::{
+	Batch, ProjectType, RegistryDetails, RegistryName, Royalty, SDGDetails, SdgType, UserLevel,
+};
 use sp_runtime::Percent;
-use sp_std::convert::TryInto;
+use sp_std::{collections::btree_map::BTreeMap, convert::TryInto};
 
 pub type CarbonCreditPoolEvent = crate::Event<Test>;
 
@@ -188,7 +194,8 @@ pub fn create_project_and_mint<T: Config>(
 		project_id,
 		group_id,
 		amount_to_mint.into(),
-		false
+		false,
+		None
 	));
 }
 
@@ -324,6 +331,138 @@ fn deposit_works() {
 	});
 }
 
+#[test]
+fn deposit_requires_minimum_kyc_level() {
+	new_test_ext().execute_with(|| {
+		let authorised_account_one = 1;
+		let asset_id = 0;
+		let pool_id = 10_000;
+
+		assert_ok!(CarbonCreditPools::create(
+			RawOrigin::Root.into(),
+			pool_id,
+			authorised_account_one,
+			Default::default(),
+			None,
+			"pool_xyz".as_bytes().to_vec().try_into().unwrap(),
+		));
+
+		create_project_and_mint::<Test>(authorised_account_one, 100, false);
+
+		// account without kyc cannot deposit
+		assert_noop!(
+			CarbonCreditPools::deposit(RawOrigin::Signed(2).into(), pool_id, asset_id, 10),
+			pallet_carbon_credits::Error::<Test>::KYCAuthorisationFailed
+		);
+
+		// members are at level 1, which is below the minimum
+		MinKycLevelToDeposit::set(UserLevel::KYCLevel2);
+		assert_noop!(
+			CarbonCreditPools::deposit(
+				RawOrigin::Signed(authorised_account_one).into(),
+				pool_id,
+				asset_id,
+				10
+			),
+			pallet_carbon_credits::Error::<Test>::KYCLevelTooLow
+		);
+	});
+}
+
+#[test]
+fn deposit_enforces_pool_eligibility_rules() {
+	new_test_ext().execute_with(|| {
+		let authorised_account_one = 1;
+		let asset_id = 0;
+
+		// the credits of the project are issued in 2020, have no project type and address
+		// LifeOnLand
+		create_project_and_mint::<Test>(authorised_account_one, 100, false);
+
+		let create_pool = |pool_id: u32, config: PoolConfigOf<Test>| {
+			CarbonCreditPools::create(
+				RawOrigin::Root.into(),
+				pool_id,
+				authorised_account_one,
+				config,
+				None,
+				"pool_xyz".as_bytes().to_vec().try_into().unwrap(),
+			)
+		};
+
+		let deposit = |pool_id: u32| {
+			CarbonCreditPools::deposit(
+				RawOrigin::Signed(authorised_account_one).into(),
+				pool_id,
+				asset_id,
+				10,
+			)
+		};
+
+		// cannot create a pool with an empty issuance year range
+		assert_noop!(
+			create_pool(
+				10_000,
+				PoolConfigOf::<Test> {
+					min_issuance_year: Some(2021),
+					max_issuance_year: Some(2020),
+					..Default::default()
+				}
+			),
+			Error::<Test>::InvalidIssuanceYearRange
+		);
+
+		// credits issued before the minimum year are not accepted
+		assert_ok!(create_pool(
+			10_000,
+			PoolConfigOf::<Test> { min_issuance_year: Some(2021), ..Default::default() }
+		));
+		assert_noop!(deposit(10_000), Error::<Test>::IssuanceYearNotPermitted);
+
+		// credits issued after the maximum year are not accepted
+		assert_ok!(create_pool(
+			10_001,
+			PoolConfigOf::<Test> { max_issuance_year: Some(2019), ..Default::default() }
+		));
+		assert_noop!(deposit(10_001), Error::<Test>::IssuanceYearNotPermitted);
+
+		// projects without a permitted type are not accepted
+		assert_ok!(create_pool(
+			10_002,
+			PoolConfigOf::<Test> {
+				project_type_list: Some(vec![ProjectType::ENERGY_INDUSTRIES].try_into().unwrap()),
+				..Default::default()
+			}
+		));
+		assert_noop!(deposit(10_002), Error::<Test>::ProjectTypeNotPermitted);
+
+		// projects must address all the required SDGs
+		assert_ok!(create_pool(
+			10_003,
+			PoolConfigOf::<Test> {
+				required_sdg_list: Some(
+					vec![SdgType::LifeOnLand, SdgType::ClimateAction].try_into().unwrap()
+				),
+				..Default::default()
+			}
+		));
+		assert_noop!(deposit(10_003), Error::<Test>::RequiredSdgMissing);
+
+		// deposit works when all rules are met
+		assert_ok!(create_pool(
+			10_004,
+			PoolConfigOf::<Test> {
+				min_issuance_year: Some(2020),
+				max_issuance_year: Some(2020),
+				required_sdg_list: Some(vec![SdgType::LifeOnLand].try_into().unwrap()),
+				..Default::default()
+			}
+		));
+		assert_ok!(deposit(10_004));
+		assert_eq!(Assets::balance(10_004, authorised_account_one), 10);
+	});
+}
+
 #[test]
 fn deposit_works_for_batch_credits() {
 	new_test_ext().execute_with(|| {
@@ -471,3 +610,178 @@ fn retire_works() {
 		assert_eq!(stored_data.batch_groups.get(&group_id).unwrap().retired, 90_u32.into());
 	});
 }
+
+#[test]
+fn redeem_works() {
+	new_test_ext().execute_with(|| {
+		let authorised_account_one = 1;
+		let pool_admin = 3;
+		let asset_id = 0;
+		let pool_id = 10_000;
+		let project_tokens_to_mint = 100;
+		let project_tokens_to_deposit = 99;
+
+		assert_ok!(CarbonCreditPools::create(
+			RawOrigin::Root.into(),
+			pool_id,
+			pool_admin,
+			Default::default(),
+			None,
+			"pool_xyz".as_bytes().to_vec().try_into().unwrap(),
+		));
+
+		create_project_and_mint::<Test>(authorised_account_one, project_tokens_to_mint, false);
+
+		assert_ok!(CarbonCreditPools::deposit(
+			RawOrigin::Signed(authorised_account_one).into(),
+			pool_id,
+			asset_id,
+			project_tokens_to_deposit
+		));
+
+		// only ForceOrigin can set the redemption fee, upto the max fee
+		assert_noop!(
+			CarbonCreditPools::force_set_redemption_fee(
+				RawOrigin::Signed(pool_admin).into(),
+				pool_id,
+				Percent::from_percent(10)
+			),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			CarbonCreditPools::force_set_redemption_fee(
+				RawOrigin::Root.into(),
+				pool_id,
+				Percent::from_percent(20)
+			),
+			Error::<Test>::CannotSetMoreThanMaxRedemptionFee
+		);
+		assert_ok!(CarbonCreditPools::force_set_redemption_fee(
+			RawOrigin::Root.into(),
+			pool_id,
+			Percent::from_percent(10)
+		));
+
+		assert_noop!(
+			CarbonCreditPools::redeem(
+				RawOrigin::Signed(authorised_account_one).into(),
+				pool_id,
+				asset_id,
+				2020,
+				0
+			),
+			Error::<Test>::InvalidAmount
+		);
+
+		// the pool does not hold credits of this issuance year
+		assert_noop!(
+			CarbonCreditPools::redeem(
+				RawOrigin::Signed(authorised_account_one).into(),
+				pool_id,
+				asset_id,
+				2021,
+				10
+			),
+			Error::<Test>::CreditsNotInPool
+		);
+
+		// redeem should work
+		assert_ok!(CarbonCreditPools::redeem(
+			RawOrigin::Signed(authorised_account_one).into(),
+			pool_id,
+			asset_id,
+			2020,
+			50
+		));
+
+		assert_eq!(
+			last_event(),
+			CarbonCreditPoolEvent::Redeemed {
+				who: authorised_account_one,
+				pool_id,
+				asset_id,
+				issuance_year: 2020,
+				amount: 45,
+				fee: 5
+			}
+			.into()
+		);
+
+		// the caller paid the fee to the admin and burned the rest of the pool tokens
+		assert_eq!(Assets::balance(pool_id, authorised_account_one), 49);
+		assert_eq!(Assets::balance(pool_id, pool_admin), 5);
+		assert_eq!(Assets::total_issuance(pool_id), 54);
+
+		// the caller received the project tokens
+		assert_eq!(Assets::balance(asset_id, authorised_account_one), 46);
+
+		let stored_pool = Pools::<Test>::get(pool_id).unwrap();
+		assert_eq!(stored_pool.credits.get(&2020).unwrap().get(&asset_id).unwrap(), &54_u128);
+
+		// redeeming all credits removes the entries from the pool
+		assert_ok!(CarbonCreditPools::force_set_redemption_fee(
+			RawOrigin::Root.into(),
+			pool_id,
+			Percent::from_percent(0)
+		));
+		assert_ok!(CarbonCreditPools::redeem(
+			RawOrigin::Signed(authorised_account_one).into(),
+			pool_id,
+			asset_id,
+			2020,
+			49
+		));
+		assert_ok!(CarbonCreditPools::redeem(
+			RawOrigin::Signed(pool_admin).into(),
+			pool_id,
+			asset_id,
+			2020,
+			5
+		));
+
+		assert_eq!(Assets::total_issuance(pool_id), 0);
+		assert_eq!(Assets::balance(asset_id, authorised_account_one), 95);
+		assert_eq!(Assets::balance(asset_id, pool_admin), 5);
+		assert!(Pools::<Test>::get(pool_id).unwrap().credits.is_empty());
+	});
+}
+
+#[test]
+fn migration_to_v1_adds_the_new_pool_rules() {
+	new_test_ext().execute_with(|| {
+		let pool_id = 10001;
+		let pool_admin = 10;
+
+		let mut credits = crate::CreditsMap::<Test>::default();
+		let projects: crate::ProjectDetails<Test> =
+			vec![(1u32, 100u128)].into_iter().collect::<BTreeMap<_, _>>().try_into().unwrap();
+		for issuance_year in 2000..2025 {
+			credits.try_insert(issuance_year, projects.clone()).unwrap();
+		}
+		let old_pool = crate::migration::v1::OldPoolOf::<Test> {
+			admin: pool_admin,
+			config: crate::migration::v1::OldPoolConfig {
+				registry_list: Some(vec![RegistryName::Verra].try_into().unwrap()),
+				project_id_list: None,
+			},
+			max_limit: 100,
+			credits,
+		};
+		unhashed::put(&Pools::<Test>::hashed_key_for(pool_id), &old_pool);
+		StorageVersion::new(0).put::<CarbonCreditPools>();
+
+		crate::migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		// the existing rules are kept and the new rules accept any credits
+		let pool = Pools::<Test>::get(pool_id).unwrap();
+		assert_eq!(pool.admin, pool_admin);
+		assert_eq!(pool.config.registry_list, Some(vec![RegistryName::Verra].try_into().unwrap()));
+		assert_eq!(pool.config.min_issuance_year, None);
+		assert_eq!(pool.config.project_type_list, None);
+
+		// every issuance year is kept, the pool tokens stay backed
+		assert_eq!(pool.credits.len(), 25);
+		assert_eq!(pool.credits.get(&2024), Some(&projects));
+		assert_eq!(CarbonCreditPools::on_chain_storage_version(), 1);
+	});
+}
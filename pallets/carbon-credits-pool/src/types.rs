@@ -4,8 +4,8 @@
 //
 //! Types for CarbonCredits-pools
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::{BoundedBTreeMap, BoundedVec};
-use primitives::{IssuanceYear, RegistryName};
+use frame_support::{traits::ConstU32, BoundedBTreeMap, BoundedVec};
+use primitives::{IssuanceYear, ProjectType, RegistryName, SdgType};
 use scale_info::TypeInfo;
 
 use super::*;
@@ -17,6 +17,12 @@ pub type RegistryNameList<T> = BoundedVec<RegistryName, <T as Config>::MaxRegist
 pub type MaxProjectIdList<T> =
 	BoundedVec<<T as pallet_carbon_credits::Config>::AssetId, <T as Config>::MaxProjectIdList>;
 
+/// List of permitted project types, this can go upto the number of project types
+pub type ProjectTypeList = BoundedVec<ProjectType, ConstU32<13>>;
+
+/// List of required SDGs, this can go upto the number of SDGs
+pub type SdgTypeList = BoundedVec<SdgType, ConstU32<17>>;
+
 /// type to receive symbol data
 pub type SymbolStringOf<T> = BoundedVec<u8, <T as Config>::MaxAssetSymbolLength>;
 
@@ -26,6 +32,14 @@ pub type SymbolStringOf<T> = BoundedVec<u8, <T as Config>::MaxAssetSymbolLength>
 pub struct PoolConfig<RegistryList, MaxProjectIdList> {
 	pub registry_list: Option<RegistryList>,
 	pub project_id_list: Option<MaxProjectIdList>,
+	/// Credits issued before this year are not accepted
+	pub min_issuance_year: Option<IssuanceYear>,
+	/// Credits issued after this year are not accepted
+	pub max_issuance_year: Option<IssuanceYear>,
+	/// Only credits of projects with one of these types are accepted
+	pub project_type_list: Option<ProjectTypeList>,
+	/// Only credits of projects that address all of these SDGs are accepted
+	pub required_sdg_list: Option<SdgTypeList>,
 }
 
 /// Map storing the details of a given project in a pool
@@ -35,6 +35,8 @@ pub trait WeightInfo {
 	fn create() -> Weight;
 	fn deposit() -> Weight;
 	fn retire() -> Weight;
+	fn redeem() -> Weight;
+	fn force_set_redemption_fee() -> Weight;
 }
 
 /// Weights for pallet_carbon_credits_pool using the Substrate node and recommended hardware.
@@ -74,6 +76,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11_u64))
 			.saturating_add(T::DbWeight::get().writes(13_u64))
 	}
+	// Storage: VCUPools Pools (r:1 w:1)
+	// Storage: VCUPools RedemptionFees (r:1 w:0)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	// Storage: System Account (r:2 w:2)
+	fn redeem() -> Weight {
+		Weight::from_parts(72_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	// Storage: VCUPools Pools (r:1 w:0)
+	// Storage: VCUPools RedemptionFees (r:0 w:1)
+	fn force_set_redemption_fee() -> Weight {
+		Weight::from_parts(12_000_000_u64, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -112,4 +131,21 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(11_u64))
 			.saturating_add(RocksDbWeight::get().writes(13_u64))
 	}
+	// Storage: VCUPools Pools (r:1 w:1)
+	// Storage: VCUPools RedemptionFees (r:1 w:0)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	// Storage: System Account (r:2 w:2)
+	fn redeem() -> Weight {
+		Weight::from_parts(72_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	// Storage: VCUPools Pools (r:1 w:0)
+	// Storage: VCUPools RedemptionFees (r:0 w:1)
+	fn force_set_redemption_fee() -> Weight {
+		Weight::from_parts(12_000_000_u64, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
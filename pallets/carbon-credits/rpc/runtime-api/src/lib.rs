@@ -0,0 +1,39 @@
+// This file is part of BitGreen.
+// Copyright (C) 2022 BitGreen.
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//! Runtime API for CarbonCredits pallet. Can be used by clients to read the details and
+//! batch groups of a project, and the retirements recorded for an account or NFT item,
+//! without decoding the pallet storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait CarbonCreditsApi<AccountId, ProjectId, GroupId, AssetId, ItemId, ProjectDetail, BatchGroup, RetiredCredits> where
+		AccountId: Codec,
+		ProjectId: Codec,
+		GroupId: Codec,
+		AssetId: Codec,
+		ItemId: Codec,
+		ProjectDetail: Codec,
+		BatchGroup: Codec,
+		RetiredCredits: Codec,
+	{
+		/// Return the details of the given `ProjectId`.
+		/// Returns `None` if the project does not exist.
+		fn project_details(project_id: ProjectId) -> Option<ProjectDetail>;
+		/// Return the batch groups of the given `ProjectId`, every group includes
+		/// its minted and retired totals.
+		fn project_groups(project_id: ProjectId) -> Vec<(GroupId, BatchGroup)>;
+		/// Return the project and group represented by the given `AssetId`.
+		/// Returns `None` if the asset is not a carbon credit.
+		fn asset_project(asset_id: AssetId) -> Option<(ProjectId, GroupId)>;
+		/// Return the retirements of the given `AccountId`, along with the NFT
+		/// collection and item representing every retirement.
+		fn retirements_by_account(account: AccountId) -> Vec<(AssetId, ItemId, RetiredCredits)>;
+		/// Return the retirement represented by the NFT `item_id` of collection `asset_id`.
+		fn retirement(asset_id: AssetId, item_id: ItemId) -> Option<RetiredCredits>;
+	}
+}
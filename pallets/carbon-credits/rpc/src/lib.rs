@@ -0,0 +1,202 @@
+// This file is part of BitGreen.
+// Copyright (C) 2022 BitGreen.
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//! JSON-RPC interface of the CarbonCredits pallet.
+//! Project details, batch groups and retirements are returned as JSON objects with the fields
+//! of the pallet types. The batch groups of a project are only returned by
+//! `carbonCredits_projectGroups`.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_carbon_credits_runtime_api::CarbonCreditsApi as CarbonCreditsRuntimeApi;
+use parity_scale_codec::Codec;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code returned when the runtime api call fails
+const RUNTIME_ERROR: i32 = 1;
+
+#[rpc(client, server)]
+pub trait CarbonCreditsApi<
+	BlockHash,
+	AccountId,
+	ProjectId,
+	GroupId,
+	AssetId,
+	ItemId,
+	ProjectDetail,
+	BatchGroup,
+	RetiredCredits,
+>
+{
+	/// Return the details of the given project, without its batch groups
+	#[method(name = "carbonCredits_projectDetails")]
+	fn project_details(
+		&self,
+		project_id: ProjectId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ProjectDetail>>;
+
+	/// Return the list of (GroupId, BatchGroup) of the given project
+	#[method(name = "carbonCredits_projectGroups")]
+	fn project_groups(
+		&self,
+		project_id: ProjectId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(GroupId, BatchGroup)>>;
+
+	/// Return the project and group represented by the given asset
+	#[method(name = "carbonCredits_assetProject")]
+	fn asset_project(
+		&self,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<(ProjectId, GroupId)>>;
+
+	/// Return the list of (AssetId, ItemId, RetiredCarbonCreditsData) of the given account
+	#[method(name = "carbonCredits_retirementsByAccount")]
+	fn retirements_by_account(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(AssetId, ItemId, RetiredCredits)>>;
+
+	/// Return the retirement represented by the given NFT item
+	#[method(name = "carbonCredits_retirement")]
+	fn retirement(
+		&self,
+		asset_id: AssetId,
+		item_id: ItemId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<RetiredCredits>>;
+}
+
+/// Provides RPC methods to query the CarbonCredits pallet
+pub struct CarbonCredits<C, P> {
+	client: Arc<C>,
+	_marker: PhantomData<P>,
+}
+
+impl<C, P> CarbonCredits<C, P> {
+	/// Create new `CarbonCredits` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+impl<
+		C,
+		Block,
+		AccountId,
+		ProjectId,
+		GroupId,
+		AssetId,
+		ItemId,
+		ProjectDetail,
+		BatchGroup,
+		RetiredCredits,
+	>
+	CarbonCreditsApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		ProjectId,
+		GroupId,
+		AssetId,
+		ItemId,
+		ProjectDetail,
+		BatchGroup,
+		RetiredCredits,
+	> for CarbonCredits<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CarbonCreditsRuntimeApi<
+		Block,
+		AccountId,
+		ProjectId,
+		GroupId,
+		AssetId,
+		ItemId,
+		ProjectDetail,
+		BatchGroup,
+		RetiredCredits,
+	>,
+	AccountId: Codec,
+	ProjectId: Codec,
+	GroupId: Codec,
+	AssetId: Codec,
+	ItemId: Codec,
+	ProjectDetail: Codec + Send + Sync + 'static,
+	BatchGroup: Codec + Send + Sync + 'static,
+	RetiredCredits: Codec + Send + Sync + 'static,
+{
+	fn project_details(
+		&self,
+		project_id: ProjectId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<ProjectDetail>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.project_details(at, project_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn project_groups(
+		&self,
+		project_id: ProjectId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(GroupId, BatchGroup)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().project_groups(at, project_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn asset_project(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<(ProjectId, GroupId)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().asset_project(at, asset_id).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn retirements_by_account(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(AssetId, ItemId, RetiredCredits)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.retirements_by_account(at, account)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn retirement(
+		&self,
+		asset_id: AssetId,
+		item_id: ItemId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<RetiredCredits>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.retirement(at, asset_id, item_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -8,7 +8,7 @@ use frame_benchmarking::{account, benchmarks, vec};
 use frame_support::BoundedVec;
 use frame_system::RawOrigin;
 use primitives::{Batch, RegistryDetails, RegistryName, SDGDetails, SdgType};
-use sp_std::convert::TryInto;
+use sp_std::{convert::TryInto, vec::Vec};
 
 use super::*;
 use crate::{Event, Pallet as CarbonCredits};
@@ -55,8 +55,33 @@ fn get_single_batch_list<T: Config>() -> BoundedVec<BatchOf<T>, T::MaxGroupSize>
 	.unwrap()
 }
 
+/// helper function to generate a list of `batches` batches of different issuance years
+fn get_batch_list<T: Config>(batches: u32) -> BoundedVec<BatchOf<T>, T::MaxGroupSize> {
+	(0..batches)
+		.map(|index| Batch {
+			name: "batch_name".as_bytes().to_vec().try_into().unwrap(),
+			uuid: "batch_uuid".as_bytes().to_vec().try_into().unwrap(),
+			issuance_year: 2000_u16 + index as u16,
+			start_date: 2000_u16 + index as u16,
+			end_date: 2000_u16 + index as u16,
+			total_supply: 10_u32.into(),
+			minted: 0_u32.into(),
+			retired: 0_u32.into(),
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
 /// helper function to generate standard batch details
 fn get_default_batch_group<T: Config>() -> BatchGroupListOf<T> {
+	get_batch_group::<T>(get_single_batch_list::<T>())
+}
+
+/// helper function to generate a batch group with the given batches
+fn get_batch_group<T: Config>(
+	batches: BoundedVec<BatchOf<T>, T::MaxGroupSize>,
+) -> BatchGroupListOf<T> {
 	vec![BatchGroupOf::<T> {
 		name: "batch_group_name".as_bytes().to_vec().try_into().unwrap(),
 		uuid: "batch_group_uuid".as_bytes().to_vec().try_into().unwrap(),
@@ -64,7 +89,7 @@ fn get_default_batch_group<T: Config>() -> BatchGroupListOf<T> {
 		total_supply: 100_u32.into(),
 		minted: 0_u32.into(),
 		retired: 0_u32.into(),
-		batches: get_single_batch_list::<T>(),
+		batches,
 	}]
 	.try_into()
 	.unwrap()
@@ -136,17 +161,20 @@ benchmarks! {
 		CarbonCredits::<T>::force_add_authorized_account(RawOrigin::Root.into(), caller.clone().into())?;
 		CarbonCredits::<T>::create(RawOrigin::Signed(caller.clone()).into(), creation_params)?;
 		CarbonCredits::<T>::approve_project(RawOrigin::Signed(caller.clone()).into(), project_id, true)?;
-	}: _(RawOrigin::Signed(caller.clone()), project_id, group_id, 100_u32.into(), false)
+	}: _(RawOrigin::Signed(caller.clone()), project_id, group_id, 100_u32.into(), false, None)
 	verify {
 		assert_last_event::<T>(Event::CarbonCreditMinted { project_id, group_id, recipient : caller, amount : 100_u32.into() }.into());
 	}
 
 	retire {
+		let b in 1 .. T::MaxGroupSize::get();
 		let caller : T::AccountId = account("account_id", 0, 0);
 		let project_id : T::ProjectId = 0_u32.into();
 		let group_id : T::GroupId = 0_u32.into();
 		let asset_id : T::AssetId = 0_u32.into();
-		let creation_params = get_default_creation_params::<T>();
+		// the credits are retired from `b` batches
+		let mut creation_params = get_default_creation_params::<T>();
+		creation_params.batch_groups = get_batch_group::<T>(get_batch_list::<T>(b));
 
 		let caller_lookup = <T::Lookup as sp_runtime::traits::StaticLookup>::unlookup(caller.clone());
 		pallet_membership::Pallet::<T>::add_member(RawOrigin::Root.into(), caller_lookup)?;
@@ -154,12 +182,14 @@ benchmarks! {
 		CarbonCredits::<T>::force_add_authorized_account(RawOrigin::Root.into(), caller.clone().into())?;
 		CarbonCredits::<T>::create(RawOrigin::Signed(caller.clone()).into(), creation_params)?;
 		CarbonCredits::<T>::approve_project(RawOrigin::Signed(caller.clone()).into(), project_id, true)?;
-		CarbonCredits::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, 100_u32.into(), false)?;
-	}: _(RawOrigin::Signed(caller.clone()), project_id, group_id, 10_u32.into())
+		CarbonCredits::<T>::mint(RawOrigin::Signed(caller.clone()).into(), project_id, group_id, (10_u32 * b).into(), false, None)?;
+		let beneficiary : T::AccountId = account("beneficiary", 0, 0);
+	}: _(RawOrigin::Signed(caller.clone()), project_id, group_id, (10_u32 * b).into(), None, Some(beneficiary.clone()))
 	verify {
 		let item_id : T::ItemId = 0_u32.into();
 		let retire_data = RetiredCredits::<T>::get(asset_id, item_id).unwrap();
-		assert_last_event::<T>(Event::CarbonCreditRetired { project_id, group_id, asset_id, account : caller, amount : 10_u32.into(), retire_data :retire_data.retire_data }.into());
+		assert_eq!(retire_data.retire_data.len() as u32, b);
+		assert_last_event::<T>(Event::CarbonCreditRetired { project_id, group_id, asset_id, account : caller, amount : (10_u32 * b).into(), retire_data :retire_data.retire_data, reason : Default::default(), beneficiary : Some(beneficiary) }.into());
 	}
 
 	force_add_authorized_account {
@@ -226,7 +256,9 @@ benchmarks! {
 			account,
 			retire_data : vec![batch_data].try_into().unwrap(),
 			timestamp : 1_u32.into(),
-			count : 100_u32.into()
+			count : 100_u32.into(),
+			reason : Default::default(),
+			beneficiary : None
 		};
 	}: _(RawOrigin::Root, asset_id, item_id, new_retire_data)
 	verify {
@@ -2,7 +2,10 @@
 // Copyright (C) 2022 BitGreen.
 // This code is licensed under MIT license (see LICENSE.txt for details)
 //! CarbonCredits pallet helper functions
-use codec::alloc::string::ToString;
+use codec::alloc::{
+	format,
+	string::{String, ToString},
+};
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
@@ -13,19 +16,23 @@ use frame_support::{
 			Fortitude::Polite,
 			Precision::Exact,
 		},
-		Contains, Get,
+		Get,
 	},
 	BoundedBTreeMap,
 };
-use primitives::BatchRetireData;
-use sp_runtime::traits::{AccountIdConversion, CheckedAdd, CheckedSub, One, Zero};
+use primitives::{BatchRetireData, KycLevelProvider, MarketplaceListing, UserLevel};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedAdd, CheckedSub, One, Zero},
+	PerThing, SaturatedConversion,
+};
 use sp_std::{cmp, convert::TryInto, vec::Vec};
 
 use crate::{
 	AssetIdLookup, AuthorizedAccounts, BatchGroupOf, BatchRetireDataList, BatchRetireDataOf,
-	Config, Error, Event, NextAssetId, NextItemId, NextProjectId, Pallet, ProjectApprovalStatus,
-	ProjectCreateParams, ProjectDetail, Projects, RetiredCarbonCreditsData, RetiredCredits,
-	ShortStringOf,
+	Config, Error, Event, MarketplacePriceOf, NextAssetId, NextItemId, NextProjectId, Pallet,
+	ProjectApprovalStatus, ProjectCreateParams, ProjectDetail, Projects, RetiredCarbonCreditsData,
+	RetiredCredits, RoyaltyRecipientsOf, ShortStringOf,
 };
 
 impl<T: Config> Pallet<T> {
@@ -39,15 +46,55 @@ impl<T: Config> Pallet<T> {
 		Projects::<T>::get(project_id)
 	}
 
+	/// Get the batch groups of a project, including the minted and retired totals of every group
+	pub fn get_project_groups(project_id: T::ProjectId) -> Vec<(T::GroupId, BatchGroupOf<T>)> {
+		Projects::<T>::get(project_id)
+			.map(|project| project.batch_groups.into_iter().collect())
+			.unwrap_or_default()
+	}
+
+	/// Get the project and group represented by the given AssetId
+	pub fn get_asset_project(asset_id: T::AssetId) -> Option<(T::ProjectId, T::GroupId)> {
+		AssetIdLookup::<T>::get(asset_id)
+	}
+
+	/// Get the retirement represented by the given NFT item
+	pub fn get_retirement(
+		asset_id: T::AssetId,
+		item_id: T::ItemId,
+	) -> Option<RetiredCarbonCreditsData<T>> {
+		RetiredCredits::<T>::get(asset_id, item_id)
+	}
+
+	/// Get all retirements made by the given account along with the NFT item representing them
+	/// This iterates over all the `RetiredCredits` records and is meant to be used by runtime apis
+	pub fn get_retirements_by_account(
+		account_id: &T::AccountId,
+	) -> Vec<(T::AssetId, T::ItemId, RetiredCarbonCreditsData<T>)> {
+		RetiredCredits::<T>::iter()
+			.filter(|(_, _, retirement)| retirement.account == *account_id)
+			.collect()
+	}
+
 	/// Checks if given account is kyc approved
 	pub fn check_kyc_approval(account_id: &T::AccountId) -> DispatchResult {
-		if !T::KYCProvider::contains(account_id) {
+		if T::KYCProvider::kyc_level(account_id).is_none() {
 			Err(Error::<T>::KYCAuthorisationFailed.into())
 		} else {
 			Ok(())
 		}
 	}
 
+	/// Checks if given account is kyc approved at `min_level` or higher
+	pub fn check_kyc_level(account_id: &T::AccountId, min_level: UserLevel) -> DispatchResult {
+		Self::check_kyc_approval(account_id)?;
+		if !T::KYCProvider::has_kyc_level(account_id, &min_level) {
+			Err(Error::<T>::KYCLevelTooLow.into())
+		} else {
+			Ok(())
+		}
+	}
+
 	/// Checks if the given account_id is part of authorized account list
 	pub fn check_authorized_account(account_id: &T::AccountId) -> DispatchResult {
 		let authorized_accounts = AuthorizedAccounts::<T>::get();
@@ -58,6 +105,17 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Checks that the royalties of a project do not add up to more than 100%
+	pub fn check_royalties(royalties: &Option<RoyaltyRecipientsOf<T>>) -> DispatchResult {
+		let total_percent: u32 = royalties
+			.iter()
+			.flatten()
+			.map(|royalty| u32::from(royalty.percent_of_fees.deconstruct()))
+			.sum();
+		ensure!(total_percent <= 100, Error::<T>::RoyaltiesExceedHundredPercent);
+		Ok(())
+	}
+
 	/// Approve/reject a project
 	pub fn do_approve_project(project_id: T::ProjectId, is_approved: bool) -> DispatchResult {
 		Projects::<T>::try_mutate(project_id, |project| -> DispatchResult {
@@ -145,6 +203,8 @@ impl<T: Config> Pallet<T> {
 			// cannot create a new project with empty batch_groups
 			ensure!(!params.batch_groups.is_empty(), Error::<T>::CannotCreateProjectWithoutCredits);
 
+			Self::check_royalties(&params.royalties)?;
+
 			let mut batch_group_map: BoundedBTreeMap<_, _, _> = Default::default();
 			let mut group_id: T::GroupId = 0u32.into();
 
@@ -231,6 +291,8 @@ impl<T: Config> Pallet<T> {
 			// approved projects cannot be modified
 			ensure!(!project.approved.is_approved(), Error::<T>::CannotModifyApprovedProject);
 
+			Self::check_royalties(&params.royalties)?;
+
 			let mut batch_group_map: BoundedBTreeMap<_, _, _> = Default::default();
 			let mut group_id: T::GroupId = 0u32.into();
 
@@ -321,6 +383,8 @@ impl<T: Config> Pallet<T> {
 			// only originator can resubmit
 			ensure!(project.originator == admin, Error::<T>::NotAuthorised);
 
+			Self::check_royalties(&params.royalties)?;
+
 			let new_project = ProjectDetail {
 				originator: admin,
 				name: params.name,
@@ -420,11 +484,17 @@ impl<T: Config> Pallet<T> {
 		group_id: T::GroupId,
 		amount_to_mint: T::Balance,
 		list_to_marketplace: bool,
+		listing_price: Option<MarketplacePriceOf<T>>,
 	) -> DispatchResult {
 		if amount_to_mint.is_zero() {
 			return Ok(())
 		}
 
+		let listing_price = match list_to_marketplace {
+			true => Some(listing_price.ok_or(Error::<T>::ListingPriceRequired)?),
+			false => None,
+		};
+
 		Projects::<T>::try_mutate(project_id, |project| -> DispatchResult {
 			// ensure the project exists
 			let project = project.as_mut().ok_or(Error::<T>::ProjectNotFound)?;
@@ -445,8 +515,7 @@ impl<T: Config> Pallet<T> {
 			);
 
 			let recipient = match list_to_marketplace {
-				// TODO : Support marketplace escrow
-				true => project.originator.clone(),
+				true => T::MarketplaceEscrow::get(),
 				false => project.originator.clone(),
 			};
 
@@ -485,6 +554,17 @@ impl<T: Config> Pallet<T> {
 			// mint the asset to the recipient
 			T::AssetHandler::mint_into(group.asset_id, &recipient, amount_to_mint)?;
 
+			// list the escrowed tokens for sale on behalf of the originator
+			if let Some(price_per_unit) = listing_price {
+				T::Marketplace::list_from_escrow(
+					&recipient,
+					&project.originator,
+					group.asset_id,
+					amount_to_mint,
+					price_per_unit,
+				)?;
+			}
+
 			// emit event
 			Self::deposit_event(Event::CarbonCreditMinted {
 				project_id,
@@ -498,20 +578,22 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Retire carbon credits for given project_id
+	/// Returns the number of batches the credits were retired from
 	pub fn retire_carbon_credits(
 		from: T::AccountId,
 		project_id: T::ProjectId,
 		group_id: T::GroupId,
 		amount: T::Balance,
 		reason: Option<Vec<u8>>,
-	) -> DispatchResult {
+		beneficiary: Option<T::AccountId>,
+	) -> Result<u32, DispatchError> {
 		let now = frame_system::Pallet::<T>::block_number();
 
 		if amount.is_zero() {
-			return Ok(())
+			return Ok(0)
 		}
 
-		Projects::<T>::try_mutate(project_id, |project| -> DispatchResult {
+		Projects::<T>::try_mutate(project_id, |project| -> Result<u32, DispatchError> {
 			// ensure the project exists
 			let project = project.as_mut().ok_or(Error::<T>::ProjectNotFound)?;
 
@@ -622,10 +704,23 @@ impl<T: Config> Pallet<T> {
 				timestamp: now,
 				count: amount,
 				reason: ret_reason.clone(),
+				beneficiary: beneficiary.clone(),
 			};
 
+			// write the retirement details to the NFT so the certificate is self describing
+			Self::set_retirement_attributes(
+				group.asset_id,
+				item_id,
+				project_id,
+				group_id,
+				&retired_carbon_credit_data,
+			)?;
+
 			//Store the details of retired batches in storage
 			RetiredCredits::<T>::insert(group.asset_id, item_id, retired_carbon_credit_data);
+			RetiredCreditsByAccount::<T>::mutate(&from, |total| *total = total.saturating_add(amount));
+
+			let batches_retired = batch_retire_data_list.len() as u32;
 
 			// emit event
 			Self::deposit_event(Event::CarbonCreditRetired {
@@ -636,9 +731,54 @@ impl<T: Config> Pallet<T> {
 				amount,
 				retire_data: batch_retire_data_list,
 				reason: ret_reason,
+				beneficiary,
 			});
 
-			Ok(())
+			Ok(batches_retired)
 		})
 	}
+
+	/// Set the details of a retirement as attributes of the NFT `item_id` representing it
+	/// The details of every retired batch are stored under keys prefixed by the batch index, for
+	/// example `batch_0_uuid`, `batch_0_issuance_year` and `batch_0_count`. The accounts are
+	/// stored as `0x` prefixed hex strings of the encoded account ids
+	pub fn set_retirement_attributes(
+		asset_id: T::AssetId,
+		item_id: T::ItemId,
+		project_id: T::ProjectId,
+		group_id: T::GroupId,
+		retirement: &RetiredCarbonCreditsData<T>,
+	) -> DispatchResult {
+		let set_attribute = |key: &[u8], value: &[u8]| {
+			T::NFTHandler::set_attribute(&asset_id, &item_id, key, value)
+		};
+
+		set_attribute(b"project_id", project_id.to_string().as_bytes())?;
+		set_attribute(b"group_id", group_id.to_string().as_bytes())?;
+		set_attribute(b"account", Self::account_to_hex(&retirement.account).as_bytes())?;
+		if let Some(beneficiary) = &retirement.beneficiary {
+			set_attribute(b"beneficiary", Self::account_to_hex(beneficiary).as_bytes())?;
+		}
+		set_attribute(b"count", retirement.count.saturated_into::<u128>().to_string().as_bytes())?;
+		set_attribute(b"reason", &retirement.reason)?;
+
+		for (index, batch) in retirement.retire_data.iter().enumerate() {
+			set_attribute(format!("batch_{}_uuid", index).as_bytes(), &batch.uuid)?;
+			set_attribute(
+				format!("batch_{}_issuance_year", index).as_bytes(),
+				batch.issuance_year.to_string().as_bytes(),
+			)?;
+			set_attribute(
+				format!("batch_{}_count", index).as_bytes(),
+				batch.count.saturated_into::<u128>().to_string().as_bytes(),
+			)?;
+		}
+
+		Ok(())
+	}
+
+	/// Format an account id as a `0x` prefixed hex string of its encoding
+	pub fn account_to_hex(account: &T::AccountId) -> String {
+		format!("0x{}", HexDisplay::from(&account.encode()))
+	}
 }
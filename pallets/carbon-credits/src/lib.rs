@@ -23,8 +23,9 @@
 //!
 //! * `create`: Creates a new project onchain with details of batches of credits
 //! * `resubmit`: Resubmit data for a project that has not been approved
-//! * `mint`: Mint a specified amount of token credits
-//! * `retire`: Burn a specified amount of token credits
+//! * `mint`: Mint a specified amount of token credits, optionally listing them for sale
+//! * `retire`: Burn a specified amount of token credits, optionally on behalf of a beneficiary. A
+//!   certificate NFT describing the retirement is minted to the caller
 //!
 //! ### Permissioned Functions
 //!
@@ -55,8 +56,11 @@ mod functions;
 pub mod migration;
 pub use functions::*;
 
+mod nft;
+pub use nft::*;
+
 mod weights;
-use frame_support::{pallet_prelude::DispatchResult, traits::Contains};
+use frame_support::pallet_prelude::DispatchResult;
 pub use weights::WeightInfo;
 
 #[frame_support::pallet]
@@ -71,6 +75,7 @@ pub mod pallet {
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
+	use primitives::{KycLevelProvider, MarketplaceListing, UserLevel};
 	use sp_runtime::traits::{AtLeast32BitUnsigned, CheckedAdd, One};
 	use sp_std::{convert::TryInto, vec::Vec};
 
@@ -166,15 +171,26 @@ pub mod pallet {
 
 		// NFT handler config
 		type NFTHandler: NFTCreate<Self::AccountId, CollectionId = Self::AssetId, ItemId = Self::ItemId>
-			+ NFTMutate<Self::AccountId>;
+			+ NFTMutate<Self::AccountId>
+			+ CertificateCollectionMigration<Self::AccountId, Self::AssetId>;
 
 		/// KYC provider config
-		type KYCProvider: Contains<Self::AccountId>;
+		type KYCProvider: KycLevelProvider<Self::AccountId>;
+
+		/// Minimum KYC level required to create or resubmit a project
+		#[pallet::constant]
+		type MinKycLevelToCreate: Get<UserLevel>;
+
+		/// Minimum KYC level required to retire credits
+		#[pallet::constant]
+		type MinKycLevelToRetire: Get<UserLevel>;
 
 		/// The origin which may forcibly set storage or add authorised accounts
 		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		/// Marketplace Escrow provider
 		type MarketplaceEscrow: Get<Self::AccountId>;
+		/// Marketplace where credits minted to the escrow are listed for sale
+		type Marketplace: MarketplaceListing<Self::AccountId, Self::AssetId, Self::Balance>;
 		/// Maximum amount of authorised accounts permitted
 		type MaxAuthorizedAccountCount: Get<u32>;
 		/// Maximum amount of royalty recipient accounts permitted
@@ -197,8 +213,11 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 	}
 
-	#[pallet::pallet]
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -246,6 +265,24 @@ pub mod pallet {
 		RetiredCarbonCreditsData<T>,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn retired_credits_of)]
+	/// The total amount of CarbonCredits retired by each account
+	pub type RetiredCreditsByAccount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Balance, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::unbounded]
+	/// Progress of the migration to version 4, only set while it runs in `on_idle`
+	pub type RetirementMigration<T: Config> = StorageValue<_, RetirementMigrationStage>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			crate::migration::v4::migrate_retirements::<T>(remaining_weight)
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -302,6 +339,8 @@ pub mod pallet {
 			retire_data: BatchRetireDataList<T>,
 			/// reason for retirement
 			reason: ShortStringOf<T>,
+			/// The account the credits were retired on behalf of
+			beneficiary: Option<T::AccountId>,
 		},
 		/// A project details has been updated
 		ProjectUpdated {
@@ -360,6 +399,12 @@ pub mod pallet {
 		ApprovalAlreadyProcessed,
 		/// Retirement reason out of bounds
 		RetirementReasonOutOfBounds,
+		/// A listing price is required to list minted credits to the marketplace
+		ListingPriceRequired,
+		/// The KYC level of the account is below the minimum required for the action
+		KYCLevelTooLow,
+		/// The royalties of the project add up to more than 100%
+		RoyaltiesExceedHundredPercent,
 	}
 
 	#[pallet::call]
@@ -370,7 +415,7 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::create())]
 		pub fn create(origin: OriginFor<T>, params: ProjectCreateParams<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			Self::check_kyc_approval(&sender)?;
+			Self::check_kyc_level(&sender, T::MinKycLevelToCreate::get())?;
 			let project_id = Self::create_project(sender, params)?;
 			// emit event
 			Self::deposit_event(Event::ProjectCreated { project_id });
@@ -387,7 +432,7 @@ pub mod pallet {
 			params: ProjectCreateParams<T>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			Self::check_kyc_approval(&sender)?;
+			Self::check_kyc_level(&sender, T::MinKycLevelToCreate::get())?;
 			Self::resubmit_project(sender, project_id, params)
 		}
 
@@ -407,6 +452,8 @@ pub mod pallet {
 		/// The tokens are always minted in the ascending order of credits, for example, if the
 		/// `amount_to_mint` is 150 and the project has 100 tokens of 2019 and 2020 year. Then we
 		/// mint 100 from 2019 and 50 from 2020.
+		/// If `list_to_marketplace` is true, the tokens are minted to the marketplace escrow and
+		/// listed for sale at `listing_price` per unit on behalf of the project originator.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::mint())]
 		pub fn mint(
@@ -415,6 +462,7 @@ pub mod pallet {
 			group_id: T::GroupId,
 			amount_to_mint: T::Balance,
 			list_to_marketplace: bool,
+			listing_price: Option<MarketplacePriceOf<T>>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			Self::check_authorized_account(&sender)?;
@@ -425,6 +473,7 @@ pub mod pallet {
 				group_id,
 				amount_to_mint,
 				list_to_marketplace,
+				listing_price,
 			)
 		}
 
@@ -432,18 +481,29 @@ pub mod pallet {
 		/// The tokens are always retired in the ascending order of credits, for example, if the
 		/// `amount` is 150 and the project has 100 tokens of 2019 and 2020 year. Then we retire
 		/// 100 from 2019 and 50 from 2020.
+		/// The retirement can be made on behalf of a `beneficiary`, the certificate NFT is minted
+		/// to the caller and records both the caller and the beneficiary.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::retire())]
+		#[pallet::weight(T::WeightInfo::retire(T::MaxGroupSize::get()))]
 		pub fn retire(
 			origin: OriginFor<T>,
 			project_id: T::ProjectId,
 			group_id: T::GroupId,
 			amount: T::Balance,
 			reason: Option<Vec<u8>>,
-		) -> DispatchResult {
+			beneficiary: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			Self::check_kyc_approval(&sender)?;
-			Self::retire_carbon_credits(sender, project_id, group_id, amount, reason)
+			Self::check_kyc_level(&sender, T::MinKycLevelToRetire::get())?;
+			let batches_retired = Self::retire_carbon_credits(
+				sender,
+				project_id,
+				group_id,
+				amount,
+				reason,
+				beneficiary,
+			)?;
+			Ok(Some(T::WeightInfo::retire(batches_retired)).into())
 		}
 
 		/// Add a new account to the list of authorised Accounts
@@ -558,6 +618,7 @@ pub mod pallet {
 			amount_to_mint: T::Balance,
 			list_to_marketplace: bool,
 			group_id: T::GroupId,
+			listing_price: Option<MarketplacePriceOf<T>>,
 		) -> DispatchResult {
 			T::ForceOrigin::ensure_origin(origin)?;
 			Self::check_kyc_approval(&sender)?;
@@ -568,6 +629,7 @@ pub mod pallet {
 				group_id,
 				amount_to_mint,
 				list_to_marketplace,
+				listing_price,
 			)?;
 			Ok(())
 		}
@@ -636,6 +698,27 @@ impl<T: Config> primitives::CarbonCreditsValidator for Pallet<T> {
 		AssetIdLookup::<T>::get(asset_id)
 	}
 
+	fn get_royalties(
+		project_id: &Self::ProjectId,
+	) -> sp_std::vec::Vec<primitives::Royalty<Self::Address>> {
+		Projects::<T>::get(project_id)
+			.and_then(|project| project.royalties)
+			.map(|royalties| royalties.into_inner())
+			.unwrap_or_default()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_royalties(
+		project_id: &Self::ProjectId,
+		royalties: sp_std::vec::Vec<primitives::Royalty<Self::Address>>,
+	) {
+		Projects::<T>::mutate(project_id, |project| {
+			if let Some(project) = project {
+				project.royalties = royalties.try_into().ok();
+			}
+		});
+	}
+
 	fn retire_credits(
 		sender: Self::Address,
 		project_id: Self::ProjectId,
@@ -643,6 +726,7 @@ impl<T: Config> primitives::CarbonCreditsValidator for Pallet<T> {
 		amount: Self::Amount,
 		reason: Option<sp_std::vec::Vec<u8>>,
 	) -> DispatchResult {
-		Self::retire_carbon_credits(sender, project_id, group_id, amount, reason)
+		Self::retire_carbon_credits(sender, project_id, group_id, amount, reason, None)?;
+		Ok(())
 	}
 }
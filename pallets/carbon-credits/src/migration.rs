@@ -25,3 +25,154 @@ pub mod v3 {
 		}
 	}
 }
+
+pub mod v4 {
+	use super::*;
+
+	use codec::{Decode, DecodeAll, Encode};
+	use frame_support::{
+		pallet_prelude::*,
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+	use frame_system::pallet_prelude::BlockNumberFor;
+	use sp_std::marker::PhantomData;
+
+	/// The retirement data before version 4, without a beneficiary
+	#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+	pub struct OldRetiredCarbonCreditsData<T: Config> {
+		pub account: T::AccountId,
+		pub retire_data: BatchRetireDataList<T>,
+		pub timestamp: BlockNumberFor<T>,
+		pub count: T::Balance,
+		pub reason: ShortStringOf<T>,
+	}
+
+	/// Add the beneficiary to the existing retirements and make the existing certificate
+	/// collections free holding.
+	///
+	/// The existing retirements were made by the retiring account for itself, so they have no
+	/// beneficiary. The certificate collections created before version 4 hold deposits for their
+	/// items and attributes, which the pallet account cannot pay for the retirement attributes,
+	/// so they are switched to free holding with the pallet account as owner, issuer, admin and
+	/// freezer, like the collections created by `UniquesCertificateHandler`. This requires the
+	/// `ForceOrigin` of pallet-uniques to accept the root origin.
+	///
+	/// The upgrade only starts the migration, the collections and then the retirements are
+	/// visited in `on_idle` by [`migrate_retirements`] so the work of a block stays bounded. A
+	/// retirement is not returned by the runtime api until it is migrated, and retiring credits of
+	/// a collection that is not migrated yet can fail on the attribute deposit.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 4 {
+				log::info!("carbon-credits migration to v4 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			RetirementMigration::<T>::put(RetirementMigrationStage::Collections(None));
+			StorageVersion::new(4).put::<Pallet<T>>();
+			log::info!("carbon-credits migration to v4 started, retirements are migrated on idle");
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+	}
+
+	/// Run the migration to version 4 within `limit`, returning the weight used
+	pub fn migrate_retirements<T: Config>(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut used = db_weight.reads(1);
+		let Some(mut stage) = RetirementMigration::<T>::get() else { return used };
+
+		// reading the key of a collection and switching the collection to free holding
+		let per_collection = db_weight.reads_writes(2, 1);
+		// reading a retirement and writing it back with the beneficiary
+		let per_retirement = db_weight.reads_writes(2, 1);
+
+		loop {
+			match stage {
+				RetirementMigrationStage::Collections(cursor) => {
+					let mut iter = match cursor {
+						Some(raw_key) => NextItemId::<T>::iter_keys_from(raw_key),
+						None => NextItemId::<T>::iter_keys(),
+					};
+					let pallet_account = Pallet::<T>::account_id();
+					loop {
+						if used.saturating_add(per_collection).any_gt(limit) {
+							RetirementMigration::<T>::put(RetirementMigrationStage::Collections(
+								Some(iter.last_raw_key().to_vec()),
+							));
+							return used.saturating_add(db_weight.writes(1));
+						}
+						let Some(asset_id) = iter.next() else { break };
+						used.saturating_accrue(per_collection);
+
+						if let Err(err) =
+							T::NFTHandler::make_free_holding(&asset_id, &pallet_account)
+						{
+							log::error!(
+								"carbon-credits migration to v4 failed to make collection {} free holding: {:?}",
+								asset_id,
+								err
+							);
+						}
+					}
+					stage = RetirementMigrationStage::Retirements(None);
+				},
+				RetirementMigrationStage::Retirements(cursor) => {
+					let mut iter = match cursor {
+						Some(raw_key) => RetiredCredits::<T>::iter_keys_from(raw_key),
+						None => RetiredCredits::<T>::iter_keys(),
+					};
+					loop {
+						if used.saturating_add(per_retirement).any_gt(limit) {
+							RetirementMigration::<T>::put(RetirementMigrationStage::Retirements(
+								Some(iter.last_raw_key().to_vec()),
+							));
+							return used.saturating_add(db_weight.writes(1));
+						}
+						let Some((asset_id, item_id)) = iter.next() else {
+							RetirementMigration::<T>::kill();
+							log::info!("carbon-credits migration to v4 done");
+							return used.saturating_add(db_weight.writes(1));
+						};
+						used.saturating_accrue(per_retirement);
+
+						migrate_retirement::<T>(asset_id, item_id);
+					}
+				},
+			}
+		}
+	}
+
+	/// Add the beneficiary to the given retirement, retirements made after the upgrade are kept
+	fn migrate_retirement<T: Config>(asset_id: T::AssetId, item_id: T::ItemId) {
+		let key = RetiredCredits::<T>::hashed_key_for(asset_id, item_id);
+		let Some(raw) = unhashed::get_raw(&key) else { return };
+		if RetiredCarbonCreditsData::<T>::decode_all(&mut &raw[..]).is_ok() {
+			return;
+		}
+
+		match OldRetiredCarbonCreditsData::<T>::decode_all(&mut &raw[..]) {
+			Ok(old) => RetiredCredits::<T>::insert(
+				asset_id,
+				item_id,
+				RetiredCarbonCreditsData {
+					account: old.account,
+					retire_data: old.retire_data,
+					timestamp: old.timestamp,
+					count: old.count,
+					reason: old.reason,
+					beneficiary: None,
+				},
+			),
+			Err(err) => log::error!(
+				"carbon-credits migration to v4 failed to decode retirement {:?} of collection {}: {:?}",
+				item_id,
+				asset_id,
+				err
+			),
+		}
+	}
+}
@@ -4,12 +4,14 @@
 use crate as pallet_carbon_credits;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
+	pallet_prelude::DispatchResult,
 	parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32},
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, Contains},
 	PalletId,
 };
 
 use frame_system::{EnsureRoot, EnsureSigned};
+use primitives::{KycLevelProvider, MarketplaceListing, UserLevel};
 use scale_info::TypeInfo;
 use sp_core::{ConstU16, ConstU64, H256};
 use sp_runtime::{
@@ -127,6 +129,48 @@ parameter_types! {
   pub const MaxGroupSize: u32 = 10;
 }
 
+parameter_types! {
+	/// (escrow, owner, asset_id, units, price_per_unit) of every listing created
+	pub static MarketplaceListings: Vec<(u64, u64, u32, u128, u128)> = vec![];
+}
+
+parameter_types! {
+	/// KYC levels of members above the default `KYCLevel1`
+	pub static MockKycLevels: Vec<(u64, UserLevel)> = vec![];
+	pub static MinKycLevelToCreate: UserLevel = UserLevel::KYCLevel1;
+	pub static MinKycLevelToRetire: UserLevel = UserLevel::KYCLevel1;
+}
+
+pub struct MockKycProvider;
+impl KycLevelProvider<u64> for MockKycProvider {
+	fn kyc_level(who: &u64) -> Option<UserLevel> {
+		if !KYCMembership::contains(who) {
+			return None;
+		}
+
+		let level = MockKycLevels::get().into_iter().find(|(account, _)| account == who);
+		Some(level.map(|(_, level)| level).unwrap_or_default())
+	}
+}
+
+pub struct MockMarketplace;
+impl MarketplaceListing<u64, u32, u128> for MockMarketplace {
+	type Price = u128;
+
+	fn list_from_escrow(
+		escrow: &u64,
+		owner: &u64,
+		asset_id: u32,
+		units: u128,
+		price_per_unit: u128,
+	) -> DispatchResult {
+		MarketplaceListings::mutate(|listings| {
+			listings.push((*escrow, *owner, asset_id, units, price_per_unit))
+		});
+		Ok(())
+	}
+}
+
 impl pallet_carbon_credits::Config for Test {
 	type AssetHandler = Assets;
 	type AssetId = u32;
@@ -136,8 +180,11 @@ impl pallet_carbon_credits::Config for Test {
 	type ItemId = u32;
 	type ProjectId = u32;
 	type GroupId = u32;
-	type KYCProvider = KYCMembership;
+	type KYCProvider = MockKycProvider;
+	type MinKycLevelToCreate = MinKycLevelToCreate;
+	type MinKycLevelToRetire = MinKycLevelToRetire;
 	type MarketplaceEscrow = MarketplaceEscrowAccount;
+	type Marketplace = MockMarketplace;
 	type MaxAuthorizedAccountCount = ConstU32<2>;
 	type MaxDocumentCount = ConstU32<2>;
 	type MaxGroupSize = MaxGroupSize;
@@ -147,7 +194,7 @@ impl pallet_carbon_credits::Config for Test {
 	type MaxRoyaltyRecipients = ConstU32<5>;
 	type MaxShortStringLength = ConstU32<20>;
 	type MinProjectId = ConstU32<1000>;
-	type NFTHandler = Uniques;
+	type NFTHandler = crate::UniquesCertificateHandler<Test>;
 	type PalletId = CarbonCreditsPalletId;
 	type WeightInfo = ();
 }
@@ -0,0 +1,135 @@
+// This file is part of BitGreen.
+// Copyright (C) 2022 BitGreen.
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//! NFT handler for the retirement certificates
+//!
+//! pallet-uniques does not support setting attributes through the `nonfungibles` traits, this
+//! wrapper forwards all the calls to pallet-uniques and sets the attributes as the collection
+//! owner. The certificate collections are created without deposit and with free holding, so no
+//! funds need to be reserved from the pallet account for the certificates and their attributes.
+//! The collections created before this handler was used are not free holding, they are converted
+//! by `migration::v4::MigrateToV4`.
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::tokens::nonfungibles::{Create, Inspect, Mutate},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	DispatchError,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// Converts the certificate collections created before `UniquesCertificateHandler` was used
+pub trait CertificateCollectionMigration<AccountId, CollectionId> {
+	/// Make `collection` free holding, with `owner` as its owner, issuer, admin and freezer
+	fn make_free_holding(collection: &CollectionId, owner: &AccountId) -> DispatchResult;
+}
+
+/// Wrapper over pallet-uniques to be used as the `NFTHandler` of the pallet
+pub struct UniquesCertificateHandler<T, I = ()>(PhantomData<(T, I)>);
+
+impl<T: pallet_uniques::Config<I>, I: 'static> Inspect<T::AccountId>
+	for UniquesCertificateHandler<T, I>
+{
+	type ItemId = T::ItemId;
+	type CollectionId = T::CollectionId;
+
+	fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+		<pallet_uniques::Pallet<T, I> as Inspect<T::AccountId>>::owner(collection, item)
+	}
+
+	fn collection_owner(collection: &Self::CollectionId) -> Option<T::AccountId> {
+		<pallet_uniques::Pallet<T, I> as Inspect<T::AccountId>>::collection_owner(collection)
+	}
+
+	fn attribute(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		<pallet_uniques::Pallet<T, I> as Inspect<T::AccountId>>::attribute(collection, item, key)
+	}
+
+	fn can_transfer(collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		<pallet_uniques::Pallet<T, I> as Inspect<T::AccountId>>::can_transfer(collection, item)
+	}
+}
+
+impl<T: pallet_uniques::Config<I>, I: 'static> Create<T::AccountId>
+	for UniquesCertificateHandler<T, I>
+{
+	fn create_collection(
+		collection: &Self::CollectionId,
+		who: &T::AccountId,
+		admin: &T::AccountId,
+	) -> DispatchResult {
+		pallet_uniques::Pallet::<T, I>::do_create_collection(
+			*collection,
+			who.clone(),
+			admin.clone(),
+			Zero::zero(),
+			true,
+			pallet_uniques::Event::ForceCreated { collection: *collection, owner: who.clone() },
+		)
+	}
+}
+
+impl<T: pallet_uniques::Config<I>, I: 'static> Mutate<T::AccountId>
+	for UniquesCertificateHandler<T, I>
+{
+	fn mint_into(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		<pallet_uniques::Pallet<T, I> as Mutate<T::AccountId>>::mint_into(collection, item, who)
+	}
+
+	fn burn(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		maybe_check_owner: Option<&T::AccountId>,
+	) -> DispatchResult {
+		<pallet_uniques::Pallet<T, I> as Mutate<T::AccountId>>::burn(
+			collection,
+			item,
+			maybe_check_owner,
+		)
+	}
+
+	fn set_attribute(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+		value: &[u8],
+	) -> DispatchResult {
+		let owner = Self::collection_owner(collection)
+			.ok_or(pallet_uniques::Error::<T, I>::UnknownCollection)?;
+		pallet_uniques::Pallet::<T, I>::set_attribute(
+			RawOrigin::Signed(owner).into(),
+			*collection,
+			Some(*item),
+			key.to_vec().try_into().map_err(|_| DispatchError::Other("AttributeKeyTooLong"))?,
+			value.to_vec().try_into().map_err(|_| DispatchError::Other("AttributeValueTooLong"))?,
+		)
+	}
+}
+
+impl<T: pallet_uniques::Config<I>, I: 'static>
+	CertificateCollectionMigration<T::AccountId, T::CollectionId> for UniquesCertificateHandler<T, I>
+{
+	fn make_free_holding(collection: &T::CollectionId, owner: &T::AccountId) -> DispatchResult {
+		let owner = T::Lookup::unlookup(owner.clone());
+		pallet_uniques::Pallet::<T, I>::force_item_status(
+			RawOrigin::Root.into(),
+			*collection,
+			owner.clone(),
+			owner.clone(),
+			owner.clone(),
+			owner,
+			true,
+			false,
+		)
+	}
+}
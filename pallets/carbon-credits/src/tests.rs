@@ -4,15 +4,27 @@
 //! Tests for CarbonCredits pallet
 use crate::{
 	mock::*, BatchGroupListOf, BatchGroupOf, BatchOf, Config, Error, NextItemId,
-	ProjectCreateParams, Projects, RegistryListOf, RetiredCredits, SDGTypesListOf,
+	ProjectCreateParams, Projects, RegistryListOf, RetiredCredits, RetirementMigration,
+	SDGTypesListOf, UniquesCertificateHandler,
 };
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::tokens::fungibles::{metadata::Inspect as MetadataInspect, Inspect},
+	storage::unhashed,
+	traits::{
+		tokens::{
+			fungibles::{metadata::Inspect as MetadataInspect, Inspect},
+			nonfungibles::{Create as NFTCreate, Inspect as NFTInspect, Mutate as NFTMutate},
+		},
+		GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+	},
+	weights::Weight,
 	BoundedVec, PalletId,
 };
 use frame_system::RawOrigin;
-use primitives::{Batch, RegistryDetails, RegistryName, Royalty, SDGDetails, SdgType};
+use primitives::{
+	Batch, CarbonCreditsValidator, RegistryDetails, RegistryName, Royalty, SDGDetails, SdgType,
+	UserLevel,
+};
 use sp_runtime::{traits::AccountIdConversion, Percent, TokenError::FundsUnavailable};
 use sp_std::convert::TryInto;
 
@@ -441,6 +453,39 @@ fn create_fails_for_empty_batches() {
 	});
 }
 
+#[test]
+fn create_requires_minimum_kyc_level() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+
+		// account without kyc cannot create
+		assert_noop!(
+			CarbonCredits::create(
+				RawOrigin::Signed(2).into(),
+				get_default_creation_params::<Test>()
+			),
+			Error::<Test>::KYCAuthorisationFailed
+		);
+
+		// account below the minimum level cannot create
+		MinKycLevelToCreate::set(UserLevel::KYCLevel2);
+		assert_noop!(
+			CarbonCredits::create(
+				RawOrigin::Signed(originator_account).into(),
+				get_default_creation_params::<Test>()
+			),
+			Error::<Test>::KYCLevelTooLow
+		);
+
+		// account at a higher level can create
+		MockKycLevels::set(vec![(originator_account, UserLevel::KYCLevel3)]);
+		assert_ok!(CarbonCredits::create(
+			RawOrigin::Signed(originator_account).into(),
+			get_default_creation_params::<Test>()
+		));
+	});
+}
+
 #[test]
 fn resubmit_works() {
 	new_test_ext().execute_with(|| {
@@ -617,7 +662,7 @@ fn cleanup_after_project_reject_works() {
 fn mint_non_authorised_account_should_fail() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			CarbonCredits::mint(RawOrigin::Signed(1).into(), 1001, 100, 100, false),
+			CarbonCredits::mint(RawOrigin::Signed(1).into(), 1001, 100, 100, false, None),
 			Error::<Test>::NotAuthorised
 		);
 	});
@@ -630,7 +675,7 @@ fn mint_non_existent_project_should_fail() {
 
 		// minting a non existent project should fail
 		assert_noop!(
-			CarbonCredits::mint(RawOrigin::Signed(1).into(), 1001, 100, 100, false),
+			CarbonCredits::mint(RawOrigin::Signed(1).into(), 1001, 100, 100, false, None),
 			Error::<Test>::ProjectNotFound
 		);
 	});
@@ -660,7 +705,8 @@ fn mint_non_approved_project_should_fail() {
 				project_id,
 				group_id,
 				amount_to_mint,
-				list_to_marketplace
+				list_to_marketplace,
+				None
 			),
 			Error::<Test>::ProjectNotApproved
 		);
@@ -713,7 +759,8 @@ fn test_cannot_mint_more_than_supply() {
 				project_id,
 				group_id,
 				10_000,
-				list_to_marketplace
+				list_to_marketplace,
+				None
 			),
 			Error::<Test>::AmountGreaterThanSupply
 		);
@@ -740,7 +787,8 @@ fn mint_without_list_to_marketplace_works_for_single_batch() {
 			project_id,
 			group_id,
 			amount_to_mint,
-			list_to_marketplace
+			list_to_marketplace,
+			None
 		));
 
 		assert_eq!(
@@ -833,7 +881,8 @@ fn mint_without_list_to_marketplace_works_for_multiple_batches() {
 			project_id,
 			group_id,
 			amount_to_mint,
-			list_to_marketplace
+			list_to_marketplace,
+			None
 		));
 
 		assert_eq!(
@@ -918,7 +967,8 @@ fn mint_without_list_to_marketplace_works_for_multiple_batches() {
 				project_id,
 				group_id,
 				amount_to_mint,
-				list_to_marketplace
+				list_to_marketplace,
+				None
 			),
 			Error::<Test>::AmountGreaterThanSupply
 		);
@@ -929,7 +979,8 @@ fn mint_without_list_to_marketplace_works_for_multiple_batches() {
 			project_id,
 			group_id,
 			50,
-			list_to_marketplace
+			list_to_marketplace,
+			None
 		));
 
 		// ensure minting worked correctly
@@ -960,14 +1011,90 @@ fn mint_without_list_to_marketplace_works_for_multiple_batches() {
 	});
 }
 
-// // TODO : Add tests for list_marketplace true
+#[test]
+fn mint_with_list_to_marketplace_works() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+		let authorised_account = 10;
+		let project_id = 0;
+		let group_id = 0;
+		// token minting params
+		let amount_to_mint: u128 = 50;
+		let listing_price: u128 = 20;
+		let list_to_marketplace = true;
+		let expected_asset_id = project_id;
+		let escrow_account = MarketplaceEscrowAccount::get();
+
+		create_and_approve_project(originator_account, authorised_account);
+
+		// listing without a price should fail
+		assert_noop!(
+			CarbonCredits::mint(
+				RawOrigin::Signed(authorised_account).into(),
+				project_id,
+				group_id,
+				amount_to_mint,
+				list_to_marketplace,
+				None
+			),
+			Error::<Test>::ListingPriceRequired
+		);
+
+		assert_ok!(CarbonCredits::mint(
+			RawOrigin::Signed(authorised_account).into(),
+			project_id,
+			group_id,
+			amount_to_mint,
+			list_to_marketplace,
+			Some(listing_price)
+		));
+
+		assert_eq!(
+			last_event(),
+			CarbonCreditsEvent::CarbonCreditMinted {
+				project_id,
+				group_id,
+				recipient: escrow_account,
+				amount: amount_to_mint
+			}
+			.into()
+		);
+
+		// the tokens are minted to the escrow and not to the originator
+		assert_eq!(Assets::balance(expected_asset_id, escrow_account), amount_to_mint);
+		assert_eq!(Assets::balance(expected_asset_id, originator_account), 0);
+
+		// the escrowed tokens are listed on behalf of the originator
+		assert_eq!(
+			MarketplaceListings::get(),
+			vec![(
+				escrow_account,
+				originator_account,
+				expected_asset_id,
+				amount_to_mint,
+				listing_price
+			)]
+		);
+
+		let stored_data = CarbonCredits::get_project_details(project_id).unwrap();
+		let group_data = stored_data.batch_groups.get(&group_id).unwrap();
+		assert_eq!(group_data.minted, amount_to_mint);
+	});
+}
 
 #[test]
 fn retire_non_existent_project_should_fail() {
 	new_test_ext().execute_with(|| {
 		// retire a non existent project should fail
 		assert_noop!(
-			CarbonCredits::retire(RawOrigin::Signed(10).into(), 1001, 100, 100, Default::default()),
+			CarbonCredits::retire(
+				RawOrigin::Signed(10).into(),
+				1001,
+				100,
+				100,
+				Default::default(),
+				None
+			),
 			Error::<Test>::ProjectNotFound
 		);
 	});
@@ -990,7 +1117,8 @@ fn test_retire_non_minted_project_should_fail() {
 				project_id,
 				group_id,
 				100u128,
-				Default::default()
+				Default::default(),
+				None
 			),
 			FundsUnavailable
 		);
@@ -1018,7 +1146,8 @@ fn test_retire_for_single_batch() {
 			project_id,
 			group_id,
 			amount_to_mint,
-			list_to_marketplace
+			list_to_marketplace,
+			None
 		));
 
 		// calling retire from an account that holds no token should fail
@@ -1028,7 +1157,8 @@ fn test_retire_for_single_batch() {
 				project_id,
 				group_id,
 				amount_to_mint,
-				Default::default()
+				Default::default(),
+				None
 			),
 			FundsUnavailable
 		);
@@ -1040,7 +1170,8 @@ fn test_retire_for_single_batch() {
 				project_id,
 				group_id,
 				amount_to_mint + 1,
-				Default::default()
+				Default::default(),
+				None
 			),
 			FundsUnavailable
 		);
@@ -1051,7 +1182,8 @@ fn test_retire_for_single_batch() {
 			project_id,
 			group_id,
 			amount_to_retire,
-			b"reason".to_vec().try_into().unwrap()
+			b"reason".to_vec().try_into().unwrap(),
+			None
 		));
 
 		// Ensure the retirement happend correctly
@@ -1118,7 +1250,8 @@ fn test_retire_for_single_batch() {
 				account: originator_account,
 				amount: amount_to_retire,
 				retire_data: stored_retired_data.retire_data,
-				reason: b"reason".to_vec().try_into().unwrap()
+				reason: b"reason".to_vec().try_into().unwrap(),
+				beneficiary: None
 			}
 			.into()
 		);
@@ -1129,7 +1262,8 @@ fn test_retire_for_single_batch() {
 			project_id,
 			group_id,
 			amount_to_mint - amount_to_retire,
-			Default::default()
+			Default::default(),
+			None
 		));
 
 		// Ensure the retirement happend correctly
@@ -1158,7 +1292,8 @@ fn test_retire_for_single_batch() {
 				project_id,
 				group_id,
 				amount_to_mint,
-				list_to_marketplace
+				list_to_marketplace,
+				None
 			),
 			Error::<Test>::AmountGreaterThanSupply
 		);
@@ -1213,7 +1348,8 @@ fn retire_for_multiple_batch() {
 			project_id,
 			group_id,
 			amount_to_mint,
-			list_to_marketplace
+			list_to_marketplace,
+			None
 		));
 
 		// cannot retire more than holdings
@@ -1223,7 +1359,8 @@ fn retire_for_multiple_batch() {
 				project_id,
 				group_id,
 				amount_to_mint + 1,
-				Default::default()
+				Default::default(),
+				None
 			),
 			FundsUnavailable
 		);
@@ -1234,7 +1371,8 @@ fn retire_for_multiple_batch() {
 			project_id,
 			group_id,
 			amount_to_retire,
-			Default::default()
+			Default::default(),
+			None
 		));
 
 		// Ensure the retirement happend correctly
@@ -1297,7 +1435,8 @@ fn retire_for_multiple_batch() {
 				account: originator_account,
 				amount: amount_to_retire,
 				retire_data: stored_retired_data.retire_data.clone(),
-				reason: Default::default()
+				reason: Default::default(),
+				beneficiary: None
 			}
 			.into()
 		);
@@ -1314,7 +1453,8 @@ fn retire_for_multiple_batch() {
 			project_id,
 			group_id,
 			amount_to_mint - amount_to_retire,
-			Default::default()
+			Default::default(),
+			None
 		));
 
 		// Ensure the retirement happend correctly
@@ -1352,7 +1492,8 @@ fn retire_for_multiple_batch() {
 				project_id,
 				group_id,
 				amount_to_mint,
-				list_to_marketplace
+				list_to_marketplace,
+				None
 			),
 			Error::<Test>::AmountGreaterThanSupply
 		);
@@ -1385,6 +1526,51 @@ fn retire_for_multiple_batch() {
 	});
 }
 
+#[test]
+fn retire_requires_minimum_kyc_level() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+		let authorised_account = 10;
+		let project_id = 0;
+		let group_id = 0;
+
+		create_and_approve_project(originator_account, authorised_account);
+		assert_ok!(CarbonCredits::mint(
+			RawOrigin::Signed(authorised_account).into(),
+			project_id,
+			group_id,
+			100,
+			false,
+			None
+		));
+
+		// account below the minimum level cannot retire
+		MinKycLevelToRetire::set(UserLevel::KYCLevel4);
+		MockKycLevels::set(vec![(originator_account, UserLevel::KYCLevel3)]);
+		assert_noop!(
+			CarbonCredits::retire(
+				RawOrigin::Signed(originator_account).into(),
+				project_id,
+				group_id,
+				10,
+				Default::default(),
+				None
+			),
+			Error::<Test>::KYCLevelTooLow
+		);
+
+		MockKycLevels::set(vec![(originator_account, UserLevel::KYCLevel4)]);
+		assert_ok!(CarbonCredits::retire(
+			RawOrigin::Signed(originator_account).into(),
+			project_id,
+			group_id,
+			10,
+			Default::default(),
+			None
+		));
+	});
+}
+
 #[test]
 fn force_approve_and_mint_credits_works() {
 	new_test_ext().execute_with(|| {
@@ -1410,6 +1596,7 @@ fn force_approve_and_mint_credits_works() {
 			amount_to_mint,
 			list_to_marketplace,
 			group_id,
+			None,
 		));
 
 		assert_eq!(
@@ -1602,3 +1789,286 @@ fn add_batch_group_works() {
 		assert_eq!(group_data.retired, 0_u32.into());
 	});
 }
+
+#[test]
+fn get_royalties_returns_project_royalties() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+		let project_id = 0;
+
+		// unknown project has no royalties
+		assert!(<CarbonCredits as CarbonCreditsValidator>::get_royalties(&project_id).is_empty());
+
+		let creation_params = get_default_creation_params::<Test>();
+		let expected_royalties = creation_params.royalties.clone().unwrap().into_inner();
+		assert_ok!(CarbonCredits::create(
+			RawOrigin::Signed(originator_account).into(),
+			creation_params
+		));
+
+		assert_eq!(
+			<CarbonCredits as CarbonCreditsValidator>::get_royalties(&project_id),
+			expected_royalties
+		);
+	});
+}
+
+#[test]
+fn royalties_above_hundred_percent_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+		let project_id = 0;
+
+		let royalties_of = |percents: Vec<u8>| {
+			Some(
+				percents
+					.into_iter()
+					.enumerate()
+					.map(|(i, percent)| Royalty::<u64> {
+						account_id: i as u64 + 1,
+						percent_of_fees: Percent::from_percent(percent),
+					})
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap(),
+			)
+		};
+
+		let mut creation_params = get_default_creation_params::<Test>();
+		creation_params.royalties = royalties_of(vec![60, 41]);
+		assert_noop!(
+			CarbonCredits::create(
+				RawOrigin::Signed(originator_account).into(),
+				creation_params.clone()
+			),
+			Error::<Test>::RoyaltiesExceedHundredPercent
+		);
+
+		// royalties adding up to exactly 100% are accepted
+		creation_params.royalties = royalties_of(vec![60, 40]);
+		assert_ok!(CarbonCredits::create(
+			RawOrigin::Signed(originator_account).into(),
+			creation_params.clone()
+		));
+
+		// resubmitting a project checks the royalties
+		creation_params.royalties = royalties_of(vec![100, 1]);
+		assert_noop!(
+			CarbonCredits::resubmit(
+				RawOrigin::Signed(originator_account).into(),
+				project_id,
+				creation_params.clone()
+			),
+			Error::<Test>::RoyaltiesExceedHundredPercent
+		);
+
+		// updating an approved project checks the royalties
+		creation_params.royalties = royalties_of(vec![50]);
+		assert_ok!(CarbonCredits::resubmit(
+			RawOrigin::Signed(originator_account).into(),
+			project_id,
+			creation_params.clone()
+		));
+		assert_ok!(CarbonCredits::approve_project(RawOrigin::Root.into(), project_id, true));
+		creation_params.royalties = royalties_of(vec![50, 30, 30]);
+		assert_noop!(
+			CarbonCredits::update_project_details(
+				RawOrigin::Signed(originator_account).into(),
+				project_id,
+				creation_params
+			),
+			Error::<Test>::RoyaltiesExceedHundredPercent
+		);
+	});
+}
+
+#[test]
+fn runtime_api_helpers_return_project_and_retirement_data() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+		let authorised_account = 10;
+		let project_id = 0;
+		let group_id = 0;
+		let amount_to_mint: u128 = 100;
+		let amount_to_retire: u128 = 30;
+		let expected_asset_id = 0;
+
+		// nothing is returned for an unknown project
+		assert!(CarbonCredits::get_project_groups(project_id).is_empty());
+		assert_eq!(CarbonCredits::get_asset_project(expected_asset_id), None);
+
+		create_and_approve_project(originator_account, authorised_account);
+
+		assert_ok!(CarbonCredits::mint(
+			RawOrigin::Signed(authorised_account).into(),
+			project_id,
+			group_id,
+			amount_to_mint,
+			false,
+			None
+		));
+
+		assert_ok!(CarbonCredits::retire(
+			RawOrigin::Signed(originator_account).into(),
+			project_id,
+			group_id,
+			amount_to_retire,
+			b"reason".to_vec().try_into().unwrap(),
+			None
+		));
+
+		let groups = CarbonCredits::get_project_groups(project_id);
+		assert_eq!(groups.len(), 1);
+		let (stored_group_id, group) = &groups[0];
+		assert_eq!(*stored_group_id, group_id);
+		assert_eq!(group.minted, amount_to_mint);
+		assert_eq!(group.retired, amount_to_retire);
+
+		assert_eq!(
+			CarbonCredits::get_asset_project(expected_asset_id),
+			Some((project_id, group_id))
+		);
+
+		let retirement = CarbonCredits::get_retirement(expected_asset_id, 0).unwrap();
+		assert_eq!(retirement.account, originator_account);
+		assert_eq!(retirement.count, amount_to_retire);
+
+		assert_eq!(
+			CarbonCredits::get_retirements_by_account(&originator_account),
+			vec![(expected_asset_id, 0, retirement)]
+		);
+		assert!(CarbonCredits::get_retirements_by_account(&authorised_account).is_empty());
+	});
+}
+
+#[test]
+fn retire_on_behalf_of_beneficiary_writes_certificate_attributes() {
+	new_test_ext().execute_with(|| {
+		let originator_account = 1;
+		let authorised_account = 10;
+		let beneficiary = 5;
+		let project_id = 0;
+		let group_id = 0;
+		let expected_asset_id = 0;
+		let item_id = 0;
+
+		create_and_approve_project_batch(originator_account, authorised_account);
+
+		assert_ok!(CarbonCredits::mint(
+			RawOrigin::Signed(authorised_account).into(),
+			project_id,
+			group_id,
+			200,
+			false,
+			None
+		));
+
+		assert_ok!(CarbonCredits::retire(
+			RawOrigin::Signed(originator_account).into(),
+			project_id,
+			group_id,
+			150,
+			Some(b"reason".to_vec()),
+			Some(beneficiary)
+		));
+
+		// the certificate is minted to the retiring account and records the beneficiary
+		assert_eq!(Uniques::owner(expected_asset_id, item_id).unwrap(), originator_account);
+		let stored_retired_data = RetiredCredits::<Test>::get(expected_asset_id, item_id).unwrap();
+		assert_eq!(stored_retired_data.account, originator_account);
+		assert_eq!(stored_retired_data.beneficiary, Some(beneficiary));
+		assert_eq!(CarbonCredits::retired_credits_of(originator_account), 150);
+
+		let attribute = |key: &[u8]| {
+			<Uniques as NFTInspect<u64>>::attribute(&expected_asset_id, &item_id, key).unwrap()
+		};
+		assert_eq!(attribute(b"project_id"), b"0".to_vec());
+		assert_eq!(attribute(b"group_id"), b"0".to_vec());
+		assert_eq!(attribute(b"account"), b"0x0100000000000000".to_vec());
+		assert_eq!(attribute(b"beneficiary"), b"0x0500000000000000".to_vec());
+		assert_eq!(attribute(b"count"), b"150".to_vec());
+		assert_eq!(attribute(b"reason"), b"reason".to_vec());
+		assert_eq!(attribute(b"batch_0_uuid"), b"batch_uuid".to_vec());
+		assert_eq!(attribute(b"batch_0_issuance_year"), b"2020".to_vec());
+		assert_eq!(attribute(b"batch_0_count"), b"100".to_vec());
+		assert_eq!(attribute(b"batch_1_uuid"), b"batch_uuid_2".to_vec());
+		assert_eq!(attribute(b"batch_1_issuance_year"), b"2021".to_vec());
+		assert_eq!(attribute(b"batch_1_count"), b"50".to_vec());
+
+		assert_eq!(
+			last_event(),
+			CarbonCreditsEvent::CarbonCreditRetired {
+				project_id,
+				group_id,
+				asset_id: expected_asset_id,
+				account: originator_account,
+				amount: 150,
+				retire_data: stored_retired_data.retire_data,
+				reason: b"reason".to_vec().try_into().unwrap(),
+				beneficiary: Some(beneficiary)
+			}
+			.into()
+		);
+	});
+}
+
+#[test]
+fn migration_to_v4_adds_beneficiary_and_frees_certificate_collections() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let item_id = 0;
+		let retiring_account = 1;
+		let pallet_account: u64 = PalletId(*b"bitg/ccp").into_account_truncating();
+
+		// a certificate collection created before version 4 holds deposits
+		assert_ok!(<Uniques as NFTCreate<u64>>::create_collection(
+			&asset_id,
+			&pallet_account,
+			&pallet_account
+		));
+		assert_ok!(<Uniques as NFTMutate<u64>>::mint_into(&asset_id, &item_id, &retiring_account));
+		NextItemId::<Test>::insert(asset_id, 1);
+		let old_retirement = crate::migration::v4::OldRetiredCarbonCreditsData::<Test> {
+			account: retiring_account,
+			retire_data: Default::default(),
+			timestamp: 1,
+			count: 10,
+			reason: Default::default(),
+		};
+		unhashed::put(&RetiredCredits::<Test>::hashed_key_for(asset_id, item_id), &old_retirement);
+		// a retirement made after the upgrade, before the migration reached it
+		let new_retirement = crate::RetiredCarbonCreditsData::<Test> {
+			account: retiring_account,
+			retire_data: Default::default(),
+			timestamp: 2,
+			count: 5,
+			reason: Default::default(),
+			beneficiary: Some(2),
+		};
+		RetiredCredits::<Test>::insert(asset_id, item_id + 1, new_retirement.clone());
+		StorageVersion::new(0).put::<CarbonCredits>();
+
+		// the pallet account cannot pay the attribute deposit
+		assert!(<UniquesCertificateHandler<Test> as NFTMutate<u64>>::set_attribute(
+			&asset_id, &item_id, b"key", b"value"
+		)
+		.is_err());
+
+		crate::migration::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+		// the retirements are migrated on idle
+		assert!(RetiredCredits::<Test>::get(asset_id, item_id).is_none());
+		CarbonCredits::on_idle(1, Weight::MAX);
+		assert!(RetirementMigration::<Test>::get().is_none());
+
+		let retirement = RetiredCredits::<Test>::get(asset_id, item_id).unwrap();
+		assert_eq!(retirement.account, retiring_account);
+		assert_eq!(retirement.count, 10);
+		assert_eq!(retirement.beneficiary, None);
+		assert_eq!(RetiredCredits::<Test>::get(asset_id, item_id + 1), Some(new_retirement));
+		assert_ok!(<UniquesCertificateHandler<Test> as NFTMutate<u64>>::set_attribute(
+			&asset_id, &item_id, b"key", b"value"
+		));
+		assert_eq!(CarbonCredits::on_chain_storage_version(), 4);
+	});
+}
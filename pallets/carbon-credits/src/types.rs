@@ -5,8 +5,10 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{pallet_prelude::*, BoundedBTreeMap};
 use frame_system::pallet_prelude::BlockNumberFor;
 use primitives::{
-	Batch, BatchGroup, BatchRetireData, ProjectType, RegistryDetails, Royalty, SDGDetails,
+	Batch, BatchGroup, BatchRetireData, MarketplaceListing, ProjectType, RegistryDetails, Royalty,
+	SDGDetails,
 };
+use sp_std::vec::Vec;
 
 use crate::pallet;
 
@@ -42,6 +44,13 @@ pub type RoyaltyRecipientsOf<T> = BoundedVec<
 	<T as pallet::Config>::MaxRoyaltyRecipients,
 >;
 
+/// Price type of the marketplace that minted credits are listed to
+pub type MarketplacePriceOf<T> = <<T as pallet::Config>::Marketplace as MarketplaceListing<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet::Config>::AssetId,
+	<T as pallet::Config>::Balance,
+>>::Price;
+
 // Type of batch used by the pallet
 pub type BatchOf<T> = Batch<ShortStringOf<T>, <T as pallet::Config>::Balance>;
 
@@ -70,6 +79,7 @@ pub type BatchGroupMapOf<T> = BoundedBTreeMap<
 #[codec(mel_bound(T: pallet::Config))]
 #[derive(frame_support::DebugNoBound)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct ProjectCreateParams<T: pallet::Config> {
 	/// Name of the project
 	pub name: ShortStringOf<T>,
@@ -101,6 +111,7 @@ pub struct ProjectCreateParams<T: pallet::Config> {
 #[codec(mel_bound(T: pallet::Config))]
 #[derive(frame_support::DebugNoBound)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct ProjectDetail<T: pallet::Config> {
 	/// The originator of the project
 	pub originator: T::AccountId,
@@ -122,7 +133,8 @@ pub struct ProjectDetail<T: pallet::Config> {
 	pub sdg_details: SDGTypesListOf<T>,
 	/// The royalties to be paid when tokens are purchased
 	pub royalties: Option<RoyaltyRecipientsOf<T>>,
-	/// groups included in the project
+	/// groups included in the project, not serialized since they are read separately
+	#[cfg_attr(feature = "serde", serde(skip))]
 	pub batch_groups: BatchGroupMapOf<T>,
 	/// Type of carbon credit project
 	pub project_type: Option<ProjectType>,
@@ -150,6 +162,7 @@ pub type BatchRetireDataList<T> =
 #[codec(mel_bound(T: pallet::Config))]
 #[derive(frame_support::DebugNoBound)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct RetiredCarbonCreditsData<T: pallet::Config> {
 	/// The AccountId that retired the credits
 	pub account: T::AccountId,
@@ -161,6 +174,8 @@ pub struct RetiredCarbonCreditsData<T: pallet::Config> {
 	pub count: T::Balance,
 	/// Retirement reason
 	pub reason: ShortStringOf<T>,
+	/// The AccountId the credits were retired on behalf of, if different from the retiring account
+	pub beneficiary: Option<T::AccountId>,
 }
 
 /// Enum representing the approval status of a project.
@@ -203,3 +218,14 @@ impl ProjectApprovalStatus {
 		}
 	}
 }
+
+/// Progress of the migration to version 4, see `migration::v4`
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum RetirementMigrationStage {
+	/// Making the certificate collections free holding, after the raw storage key of the last
+	/// collection visited
+	Collections(Option<Vec<u8>>),
+	/// Adding the beneficiary to the retirements, after the raw storage key of the last
+	/// retirement visited
+	Retirements(Option<Vec<u8>>),
+}
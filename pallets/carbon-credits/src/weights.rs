@@ -35,7 +35,7 @@ pub trait WeightInfo {
 	fn create() -> Weight;
 	fn approve_project() -> Weight;
 	fn mint() -> Weight;
-	fn retire() -> Weight;
+	fn retire(b: u32, ) -> Weight;
 	fn force_add_authorized_account() -> Weight;
 	fn force_remove_authorized_account() -> Weight;
 	fn force_set_project_storage() -> Weight;
@@ -79,11 +79,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Uniques CollectionMaxSupply (r:1 w:0)
 	// Storage: Uniques ClassAccount (r:0 w:1)
 	// Storage: Uniques Account (r:0 w:1)
+	// Storage: Uniques Attribute (r:6 w:6)
 	// Storage: VCU RetiredCredits (r:0 w:1)
-	fn retire() -> Weight {
-		Weight::from_parts(117_000_000_u64, 0)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
-			.saturating_add(T::DbWeight::get().writes(9_u64))
+	// Storage: VCU RetiredCreditsByAccount (r:1 w:1)
+	/// The range of component `b` is `[1, 10]`.
+	fn retire(b: u32, ) -> Weight {
+		Weight::from_parts(163_000_000_u64, 0)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_parts(38_000_000_u64, 0).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(b as u64)))
+			.saturating_add(T::DbWeight::get().writes(17_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(b as u64)))
 	}
 	// Storage: VCU AuthorizedAccounts (r:1 w:1)
 	fn force_add_authorized_account() -> Weight {
@@ -149,11 +156,18 @@ impl WeightInfo for () {
 	// Storage: Uniques CollectionMaxSupply (r:1 w:0)
 	// Storage: Uniques ClassAccount (r:0 w:1)
 	// Storage: Uniques Account (r:0 w:1)
+	// Storage: Uniques Attribute (r:6 w:6)
 	// Storage: VCU RetiredCredits (r:0 w:1)
-	fn retire() -> Weight {
-		Weight::from_parts(117_000_000_u64, 0)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	// Storage: VCU RetiredCreditsByAccount (r:1 w:1)
+	/// The range of component `b` is `[1, 10]`.
+	fn retire(b: u32, ) -> Weight {
+		Weight::from_parts(163_000_000_u64, 0)
+			// Standard Error: 21_000
+			.saturating_add(Weight::from_parts(38_000_000_u64, 0).saturating_mul(b as u64))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(b as u64)))
+			.saturating_add(RocksDbWeight::get().writes(17_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(b as u64)))
 	}
 	// Storage: VCU AuthorizedAccounts (r:1 w:1)
 	fn force_add_authorized_account() -> Weight {
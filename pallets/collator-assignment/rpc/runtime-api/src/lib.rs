@@ -16,7 +16,8 @@
 
 //! Runtime API for CollatorAssignment pallet. Can be used by collators to check
 //! which parachain will they be collating, as well as the current assignment of
-//! collators to parachains and parachains to collators.
+//! collators to parachains and parachains to collators. Para managers can check the priority
+//! of their container chain in the next assignment.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -37,5 +38,10 @@ sp_api::decl_runtime_apis! {
         /// Return the list of collators of the given `ParaId`.
         /// Returns `None` if the `ParaId` is not in the registrar.
         fn parachain_collators(para_id: ParaId) -> Option<Vec<AccountId>>;
+        /// Return the container chains that will be considered in the next collator assignment,
+        /// from the highest to the lowest priority. Container chains without enough credits are
+        /// not included. If there are not enough collators, the container chains at the end of
+        /// the list will not be assigned collators.
+        fn container_chains_by_priority() -> Vec<ParaId>;
     }
 }
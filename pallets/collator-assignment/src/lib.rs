@@ -23,13 +23,22 @@
 //! The set of container chains is retrieved thanks to the GetContainerChains trait
 //! The number of collators to assign to the orchestrator chain and the number
 //! of collators to assign to each container chain is retrieved through the GetHostConfiguration
-//! trait.
+//! trait. Each container chain can be assigned a different number of collators.
 //!  
 //! The pallet uses the following approach:
 //!
 //! - First, it aims at filling the necessary collators to serve the orchestrator chain
 //! - Second, it aims at filling in-order (FIFO) the existing containerChains
 //!
+//! The containerChains are sorted by the priority given by the CollatorAssignmentPriority trait,
+//! e.g. the tip they bid and their remaining credits. When there are not enough collators for all
+//! of them, the containerChains with the lowest priority are not assigned collators. A
+//! containerChain that had collators in the previous session is not displaced by a new
+//! containerChain with the same priority, only by containerChains with a strictly higher
+//! priority. ContainerChains with the same priority are shuffled.
+//! A containerChain that needs more collators than the ones left by the containerChains with a
+//! higher priority is skipped, the next ones in the list can still be assigned collators.
+//!
 //! Upon new session, this pallet takes whatever assignation was in the PendingCollatorContainerChain
 //! storage, and assigns it as the current CollatorContainerChain. In addition, it takes the next
 //! queued set of parachains and collators and calculates the assignment for the next session, storing
@@ -53,10 +62,11 @@ use {
         traits::{AtLeast32BitUnsigned, One, Zero},
         Saturating,
     },
-    sp_std::{fmt::Debug, prelude::*, vec},
+    sp_std::{collections::vec_deque::VecDeque, fmt::Debug, mem, prelude::*, vec},
     tp_traits::{
-        GetContainerChainAuthor, GetHostConfiguration, GetSessionContainerChains, ParaId,
-        RemoveInvulnerables, RemoveParaIdsWithNoCredits, ShouldRotateAllCollators, Slot,
+        CollatorAssignmentPriority, GetContainerChainAuthor, GetHostConfiguration,
+        GetSessionContainerChains, ParaId, RemoveInvulnerables, RemoveParaIdsWithNoCredits,
+        ShouldRotateAllCollators, Slot,
     },
 };
 
@@ -70,6 +80,9 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+type PriorityOf<T> =
+    <<T as Config>::CollatorAssignmentPriority as CollatorAssignmentPriority>::Priority;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -98,6 +111,8 @@ pub mod pallet {
         type GetRandomnessForNextBlock: GetRandomnessForNextBlock<BlockNumberFor<Self>>;
         type RemoveInvulnerables: RemoveInvulnerables<Self::AccountId>;
         type RemoveParaIdsWithNoCredits: RemoveParaIdsWithNoCredits;
+        /// Priority of the container chains when there are not enough collators for all of them
+        type CollatorAssignmentPriority: CollatorAssignmentPriority;
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -181,6 +196,35 @@ pub mod pallet {
 
             // We read current assigned collators
             let old_assigned = Self::read_assigned_collators();
+            // Give collators first to the container chains with the highest priority. The sort is
+            // stable, so container chains with the same priority keep the shuffled order.
+            // We use the number of collators of each container chain scheduled at the
+            // target_session_index. A container chain that cannot pay for the collators it
+            // requested above collators_per_container gets collators_per_container collators.
+            let collators_per_container =
+                T::HostConfiguration::collators_per_container(target_session_index) as usize;
+            let container_chains: Vec<(ParaId, usize, PriorityOf<T>)> =
+                Self::sort_by_priority(container_chain_ids)
+                    .into_iter()
+                    .map(|(para_id, priority)| {
+                        let mut num_collators = T::HostConfiguration::collators_for_container(
+                            target_session_index,
+                            para_id,
+                        ) as usize;
+                        let num_extra_collators =
+                            num_collators.saturating_sub(collators_per_container);
+                        if num_extra_collators > 0
+                            && !T::CollatorAssignmentPriority::can_pay_extra_collators(
+                                &para_id,
+                                num_extra_collators as u32,
+                            )
+                        {
+                            num_collators = collators_per_container;
+                        }
+                        (para_id, num_collators, priority)
+                    })
+                    .collect();
+
             // We assign new collators
             // we use the config scheduled at the target_session_index
             let new_assigned =
@@ -199,13 +243,11 @@ pub mod pallet {
 
                     Self::assign_collators_rotate_all(
                         collators,
-                        &container_chain_ids,
+                        &container_chains,
                         T::HostConfiguration::min_collators_for_orchestrator(target_session_index)
                             as usize,
                         T::HostConfiguration::max_collators_for_orchestrator(target_session_index)
                             as usize,
-                        T::HostConfiguration::collators_per_container(target_session_index)
-                            as usize,
                     )
                 } else {
                     log::info!(
@@ -222,17 +264,30 @@ pub mod pallet {
 
                     Self::assign_collators_always_keep_old(
                         collators,
-                        &container_chain_ids,
+                        &container_chains,
                         T::HostConfiguration::min_collators_for_orchestrator(target_session_index)
                             as usize,
                         T::HostConfiguration::max_collators_for_orchestrator(target_session_index)
                             as usize,
-                        T::HostConfiguration::collators_per_container(target_session_index)
-                            as usize,
                         old_assigned.clone(),
                     )
                 };
 
+            // Notify the container chains that will be served in the target session
+            for (para_id, _, priority) in &container_chains {
+                let num_collators = new_assigned
+                    .container_chains
+                    .get(para_id)
+                    .map_or(0, |collators| collators.len());
+                if num_collators > 0 {
+                    T::CollatorAssignmentPriority::on_collators_assigned(
+                        para_id,
+                        priority,
+                        num_collators.saturating_sub(collators_per_container) as u32,
+                    );
+                }
+            }
+
             let mut pending = PendingCollatorContainerChain::<T>::get();
             let old_assigned_changed = old_assigned != new_assigned;
             let mut pending_changed = false;
@@ -267,24 +322,57 @@ pub mod pallet {
             }
         }
 
+        /// Container chains considered in the collator assignment of `target_session_index`, from
+        /// the highest to the lowest priority. Container chains without enough credits are not
+        /// included.
+        ///
+        /// If there are not enough collators for all of them, the last container chains of the
+        /// list are not assigned collators. Container chains with the same priority are shuffled
+        /// during the assignment.
+        pub fn container_chains_by_priority(target_session_index: T::SessionIndex) -> Vec<ParaId> {
+            let mut container_chain_ids =
+                T::ContainerChains::session_container_chains(target_session_index);
+            T::RemoveParaIdsWithNoCredits::remove_para_ids_with_no_credits(
+                &mut container_chain_ids,
+            );
+
+            Self::sort_by_priority(container_chain_ids)
+                .into_iter()
+                .map(|(para_id, _)| para_id)
+                .collect()
+        }
+
+        /// Sort `para_ids` from the highest to the lowest priority, along with their priority.
+        /// Para ids with the same priority keep their relative order.
+        fn sort_by_priority(para_ids: Vec<ParaId>) -> Vec<(ParaId, PriorityOf<T>)> {
+            let mut prioritized: Vec<_> = para_ids
+                .into_iter()
+                .map(|para_id| {
+                    let priority = T::CollatorAssignmentPriority::para_id_priority(&para_id);
+                    (para_id, priority)
+                })
+                .collect();
+            prioritized.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+            prioritized
+        }
+
         /// Recompute collator assignment from scratch. If the list of collators and the list of
         /// container chains are shuffled, this returns a random assignment.
         fn assign_collators_rotate_all(
             collators: Vec<T::AccountId>,
-            container_chain_ids: &[ParaId],
+            container_chains: &[(ParaId, usize, PriorityOf<T>)],
             min_num_orchestrator_chain: usize,
             max_num_orchestrator_chain: usize,
-            num_each_container_chain: usize,
         ) -> AssignedCollators<T::AccountId> {
             // This is just the "always_keep_old" algorithm but with an empty "old"
             let old_assigned = Default::default();
 
             Self::assign_collators_always_keep_old(
                 collators,
-                container_chain_ids,
+                container_chains,
                 min_num_orchestrator_chain,
                 max_num_orchestrator_chain,
-                num_each_container_chain,
                 old_assigned,
             )
         }
@@ -293,26 +381,87 @@ pub mod pallet {
         /// Old collators always have preference to remain on the same chain.
         /// If there are no missing collators, nothing is changed.
         ///
-        /// `container_chain_ids` should be shuffled or at least rotated on every session to ensure
-        /// a fair distribution, because the order of that list affects container chain priority:
-        /// the first container chain on that list will be the first one to get new collators.
+        /// `container_chains` holds the container chains along with their number of collators and
+        /// their priority, from the highest to the lowest priority. Container chains with the same
+        /// priority should be shuffled or at least rotated on every session to ensure a fair
+        /// distribution, because the order of that list affects container chain priority: the
+        /// first container chain on that list will be the first one to get new collators.
+        /// The container chains that cannot be served with the collators left by the ones with a
+        /// higher priority are removed. A container chain that had collators in `old_assigned` is
+        /// never removed in favour of a new container chain with the same priority.
         fn assign_collators_always_keep_old(
             collators: Vec<T::AccountId>,
-            container_chain_ids: &[ParaId],
+            container_chains: &[(ParaId, usize, PriorityOf<T>)],
             min_num_orchestrator_chain: usize,
             max_num_orchestrator_chain: usize,
-            num_each_container_chain: usize,
             old_assigned: AssignedCollators<T::AccountId>,
         ) -> AssignedCollators<T::AccountId> {
             // TODO: the performance of this function is sad, could be improved by having sets of
             // old_collators and new_collators instead of doing array.contains() every time.
             let mut new_assigned = old_assigned;
             new_assigned.remove_collators_not_in_list(&collators);
-            new_assigned.remove_container_chains_not_in_list(container_chain_ids);
+
+            // Among the container chains with the same priority, the ones that keep old collators
+            // go first, so that they are not displaced by the new ones
+            let mut container_chains: Vec<(ParaId, usize, &PriorityOf<T>, usize)> =
+                container_chains
+                    .iter()
+                    .map(|(para_id, num_collators, priority)| {
+                        let num_old_collators = new_assigned
+                            .container_chains
+                            .get(para_id)
+                            .map_or(0, |collators| collators.len().min(*num_collators));
+                        (*para_id, *num_collators, priority, num_old_collators)
+                    })
+                    .collect();
+            container_chains.sort_by(|(_, _, priority_a, old_a), (_, _, priority_b, old_b)| {
+                priority_b
+                    .cmp(priority_a)
+                    .then((*old_b > 0).cmp(&(*old_a > 0)))
+            });
+
+            // Only keep the container chains that can be served, the collators of the removed
+            // container chains are reassigned to the container chains with a higher priority.
+            // The container chains with old collators only compete for the collators they keep
+            // with the ones with the same priority, the incomplete ones are reorganized below. A
+            // new container chain needs all its collators to be left by the ones before it, but
+            // it does not prevent the next ones in the list from being served.
+            let mut num_available_collators =
+                collators.len().saturating_sub(min_num_orchestrator_chain);
+            let mut current_priority: Option<&PriorityOf<T>> = None;
+            let mut num_available_old_collators = 0;
+            container_chains.retain(|(_, num_collators, priority, num_old_collators)| {
+                if current_priority != Some(*priority) {
+                    current_priority = Some(*priority);
+                    num_available_old_collators = num_available_collators;
+                }
+                if *num_old_collators > 0 {
+                    if *num_old_collators > num_available_old_collators {
+                        return false;
+                    }
+                    num_available_old_collators -= num_old_collators;
+                } else if *num_collators > num_available_collators {
+                    return false;
+                }
+                num_available_collators = num_available_collators.saturating_sub(*num_collators);
+                true
+            });
+            let container_chain_ids: Vec<ParaId> = container_chains
+                .iter()
+                .map(|(para_id, _, _, _)| *para_id)
+                .collect();
+
+            new_assigned.remove_container_chains_not_in_list(&container_chain_ids);
             let extra_orchestrator_collators =
                 new_assigned.remove_orchestrator_chain_excess_collators(min_num_orchestrator_chain);
             // Only need to do this if the config params change
-            new_assigned.remove_container_chain_excess_collators(num_each_container_chain);
+            for (para_id, num_collators, _, _) in &container_chains {
+                if let Some(container_chain_collators) =
+                    new_assigned.container_chains.get_mut(para_id)
+                {
+                    container_chain_collators.truncate(*num_collators);
+                }
+            }
 
             // Collators that are not present in old_assigned
             // This is used to keep track of which collators are old and which ones are new, to keep
@@ -350,11 +499,13 @@ pub mod pallet {
                 .by_ref()
                 .chain(&mut extra_orchestrator_collators);
 
-            new_assigned.add_and_fill_new_container_chains_in_order(
-                num_each_container_chain,
-                container_chain_ids,
-                &mut new_plus_extra_collators,
-            );
+            for (para_id, num_collators, _, _) in &container_chains {
+                new_assigned.add_and_fill_new_container_chains_in_order(
+                    *num_collators,
+                    &[*para_id],
+                    &mut new_plus_extra_collators,
+                );
+            }
 
             // Fill orchestrator chain collators back up to max_num_orchestrator_chain,
             // but give preference to collators that were already there
@@ -367,20 +518,20 @@ pub mod pallet {
             );
 
             // Reorganize container chain collators to fill the maximum number of container
-            // chains. For example, if num_each_container_chain == 2 and the number of collators
-            // in each container chain is
+            // chains. For example, if each container chain needs 2 collators and the number of
+            // collators in each container chain is
             // [1, 1, 1, 1, 1]
             // Then we can convert that into
-            // [2, 2, 0, 0, 0]
+            // [0, 0, 0, 2, 2]
             // and assign 1 extra collator to the orchestrator chain, if needed.
-            let incomplete_container_chains_collators = new_assigned
-                .reorganize_incomplete_container_chains_collators(
-                    container_chain_ids,
-                    num_each_container_chain,
+            let incomplete_container_chains_collators =
+                Self::reorganize_incomplete_container_chains_collators(
+                    &mut new_assigned,
+                    &container_chains,
                 );
 
-            // Assign collators from container chains that do not reach
-            // "num_each_container_chain" to orchestrator chain
+            // Assign collators from container chains that do not reach their number of
+            // collators to orchestrator chain
             new_assigned.fill_orchestrator_chain_collators(
                 max_num_orchestrator_chain,
                 &mut incomplete_container_chains_collators.into_iter(),
@@ -389,6 +540,83 @@ pub mod pallet {
             new_assigned
         }
 
+        /// Move the collators of the container chains that do not reach their number of collators
+        /// to other incomplete container chains, to complete as many of them as possible. Returns
+        /// the collators that could not complete any container chain.
+        ///
+        /// The container chains with the highest priority are completed first, then the ones that
+        /// kept old collators and then the ones with the most collators, so that the least
+        /// collators move. The collators are taken from the container chains with the lowest
+        /// priority.
+        fn reorganize_incomplete_container_chains_collators(
+            assigned: &mut AssignedCollators<T::AccountId>,
+            container_chains: &[(ParaId, usize, &PriorityOf<T>, usize)],
+        ) -> Vec<T::AccountId> {
+            let mut incomplete_container_chains: Vec<_> = container_chains
+                .iter()
+                .filter_map(|(para_id, num_collators, priority, num_old_collators)| {
+                    let collators = assigned.container_chains.get_mut(para_id)?;
+                    if collators.is_empty() || collators.len() >= *num_collators {
+                        return None;
+                    }
+                    // Keep the para_id in the map with an empty list of collators, instead of
+                    // removing it, so that it shows up as a container chain without collators
+                    Some((
+                        *para_id,
+                        *num_collators,
+                        (*priority, *num_old_collators > 0),
+                        mem::take(collators),
+                    ))
+                })
+                .collect();
+            // The sort is stable, the last container chain of the list is completed first
+            incomplete_container_chains.sort_by(
+                |(_, _, rank_a, collators_a), (_, _, rank_b, collators_b)| {
+                    rank_a
+                        .cmp(rank_b)
+                        .then(collators_a.len().cmp(&collators_b.len()))
+                },
+            );
+            let mut incomplete_container_chains = VecDeque::from(incomplete_container_chains);
+
+            let mut released_collators = vec![];
+            while let Some((para_id, num_collators, _, mut collators)) =
+                incomplete_container_chains.pop_back()
+            {
+                let num_available_collators = collators.len()
+                    + released_collators.len()
+                    + incomplete_container_chains
+                        .iter()
+                        .map(|(_, _, _, collators)| collators.len())
+                        .sum::<usize>();
+                if num_available_collators < num_collators {
+                    // This container chain cannot be completed, its collators can still complete
+                    // the next ones, which may need less collators
+                    released_collators.append(&mut collators);
+                    continue;
+                }
+
+                // Take the collators of the first container chains of the list
+                while collators.len() < num_collators {
+                    if let Some(collator) = released_collators.pop() {
+                        collators.push(collator);
+                    } else if let Some((_, _, _, first_collators)) =
+                        incomplete_container_chains.front_mut()
+                    {
+                        collators.extend(first_collators.pop());
+                        if first_collators.is_empty() {
+                            incomplete_container_chains.pop_front();
+                        }
+                    } else {
+                        break;
+                    }
+                }
+                assigned.container_chains.insert(para_id, collators);
+            }
+
+            released_collators
+        }
+
         // Returns the assigned collators as read from storage.
         // If there is any item in PendingCollatorContainerChain, returns that element.
         // Otherwise, reads and returns the current CollatorContainerChain
@@ -412,8 +640,16 @@ pub mod pallet {
             let assigned_collators = Self::assign_collators(session_index, random_seed, collators);
             let num_total_registered_paras = assigned_collators.num_total_registered_paras;
 
+            // The priority, the payment checks and the charges of every container chain are added
+            // to the benchmarked weight, registered container chains are an upper bound
+            let weight =
+                T::WeightInfo::new_session(num_collators as u32, num_total_registered_paras)
+                    .saturating_add(
+                        T::CollatorAssignmentPriority::weight_per_para_id()
+                            .saturating_mul(num_total_registered_paras.into()),
+                    );
             frame_system::Pallet::<T>::register_extra_weight_unchecked(
-                T::WeightInfo::new_session(num_collators as u32, num_total_registered_paras),
+                weight,
                 DispatchClass::Mandatory,
             );
 
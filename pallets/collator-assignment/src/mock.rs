@@ -22,6 +22,7 @@ use {
     frame_support::{
         parameter_types,
         traits::{ConstU16, ConstU64, Hooks},
+        weights::Weight,
     },
     frame_system as system,
     parity_scale_codec::{Decode, Encode},
@@ -30,7 +31,9 @@ use {
         traits::{BlakeTwo256, IdentityLookup},
         BuildStorage,
     },
-    tp_traits::{ParaId, RemoveInvulnerables, RemoveParaIdsWithNoCredits},
+    tp_traits::{
+        CollatorAssignmentPriority, ParaId, RemoveInvulnerables, RemoveParaIdsWithNoCredits,
+    },
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -111,9 +114,20 @@ pub struct Mocks {
     pub min_orchestrator_chain_collators: u32,
     pub max_orchestrator_chain_collators: u32,
     pub collators_per_container: u32,
+    /// Number of collators of the container chains, collators_per_container if not set
+    pub container_chain_collators: Vec<(u32, u32)>,
     pub collators: Vec<u64>,
     pub container_chains: Vec<u32>,
     pub random_seed: [u8; 32],
+    /// Priority of the container chains, 0 if not set
+    pub container_chain_priorities: Vec<(u32, u32)>,
+    /// Container chains assigned collators, with their priority, in the order they were notified
+    pub assigned_container_chains: Vec<(u32, u32)>,
+    /// Container chains that cannot pay for collators above collators_per_container
+    pub container_chains_without_funds: Vec<u32>,
+    /// Container chains charged for collators above collators_per_container, with the number of
+    /// extra collators
+    pub charged_extra_collators: Vec<(u32, u32)>,
 }
 
 impl mock_data::Config for Test {}
@@ -138,6 +152,15 @@ impl pallet_collator_assignment::GetHostConfiguration<u32> for HostConfiguration
     fn collators_per_container(_session_index: u32) -> u32 {
         MockData::mock().collators_per_container
     }
+
+    fn collators_for_container(_session_index: u32, para_id: ParaId) -> u32 {
+        let mock = MockData::mock();
+        mock.container_chain_collators
+            .iter()
+            .find(|(id, _)| ParaId::from(*id) == para_id)
+            .map(|(_, num_collators)| *num_collators)
+            .unwrap_or(mock.collators_per_container)
+    }
 }
 
 pub struct CollatorsGetter;
@@ -194,6 +217,7 @@ impl pallet_collator_assignment::Config for Test {
     type GetRandomnessForNextBlock = MockGetRandomnessForNextBlock;
     type RemoveInvulnerables = RemoveAccountIdsAbove100;
     type RemoveParaIdsWithNoCredits = RemoveParaIdsAbove5000;
+    type CollatorAssignmentPriority = MockPriority;
     type WeightInfo = ();
 }
 
@@ -264,3 +288,40 @@ impl RemoveParaIdsWithNoCredits for RemoveParaIdsAbove5000 {
         }
     }
 }
+
+/// Container chains get the priority set in the mock data
+pub struct MockPriority;
+
+impl CollatorAssignmentPriority for MockPriority {
+    type Priority = u32;
+
+    fn para_id_priority(para_id: &ParaId) -> u32 {
+        MockData::mock()
+            .container_chain_priorities
+            .iter()
+            .find(|(id, _)| ParaId::from(*id) == *para_id)
+            .map(|(_, priority)| *priority)
+            .unwrap_or_default()
+    }
+
+    fn can_pay_extra_collators(para_id: &ParaId, _num_extra_collators: u32) -> bool {
+        !MockData::mock()
+            .container_chains_without_funds
+            .contains(&u32::from(*para_id))
+    }
+
+    fn on_collators_assigned(para_id: &ParaId, priority: &u32, num_extra_collators: u32) {
+        MockData::mutate(|m| {
+            m.assigned_container_chains
+                .push((u32::from(*para_id), *priority));
+            if num_extra_collators > 0 {
+                m.charged_extra_collators
+                    .push((u32::from(*para_id), num_extra_collators));
+            }
+        });
+    }
+
+    fn weight_per_para_id() -> Weight {
+        Weight::zero()
+    }
+}
@@ -356,6 +356,142 @@ fn assign_collators_if_config_collators_per_container_increases() {
     });
 }
 
+#[test]
+fn assign_collators_with_different_collators_per_container() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.container_chain_collators = vec![(1001, 4)];
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            m.container_chains = vec![1001, 1002]
+        });
+
+        assert_eq!(assigned_collators(), BTreeMap::new(),);
+        run_to_block(11);
+
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1001),
+                (4, 1001),
+                (5, 1001),
+                (6, 1001),
+                (7, 1002),
+                (8, 1002),
+                (9, 999),
+                (10, 999),
+            ]),
+        );
+        assert_eq!(MockData::mock().charged_extra_collators, vec![(1001, 2)]);
+
+        MockData::mutate(|m| {
+            // 1001 gives one collator to 1002
+            m.container_chain_collators = vec![(1001, 3), (1002, 3)];
+            m.charged_extra_collators = vec![];
+        });
+
+        run_to_block(21);
+
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1001),
+                (4, 1001),
+                (5, 1001),
+                (6, 1002),
+                (7, 1002),
+                (8, 1002),
+                (9, 999),
+                (10, 999),
+            ]),
+        );
+    });
+}
+
+#[test]
+fn assign_collators_per_container_if_extra_collators_cannot_be_paid() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.container_chain_collators = vec![(1001, 4)];
+            m.container_chains_without_funds = vec![1001];
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+            m.container_chains = vec![1001, 1002]
+        });
+
+        assert_eq!(assigned_collators(), BTreeMap::new(),);
+        run_to_block(11);
+
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1001),
+                (4, 1001),
+                (5, 1002),
+                (6, 1002),
+                (7, 999),
+                (8, 999),
+                (9, 999),
+            ]),
+        );
+        assert_eq!(MockData::mock().charged_extra_collators, vec![]);
+    });
+}
+
+#[test]
+fn assign_collators_skip_container_chain_needing_more_collators_than_available() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.container_chain_collators = vec![(1001, 4), (1003, 1)];
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 5;
+
+            m.collators = vec![1, 2, 3, 4, 5, 6, 7];
+            m.container_chains = vec![1001, 1002, 1003];
+            m.container_chain_priorities = vec![(1001, 10)];
+        });
+
+        assert_eq!(assigned_collators(), BTreeMap::new(),);
+        run_to_block(11);
+
+        // After serving 1001 there is only 1 collator left: not enough for 1002, but enough for
+        // 1003
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1001),
+                (4, 1001),
+                (5, 1001),
+                (6, 1001),
+                (7, 1003),
+            ]),
+        );
+        let assigned = MockData::mock().assigned_container_chains;
+        assert!(!assigned.iter().any(|(para_id, _)| *para_id == 1002));
+    });
+}
+
 #[test]
 fn assign_collators_if_container_chain_is_removed() {
     new_test_ext().execute_with(|| {
@@ -639,6 +775,136 @@ fn assign_collators_reorganize_container_chains_if_not_enough_collators() {
     });
 }
 
+#[test]
+fn assign_collators_prioritize_container_chains_if_not_enough_collators() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Enough collators for 2 container chains
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            m.container_chains = vec![1001, 1002, 1003];
+            m.container_chain_priorities = vec![(1002, 5), (1003, 10)];
+        });
+        assert_eq!(assigned_collators(), BTreeMap::new(),);
+        run_to_block(11);
+
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1003),
+                (4, 1003),
+                (5, 1002),
+                (6, 1002),
+            ]),
+        );
+        let assigned = MockData::mock().assigned_container_chains;
+        assert!(assigned.contains(&(1003, 10)));
+        assert!(assigned.contains(&(1002, 5)));
+        assert!(!assigned.iter().any(|(para_id, _)| *para_id == 1001));
+
+        MockData::mutate(|m| {
+            // 1001 outbids the other container chains and takes the collators of 1002
+            m.container_chain_priorities = vec![(1001, 20), (1002, 5), (1003, 10)];
+            m.assigned_container_chains = vec![];
+        });
+        run_to_block(21);
+
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1003),
+                (4, 1003),
+                (5, 1001),
+                (6, 1001),
+            ]),
+        );
+        let assigned = MockData::mock().assigned_container_chains;
+        assert!(assigned.contains(&(1001, 20)));
+        assert!(assigned.contains(&(1003, 10)));
+        assert!(!assigned.iter().any(|(para_id, _)| *para_id == 1002));
+    });
+}
+
+#[test]
+fn assign_collators_keep_old_container_chains_with_the_same_priority() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        MockData::mutate(|m| {
+            m.collators_per_container = 2;
+            m.min_orchestrator_chain_collators = 2;
+            m.max_orchestrator_chain_collators = 5;
+
+            // Enough collators for 2 container chains
+            m.collators = vec![1, 2, 3, 4, 5, 6];
+            m.container_chains = vec![1001, 1002];
+        });
+        assert_eq!(assigned_collators(), BTreeMap::new(),);
+        run_to_block(11);
+
+        let initial_assignment = BTreeMap::from_iter(vec![
+            (1, 999),
+            (2, 999),
+            (3, 1001),
+            (4, 1001),
+            (5, 1002),
+            (6, 1002),
+        ]);
+        assert_eq!(assigned_collators(), initial_assignment);
+
+        MockData::mutate(|m| {
+            // 1003 comes first in the list, but it does not outbid the other container chains
+            m.container_chains = vec![1003, 1001, 1002];
+        });
+        run_to_block(21);
+
+        assert_eq!(assigned_collators(), initial_assignment);
+
+        MockData::mutate(|m| {
+            // 1003 outbids the other container chains and takes the collators of 1002
+            m.container_chain_priorities = vec![(1003, 1)];
+        });
+        run_to_block(31);
+
+        assert_eq!(
+            assigned_collators(),
+            BTreeMap::from_iter(vec![
+                (1, 999),
+                (2, 999),
+                (3, 1001),
+                (4, 1001),
+                (5, 1003),
+                (6, 1003),
+            ]),
+        );
+    });
+}
+
+#[test]
+fn container_chains_by_priority() {
+    new_test_ext().execute_with(|| {
+        MockData::mutate(|m| {
+            m.container_chains = vec![1001, 1002, 1003, 5001];
+            m.container_chain_priorities = vec![(1002, 1), (1003, 2), (5001, 3)];
+        });
+
+        // 5001 has no credits, 1001 has the lowest priority
+        assert_eq!(
+            CollatorAssignment::container_chains_by_priority(1),
+            vec![1003.into(), 1002.into(), 1001.into()]
+        );
+    });
+}
+
 #[test]
 fn assign_collators_set_zero_per_container() {
     new_test_ext().execute_with(|| {
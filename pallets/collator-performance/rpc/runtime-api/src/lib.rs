@@ -0,0 +1,40 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for CollatorPerformance pallet. Can be used by collators and delegators to check
+//! how many container chain blocks a collator was expected to produce and actually produced.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scale_info::prelude::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait CollatorPerformanceApi<AccountId, SessionIndex, Performance> where
+        AccountId: parity_scale_codec::Codec,
+        SessionIndex: parity_scale_codec::Codec,
+        Performance: parity_scale_codec::Codec,
+    {
+        /// Return the expected and produced blocks of the given `AccountId` in `session`.
+        /// Returns `None` if the `AccountId` was not expected to produce any block or if the
+        /// session is not tracked anymore.
+        fn collator_performance(session: SessionIndex, account: AccountId) -> Option<Performance>;
+        /// Return the expected and produced blocks of all the collators in `session`.
+        fn session_performance(session: SessionIndex) -> Vec<(AccountId, Performance)>;
+        /// Return the number of consecutive sessions in which the given `AccountId`
+        /// underperformed.
+        fn consecutive_offences(account: AccountId) -> u32;
+    }
+}
@@ -0,0 +1,390 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! # Collator Performance Pallet
+//!
+//! This pallet tracks the liveness of the collators assigned to container chains.
+//!
+//! Every orchestrator block, the collator expected to author the current slot of each container
+//! chain is fetched from `ContainerChainAuthor` and its expected blocks are increased. Container
+//! chains without any block noted yet are skipped, as they may not have started producing blocks. The authors
+//! noted by pallet-author-noting, received through [`AuthorNotingHook`], increase the produced
+//! blocks. Both counters are kept per session for `HistoryDepth` sessions, for at most
+//! `MaxCollators` collators per session.
+//!
+//! The performance of a collator is used in two ways:
+//!
+//! - [`PerformanceRewardDistribution`] reduces the rewards of a collator to the proportion of its
+//!   expected blocks it produced in the current session.
+//! - At the start of a session, collators that produced less than `MinPerformance` of their
+//!   expected blocks in the previous session commit an offence. After `MaxOffences` consecutive
+//!   offences the collator is handed over to `RepeatOffenderHandler`, see
+//!   [`SlashRepeatOffenders`].
+//!
+//! Collators expected to produce less than `MinExpectedBlocks` blocks are never penalized, as a
+//! few missed slots are not significant.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use {
+    core::marker::PhantomData,
+    frame_support::{
+        dispatch::DispatchResultWithPostInfo,
+        pallet_prelude::*,
+        traits::{
+            fungible::{Balanced, Credit},
+            Imbalance, OnUnbalanced,
+        },
+    },
+    frame_system::pallet_prelude::*,
+    sp_runtime::{traits::Saturating, Perbill},
+    sp_std::vec::Vec,
+    tp_traits::{
+        AuthorNotingHook, BlockNumber, DistributeRewards, GetContainerChainAuthor,
+        GetCurrentContainerChains, GetLatestAuthor, GetSessionIndex, HandleRepeatOffender, ParaId,
+        SlashCollator, Slot,
+    },
+};
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    /// Container chain blocks a collator was expected to produce and produced during a session.
+    #[derive(
+        Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+    )]
+    pub struct BlockProduction {
+        /// Container chain slots assigned to the collator
+        pub expected: u32,
+        /// Container chain blocks of the collator noted by the orchestrator
+        pub produced: u32,
+    }
+
+    impl BlockProduction {
+        /// Proportion of the expected blocks that were produced. A collator without expected
+        /// blocks is considered fully performant.
+        pub fn ratio(&self) -> Perbill {
+            if self.expected == 0 {
+                return Perbill::one();
+            }
+
+            Perbill::from_rational(self.produced.min(self.expected), self.expected)
+        }
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        type CurrentSessionIndex: GetSessionIndex<u32>;
+
+        type ContainerChains: GetCurrentContainerChains;
+
+        /// Collator expected to author a slot of a container chain
+        type ContainerChainAuthor: GetContainerChainAuthor<Self::AccountId>;
+
+        /// Author of the latest block noted for a container chain
+        type LatestAuthor: GetLatestAuthor<Self::AccountId>;
+
+        /// Slot of the current orchestrator block
+        type CurrentSlot: Get<Slot>;
+
+        /// Collators producing less than this proportion of their expected blocks in a session
+        /// commit an offence
+        #[pallet::constant]
+        type MinPerformance: Get<Perbill>;
+
+        /// Collators expected to produce less blocks than this in a session are not penalized
+        #[pallet::constant]
+        type MinExpectedBlocks: Get<u32>;
+
+        /// Number of consecutive offences after which a collator is a repeat offender
+        #[pallet::constant]
+        type MaxOffences: Get<u32>;
+
+        /// What to do with the repeat offenders
+        type RepeatOffenderHandler: HandleRepeatOffender<Self::AccountId>;
+
+        /// Number of past sessions whose performance is kept
+        #[pallet::constant]
+        type HistoryDepth: Get<u32>;
+
+        /// Maximum number of collators tracked in a session, the blocks of further collators are
+        /// not counted. Bounds the judging and the pruning of a session.
+        #[pallet::constant]
+        type MaxCollators: Get<u32>;
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A collator produced less than `MinPerformance` of its expected blocks in a session
+        CollatorUnderperformed {
+            collator: T::AccountId,
+            session: u32,
+            expected: u32,
+            produced: u32,
+            offences: u32,
+        },
+        /// A collator underperformed in `MaxOffences` consecutive sessions and was handed over
+        /// to `RepeatOffenderHandler`
+        RepeatOffenderReported {
+            collator: T::AccountId,
+            offences: u32,
+        },
+    }
+
+    /// Expected and produced container chain blocks of the collators, per session
+    #[pallet::storage]
+    #[pallet::getter(fn performance)]
+    pub type Performance<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Blake2_128Concat,
+        T::AccountId,
+        BlockProduction,
+        OptionQuery,
+    >;
+
+    /// Number of collators tracked in `Performance` per session, at most `MaxCollators`
+    #[pallet::storage]
+    pub type TrackedCollators<T: Config> = StorageMap<_, Twox64Concat, u32, u32, ValueQuery>;
+
+    /// Number of consecutive sessions in which a collator underperformed
+    #[pallet::storage]
+    #[pallet::getter(fn consecutive_offences)]
+    pub type ConsecutiveOffences<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+            // Session index, slot and container chains
+            let mut weight = T::DbWeight::get().reads(3);
+            let session = T::CurrentSessionIndex::session_index();
+            let slot = T::CurrentSlot::get();
+
+            // Every container chain is expected to produce a block per orchestrator block, once
+            // it produced its first block
+            for para_id in T::ContainerChains::current_container_chains() {
+                weight += T::DbWeight::get().reads(1);
+                if T::LatestAuthor::latest_author(para_id).is_none() {
+                    continue;
+                }
+
+                weight += T::DbWeight::get().reads(1);
+                let Some(author) = T::ContainerChainAuthor::author_for_slot(slot, para_id) else {
+                    // No collators assigned to this chain
+                    continue;
+                };
+
+                weight += Self::mutate_performance(session, &author, |performance| {
+                    performance.expected.saturating_inc();
+                });
+            }
+
+            weight
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Judge the collators of the session that just ended and prune the performance of the
+        /// sessions older than `HistoryDepth`.
+        pub fn initializer_on_new_session(session_index: &u32) {
+            let mut weight = Weight::zero();
+
+            if let Some(ended_session) = session_index.checked_sub(1) {
+                weight += Self::judge_session(ended_session);
+            }
+
+            if let Some(expired_session) =
+                session_index.checked_sub(T::HistoryDepth::get().saturating_add(1))
+            {
+                // A session tracks at most `MaxCollators` collators, they are all removed at once
+                let result =
+                    Performance::<T>::clear_prefix(expired_session, T::MaxCollators::get(), None);
+                TrackedCollators::<T>::remove(expired_session);
+                weight += T::DbWeight::get().reads_writes(
+                    result.loops.into(),
+                    u64::from(result.unique).saturating_add(1),
+                );
+            }
+
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                weight,
+                DispatchClass::Mandatory,
+            );
+        }
+
+        /// Performance ratio of `collator` in the current session, used to reduce its rewards.
+        ///
+        /// Collators that were expected to produce less than `MinExpectedBlocks` blocks so far
+        /// are considered fully performant.
+        pub fn current_performance_ratio(collator: &T::AccountId) -> Perbill {
+            let session = T::CurrentSessionIndex::session_index();
+            match Performance::<T>::get(session, collator) {
+                Some(performance) if performance.expected >= T::MinExpectedBlocks::get() => {
+                    performance.ratio()
+                }
+                _ => Perbill::one(),
+            }
+        }
+
+        /// Performance of all the collators tracked in `session`.
+        pub fn session_performance(session: u32) -> Vec<(T::AccountId, BlockProduction)> {
+            Performance::<T>::iter_prefix(session).collect()
+        }
+
+        /// Update the block production of `collator` in `session`. A collator is only added to a
+        /// session tracking less than `MaxCollators` collators, otherwise its blocks are not
+        /// counted. Returns the consumed weight.
+        pub(crate) fn mutate_performance(
+            session: u32,
+            collator: &T::AccountId,
+            f: impl FnOnce(&mut BlockProduction),
+        ) -> Weight {
+            Performance::<T>::mutate(session, collator, |performance| {
+                if performance.is_none() {
+                    let tracked = TrackedCollators::<T>::get(session);
+                    if tracked >= T::MaxCollators::get() {
+                        return;
+                    }
+                    TrackedCollators::<T>::insert(session, tracked.saturating_add(1));
+                }
+                f(performance.get_or_insert_with(Default::default));
+            });
+
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+
+        /// Judge the collators tracked in `session`, at most `MaxCollators`
+        fn judge_session(session: u32) -> Weight {
+            let mut weight = Weight::zero();
+
+            for (collator, performance) in Performance::<T>::iter_prefix(session) {
+                weight += T::DbWeight::get().reads(1);
+                if performance.expected < T::MinExpectedBlocks::get() {
+                    continue;
+                }
+
+                if performance.ratio() >= T::MinPerformance::get() {
+                    // The offences must be consecutive
+                    weight += T::DbWeight::get().writes(1);
+                    ConsecutiveOffences::<T>::remove(&collator);
+                    continue;
+                }
+
+                weight += T::DbWeight::get().reads_writes(1, 1);
+                let offences = ConsecutiveOffences::<T>::get(&collator).saturating_add(1);
+                Self::deposit_event(Event::CollatorUnderperformed {
+                    collator: collator.clone(),
+                    session,
+                    expected: performance.expected,
+                    produced: performance.produced,
+                    offences,
+                });
+
+                if offences >= T::MaxOffences::get() {
+                    // Start counting again, the handler decides what the collator can still do
+                    ConsecutiveOffences::<T>::remove(&collator);
+                    weight += T::RepeatOffenderHandler::handle_repeat_offender(&collator, offences);
+                    Self::deposit_event(Event::RepeatOffenderReported { collator, offences });
+                } else {
+                    ConsecutiveOffences::<T>::insert(&collator, offences);
+                }
+            }
+
+            weight
+        }
+    }
+}
+
+impl<T: Config> AuthorNotingHook<T::AccountId> for Pallet<T> {
+    fn on_container_author_noted(
+        author: &T::AccountId,
+        _block_number: BlockNumber,
+        _para_id: ParaId,
+    ) -> Weight {
+        let session = T::CurrentSessionIndex::session_index();
+        let weight = Pallet::<T>::mutate_performance(session, author, |performance| {
+            performance.produced.saturating_inc();
+        });
+
+        weight.saturating_add(T::DbWeight::get().reads(1))
+    }
+}
+
+/// Distribute the rewards of a collator with `Inner`, reduced to the performance ratio of the
+/// collator in the current session. The withheld rewards are handed over to `OnPenalty`.
+pub struct PerformanceRewardDistribution<Runtime, Currency, Inner, OnPenalty>(
+    PhantomData<(Runtime, Currency, Inner, OnPenalty)>,
+);
+
+impl<Runtime, Currency, Inner, OnPenalty>
+    DistributeRewards<Runtime::AccountId, Credit<Runtime::AccountId, Currency>>
+    for PerformanceRewardDistribution<Runtime, Currency, Inner, OnPenalty>
+where
+    Runtime: Config,
+    Currency: Balanced<Runtime::AccountId>,
+    Inner: DistributeRewards<Runtime::AccountId, Credit<Runtime::AccountId, Currency>>,
+    OnPenalty: OnUnbalanced<Credit<Runtime::AccountId, Currency>>,
+{
+    fn distribute_rewards(
+        rewarded: Runtime::AccountId,
+        amount: Credit<Runtime::AccountId, Currency>,
+    ) -> DispatchResultWithPostInfo {
+        let ratio = Pallet::<Runtime>::current_performance_ratio(&rewarded);
+        let earned = ratio * amount.peek();
+        let (earned, withheld) = amount.split(earned);
+        OnPenalty::on_unbalanced(withheld);
+
+        let mut post_info = Inner::distribute_rewards(rewarded, earned)?;
+        // Session index and performance of the collator
+        post_info.actual_weight = post_info
+            .actual_weight
+            .map(|weight| weight.saturating_add(Runtime::DbWeight::get().reads(2)));
+
+        Ok(post_info)
+    }
+}
+
+/// Slash `Fraction` of the stake backing the repeat offenders with `Slasher`.
+pub struct SlashRepeatOffenders<Slasher, Fraction>(PhantomData<(Slasher, Fraction)>);
+
+impl<AccountId, Slasher, Fraction> HandleRepeatOffender<AccountId>
+    for SlashRepeatOffenders<Slasher, Fraction>
+where
+    Slasher: SlashCollator<AccountId>,
+    Fraction: Get<Perbill>,
+{
+    fn handle_repeat_offender(collator: &AccountId, _offences: u32) -> Weight {
+        Slasher::slash_collator(collator, Fraction::get())
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    crate::{self as pallet_collator_performance, PerformanceRewardDistribution},
+    frame_support::{
+        pallet_prelude::*,
+        parameter_types,
+        traits::{
+            fungible::{Balanced, Credit},
+            ConstU64, Everything, Hooks,
+        },
+    },
+    sp_core::H256,
+    sp_runtime::{
+        traits::{BlakeTwo256, IdentityLookup},
+        BuildStorage, Perbill,
+    },
+    tp_traits::{AuthorNotingHook, GetContainerChainAuthor, ParaId, Slot},
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+pub type AccountId = u64;
+pub type Balance = u128;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        CollatorPerformance: pallet_collator_performance,
+        MockData: mock_data,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type Block = Block;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u128 = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 4];
+    type MaxLocks = ();
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = ();
+    type RuntimeFreezeReason = ();
+    type MaxHolds = ConstU32<5>;
+    type WeightInfo = ();
+}
+
+// Pallet to provide some mock data, used to test
+#[frame_support::pallet]
+pub mod mock_data {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {}
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {}
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::storage]
+    #[pallet::getter(fn mock)]
+    pub(super) type Mock<T: Config> = StorageValue<_, Mocks, ValueQuery>;
+
+    impl<T: Config> Pallet<T> {
+        pub fn get() -> Mocks {
+            Mock::<T>::get()
+        }
+        pub fn mutate<F, R>(f: F) -> R
+        where
+            F: FnOnce(&mut Mocks) -> R,
+        {
+            Mock::<T>::mutate(f)
+        }
+    }
+}
+
+impl mock_data::Config for Test {}
+
+#[derive(
+    Clone, Default, Encode, Decode, PartialEq, sp_core::RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct Mocks {
+    pub session_index: u32,
+    pub slot: u64,
+    /// Collators assigned to each container chain, they author the slots in turn
+    pub container_chains: Vec<(ParaId, Vec<AccountId>)>,
+    /// Container chains with a block noted, and the author of that block
+    pub latest_authors: Vec<(ParaId, AccountId)>,
+    /// Collators handed over to the repeat offender handler, with their offences
+    pub repeat_offenders: Vec<(AccountId, u32)>,
+}
+
+pub struct MockSessionIndex;
+
+impl tp_traits::GetSessionIndex<u32> for MockSessionIndex {
+    fn session_index() -> u32 {
+        MockData::mock().session_index
+    }
+}
+
+pub struct MockContainerChainGetter;
+
+impl tp_traits::GetCurrentContainerChains for MockContainerChainGetter {
+    type MaxContainerChains = ConstU32<5>;
+
+    fn current_container_chains() -> BoundedVec<ParaId, Self::MaxContainerChains> {
+        MockData::mock()
+            .container_chains
+            .into_iter()
+            .map(|(para_id, _)| para_id)
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_current_container_chains(container_chains: &[ParaId]) {
+        MockData::mutate(|m| {
+            m.container_chains = container_chains
+                .iter()
+                .map(|para_id| (*para_id, vec![]))
+                .collect();
+        });
+    }
+}
+
+pub struct MockLatestAuthor;
+
+impl tp_traits::GetLatestAuthor<AccountId> for MockLatestAuthor {
+    fn latest_author(para_id: ParaId) -> Option<AccountId> {
+        MockData::mock()
+            .latest_authors
+            .into_iter()
+            .find(|(id, _)| *id == para_id)
+            .map(|(_, author)| author)
+    }
+}
+
+pub struct MockContainerChainAuthor;
+
+impl tp_traits::GetContainerChainAuthor<AccountId> for MockContainerChainAuthor {
+    fn author_for_slot(slot: Slot, para_id: ParaId) -> Option<AccountId> {
+        let container_chains = MockData::mock().container_chains;
+        let (_, collators) = container_chains.iter().find(|(id, _)| *id == para_id)?;
+        if collators.is_empty() {
+            return None;
+        }
+        collators
+            .get((u64::from(slot) % collators.len() as u64) as usize)
+            .cloned()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_authors_for_para_id(para_id: ParaId, authors: Vec<AccountId>) {
+        MockData::mutate(|m| {
+            m.container_chains.retain(|(id, _)| *id != para_id);
+            m.container_chains.push((para_id, authors));
+        });
+    }
+}
+
+pub struct MockCurrentSlot;
+
+impl Get<Slot> for MockCurrentSlot {
+    fn get() -> Slot {
+        MockData::mock().slot.into()
+    }
+}
+
+pub struct MockRepeatOffenderHandler;
+
+impl tp_traits::HandleRepeatOffender<AccountId> for MockRepeatOffenderHandler {
+    fn handle_repeat_offender(collator: &AccountId, offences: u32) -> Weight {
+        MockData::mutate(|m| m.repeat_offenders.push((*collator, offences)));
+        Weight::zero()
+    }
+}
+
+pub struct MockRewardsDistributor;
+impl tp_traits::DistributeRewards<AccountId, Credit<AccountId, Balances>>
+    for MockRewardsDistributor
+{
+    fn distribute_rewards(
+        rewarded: AccountId,
+        amount: Credit<AccountId, Balances>,
+    ) -> DispatchResultWithPostInfo {
+        <Balances as Balanced<AccountId>>::resolve(&rewarded, amount)
+            .map_err(|_| DispatchError::NoProviders)?;
+        Ok(().into())
+    }
+}
+
+pub struct OnPenalty;
+impl frame_support::traits::OnUnbalanced<Credit<AccountId, Balances>> for OnPenalty {
+    fn on_nonzero_unbalanced(credit: Credit<AccountId, Balances>) {
+        let _ = <Balances as Balanced<_>>::resolve(&PenaltyAccount::get(), credit);
+    }
+}
+
+pub type RewardDistribution =
+    PerformanceRewardDistribution<Test, Balances, MockRewardsDistributor, OnPenalty>;
+
+parameter_types! {
+    pub PenaltyAccount: AccountId = 0;
+    pub const MinPerformance: Perbill = Perbill::from_percent(50);
+    pub static MaxCollators: u32 = 100;
+}
+
+impl pallet_collator_performance::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type CurrentSessionIndex = MockSessionIndex;
+    type ContainerChains = MockContainerChainGetter;
+    type ContainerChainAuthor = MockContainerChainAuthor;
+    type LatestAuthor = MockLatestAuthor;
+    type CurrentSlot = MockCurrentSlot;
+    type MinPerformance = MinPerformance;
+    type MinExpectedBlocks = ConstU32<4>;
+    type MaxOffences = ConstU32<3>;
+    type RepeatOffenderHandler = MockRepeatOffenderHandler;
+    type HistoryDepth = ConstU32<2>;
+    type MaxCollators = MaxCollators;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    let balances = vec![(0, 10_000)];
+
+    pallet_balances::GenesisConfig::<Test> { balances }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MockData::mutate(|m| {
+            m.container_chains = vec![(1001.into(), vec![1, 2]), (1002.into(), vec![3])];
+            m.latest_authors = vec![(1001.into(), 1), (1002.into(), 3)];
+        });
+    });
+    ext
+}
+
+/// Run the orchestrator blocks of `slots` slots, noting the authors returned by `produced` for
+/// each expected `(para_id, author)`
+pub fn run_slots(slots: u64, produced: impl Fn(ParaId, AccountId) -> bool) {
+    for _ in 0..slots {
+        CollatorPerformance::on_initialize(System::block_number());
+        let slot = MockCurrentSlot::get();
+        for (para_id, _) in MockData::mock().container_chains {
+            if let Some(author) = MockContainerChainAuthor::author_for_slot(slot, para_id) {
+                if produced(para_id, author) {
+                    CollatorPerformance::on_container_author_noted(&author, 1, para_id);
+                }
+            }
+        }
+        System::set_block_number(System::block_number() + 1);
+        MockData::mutate(|m| m.slot += 1);
+    }
+}
+
+/// Start the next session
+pub fn start_session() {
+    let session_index = MockData::mutate(|m| {
+        m.session_index += 1;
+        m.session_index
+    });
+    CollatorPerformance::initializer_on_new_session(&session_index);
+}
@@ -0,0 +1,271 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    crate::{mock::*, *},
+    frame_support::{
+        assert_ok,
+        traits::fungible::{Balanced, Inspect},
+    },
+    sp_runtime::Perbill,
+    tp_traits::DistributeRewards,
+};
+
+fn get_balance(who: &AccountId) -> Balance {
+    <Balances as Inspect<AccountId>>::balance(who)
+}
+
+fn performance(session: u32, collator: AccountId) -> Option<BlockProduction> {
+    CollatorPerformance::performance(session, collator)
+}
+
+#[test]
+fn expected_and_produced_blocks_are_counted() {
+    new_test_ext().execute_with(|| {
+        run_slots(4, |_, _| true);
+
+        assert_eq!(
+            performance(0, 1),
+            Some(BlockProduction {
+                expected: 2,
+                produced: 2
+            })
+        );
+        assert_eq!(
+            performance(0, 2),
+            Some(BlockProduction {
+                expected: 2,
+                produced: 2
+            })
+        );
+        assert_eq!(
+            performance(0, 3),
+            Some(BlockProduction {
+                expected: 4,
+                produced: 4
+            })
+        );
+    });
+}
+
+#[test]
+fn missed_blocks_are_not_produced() {
+    new_test_ext().execute_with(|| {
+        run_slots(4, |_, author| author != 2);
+
+        assert_eq!(
+            performance(0, 2),
+            Some(BlockProduction {
+                expected: 2,
+                produced: 0
+            })
+        );
+        assert_eq!(performance(0, 2).unwrap().ratio(), Perbill::zero());
+        assert_eq!(performance(0, 1).unwrap().ratio(), Perbill::one());
+    });
+}
+
+#[test]
+fn chains_without_noted_blocks_are_not_expected_to_produce() {
+    new_test_ext().execute_with(|| {
+        MockData::mutate(|m| m.container_chains.push((1003.into(), vec![4])));
+
+        run_slots(4, |_, _| false);
+        assert_eq!(performance(0, 4), None);
+
+        // Once a block is noted the chain is expected to produce blocks
+        MockData::mutate(|m| m.latest_authors.push((1003.into(), 4)));
+        run_slots(4, |_, _| false);
+        assert_eq!(
+            performance(0, 4),
+            Some(BlockProduction {
+                expected: 4,
+                produced: 0
+            })
+        );
+    });
+}
+
+#[test]
+fn blocks_are_counted_in_the_current_session() {
+    new_test_ext().execute_with(|| {
+        run_slots(2, |_, _| true);
+        start_session();
+        run_slots(4, |_, _| true);
+
+        assert_eq!(performance(0, 3).unwrap().expected, 2);
+        assert_eq!(performance(1, 3).unwrap().expected, 4);
+        assert_eq!(CollatorPerformance::session_performance(1).len(), 3);
+    });
+}
+
+#[test]
+fn underperforming_collator_commits_offence() {
+    new_test_ext().execute_with(|| {
+        run_slots(8, |_, author| author != 2);
+        start_session();
+
+        assert_eq!(CollatorPerformance::consecutive_offences(2), 1);
+        assert_eq!(CollatorPerformance::consecutive_offences(1), 0);
+        System::assert_last_event(
+            Event::CollatorUnderperformed {
+                collator: 2,
+                session: 0,
+                expected: 4,
+                produced: 0,
+                offences: 1,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn collator_expected_to_produce_few_blocks_is_not_judged() {
+    new_test_ext().execute_with(|| {
+        // Collator 2 is expected to produce 2 blocks, less than MinExpectedBlocks
+        run_slots(4, |_, author| author != 2);
+        start_session();
+
+        assert_eq!(CollatorPerformance::consecutive_offences(2), 0);
+    });
+}
+
+#[test]
+fn offences_must_be_consecutive() {
+    new_test_ext().execute_with(|| {
+        run_slots(8, |_, author| author != 2);
+        start_session();
+        assert_eq!(CollatorPerformance::consecutive_offences(2), 1);
+
+        run_slots(8, |_, _| true);
+        start_session();
+        assert_eq!(CollatorPerformance::consecutive_offences(2), 0);
+    });
+}
+
+#[test]
+fn repeat_offender_is_reported() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..2 {
+            run_slots(8, |_, author| author != 2);
+            start_session();
+        }
+        assert_eq!(CollatorPerformance::consecutive_offences(2), 2);
+        assert!(MockData::mock().repeat_offenders.is_empty());
+
+        run_slots(8, |_, author| author != 2);
+        start_session();
+
+        assert_eq!(MockData::mock().repeat_offenders, vec![(2, 3)]);
+        assert_eq!(CollatorPerformance::consecutive_offences(2), 0);
+        System::assert_last_event(
+            Event::RepeatOffenderReported {
+                collator: 2,
+                offences: 3,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn old_sessions_are_pruned() {
+    new_test_ext().execute_with(|| {
+        run_slots(2, |_, _| true);
+        start_session();
+        start_session();
+        assert!(performance(0, 1).is_some());
+
+        start_session();
+        assert!(performance(0, 1).is_none());
+        assert!(CollatorPerformance::session_performance(0).is_empty());
+    });
+}
+
+#[test]
+fn collators_above_max_collators_are_not_tracked() {
+    new_test_ext().execute_with(|| {
+        MaxCollators::set(2);
+        run_slots(4, |_, _| true);
+
+        assert_eq!(TrackedCollators::<Test>::get(0), 2);
+        assert_eq!(CollatorPerformance::session_performance(0).len(), 2);
+        // Collator 2 is expected after 1 and 3 were tracked
+        assert_eq!(performance(0, 2), None);
+        // The tracked collators are still counted
+        assert_eq!(
+            performance(0, 1),
+            Some(BlockProduction {
+                expected: 2,
+                produced: 2
+            })
+        );
+
+        start_session();
+        start_session();
+        start_session();
+        assert!(CollatorPerformance::session_performance(0).is_empty());
+        assert_eq!(TrackedCollators::<Test>::get(0), 0);
+    });
+}
+
+#[test]
+fn rewards_are_reduced_to_performance() {
+    new_test_ext().execute_with(|| {
+        run_slots(4, |_, _| true);
+        run_slots(4, |_, author| author != 2);
+        assert_eq!(
+            performance(0, 2).unwrap().ratio(),
+            Perbill::from_percent(50)
+        );
+
+        let penalty_balance = get_balance(&PenaltyAccount::get());
+        assert_ok!(RewardDistribution::distribute_rewards(
+            2,
+            <Balances as Balanced<AccountId>>::issue(100)
+        ));
+
+        assert_eq!(get_balance(&2), 50);
+        assert_eq!(get_balance(&PenaltyAccount::get()), penalty_balance + 50);
+    });
+}
+
+#[test]
+fn rewards_are_not_reduced_below_min_expected_blocks() {
+    new_test_ext().execute_with(|| {
+        run_slots(2, |_, author| author != 2);
+
+        assert_ok!(RewardDistribution::distribute_rewards(
+            2,
+            <Balances as Balanced<AccountId>>::issue(100)
+        ));
+
+        assert_eq!(get_balance(&2), 100);
+    });
+}
+
+#[test]
+fn rewards_of_untracked_collator_are_not_reduced() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RewardDistribution::distribute_rewards(
+            4,
+            <Balances as Balanced<AccountId>>::issue(100)
+        ));
+
+        assert_eq!(get_balance(&4), 100);
+    });
+}
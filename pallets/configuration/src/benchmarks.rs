@@ -18,14 +18,26 @@
 
 //! Benchmarking
 use {
-    crate::{Call, Config, Pallet},
+    crate::{Call, Config, Pallet, PendingCollatorsPerContainerRequests},
     frame_benchmarking::benchmarks,
+    frame_support::traits::EnsureOriginWithArg,
     frame_system::RawOrigin,
+    tp_traits::ParaId,
 };
 
 benchmarks! {
     set_config_with_u32 {}: set_max_collators(RawOrigin::Root, 100)
 
+    request_collators_per_container {
+        let para_id = ParaId::from(1001);
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let collators = Pallet::<T>::config().max_collators_per_container;
+    }: _<T::RuntimeOrigin>(origin, para_id, Some(collators))
+    verify {
+        assert_eq!(PendingCollatorsPerContainerRequests::<T>::get().len(), 1);
+    }
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::mock::new_test_ext(),
@@ -23,7 +23,9 @@
 //!    - How many of those collators should be serving the orchestrator chain
 //!    - Howe many of those collators should be serving the containerChains
 //!
-//! All configuration changes are protected behind the root origin
+//! All configuration changes are protected behind the root origin, except the number of
+//! collators requested by a containerChain, which can also be set by its manager within the
+//! `min_collators_per_container` and `max_collators_per_container` bounds.
 //! CHanges to the configuration are not immeditaly applied, but rather we wait
 //! T::SessionDelay to apply these changes
 
@@ -43,12 +45,12 @@ mod benchmarks;
 
 pub use pallet::*;
 use {
-    frame_support::pallet_prelude::*,
+    frame_support::{pallet_prelude::*, traits::EnsureOriginWithArg},
     frame_system::pallet_prelude::*,
     serde::{Deserialize, Serialize},
     sp_runtime::{traits::AtLeast32BitUnsigned, RuntimeAppPublic, Saturating},
     sp_std::prelude::*,
-    tp_traits::GetSessionIndex,
+    tp_traits::{GetSessionIndex, ParaId},
 };
 
 const LOG_TARGET: &str = "pallet_configuration";
@@ -69,6 +71,10 @@ pub struct HostConfiguration {
     pub min_orchestrator_collators: u32,
     pub max_orchestrator_collators: u32,
     pub collators_per_container: u32,
+    // Bounds of the number of collators a container chain can request instead of
+    // `collators_per_container`
+    pub min_collators_per_container: u32,
+    pub max_collators_per_container: u32,
     // If this value is 0 means that there is no rotation
     pub full_rotation_period: u32,
 }
@@ -81,6 +87,8 @@ impl Default for HostConfiguration {
             // TODO: for zombienet testing
             max_orchestrator_collators: 5u32,
             collators_per_container: 2u32,
+            min_collators_per_container: 1u32,
+            max_collators_per_container: 5u32,
             full_rotation_period: 24u32,
         }
     }
@@ -95,6 +103,13 @@ pub enum InconsistentError {
     MinOrchestratorCollatorsTooLow,
     /// `max_collators` must be at least 1
     MaxCollatorsTooLow,
+    /// `max_collators_per_container` is lower than `min_collators_per_container`
+    MaxCollatorsPerContainerLowerThanMinCollatorsPerContainer,
+    /// `min_collators_per_container` must be at least 1
+    MinCollatorsPerContainerTooLow,
+    /// `collators_per_container` is not within `min_collators_per_container` and
+    /// `max_collators_per_container`
+    CollatorsPerContainerOutOfBounds,
 }
 
 impl HostConfiguration {
@@ -113,6 +128,19 @@ impl HostConfiguration {
         if self.max_orchestrator_collators < self.min_orchestrator_collators {
             return Err(InconsistentError::MaxCollatorsLowerThanMinCollators);
         }
+        if self.min_collators_per_container < 1 {
+            return Err(InconsistentError::MinCollatorsPerContainerTooLow);
+        }
+        if self.max_collators_per_container < self.min_collators_per_container {
+            return Err(
+                InconsistentError::MaxCollatorsPerContainerLowerThanMinCollatorsPerContainer,
+            );
+        }
+        if self.collators_per_container < self.min_collators_per_container
+            || self.collators_per_container > self.max_collators_per_container
+        {
+            return Err(InconsistentError::CollatorsPerContainerOutOfBounds);
+        }
         Ok(())
     }
 
@@ -126,6 +154,18 @@ impl HostConfiguration {
             panic!("Host configuration is inconsistent: {:?}", err);
         }
     }
+
+    /// Number of collators of a container chain that requested `requested` collators, if any.
+    /// The request is bounded by `min_collators_per_container` and `max_collators_per_container`
+    /// in case the bounds changed after it was made.
+    pub fn collators_for_container(&self, requested: Option<u32>) -> u32 {
+        match requested {
+            Some(collators) => collators
+                .max(self.min_collators_per_container)
+                .min(self.max_collators_per_container),
+            None => self.collators_per_container,
+        }
+    }
 }
 
 #[frame_support::pallet]
@@ -141,6 +181,9 @@ pub mod pallet {
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
     pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
         type SessionIndex: parity_scale_codec::FullCodec + TypeInfo + Copy + AtLeast32BitUnsigned;
 
         // `SESSION_DELAY` is used to delay any changes to Paras registration or configurations.
@@ -157,14 +200,33 @@ pub mod pallet {
             + MaybeSerializeDeserialize
             + MaxEncodedLen;
 
+        /// Origin allowed to request the number of collators of a container chain, usually its
+        /// manager or root
+        type ManagerOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, ParaId>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A container chain requested a number of collators, or removed its request, which will
+        /// be applied at `session`
+        CollatorsPerContainerRequested {
+            para_id: ParaId,
+            collators: Option<u32>,
+            session: T::SessionIndex,
+        },
+    }
+
     #[pallet::error]
     pub enum Error<T> {
         /// The new value for a configuration parameter is invalid.
         InvalidNewValue,
+        /// The requested number of collators is not within the `min_collators_per_container` and
+        /// `max_collators_per_container` bounds.
+        CollatorsPerContainerOutOfBounds,
     }
 
     /// The active configuration for the current session.
@@ -189,6 +251,22 @@ pub mod pallet {
     #[pallet::storage]
     pub(crate) type BypassConsistencyCheck<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+    /// Number of collators requested by a container chain, instead of `collators_per_container`.
+    #[pallet::storage]
+    #[pallet::getter(fn collators_per_container_request)]
+    pub(crate) type CollatorsPerContainerRequests<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, u32, OptionQuery>;
+
+    /// Pending changes of `CollatorsPerContainerRequests`, each with the session index at which
+    /// it should be applied. `None` removes the request of the container chain.
+    ///
+    /// The list is sorted ascending by session index, and contains at most one change per
+    /// container chain and session.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_collators_per_container_requests)]
+    pub(crate) type PendingCollatorsPerContainerRequests<T: Config> =
+        StorageValue<_, Vec<(T::SessionIndex, ParaId, Option<u32>)>, ValueQuery>;
+
     #[pallet::genesis_config]
     #[derive(frame_support::DefaultNoBound)]
     pub struct GenesisConfig<T: Config> {
@@ -257,6 +335,12 @@ pub mod pallet {
         pub fn set_collators_per_container(origin: OriginFor<T>, new: u32) -> DispatchResult {
             ensure_root(origin)?;
             Self::schedule_config_update(|config| {
+                if config.min_collators_per_container > new {
+                    config.min_collators_per_container = new;
+                }
+                if config.max_collators_per_container < new {
+                    config.max_collators_per_container = new;
+                }
                 config.collators_per_container = new;
             })
         }
@@ -273,6 +357,91 @@ pub mod pallet {
             })
         }
 
+        #[pallet::call_index(5)]
+        #[pallet::weight((
+			T::WeightInfo::set_config_with_u32(),
+			DispatchClass::Operational,
+		))]
+        pub fn set_min_collators_per_container(origin: OriginFor<T>, new: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::schedule_config_update(|config| {
+                if config.max_collators_per_container < new {
+                    config.max_collators_per_container = new;
+                }
+                if config.collators_per_container < new {
+                    config.collators_per_container = new;
+                }
+                config.min_collators_per_container = new;
+            })
+        }
+
+        #[pallet::call_index(6)]
+        #[pallet::weight((
+			T::WeightInfo::set_config_with_u32(),
+			DispatchClass::Operational,
+		))]
+        pub fn set_max_collators_per_container(origin: OriginFor<T>, new: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::schedule_config_update(|config| {
+                if config.min_collators_per_container > new {
+                    config.min_collators_per_container = new;
+                }
+                if config.collators_per_container > new {
+                    config.collators_per_container = new;
+                }
+                config.max_collators_per_container = new;
+            })
+        }
+
+        /// Request `collators` collators for `para_id` instead of `collators_per_container`, or
+        /// remove the request with `None`. Like the configuration changes, the request is applied
+        /// after `SessionDelay` sessions.
+        ///
+        /// The collators above `collators_per_container` are paid every session through the
+        /// collator assignment, a container chain that cannot pay for them is assigned
+        /// `collators_per_container` collators.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::request_collators_per_container())]
+        pub fn request_collators_per_container(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            collators: Option<u32>,
+        ) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            let scheduled_session = Self::scheduled_session();
+            if let Some(collators) = collators {
+                let config = Self::config_at_session(scheduled_session);
+                ensure!(
+                    collators >= config.min_collators_per_container
+                        && collators <= config.max_collators_per_container,
+                    Error::<T>::CollatorsPerContainerOutOfBounds
+                );
+            }
+
+            PendingCollatorsPerContainerRequests::<T>::mutate(|pending| {
+                if let Some(&mut (_, _, ref mut pending_collators)) =
+                    pending
+                        .iter_mut()
+                        .find(|&&mut (apply_at_session, pending_para_id, _)| {
+                            apply_at_session == scheduled_session && pending_para_id == para_id
+                        })
+                {
+                    *pending_collators = collators;
+                } else {
+                    pending.push((scheduled_session, para_id, collators));
+                }
+            });
+
+            Self::deposit_event(Event::CollatorsPerContainerRequested {
+                para_id,
+                collators,
+                session: scheduled_session,
+            });
+
+            Ok(())
+        }
+
         /// Setting this to true will disable consistency checks for the configuration setters.
         /// Use with caution.
         #[pallet::call_index(44)]
@@ -303,6 +472,8 @@ pub mod pallet {
         /// that became active after the session change. If there were no scheduled changes, both will
         /// be the same.
         pub fn initializer_on_new_session(session_index: &T::SessionIndex) -> SessionChangeOutcome {
+            Self::apply_collators_per_container_requests(session_index);
+
             let pending_configs = <PendingConfigs<T>>::get();
             let prev_config = ActiveConfig::<T>::get();
 
@@ -346,6 +517,70 @@ pub mod pallet {
             }
         }
 
+        /// Apply the requests of collators per container chain scheduled up to `session_index`.
+        fn apply_collators_per_container_requests(session_index: &T::SessionIndex) {
+            let pending_requests = <PendingCollatorsPerContainerRequests<T>>::get();
+            if pending_requests.is_empty() {
+                return;
+            }
+
+            let (past_and_present, future) =
+                pending_requests
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|&(apply_at_session, _, _)| {
+                        apply_at_session <= *session_index
+                    });
+
+            for (_, para_id, collators) in past_and_present {
+                match collators {
+                    Some(collators) => {
+                        CollatorsPerContainerRequests::<T>::insert(para_id, collators)
+                    }
+                    None => CollatorsPerContainerRequests::<T>::remove(para_id),
+                }
+            }
+
+            <PendingCollatorsPerContainerRequests<T>>::put(future);
+        }
+
+        /// Called when a container chain is deregistered, removes its request of collators.
+        pub fn para_deregistered(para_id: ParaId) {
+            CollatorsPerContainerRequests::<T>::remove(para_id);
+            PendingCollatorsPerContainerRequests::<T>::mutate(|pending| {
+                pending.retain(|&(_, pending_para_id, _)| pending_para_id != para_id)
+            });
+        }
+
+        /// The configuration that will be active at `session_index`, taking into account the
+        /// pending configuration changes.
+        fn config_at_session(session_index: T::SessionIndex) -> HostConfiguration {
+            let (past_and_present, _) = Pallet::<T>::pending_configs()
+                .into_iter()
+                .partition::<Vec<_>, _>(|&(apply_at_session, _)| apply_at_session <= session_index);
+
+            if let Some(last) = past_and_present.last() {
+                last.1.clone()
+            } else {
+                Pallet::<T>::config()
+            }
+        }
+
+        /// The number of collators `para_id` will have requested at `session_index`, taking into
+        /// account the pending requests.
+        fn collators_per_container_request_at_session(
+            session_index: T::SessionIndex,
+            para_id: ParaId,
+        ) -> Option<u32> {
+            Pallet::<T>::pending_collators_per_container_requests()
+                .into_iter()
+                .filter(|&(apply_at_session, pending_para_id, _)| {
+                    apply_at_session <= session_index && pending_para_id == para_id
+                })
+                .last()
+                .map(|(_, _, collators)| collators)
+                .unwrap_or_else(|| Pallet::<T>::collators_per_container_request(para_id))
+        }
+
         /// Return the session index that should be used for any future scheduled changes.
         fn scheduled_session() -> T::SessionIndex {
             T::CurrentSessionIndex::session_index().saturating_add(T::SessionDelay::get())
@@ -466,42 +701,21 @@ pub mod pallet {
 
     impl<T: Config> GetHostConfiguration<T::SessionIndex> for Pallet<T> {
         fn collators_per_container(session_index: T::SessionIndex) -> u32 {
-            let (past_and_present, _) = Pallet::<T>::pending_configs()
-                .into_iter()
-                .partition::<Vec<_>, _>(|&(apply_at_session, _)| apply_at_session <= session_index);
+            Self::config_at_session(session_index).collators_per_container
+        }
 
-            let config = if let Some(last) = past_and_present.last() {
-                last.1.clone()
-            } else {
-                Pallet::<T>::config()
-            };
-            config.collators_per_container
+        fn collators_for_container(session_index: T::SessionIndex, para_id: ParaId) -> u32 {
+            Self::config_at_session(session_index).collators_for_container(
+                Self::collators_per_container_request_at_session(session_index, para_id),
+            )
         }
 
         fn min_collators_for_orchestrator(session_index: T::SessionIndex) -> u32 {
-            let (past_and_present, _) = Pallet::<T>::pending_configs()
-                .into_iter()
-                .partition::<Vec<_>, _>(|&(apply_at_session, _)| apply_at_session <= session_index);
-
-            let config = if let Some(last) = past_and_present.last() {
-                last.1.clone()
-            } else {
-                Pallet::<T>::config()
-            };
-            config.min_orchestrator_collators
+            Self::config_at_session(session_index).min_orchestrator_collators
         }
 
         fn max_collators_for_orchestrator(session_index: T::SessionIndex) -> u32 {
-            let (past_and_present, _) = Pallet::<T>::pending_configs()
-                .into_iter()
-                .partition::<Vec<_>, _>(|&(apply_at_session, _)| apply_at_session <= session_index);
-
-            let config = if let Some(last) = past_and_present.last() {
-                last.1.clone()
-            } else {
-                Pallet::<T>::config()
-            };
-            config.max_orchestrator_collators
+            Self::config_at_session(session_index).max_orchestrator_collators
         }
     }
 }
@@ -16,7 +16,7 @@
 
 use {
     crate::{self as pallet_configuration, HostConfiguration},
-    frame_support::traits::{ConstU16, ConstU64},
+    frame_support::traits::{ConstU16, ConstU64, EnsureOriginWithArg},
     frame_system as system,
     sp_core::{ConstU32, H256},
     sp_runtime::{
@@ -24,6 +24,7 @@ use {
         traits::{BlakeTwo256, IdentityLookup},
         BuildStorage,
     },
+    tp_traits::ParaId,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -73,12 +74,34 @@ impl pallet_configuration::GetSessionIndex<u32> for CurrentSessionIndexGetter {
     }
 }
 
+/// The manager of a para id is the account with the same id, root can act as any manager
+pub struct EnsureSignedByManagerOrRoot;
+impl EnsureOriginWithArg<RuntimeOrigin, ParaId> for EnsureSignedByManagerOrRoot {
+    type Success = ();
+
+    fn try_origin(o: RuntimeOrigin, para_id: &ParaId) -> Result<Self::Success, RuntimeOrigin> {
+        let result: Result<frame_system::RawOrigin<u64>, RuntimeOrigin> = o.clone().into();
+        match result {
+            Ok(frame_system::RawOrigin::Root) => Ok(()),
+            Ok(frame_system::RawOrigin::Signed(who)) if who == u32::from(*para_id) as u64 => Ok(()),
+            _ => Err(o),
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin(para_id: &ParaId) -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::signed(u32::from(*para_id) as u64))
+    }
+}
+
 impl pallet_configuration::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type SessionDelay = ConstU32<2>;
     type SessionIndex = u32;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type AuthorityId = UintAuthorityId;
+    type ManagerOrigin = EnsureSignedByManagerOrRoot;
 }
 
 // Build genesis storage according to the mock runtime.
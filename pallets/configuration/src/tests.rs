@@ -15,9 +15,14 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{mock::*, Error, HostConfiguration, PendingConfigs},
+    crate::{
+        mock::*, CollatorsPerContainerRequests, Error, Event, HostConfiguration, InconsistentError,
+        PendingCollatorsPerContainerRequests, PendingConfigs,
+    },
     frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo},
+    sp_runtime::DispatchError,
     sp_std::vec,
+    tp_traits::{GetHostConfiguration, ParaId},
 };
 
 #[test]
@@ -27,6 +32,8 @@ fn config_sets_values_from_genesis() {
         min_orchestrator_collators: 40,
         max_orchestrator_collators: 40,
         collators_per_container: 20,
+        min_collators_per_container: 1,
+        max_collators_per_container: 20,
         full_rotation_period: 24,
     };
     new_test_ext_with_genesis(custom_config.clone()).execute_with(|| {
@@ -42,6 +49,8 @@ fn config_sets_default_values() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     };
     new_test_ext().execute_with(|| {
@@ -57,6 +66,8 @@ fn config_set_value() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     })
     .execute_with(|| {
@@ -76,6 +87,8 @@ fn config_set_value() {
                     min_orchestrator_collators: 2,
                     max_orchestrator_collators: 5,
                     collators_per_container: 2,
+                    min_collators_per_container: 1,
+                    max_collators_per_container: 5,
                     full_rotation_period: 24,
                 }
             )]
@@ -104,6 +117,8 @@ fn config_set_full_rotation_period_to_zero_works() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     })
     .execute_with(|| {
@@ -123,6 +138,8 @@ fn config_set_full_rotation_period_to_zero_works() {
                     min_orchestrator_collators: 2,
                     max_orchestrator_collators: 5,
                     collators_per_container: 2,
+                    min_collators_per_container: 1,
+                    max_collators_per_container: 5,
                     full_rotation_period: 0,
                 }
             )]
@@ -151,6 +168,8 @@ fn config_set_many_values_same_block() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     })
     .execute_with(|| {
@@ -180,6 +199,8 @@ fn config_set_many_values_same_block() {
                     min_orchestrator_collators: 20,
                     max_orchestrator_collators: 20,
                     collators_per_container: 10,
+                    min_collators_per_container: 1,
+                    max_collators_per_container: 10,
                     full_rotation_period: 24,
                 }
             )]
@@ -206,6 +227,8 @@ fn config_set_many_values_different_blocks() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     })
     .execute_with(|| {
@@ -237,6 +260,8 @@ fn config_set_many_values_different_blocks() {
                     min_orchestrator_collators: 20,
                     max_orchestrator_collators: 20,
                     collators_per_container: 10,
+                    min_collators_per_container: 1,
+                    max_collators_per_container: 10,
                     full_rotation_period: 24,
                 }
             )]
@@ -263,6 +288,8 @@ fn config_set_many_values_different_sessions() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     })
     .execute_with(|| {
@@ -298,6 +325,8 @@ fn config_set_many_values_different_sessions() {
                         min_orchestrator_collators: 20,
                         max_orchestrator_collators: 20,
                         collators_per_container: 2,
+                        min_collators_per_container: 1,
+                        max_collators_per_container: 5,
                         full_rotation_period: 24,
                     }
                 ),
@@ -308,6 +337,8 @@ fn config_set_many_values_different_sessions() {
                         min_orchestrator_collators: 20,
                         max_orchestrator_collators: 20,
                         collators_per_container: 10,
+                        min_collators_per_container: 1,
+                        max_collators_per_container: 10,
                         full_rotation_period: 24,
                     }
                 )
@@ -335,6 +366,8 @@ fn config_cannot_set_invalid_values() {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 5,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     })
     .execute_with(|| {
@@ -351,6 +384,226 @@ fn config_cannot_set_invalid_values() {
             Configuration::set_max_orchestrator_collators(RuntimeOrigin::root(), 0),
             Error::<Test>::InvalidNewValue
         );
+        assert_noop!(
+            Configuration::set_collators_per_container(RuntimeOrigin::root(), 0),
+            Error::<Test>::InvalidNewValue
+        );
+    });
+}
+
+#[test]
+fn config_set_collators_per_container_bounds() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_noop!(
+            Configuration::set_min_collators_per_container(RuntimeOrigin::root(), 0),
+            Error::<Test>::InvalidNewValue
+        );
+        assert_ok!(Configuration::set_min_collators_per_container(
+            RuntimeOrigin::root(),
+            8
+        ));
+        assert_ok!(Configuration::set_max_collators_per_container(
+            RuntimeOrigin::root(),
+            6
+        ));
+
+        // Setting a bound moves the other one and collators_per_container if needed to keep the
+        // config consistent
+        let (_, pending_config) = PendingConfigs::<Test>::get().pop().unwrap();
+        assert_eq!(pending_config.min_collators_per_container, 6);
+        assert_eq!(pending_config.max_collators_per_container, 6);
+        assert_eq!(pending_config.collators_per_container, 6);
+
+        // Setting collators_per_container out of the bounds moves the bounds
+        assert_ok!(Configuration::set_collators_per_container(
+            RuntimeOrigin::root(),
+            3
+        ));
+        let (_, pending_config) = PendingConfigs::<Test>::get().pop().unwrap();
+        assert_eq!(pending_config.min_collators_per_container, 3);
+        assert_eq!(pending_config.max_collators_per_container, 6);
+        assert_eq!(pending_config.collators_per_container, 3);
+    });
+}
+
+#[test]
+fn config_collators_per_container_must_be_within_bounds() {
+    let mut config = HostConfiguration {
+        collators_per_container: 2,
+        min_collators_per_container: 3,
+        max_collators_per_container: 5,
+        ..Default::default()
+    };
+    assert!(matches!(
+        config.check_consistency(),
+        Err(InconsistentError::CollatorsPerContainerOutOfBounds)
+    ));
+
+    config.collators_per_container = 6;
+    assert!(matches!(
+        config.check_consistency(),
+        Err(InconsistentError::CollatorsPerContainerOutOfBounds)
+    ));
+
+    config.collators_per_container = 5;
+    assert!(config.check_consistency().is_ok());
+}
+
+#[test]
+fn manager_can_request_collators_per_container() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let para_id = ParaId::from(1001);
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::signed(1001),
+            para_id,
+            Some(4)
+        ));
+        System::assert_last_event(
+            Event::CollatorsPerContainerRequested {
+                para_id,
+                collators: Some(4),
+                session: 2,
+            }
+            .into(),
+        );
+        assert_eq!(
+            PendingCollatorsPerContainerRequests::<Test>::get(),
+            vec![(2, para_id, Some(4))]
+        );
+
+        // The request is applied after the session delay, like the configuration changes
+        assert_eq!(Configuration::collators_for_container(1, para_id), 2);
+        assert_eq!(Configuration::collators_for_container(2, para_id), 4);
+        // Other container chains keep collators_per_container
+        assert_eq!(Configuration::collators_for_container(2, 1002.into()), 2);
+
+        run_to_block(10);
+        assert_eq!(CollatorsPerContainerRequests::<Test>::get(para_id), None);
+        run_to_block(11);
+        assert_eq!(CollatorsPerContainerRequests::<Test>::get(para_id), Some(4));
+        assert!(PendingCollatorsPerContainerRequests::<Test>::get().is_empty());
+
+        // Removing the request goes back to collators_per_container
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::signed(1001),
+            para_id,
+            None
+        ));
+        assert_eq!(Configuration::collators_for_container(3, para_id), 4);
+        assert_eq!(Configuration::collators_for_container(4, para_id), 2);
+        run_to_block(21);
+        assert_eq!(CollatorsPerContainerRequests::<Test>::get(para_id), None);
+    });
+}
+
+#[test]
+fn request_collators_per_container_same_session_overrides_previous_request() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let para_id = ParaId::from(1001);
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::root(),
+            para_id,
+            Some(4)
+        ));
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::root(),
+            para_id,
+            Some(3)
+        ));
+        assert_eq!(
+            PendingCollatorsPerContainerRequests::<Test>::get(),
+            vec![(2, para_id, Some(3))]
+        );
+    });
+}
+
+#[test]
+fn request_collators_per_container_checks_origin_and_bounds() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let para_id = ParaId::from(1001);
+        assert_noop!(
+            Configuration::request_collators_per_container(
+                RuntimeOrigin::signed(1002),
+                para_id,
+                Some(4)
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Configuration::request_collators_per_container(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                Some(0)
+            ),
+            Error::<Test>::CollatorsPerContainerOutOfBounds
+        );
+        assert_noop!(
+            Configuration::request_collators_per_container(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                Some(6)
+            ),
+            Error::<Test>::CollatorsPerContainerOutOfBounds
+        );
+
+        // The bounds are the ones of the config active when the request is applied
+        assert_ok!(Configuration::set_max_collators_per_container(
+            RuntimeOrigin::root(),
+            8
+        ));
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::signed(1001),
+            para_id,
+            Some(6)
+        ));
+    });
+}
+
+#[test]
+fn collators_per_container_request_is_bounded_by_new_config() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let para_id = ParaId::from(1001);
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::signed(1001),
+            para_id,
+            Some(5)
+        ));
+        run_to_block(11);
+        assert_ok!(Configuration::set_max_collators_per_container(
+            RuntimeOrigin::root(),
+            3
+        ));
+
+        assert_eq!(Configuration::collators_for_container(3, para_id), 5);
+        assert_eq!(Configuration::collators_for_container(4, para_id), 3);
+    });
+}
+
+#[test]
+fn para_deregistered_removes_collators_per_container_request() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let para_id = ParaId::from(1001);
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::signed(1001),
+            para_id,
+            Some(4)
+        ));
+        run_to_block(11);
+        assert_ok!(Configuration::request_collators_per_container(
+            RuntimeOrigin::signed(1001),
+            para_id,
+            Some(3)
+        ));
+
+        Configuration::para_deregistered(para_id);
+        assert_eq!(CollatorsPerContainerRequests::<Test>::get(para_id), None);
+        assert!(PendingCollatorsPerContainerRequests::<Test>::get().is_empty());
     });
 }
 
@@ -384,5 +637,15 @@ fn weights_assigned_to_extrinsics_are_correct() {
                 .weight,
             <() as crate::weights::WeightInfo>::set_config_with_u32()
         );
+
+        assert_eq!(
+            crate::Call::<Test>::request_collators_per_container {
+                para_id: 1001.into(),
+                collators: Some(1u32)
+            }
+            .get_dispatch_info()
+            .weight,
+            <() as crate::weights::WeightInfo>::request_collators_per_container()
+        );
     });
 }
@@ -0,0 +1,36 @@
+// This file is part of BitGreen.
+// Copyright (C) 2022 BitGreen.
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//! Runtime API for Dex pallet. Can be used by clients to read the open sell orders, the
+//! buy orders waiting for payment validation and the receivables of sellers, and to quote
+//! a purchase before creating a buy order.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::Codec;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DexApi<AccountId, AssetId, ProjectId, OrderId, AssetBalance, Balance, SellOrder, PendingBuyOrder> where
+		AccountId: Codec,
+		AssetId: Codec,
+		ProjectId: Codec,
+		OrderId: Codec,
+		AssetBalance: Codec,
+		Balance: Codec,
+		SellOrder: Codec,
+		PendingBuyOrder: Codec,
+	{
+		/// Return the open sell orders, filtered by `asset_id` and `project_id` when given.
+		fn sell_orders(asset_id: Option<AssetId>, project_id: Option<ProjectId>) -> Vec<(OrderId, SellOrder)>;
+		/// Return the buy orders waiting for payment validation, filtered by `buyer` when
+		/// given, along with the validations received and required.
+		fn pending_buy_orders(buyer: Option<AccountId>) -> Vec<PendingBuyOrder>;
+		/// Quote the purchase of `units` from the sell order `order_id`.
+		/// Returns the (price of units, total fee, total amount including fees).
+		fn quote_buy_order(order_id: OrderId, units: AssetBalance) -> Result<(Balance, Balance, Balance), DispatchError>;
+		/// Return the amount receivable by the given `seller` from sales.
+		fn seller_receivables(seller: AccountId) -> Balance;
+	}
+}
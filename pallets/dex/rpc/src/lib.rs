@@ -0,0 +1,190 @@
+// This file is part of BitGreen.
+// Copyright (C) 2022 BitGreen.
+// This code is licensed under MIT license (see LICENSE.txt for details)
+//! JSON-RPC interface of the Dex pallet.
+//! Sell orders and pending buy orders are returned as JSON objects with the fields of the
+//! pallet types.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_dex_runtime_api::DexApi as DexRuntimeApi;
+use parity_scale_codec::Codec;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code returned when the runtime api call fails
+const RUNTIME_ERROR: i32 = 1;
+/// Error code returned when a purchase cannot be quoted
+const QUOTE_ERROR: i32 = 2;
+
+#[rpc(client, server)]
+pub trait DexApi<
+	BlockHash,
+	AccountId,
+	AssetId,
+	ProjectId,
+	OrderId,
+	AssetBalance,
+	Balance,
+	SellOrder,
+	PendingBuyOrder,
+>
+{
+	/// Return the list of (OrderId, OrderInfo) of the open sell orders, filtered by asset and
+	/// project when given
+	#[method(name = "dex_sellOrders")]
+	fn sell_orders(
+		&self,
+		asset_id: Option<AssetId>,
+		project_id: Option<ProjectId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(OrderId, SellOrder)>>;
+
+	/// Return the list of PendingBuyOrder, filtered by buyer when given
+	#[method(name = "dex_pendingBuyOrders")]
+	fn pending_buy_orders(
+		&self,
+		buyer: Option<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PendingBuyOrder>>;
+
+	/// Return the (price of units, total fee, total amount including fees) to buy `units`
+	/// from the given sell order
+	#[method(name = "dex_quoteBuyOrder")]
+	fn quote_buy_order(
+		&self,
+		order_id: OrderId,
+		units: AssetBalance,
+		at: Option<BlockHash>,
+	) -> RpcResult<(Balance, Balance, Balance)>;
+
+	/// Return the amount receivable by the given seller from sales
+	#[method(name = "dex_sellerReceivables")]
+	fn seller_receivables(&self, seller: AccountId, at: Option<BlockHash>) -> RpcResult<Balance>;
+}
+
+/// Provides RPC methods to query the Dex pallet
+pub struct Dex<C, P> {
+	client: Arc<C>,
+	_marker: PhantomData<P>,
+}
+
+impl<C, P> Dex<C, P> {
+	/// Create new `Dex` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+impl<
+		C,
+		Block,
+		AccountId,
+		AssetId,
+		ProjectId,
+		OrderId,
+		AssetBalance,
+		Balance,
+		SellOrder,
+		PendingBuyOrder,
+	>
+	DexApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		AssetId,
+		ProjectId,
+		OrderId,
+		AssetBalance,
+		Balance,
+		SellOrder,
+		PendingBuyOrder,
+	> for Dex<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DexRuntimeApi<
+		Block,
+		AccountId,
+		AssetId,
+		ProjectId,
+		OrderId,
+		AssetBalance,
+		Balance,
+		SellOrder,
+		PendingBuyOrder,
+	>,
+	AccountId: Codec,
+	AssetId: Codec,
+	ProjectId: Codec,
+	OrderId: Codec,
+	AssetBalance: Codec,
+	Balance: Codec,
+	SellOrder: Codec + Send + Sync + 'static,
+	PendingBuyOrder: Codec + Send + Sync + 'static,
+{
+	fn sell_orders(
+		&self,
+		asset_id: Option<AssetId>,
+		project_id: Option<ProjectId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(OrderId, SellOrder)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.sell_orders(at, asset_id, project_id)
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn pending_buy_orders(
+		&self,
+		buyer: Option<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PendingBuyOrder>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().pending_buy_orders(at, buyer).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn quote_buy_order(
+		&self,
+		order_id: OrderId,
+		units: AssetBalance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(Balance, Balance, Balance)> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.quote_buy_order(at, order_id, units)
+			.map_err(runtime_error_into_rpc_err)?
+			.map_err(|err| {
+				JsonRpseeError::from(CallError::Custom(ErrorObject::owned(
+					QUOTE_ERROR,
+					"Unable to quote buy order",
+					Some(format!("{:?}", err)),
+				)))
+			})
+	}
+
+	fn seller_receivables(
+		&self,
+		seller: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Balance> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().seller_receivables(at, seller).map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -5,18 +5,31 @@
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::{RawOrigin, RawOrigin as SystemOrigin};
 use orml_traits::MultiCurrency;
-use primitives::CurrencyId;
+use primitives::{CarbonCreditsValidator, CurrencyId, Royalty};
 use sp_runtime::traits::StaticLookup;
 use sp_std::prelude::*;
 
 use super::*;
-use crate::Pallet as Dex;
+use crate::{types::CurrencyIdOf, Pallet as Dex};
 use sp_runtime::Percent;
 
 fn get_currency_id() -> CurrencyId {
 	primitives::CurrencyId::USDT
 }
 
+/// Set `r` royalty recipients on the project of the listed asset
+fn set_royalties<T: Config>(r: u32) {
+	let royalties = (0..r)
+		.map(|i| Royalty {
+			account_id: account("royalty_recipient", i, 1),
+			percent_of_fees: Percent::from_percent(1),
+		})
+		.collect();
+	let (project_id, _) = T::AssetValidator::get_project_details(&0u32.into())
+		.expect("the listed asset belongs to a project");
+	T::AssetValidator::set_royalties(&project_id, royalties);
+}
+
 fn create_default_asset<T: Config + pallet_assets::Config>(
 	is_sufficient: bool,
 ) -> (T::AccountId, <T::Lookup as StaticLookup>::Source) {
@@ -57,14 +70,24 @@ benchmarks! {
 		T: orml_tokens::Config,
 		<<T as pallet::Config>::Asset as frame_support::traits::fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId : From<u32>,
 		T: orml_tokens::Config<CurrencyId = CurrencyId>,
+		CurrencyIdOf<T>: From<CurrencyId>,
 	}
 
 	create_sell_order {
-		create_default_minted_asset::<T>(true, 100u32.into());
+		let m in 1 .. T::MaxMatchesPerOrder::get();
+		create_default_minted_asset::<T>(true, 1_000_000u32.into());
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller.into()), 0u32.into(), 100u32.into(), 10u32.into())
+		// the sell order is matched against `m` resting bids, each on its own price level
+		let buyer : T::AccountId = account("account_id", 0, 1);
+		<orml_tokens::Pallet<T>>::deposit(get_currency_id(), &buyer, 1_000_000u32.into())?;
+		let expiry_time = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		for i in 0 .. m {
+			Dex::<T>::create_bid_order(RawOrigin::Signed(buyer.clone()).into(), 0u32.into(), 2u32.into(), (10u32 + i).into(), get_currency_id().into(), expiry_time)?;
+		}
+	}: _(RawOrigin::Signed(caller.into()), 0u32.into(), (2u32 * m + 2u32).into(), 10u32.into())
 	verify {
-		assert!(Orders::<T>::get(0u128).is_some())
+		assert!(Orders::<T>::get(0u128).is_some());
+		assert_eq!(BidOrders::<T>::iter().count(), 0);
 	}
 
 	cancel_sell_order {
@@ -77,7 +100,9 @@ benchmarks! {
 	}
 
 	create_buy_order {
+		let r in 0 .. T::MaxRoyaltyRecipients::get();
 		create_default_minted_asset::<T>(true, 100u32.into());
+		set_royalties::<T>(r);
 		let caller: T::AccountId = whitelisted_caller();
 		Dex::<T>::create_sell_order(RawOrigin::Signed(caller.clone()).into(), 0u32.into(), 100u32.into(), 1u32.into())?;
 		// give the buyer some tokens to pay
@@ -87,6 +112,52 @@ benchmarks! {
 	verify {}
 
 
+	create_buy_order_onchain {
+		let r in 0 .. T::MaxRoyaltyRecipients::get();
+		create_default_minted_asset::<T>(true, 100u32.into());
+		// every royalty recipient receives 1% of the gross amount
+		set_royalties::<T>(r);
+		RoyaltyCalculationBasis::<T>::put(RoyaltyBasis::GrossAmount);
+		let caller: T::AccountId = whitelisted_caller();
+		Dex::<T>::create_sell_order(RawOrigin::Signed(caller.clone()).into(), 0u32.into(), 100u32.into(), 100u32.into())?;
+		// give the buyer some tokens to pay
+		let buyer : T::AccountId = account("account_id", 0, 1);
+		<orml_tokens::Pallet<T>>::deposit(get_currency_id(), &buyer, 1000u32.into())?;
+	}: _(RawOrigin::Signed(buyer.clone().into()), 0u128, 0u32.into(), 1u32.into(), 100u32.into(), get_currency_id().into())
+	verify {
+		assert_eq!(<orml_tokens::Pallet<T>>::free_balance(get_currency_id(), &buyer), 900u32.into());
+	}
+
+	create_bid_order {
+		let m in 1 .. T::MaxMatchesPerOrder::get();
+		create_default_minted_asset::<T>(true, 1_000_000u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		// the bid is matched against `m` resting sell orders, each on its own price level
+		for i in 0 .. m {
+			Dex::<T>::create_sell_order(RawOrigin::Signed(caller.clone()).into(), 0u32.into(), 2u32.into(), (1u32 + i).into())?;
+		}
+		// give the buyer some tokens to pay
+		let buyer : T::AccountId = account("account_id", 0, 1);
+		<orml_tokens::Pallet<T>>::deposit(get_currency_id(), &buyer, 1_000_000u32.into())?;
+		let expiry_time = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(buyer.clone().into()), 0u32.into(), (2u32 * m + 2u32).into(), (1u32 + m).into(), get_currency_id().into(), expiry_time)
+	verify {
+		// the unfilled units rest in the book
+		assert_eq!(BidOrders::<T>::get(0u128).map(|bid| bid.units), Some(2u32.into()));
+	}
+
+	cancel_bid_order {
+		create_default_minted_asset::<T>(true, 100u32.into());
+		// give the buyer some tokens to pay
+		let buyer : T::AccountId = account("account_id", 0, 1);
+		<orml_tokens::Pallet<T>>::deposit(get_currency_id(), &buyer, 1000u32.into())?;
+		let expiry_time = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Dex::<T>::create_bid_order(RawOrigin::Signed(buyer.clone()).into(), 0u32.into(), 10u32.into(), 2u32.into(), get_currency_id().into(), expiry_time)?;
+	}: _(RawOrigin::Signed(buyer.clone().into()), 0u128)
+	verify {
+		assert_eq!(<orml_tokens::Pallet<T>>::free_balance(get_currency_id(), &buyer), 1000u32.into());
+	}
+
 	force_set_payment_fee {
 	}: _(RawOrigin::Root, Percent::from_percent(10))
 	verify {
@@ -99,6 +170,29 @@ benchmarks! {
 		assert_eq!(PurchaseFees::<T>::get(), 10u32.into());
 	}
 
+	force_set_royalty_basis {
+	}: _(RawOrigin::Root, RoyaltyBasis::GrossAmount)
+	verify {
+		assert_eq!(RoyaltyCalculationBasis::<T>::get(), RoyaltyBasis::GrossAmount);
+	}
+
+	record_payment_to_royalty_recipient {
+		let authority: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("account_id", 0, 1);
+		Dex::<T>::force_set_seller_payout_authority(RawOrigin::Root.into(), authority.clone())?;
+		RoyaltyReceivables::<T>::insert(recipient.clone(), CurrencyBalanceOf::<T>::from(100u32));
+		let payout = PayoutExecutedToSeller {
+			order_id: vec![0u128].try_into().unwrap(),
+			chain_id: 0,
+			recipient_address: vec![].try_into().unwrap(),
+			amount: 100u32.into(),
+			tx_hash: vec![].try_into().unwrap(),
+		};
+	}: _(RawOrigin::Signed(authority), recipient.clone(), payout)
+	verify {
+		assert_eq!(RoyaltyReceivables::<T>::get(recipient), Some(0u32.into()));
+	}
+
 
 	impl_benchmark_test_suite!(Dex, crate::mock::new_test_ext(), crate::mock::Test);
-}
\ No newline at end of file
+}
@@ -17,14 +17,23 @@
 //! * `create_sell_order`: Creates a new sell order onchain
 //! * `cancel_sell_order`: Cancel an existing sell order
 //! * `buy_order`: Purchase units from exising sell order
+//! * `create_buy_order_onchain`: Purchase units from an existing sell order and settle the payment
+//!   in the same extrinsic using an accepted on-chain currency
+//! * `create_bid_order`: Place a standing bid for an asset, the bid is matched against the open
+//!   sell orders with the best price first and rests in the order book until filled or expired
+//! * `cancel_bid_order`: Cancel an open bid order and refund the unused funds
 //!
 //! ### Permissioned Functions
 //!
 //! * `force_set_purchase_fee` : Set the purchase fee percentage for the dex
 //! * `force_set_payment_fee` : Set the payment fee percentage for the dex
+//! * `force_set_royalty_basis` : Set whether royalties are calculated from fees or gross amount
+//! * `record_payment_to_royalty_recipient` : Record a payout executed to a royalty recipient
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::type_complexity, clippy::too_many_arguments)]
 use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::DispatchResult;
+use primitives::MarketplaceListing;
 use sp_runtime::RuntimeDebug;
 
 pub use pallet::*;
@@ -39,9 +48,11 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migration;
 mod weights;
 pub use weights::WeightInfo;
 mod types;
+pub use types::*;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -53,15 +64,18 @@ pub mod pallet {
 	};
 	use frame_system::pallet_prelude::{OriginFor, *};
 	use orml_traits::MultiCurrency;
-	use primitives::CarbonCreditsValidator;
+	use primitives::{CarbonCreditsValidator, KycLevelProvider};
 	use sp_runtime::{
 		traits::{AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, CheckedSub, One, Zero},
 		Percent, Saturating,
 	};
-	use sp_std::fmt::Debug;
+	use sp_std::{fmt::Debug, vec::Vec};
 
-	#[pallet::pallet]
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -149,11 +163,33 @@ pub mod pallet {
 		/// The maximum open orders allowed for a user
 		type MaxOpenOrdersPerUser: Get<u32> + TypeInfo + Clone + Debug + PartialEq;
 
-		/// KYC provider config
-		type KYCProvider: Contains<Self::AccountId>;
+		/// KYC provider config, the KYC level of a buyer determines their open order limits
+		type KYCProvider: KycLevelProvider<Self::AccountId>;
 
 		/// The expiry time for buy order
 		type BuyOrderExpiryTime: Get<BlockNumberFor<Self>>;
+
+		/// The account that receives the fees from on-chain settled buy orders
+		type FeeAccount: Get<Self::AccountId>;
+
+		/// The currencies accepted as payment for on-chain settled buy orders
+		type SettlementCurrencies: Contains<CurrencyIdOf<Self>>;
+
+		/// The maximum number of price levels on each side of the order book of an asset
+		#[pallet::constant]
+		type MaxPriceLevels: Get<u32>;
+
+		/// The maximum number of orders resting at a single price level of the order book
+		#[pallet::constant]
+		type MaxOrdersPerPriceLevel: Get<u32>;
+
+		/// The maximum number of resting orders a new order visits while matching, filled or not
+		#[pallet::constant]
+		type MaxMatchesPerOrder: Get<u32>;
+
+		/// The maximum number of royalty recipients of a project, paid on every fill
+		#[pallet::constant]
+		type MaxRoyaltyRecipients: Get<u32>;
 	}
 
 	// orders information
@@ -206,6 +242,17 @@ pub mod pallet {
 	pub type SellerReceivables<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, CurrencyBalanceOf<T>>;
 
+	// Royalty receivables from sales
+	#[pallet::storage]
+	#[pallet::getter(fn royalty_receivables)]
+	pub type RoyaltyReceivables<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CurrencyBalanceOf<T>>;
+
+	// The amount royalties are calculated from
+	#[pallet::storage]
+	#[pallet::getter(fn royalty_basis)]
+	pub type RoyaltyCalculationBasis<T: Config> = StorageValue<_, RoyaltyBasis, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn seller_payout_authority)]
 	// The account that can confirm payouts to seller
@@ -233,6 +280,54 @@ pub mod pallet {
 	pub type UserOpenOrderUnitsAllowed<T: Config> =
 		StorageMap<_, Blake2_128Concat, UserLevel, AssetBalanceOf<T>>;
 
+	// bid orders count
+	#[pallet::storage]
+	#[pallet::getter(fn bid_order_count)]
+	pub type BidOrderCount<T: Config> = StorageValue<_, BidOrderId, ValueQuery>;
+
+	// open bid orders information
+	#[pallet::storage]
+	#[pallet::getter(fn bid_order_info)]
+	pub type BidOrders<T: Config> = StorageMap<_, Blake2_128Concat, BidOrderId, BidOrderInfoOf<T>>;
+
+	// Price levels with resting sell orders for an asset
+	#[pallet::storage]
+	#[pallet::getter(fn ask_price_levels)]
+	pub type AskPriceLevels<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, PriceLevelsOf<T>, ValueQuery>;
+
+	// Price levels with resting bid orders for an asset
+	#[pallet::storage]
+	#[pallet::getter(fn bid_price_levels)]
+	pub type BidPriceLevels<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, PriceLevelsOf<T>, ValueQuery>;
+
+	// Sell orders resting at a price level of an asset
+	#[pallet::storage]
+	#[pallet::getter(fn ask_book)]
+	pub type AskBook<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		CurrencyBalanceOf<T>,
+		PriceLevelOrdersOf<T>,
+		ValueQuery,
+	>;
+
+	// Bid orders resting at a price level of an asset
+	#[pallet::storage]
+	#[pallet::getter(fn bid_book)]
+	pub type BidBook<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		CurrencyBalanceOf<T>,
+		PriceLevelOrdersOf<T>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -299,6 +394,58 @@ pub mod pallet {
 		UserOpenOrderUnitsLimitUpdated { level: UserLevel, limit: AssetBalanceOf<T> },
 		/// BuyOrdersByUser storage was cleard
 		BuyOrdersByUserCleared { user: T::AccountId },
+		/// A project royalty was paid for a buy order, `paid_onchain` is false if the royalty was
+		/// added to the recipient receivables
+		RoyaltyPaid {
+			order_id: BuyOrderId,
+			project_id: ProjectIdOf<T>,
+			recipient: T::AccountId,
+			amount: CurrencyBalanceOf<T>,
+			paid_onchain: bool,
+		},
+		/// Royalty calculation basis has been set
+		RoyaltyBasisSet { basis: RoyaltyBasis },
+		/// A royalty recipient was paid
+		RoyaltyPayoutExecuted { recipient: T::AccountId, payout: PayoutExecutedToSellerOf<T> },
+		/// A buy order was paid and settled on-chain
+		BuyOrderSettled {
+			order_id: BuyOrderId,
+			sell_order_id: OrderId,
+			currency_id: CurrencyIdOf<T>,
+			amount_to_seller: CurrencyBalanceOf<T>,
+			fees_paid: CurrencyBalanceOf<T>,
+			fee_account: T::AccountId,
+		},
+		/// A new bid order has been created, `reserved` is held until the bid is closed
+		BidOrderCreated {
+			bid_order_id: BidOrderId,
+			asset_id: AssetIdOf<T>,
+			units: AssetBalanceOf<T>,
+			price_per_unit: CurrencyBalanceOf<T>,
+			currency_id: CurrencyIdOf<T>,
+			reserved: CurrencyBalanceOf<T>,
+			expiry_time: BlockNumberFor<T>,
+			owner: T::AccountId,
+		},
+		/// A bid order was matched with a sell order
+		OrdersMatched {
+			bid_order_id: BidOrderId,
+			sell_order_id: OrderId,
+			asset_id: AssetIdOf<T>,
+			units: AssetBalanceOf<T>,
+			price_per_unit: CurrencyBalanceOf<T>,
+			currency_id: CurrencyIdOf<T>,
+			fees_paid: CurrencyBalanceOf<T>,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+		},
+		/// A bid order was filled, cancelled or expired and the unused funds were refunded
+		BidOrderClosed {
+			bid_order_id: BidOrderId,
+			units_filled: AssetBalanceOf<T>,
+			refunded: CurrencyBalanceOf<T>,
+			owner: T::AccountId,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -362,6 +509,20 @@ pub mod pallet {
 		UserOpenOrderUnitsLimtNotFound,
 		/// Min validators cannot be zero
 		MinValidatorsCannotBeZero,
+		/// The currency is not accepted for on-chain settlement
+		SettlementCurrencyNotAccepted,
+		/// The royalties of the project exceed the amount they are calculated from
+		RoyaltiesExceedAmount,
+		/// The bid orderId does not exist
+		InvalidBidOrderId,
+		/// The expiry time of the bid order is not in the future
+		InvalidBidOrderExpiry,
+		/// The order book of the asset has too many price levels
+		TooManyPriceLevels,
+		/// The price level of the order book has too many orders
+		PriceLevelFull,
+		/// The funds reserved for the bid order do not cover the fill
+		BidOrderReserveExhausted,
 	}
 
 	#[pallet::hooks]
@@ -403,6 +564,22 @@ pub mod pallet {
 						);
 					}
 
+					// the sell order can be matched with bids again, at its original position
+					if let Some(order) = Orders::<T>::get(buy_order.order_id) {
+						let res = Self::add_to_ask_book(
+							&order.asset_id,
+							order.price_per_unit,
+							buy_order.order_id,
+						);
+						if res.is_err() {
+							log::warn!(
+								target: "runtime::dex",
+								"WARNING: Sell order not added back to order book for buy_order_id: {}",
+								key
+							);
+						}
+					}
+
 					log::info!(
 						target: "runtime::dex",
 						"INFO: Removed Expired buy order with buy_order_id: {}",
@@ -450,7 +627,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Create a new sell order for given `asset_id`
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::create_sell_order())]
+		#[pallet::weight(T::WeightInfo::create_sell_order(T::MaxMatchesPerOrder::get()))]
 		pub fn create_sell_order(
 			origin: OriginFor<T>,
 			asset_id: AssetIdOf<T>,
@@ -458,44 +635,7 @@ pub mod pallet {
 			price_per_unit: CurrencyBalanceOf<T>,
 		) -> DispatchResult {
 			let seller = ensure_signed(origin.clone())?;
-			Self::check_kyc_approval(&seller)?;
-			// ensure the asset_id can be listed
-			let (project_id, group_id) = T::AssetValidator::get_project_details(&asset_id)
-				.ok_or(Error::<T>::AssetNotPermitted)?;
-
-			// ensure minimums are satisfied
-			ensure!(units >= T::MinUnitsToCreateSellOrder::get(), Error::<T>::BelowMinimumUnits);
-			ensure!(price_per_unit >= T::MinPricePerUnit::get(), Error::<T>::BelowMinimumPrice);
-
-			// transfer assets from seller to pallet
-			T::Asset::transfer(asset_id.clone(), &seller, &Self::account_id(), units, Expendable)?;
-
-			let order_id = Self::order_count();
-			let next_order_id =
-				order_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOverflow)?;
-			OrderCount::<T>::put(next_order_id);
-
-			// order values
-			Orders::<T>::insert(
-				order_id,
-				OrderInfo {
-					owner: seller.clone(),
-					units,
-					price_per_unit,
-					asset_id: asset_id.clone(),
-				},
-			);
-
-			Self::deposit_event(Event::SellOrderCreated {
-				order_id,
-				asset_id,
-				project_id,
-				group_id,
-				units,
-				price_per_unit,
-				owner: seller,
-			});
-
+			Self::do_create_sell_order(&seller, seller.clone(), asset_id, units, price_per_unit)?;
 			Ok(())
 		}
 
@@ -510,6 +650,8 @@ pub mod pallet {
 
 			ensure!(seller == order.owner, Error::<T>::InvalidOrderOwner);
 
+			Self::remove_from_ask_book(&order.asset_id, order.price_per_unit, order_id);
+
 			// transfer assets from pallet to seller
 			T::Asset::transfer(
 				order.asset_id,
@@ -526,7 +668,7 @@ pub mod pallet {
 		/// Buy `units` of `asset_id` from the given `order_id`
 		/// This will be called by one of the approved validators when an order is created
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::buy_order())]
+		#[pallet::weight(T::WeightInfo::buy_order(0))]
 		pub fn create_buy_order(
 			origin: OriginFor<T>,
 			order_id: OrderId,
@@ -535,7 +677,7 @@ pub mod pallet {
 			max_fee: CurrencyBalanceOf<T>,
 		) -> DispatchResult {
 			let buyer = ensure_signed(origin)?;
-			Self::check_kyc_approval(&buyer)?;
+			let buyer_level = Self::check_kyc_approval(&buyer)?;
 
 			if units.is_zero() {
 				return Ok(())
@@ -562,25 +704,8 @@ pub mod pallet {
 					order.units.checked_sub(&units).ok_or(Error::<T>::OrderUnitsOverflow)?;
 
 				// calculate fees
-				let units_as_u128: u128 =
-					units.try_into().map_err(|_| Error::<T>::ArithmeticError)?;
-				let price_per_unit_as_u128: u128 =
-					order.price_per_unit.try_into().map_err(|_| Error::<T>::ArithmeticError)?;
-
-				let required_currency = price_per_unit_as_u128
-					.checked_mul(units_as_u128)
-					.ok_or(Error::<T>::ArithmeticError)?;
-
-				let payment_fee = PaymentFees::<T>::get().mul_ceil(required_currency);
-				let purchase_fee: u128 =
-					PurchaseFees::<T>::get().try_into().map_err(|_| Error::<T>::ArithmeticError)?;
-
-				let total_fee =
-					payment_fee.checked_add(purchase_fee).ok_or(Error::<T>::OrderUnitsOverflow)?;
-
-				let total_amount = total_fee
-					.checked_add(required_currency)
-					.ok_or(Error::<T>::OrderUnitsOverflow)?;
+				let (_, total_fee, total_amount) =
+					Self::calculate_order_amounts(order.price_per_unit, units)?;
 
 				ensure!(max_fee >= total_fee.into(), Error::<T>::FeeExceedsUserLimit);
 
@@ -603,11 +728,10 @@ pub mod pallet {
 						let current_open_orders_units: AssetBalanceOf<T> =
 							open_buy_orders.iter().fold(0u32.into(), |sum, val| sum + val.1);
 
-						// everyone is considered KYC level 1
+						// the limit depends on the kyc level of the buyer
 						// throw error if limit is not found, more safer that silently ignoring
-						let allowed_open_order_units =
-							UserOpenOrderUnitsAllowed::<T>::get(UserLevel::KYCLevel1)
-								.ok_or(Error::<T>::UserOpenOrderUnitsLimtNotFound)?;
+						let allowed_open_order_units = Self::open_order_units_allowed(&buyer_level)
+							.ok_or(Error::<T>::UserOpenOrderUnitsLimtNotFound)?;
 
 						// ensure the user does not exceed the maximum allowed amount
 						ensure!(
@@ -691,7 +815,7 @@ pub mod pallet {
 		/// Buy `units` of `asset_id` from the given `order_id`
 		/// This will be called by one of the approved validators when an order is created
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::buy_order())]
+		#[pallet::weight(T::WeightInfo::buy_order(T::MaxRoyaltyRecipients::get()))]
 		pub fn validate_buy_order(
 			origin: OriginFor<T>,
 			order_id: BuyOrderId,
@@ -745,16 +869,36 @@ pub mod pallet {
 							Expendable,
 						)?;
 
+						// get the projectId and groupId for events
+						let (project_id, group_id) =
+							T::AssetValidator::get_project_details(&order.asset_id)
+								.ok_or(Error::<T>::AssetNotPermitted)?;
+
+						// calculate the royalties to be paid for the purchase
+						let gross_amount = order
+							.total_amount
+							.checked_sub(&order.total_fee)
+							.ok_or(Error::<T>::OrderUnitsOverflow)?;
+						let (royalties, royalties_total) = Self::calculate_royalties(
+							&project_id,
+							gross_amount.try_into().map_err(|_| Error::<T>::ArithmeticError)?,
+							order.total_fee.try_into().map_err(|_| Error::<T>::ArithmeticError)?,
+						)?;
+
+						// royalties calculated from the gross amount are paid by the seller
+						let amount_to_seller = match Self::royalty_basis() {
+							RoyaltyBasis::Fees => gross_amount,
+							RoyaltyBasis::GrossAmount => gross_amount
+								.checked_sub(&royalties_total.into())
+								.ok_or(Error::<T>::RoyaltiesExceedAmount)?,
+						};
+
 						// add amount record to the seller
 						SellerReceivables::<T>::try_mutate(
 							sell_order.owner.clone(),
 							|receivable| -> DispatchResult {
 								let current_receivables =
 									receivable.get_or_insert_with(Default::default);
-								let amount_to_seller = order
-									.total_amount
-									.checked_sub(&order.total_fee)
-									.ok_or(Error::<T>::OrderUnitsOverflow)?;
 								let new_receivables = current_receivables
 									.checked_add(&amount_to_seller)
 									.ok_or(Error::<T>::OrderUnitsOverflow)?;
@@ -763,6 +907,30 @@ pub mod pallet {
 							},
 						)?;
 
+						// add amount record to the royalty recipients
+						for (recipient, amount) in royalties {
+							RoyaltyReceivables::<T>::try_mutate(
+								recipient.clone(),
+								|receivable| -> DispatchResult {
+									let current_receivables =
+										receivable.get_or_insert_with(Default::default);
+									let new_receivables = current_receivables
+										.checked_add(&amount.into())
+										.ok_or(Error::<T>::OrderUnitsOverflow)?;
+									*receivable = Some(new_receivables);
+									Ok(())
+								},
+							)?;
+
+							Self::deposit_event(Event::RoyaltyPaid {
+								order_id,
+								project_id: project_id.clone(),
+								recipient,
+								amount: amount.into(),
+								paid_onchain: false,
+							});
+						}
+
 						BuyOrdersByUser::<T>::try_mutate(
 							order.buyer.clone(),
 							|open_orders| -> DispatchResult {
@@ -772,11 +940,6 @@ pub mod pallet {
 							},
 						)?;
 
-						// get the projectId and groupId for events
-						let (project_id, group_id) =
-							T::AssetValidator::get_project_details(&order.asset_id)
-								.ok_or(Error::<T>::AssetNotPermitted)?;
-
 						Self::deposit_event(Event::BuyOrderFilled {
 							order_id,
 							sell_order_id: order.order_id,
@@ -1046,6 +1209,291 @@ pub mod pallet {
 			Self::deposit_event(Event::BuyOrdersByUserCleared { user });
 			Ok(())
 		}
+
+		/// Buy `units` of `asset_id` from the given `order_id` and settle the payment on-chain.
+		///
+		/// Unlike `create_buy_order`, this does not wait for payment validations. The buyer pays
+		/// in `currency_id` within the same extrinsic: the price of the units is transferred to
+		/// the seller, the payment and purchase fees are transferred to `FeeAccount`, and the
+		/// asset is transferred from the pallet account to the buyer.
+		///
+		/// - `origin`: The buyer, must be KYC approved.
+		/// - `order_id`: The sell order to buy from.
+		/// - `asset_id`: The expected asset of the sell order.
+		/// - `units`: The units to buy.
+		/// - `max_fee`: The maximum fee the buyer is willing to pay.
+		/// - `currency_id`: The currency used for payment, must be one of `SettlementCurrencies`.
+		///
+		/// Emits `BuyOrderFilled` and `BuyOrderSettled` events on success.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::create_buy_order_onchain(T::MaxRoyaltyRecipients::get()))]
+		pub fn create_buy_order_onchain(
+			origin: OriginFor<T>,
+			order_id: OrderId,
+			asset_id: AssetIdOf<T>,
+			units: AssetBalanceOf<T>,
+			max_fee: CurrencyBalanceOf<T>,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			Self::check_kyc_approval(&buyer)?;
+
+			ensure!(
+				T::SettlementCurrencies::contains(&currency_id),
+				Error::<T>::SettlementCurrencyNotAccepted
+			);
+
+			if units.is_zero() {
+				return Ok(())
+			}
+
+			Orders::<T>::try_mutate(order_id, |maybe_order| -> DispatchResult {
+				let mut order = maybe_order.take().ok_or(Error::<T>::InvalidOrderId)?;
+
+				// ensure the expected asset matches the order
+				ensure!(asset_id == order.asset_id, Error::<T>::InvalidAssetId);
+
+				// ensure the seller and buyer are not the same
+				ensure!(buyer != order.owner, Error::<T>::SellerAndBuyerCannotBeSame);
+
+				// ensure volume remaining can cover the buy order
+				ensure!(units <= order.units, Error::<T>::OrderUnitsOverflow);
+
+				// get the projectId and groupId for events
+				let (project_id, group_id) = T::AssetValidator::get_project_details(&asset_id)
+					.ok_or(Error::<T>::AssetNotPermitted)?;
+
+				// reduce the buy_order units from total volume
+				order.units =
+					order.units.checked_sub(&units).ok_or(Error::<T>::OrderUnitsOverflow)?;
+
+				// calculate fees
+				let (amount_to_seller, total_fee, _) =
+					Self::calculate_order_amounts(order.price_per_unit, units)?;
+
+				ensure!(max_fee >= total_fee.into(), Error::<T>::FeeExceedsUserLimit);
+
+				// Allocate a buy order id to keep the events consistent with validated orders
+				let buy_order_id = Self::buy_order_count();
+				let next_buy_order_id =
+					buy_order_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOverflow)?;
+				BuyOrderCount::<T>::put(next_buy_order_id);
+
+				// pay the seller, the fee account and the royalty recipients
+				let amount_to_seller = Self::settle_payment(
+					&buyer,
+					&order.owner,
+					currency_id,
+					&project_id,
+					buy_order_id,
+					amount_to_seller,
+					total_fee,
+				)?;
+
+				// transfer the asset to the buyer
+				T::Asset::transfer(
+					asset_id.clone(),
+					&Self::account_id(),
+					&buyer,
+					units,
+					Expendable,
+				)?;
+
+				Self::deposit_event(Event::BuyOrderFilled {
+					order_id: buy_order_id,
+					sell_order_id: order_id,
+					units,
+					project_id,
+					group_id,
+					price_per_unit: order.price_per_unit,
+					fees_paid: total_fee.into(),
+					seller: order.owner.clone(),
+					buyer,
+				});
+
+				Self::deposit_event(Event::BuyOrderSettled {
+					order_id: buy_order_id,
+					sell_order_id: order_id,
+					currency_id,
+					amount_to_seller: amount_to_seller.into(),
+					fees_paid: total_fee.into(),
+					fee_account: T::FeeAccount::get(),
+				});
+
+				*maybe_order = Some(order);
+
+				Ok(())
+			})
+		}
+
+		/// Set whether project royalties are calculated from the fees or the gross amount
+		/// Can only be called by ForceOrigin
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::force_set_royalty_basis())]
+		pub fn force_set_royalty_basis(
+			origin: OriginFor<T>,
+			basis: RoyaltyBasis,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			RoyaltyCalculationBasis::<T>::set(basis);
+			Self::deposit_event(Event::RoyaltyBasisSet { basis });
+			Ok(())
+		}
+
+		/// Record a payment executed to a royalty recipient.
+		///
+		/// This works like `record_payment_to_seller` for the `RoyaltyReceivables` accrued by
+		/// buy orders that were validated off-chain, and can only be called by the
+		/// `SellerPayoutAuthority`.
+		///
+		/// Emits an `Event::RoyaltyPayoutExecuted` event on success.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::record_payment_to_royalty_recipient())]
+		pub fn record_payment_to_royalty_recipient(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			payout: PayoutExecutedToSellerOf<T>,
+		) -> DispatchResult {
+			let authority = ensure_signed(origin)?;
+
+			// ensure the caller is the approved authority
+			let expected_authority =
+				SellerPayoutAuthority::<T>::get().ok_or(Error::<T>::SellerPayoutAuthorityNotSet)?;
+			ensure!(authority == expected_authority, Error::<T>::NotSellerPayoutAuthority);
+
+			// subtract the paid amount from the receivables
+			RoyaltyReceivables::<T>::try_mutate(
+				recipient.clone(),
+				|receivable| -> DispatchResult {
+					let receivable = receivable.as_mut().ok_or(Error::<T>::NoReceivables)?;
+					*receivable = receivable
+						.checked_sub(&payout.amount)
+						.ok_or(Error::<T>::ReceivableLessThanPayment)?;
+					Ok(())
+				},
+			)?;
+
+			Self::deposit_event(Event::RoyaltyPayoutExecuted { recipient, payout });
+			Ok(())
+		}
+
+		/// Place a standing bid to buy `units` of `asset_id` at up to `price_per_unit`.
+		///
+		/// The price of the units and the fees are reserved from the buyer in `currency_id`. The
+		/// bid is first matched against the open sell orders of the asset priced at or below
+		/// `price_per_unit`, lowest price first and oldest order first within a price level, and
+		/// every fill is settled at the price of the sell order. Any units left unfilled rest in
+		/// the order book and are matched against new sell orders until `expiry_time`.
+		///
+		/// - `origin`: The buyer, must be KYC approved.
+		/// - `asset_id`: The asset to buy.
+		/// - `units`: The units to buy.
+		/// - `price_per_unit`: The maximum price to pay per unit.
+		/// - `currency_id`: The currency used for payment, must be one of `SettlementCurrencies`.
+		/// - `expiry_time`: The last block at which the bid can be filled.
+		///
+		/// Emits `BidOrderCreated` and an `OrdersMatched` event for every fill.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::create_bid_order(T::MaxMatchesPerOrder::get()))]
+		pub fn create_bid_order(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			units: AssetBalanceOf<T>,
+			price_per_unit: CurrencyBalanceOf<T>,
+			currency_id: CurrencyIdOf<T>,
+			expiry_time: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+			Self::check_kyc_approval(&buyer)?;
+
+			ensure!(
+				T::SettlementCurrencies::contains(&currency_id),
+				Error::<T>::SettlementCurrencyNotAccepted
+			);
+
+			// ensure the asset_id can be traded
+			T::AssetValidator::get_project_details(&asset_id)
+				.ok_or(Error::<T>::AssetNotPermitted)?;
+
+			// ensure minimums are satisfied
+			ensure!(units >= T::MinUnitsToCreateSellOrder::get(), Error::<T>::BelowMinimumUnits);
+			ensure!(price_per_unit >= T::MinPricePerUnit::get(), Error::<T>::BelowMinimumPrice);
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			ensure!(expiry_time > current_block_number, Error::<T>::InvalidBidOrderExpiry);
+
+			// reserve the price of all units including fees from the buyer
+			let (_, _, total_amount) = Self::calculate_order_amounts(price_per_unit, units)?;
+			T::Currency::transfer(currency_id, &buyer, &Self::account_id(), total_amount.into())?;
+
+			let bid_order_id = Self::bid_order_count();
+			let next_bid_order_id =
+				bid_order_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOverflow)?;
+			BidOrderCount::<T>::put(next_bid_order_id);
+
+			let mut bid = BidOrderInfo {
+				owner: buyer.clone(),
+				asset_id: asset_id.clone(),
+				currency_id,
+				price_per_unit,
+				units,
+				units_filled: Zero::zero(),
+				amount_paid: Zero::zero(),
+				reserved: total_amount.into(),
+				expiry_time,
+			};
+
+			Self::deposit_event(Event::BidOrderCreated {
+				bid_order_id,
+				asset_id: asset_id.clone(),
+				units,
+				price_per_unit,
+				currency_id,
+				reserved: total_amount.into(),
+				expiry_time,
+				owner: buyer,
+			});
+
+			Self::match_bid_order(bid_order_id, &mut bid)?;
+
+			if bid.units.is_zero() {
+				Self::close_bid_order(bid_order_id, bid)?;
+			} else {
+				if Self::add_to_bid_book(&asset_id, price_per_unit, bid_order_id).is_err() {
+					// a full order book does not block the bid, like the sell orders the bid is
+					// kept outside the book until it is cancelled or expires
+					log::warn!(
+						target: "runtime::dex",
+						"WARNING: Order book full, bid order {} not added to the order book",
+						bid_order_id
+					);
+				}
+				BidOrders::<T>::insert(bid_order_id, bid);
+			}
+
+			Ok(())
+		}
+
+		/// Cancel the bid order with `bid_order_id` and refund the unused funds to the owner.
+		/// The owner can cancel the bid at any time, any other account can only cancel an expired
+		/// bid.
+		///
+		/// Emits `BidOrderClosed` event on success.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::cancel_bid_order())]
+		pub fn cancel_bid_order(origin: OriginFor<T>, bid_order_id: BidOrderId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let bid = BidOrders::<T>::get(bid_order_id).ok_or(Error::<T>::InvalidBidOrderId)?;
+
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				sender == bid.owner || bid.expiry_time < current_block_number,
+				Error::<T>::InvalidOrderOwner
+			);
+
+			Self::close_bid_order(bid_order_id, bid)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -1064,13 +1512,568 @@ pub mod pallet {
 			}
 		}
 
-		/// Checks if given account is kyc approved
-		pub fn check_kyc_approval(account_id: &T::AccountId) -> DispatchResult {
-			if !T::KYCProvider::contains(account_id) {
-				Err(Error::<T>::KYCAuthorisationFailed.into())
+		/// Calculate the amounts to be paid for `units` at `price_per_unit`
+		/// Returns the (price of units, total fee, total amount including fees)
+		pub fn calculate_order_amounts(
+			price_per_unit: CurrencyBalanceOf<T>,
+			units: AssetBalanceOf<T>,
+		) -> Result<(u128, u128, u128), DispatchError> {
+			let units_as_u128: u128 = units.try_into().map_err(|_| Error::<T>::ArithmeticError)?;
+			let price_per_unit_as_u128: u128 =
+				price_per_unit.try_into().map_err(|_| Error::<T>::ArithmeticError)?;
+
+			let required_currency = price_per_unit_as_u128
+				.checked_mul(units_as_u128)
+				.ok_or(Error::<T>::ArithmeticError)?;
+
+			let payment_fee = PaymentFees::<T>::get().mul_ceil(required_currency);
+			let purchase_fee: u128 =
+				PurchaseFees::<T>::get().try_into().map_err(|_| Error::<T>::ArithmeticError)?;
+
+			let total_fee =
+				payment_fee.checked_add(purchase_fee).ok_or(Error::<T>::OrderUnitsOverflow)?;
+
+			let total_amount =
+				total_fee.checked_add(required_currency).ok_or(Error::<T>::OrderUnitsOverflow)?;
+
+			Ok((required_currency, total_fee, total_amount))
+		}
+
+		/// Get the open sell orders, optionally filtered by asset and project
+		/// This iterates over all the `Orders` and is meant to be used by runtime apis
+		pub fn open_sell_orders(
+			asset_id: Option<AssetIdOf<T>>,
+			project_id: Option<ProjectIdOf<T>>,
+		) -> Vec<(OrderId, OrderInfoOf<T>)> {
+			let mut orders: Vec<(OrderId, OrderInfoOf<T>)> = Orders::<T>::iter()
+				.filter(|(_, order)| !order.units.is_zero())
+				.filter(|(_, order)| asset_id.as_ref().map_or(true, |id| order.asset_id == *id))
+				.filter(|(_, order)| {
+					project_id.as_ref().map_or(true, |id| {
+						T::AssetValidator::get_project_details(&order.asset_id)
+							.map_or(false, |(order_project_id, _)| order_project_id == *id)
+					})
+				})
+				.collect();
+			orders.sort_by_key(|(order_id, _)| *order_id);
+			orders
+		}
+
+		/// Get the buy orders waiting for payment validation, optionally filtered by buyer, along
+		/// with the number of validations received and required
+		pub fn pending_buy_orders(buyer: Option<T::AccountId>) -> Vec<PendingBuyOrderOf<T>> {
+			let mut buy_orders: Vec<(BuyOrderId, BuyOrderInfoOf<T>)> = match buyer {
+				Some(buyer) => BuyOrdersByUser::<T>::get(buyer)
+					.unwrap_or_default()
+					.into_iter()
+					.filter_map(|(buy_order_id, _)| {
+						BuyOrders::<T>::get(buy_order_id).map(|order| (buy_order_id, order))
+					})
+					.collect(),
+				None => BuyOrders::<T>::iter().collect(),
+			};
+
+			buy_orders.sort_by_key(|(buy_order_id, _)| *buy_order_id);
+
+			let required_validations = MinPaymentValidations::<T>::get();
+			buy_orders
+				.into_iter()
+				.map(|(buy_order_id, order)| PendingBuyOrder {
+					buy_order_id,
+					validations: order
+						.payment_info
+						.as_ref()
+						.map_or(0, |payment_info| payment_info.validators.len() as u32),
+					required_validations,
+					order,
+				})
+				.collect()
+		}
+
+		/// Quote a purchase of `units` from the sell order `order_id`
+		/// Returns the (price of units, total fee, total amount including fees)
+		pub fn quote_buy_order(
+			order_id: OrderId,
+			units: AssetBalanceOf<T>,
+		) -> Result<(u128, u128, u128), DispatchError> {
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::InvalidOrderId)?;
+			ensure!(units <= order.units, Error::<T>::OrderUnitsOverflow);
+			Self::calculate_order_amounts(order.price_per_unit, units)
+		}
+
+		/// Calculate the royalties of `project_id` for a purchase with `amount_to_seller` and
+		/// `total_fee`, depending on the `RoyaltyCalculationBasis`
+		/// Returns the amount for every royalty recipient and the total of all royalties
+		pub fn calculate_royalties(
+			project_id: &ProjectIdOf<T>,
+			amount_to_seller: u128,
+			total_fee: u128,
+		) -> Result<(Vec<(T::AccountId, u128)>, u128), DispatchError> {
+			let basis_amount = match Self::royalty_basis() {
+				RoyaltyBasis::Fees => total_fee,
+				RoyaltyBasis::GrossAmount => amount_to_seller,
+			};
+
+			let mut royalties: Vec<(T::AccountId, u128)> = Default::default();
+			let mut royalties_total: u128 = Zero::zero();
+			for royalty in T::AssetValidator::get_royalties(project_id) {
+				let amount = royalty.percent_of_fees.mul_floor(basis_amount);
+				if amount.is_zero() {
+					continue
+				}
+
+				royalties_total =
+					royalties_total.checked_add(amount).ok_or(Error::<T>::ArithmeticError)?;
+				royalties.push((royalty.account_id, amount));
+			}
+
+			ensure!(royalties_total <= basis_amount, Error::<T>::RoyaltiesExceedAmount);
+
+			Ok((royalties, royalties_total))
+		}
+
+		/// Create a sell order owned by `seller` with `units` transferred from the `source` account
+		pub fn do_create_sell_order(
+			source: &T::AccountId,
+			seller: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			units: AssetBalanceOf<T>,
+			price_per_unit: CurrencyBalanceOf<T>,
+		) -> Result<OrderId, DispatchError> {
+			Self::check_kyc_approval(&seller)?;
+			// ensure the asset_id can be listed
+			let (project_id, group_id) = T::AssetValidator::get_project_details(&asset_id)
+				.ok_or(Error::<T>::AssetNotPermitted)?;
+
+			// ensure minimums are satisfied
+			ensure!(units >= T::MinUnitsToCreateSellOrder::get(), Error::<T>::BelowMinimumUnits);
+			ensure!(price_per_unit >= T::MinPricePerUnit::get(), Error::<T>::BelowMinimumPrice);
+
+			// transfer assets from source to pallet
+			T::Asset::transfer(asset_id.clone(), source, &Self::account_id(), units, Expendable)?;
+
+			let order_id = Self::order_count();
+			let next_order_id =
+				order_id.checked_add(One::one()).ok_or(Error::<T>::OrderIdOverflow)?;
+			OrderCount::<T>::put(next_order_id);
+
+			Self::deposit_event(Event::SellOrderCreated {
+				order_id,
+				asset_id: asset_id.clone(),
+				project_id,
+				group_id,
+				units,
+				price_per_unit,
+				owner: seller.clone(),
+			});
+
+			// fill the resting bids first, any units left rest in the order book
+			let mut order = OrderInfo { owner: seller, units, price_per_unit, asset_id };
+			Self::match_sell_order(order_id, &mut order)?;
+			if !order.units.is_zero() &&
+				Self::add_to_ask_book(&order.asset_id, price_per_unit, order_id).is_err()
+			{
+				// a full order book does not block the listing, the order is kept outside the
+				// book where it can still be bought with `create_buy_order`
+				log::warn!(
+					target: "runtime::dex",
+					"WARNING: Order book full, sell order {} not added to the order book",
+					order_id
+				);
+			}
+
+			// order values
+			Orders::<T>::insert(order_id, order);
+
+			Ok(order_id)
+		}
+
+		/// Pay `amount_to_seller` and `total_fee` in `currency_id` from `payer` to the seller, the
+		/// fee account and the royalty recipients of `project_id`
+		/// Returns the amount received by the seller after royalties
+		pub fn settle_payment(
+			payer: &T::AccountId,
+			seller: &T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+			project_id: &ProjectIdOf<T>,
+			order_id: BuyOrderId,
+			amount_to_seller: u128,
+			total_fee: u128,
+		) -> Result<u128, DispatchError> {
+			// calculate the royalties to be paid for the purchase
+			let (royalties, royalties_total) =
+				Self::calculate_royalties(project_id, amount_to_seller, total_fee)?;
+			let (amount_to_seller, amount_to_fee_account) = match Self::royalty_basis() {
+				RoyaltyBasis::Fees => (
+					amount_to_seller,
+					total_fee
+						.checked_sub(royalties_total)
+						.ok_or(Error::<T>::RoyaltiesExceedAmount)?,
+				),
+				RoyaltyBasis::GrossAmount => (
+					amount_to_seller
+						.checked_sub(royalties_total)
+						.ok_or(Error::<T>::RoyaltiesExceedAmount)?,
+					total_fee,
+				),
+			};
+
+			// transfer the payment from the payer to the seller and the fee account
+			T::Currency::transfer(currency_id, payer, seller, amount_to_seller.into())?;
+			if !amount_to_fee_account.is_zero() {
+				T::Currency::transfer(
+					currency_id,
+					payer,
+					&T::FeeAccount::get(),
+					amount_to_fee_account.into(),
+				)?;
+			}
+
+			// pay the royalties directly to the recipients
+			for (recipient, amount) in royalties {
+				T::Currency::transfer(currency_id, payer, &recipient, amount.into())?;
+				Self::deposit_event(Event::RoyaltyPaid {
+					order_id,
+					project_id: project_id.clone(),
+					recipient,
+					amount: amount.into(),
+					paid_onchain: true,
+				});
+			}
+
+			Ok(amount_to_seller)
+		}
+
+		/// Match the bid order `bid_order_id` against the resting sell orders of the asset priced
+		/// at or below the bid price, the lowest priced and oldest sell orders are filled first
+		fn match_bid_order(
+			bid_order_id: BidOrderId,
+			bid: &mut BidOrderInfoOf<T>,
+		) -> DispatchResult {
+			let mut matches: u32 = 0;
+			for price in Self::ask_price_levels(&bid.asset_id) {
+				if price > bid.price_per_unit || bid.units.is_zero() {
+					break
+				}
+
+				for order_id in Self::ask_book(&bid.asset_id, price) {
+					if bid.units.is_zero() || matches >= T::MaxMatchesPerOrder::get() {
+						return Ok(())
+					}
+					// every visited order counts towards the limit, including the skipped ones
+					matches.saturating_inc();
+
+					let mut order = match Orders::<T>::get(order_id) {
+						Some(order) => order,
+						None => {
+							Self::remove_from_ask_book(&bid.asset_id, price, order_id);
+							continue
+						},
+					};
+
+					// sold out orders leave the book, units returned by expired buy orders add
+					// them back
+					if order.units.is_zero() {
+						Self::remove_from_ask_book(&bid.asset_id, price, order_id);
+						continue
+					}
+
+					if order.owner == bid.owner {
+						continue
+					}
+
+					let units = order.units.min(bid.units);
+					Self::execute_match(bid_order_id, bid, order_id, &mut order, units, price)?;
+
+					if order.units.is_zero() {
+						Self::remove_from_ask_book(&bid.asset_id, price, order_id);
+					}
+					Orders::<T>::insert(order_id, order);
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Match the sell order `order_id` against the resting bid orders of the asset priced at
+		/// or above the sell price, the highest priced and oldest bid orders are filled first
+		fn match_sell_order(order_id: OrderId, order: &mut OrderInfoOf<T>) -> DispatchResult {
+			let current_block_number = <frame_system::Pallet<T>>::block_number();
+			let mut matches: u32 = 0;
+			for price in Self::bid_price_levels(&order.asset_id).into_iter().rev() {
+				if price < order.price_per_unit || order.units.is_zero() {
+					break
+				}
+
+				for bid_order_id in Self::bid_book(&order.asset_id, price) {
+					if order.units.is_zero() || matches >= T::MaxMatchesPerOrder::get() {
+						return Ok(())
+					}
+					// every visited bid counts towards the limit, including the skipped ones
+					matches.saturating_inc();
+
+					let mut bid = match BidOrders::<T>::get(bid_order_id) {
+						Some(bid) => bid,
+						None => {
+							Self::remove_from_bid_book(&order.asset_id, price, bid_order_id);
+							continue
+						},
+					};
+
+					// expired bids are closed when they are found
+					if bid.expiry_time < current_block_number {
+						Self::close_bid_order(bid_order_id, bid)?;
+						continue
+					}
+
+					if bid.owner == order.owner {
+						continue
+					}
+
+					let units = order.units.min(bid.units);
+					Self::execute_match(bid_order_id, &mut bid, order_id, order, units, price)?;
+
+					if bid.units.is_zero() {
+						Self::close_bid_order(bid_order_id, bid)?;
+					} else {
+						BidOrders::<T>::insert(bid_order_id, bid);
+					}
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Fill `units` of the sell order `order_id` for the bid order `bid_order_id` at
+		/// `price_per_unit`, the payment is settled from the funds reserved for the bid
+		fn execute_match(
+			bid_order_id: BidOrderId,
+			bid: &mut BidOrderInfoOf<T>,
+			order_id: OrderId,
+			order: &mut OrderInfoOf<T>,
+			units: AssetBalanceOf<T>,
+			price_per_unit: CurrencyBalanceOf<T>,
+		) -> DispatchResult {
+			let (project_id, _) = T::AssetValidator::get_project_details(&order.asset_id)
+				.ok_or(Error::<T>::AssetNotPermitted)?;
+
+			let (amount_to_seller, _, _) = Self::calculate_order_amounts(price_per_unit, units)?;
+
+			// the payment fee is charged on the total paid by the bid and the purchase fee only on
+			// the first fill, so a bid filled in parts pays the same fees as a single fill
+			let amount_paid: u128 =
+				bid.amount_paid.try_into().map_err(|_| Error::<T>::ArithmeticError)?;
+			let amount_paid_after =
+				amount_paid.checked_add(amount_to_seller).ok_or(Error::<T>::ArithmeticError)?;
+			let payment_fee = PaymentFees::<T>::get()
+				.mul_ceil(amount_paid_after)
+				.saturating_sub(PaymentFees::<T>::get().mul_ceil(amount_paid));
+			let purchase_fee: u128 = if bid.units_filled.is_zero() {
+				PurchaseFees::<T>::get().try_into().map_err(|_| Error::<T>::ArithmeticError)?
 			} else {
-				Ok(())
+				Zero::zero()
+			};
+			let total_fee =
+				payment_fee.checked_add(purchase_fee).ok_or(Error::<T>::ArithmeticError)?;
+			let total_amount =
+				amount_to_seller.checked_add(total_fee).ok_or(Error::<T>::ArithmeticError)?;
+
+			bid.reserved = bid
+				.reserved
+				.checked_sub(&total_amount.into())
+				.ok_or(Error::<T>::BidOrderReserveExhausted)?;
+
+			Self::settle_payment(
+				&Self::account_id(),
+				&order.owner,
+				bid.currency_id,
+				&project_id,
+				bid_order_id,
+				amount_to_seller,
+				total_fee,
+			)?;
+
+			// transfer the asset to the buyer
+			T::Asset::transfer(
+				order.asset_id.clone(),
+				&Self::account_id(),
+				&bid.owner,
+				units,
+				Expendable,
+			)?;
+
+			order.units = order.units.checked_sub(&units).ok_or(Error::<T>::OrderUnitsOverflow)?;
+			bid.units = bid.units.checked_sub(&units).ok_or(Error::<T>::OrderUnitsOverflow)?;
+			bid.units_filled =
+				bid.units_filled.checked_add(&units).ok_or(Error::<T>::OrderUnitsOverflow)?;
+			bid.amount_paid = amount_paid_after.into();
+
+			Self::deposit_event(Event::OrdersMatched {
+				bid_order_id,
+				sell_order_id: order_id,
+				asset_id: order.asset_id.clone(),
+				units,
+				price_per_unit,
+				currency_id: bid.currency_id,
+				fees_paid: total_fee.into(),
+				seller: order.owner.clone(),
+				buyer: bid.owner.clone(),
+			});
+
+			Ok(())
+		}
+
+		/// Remove the bid order from storage and the order book and refund the unused funds
+		fn close_bid_order(bid_order_id: BidOrderId, bid: BidOrderInfoOf<T>) -> DispatchResult {
+			BidOrders::<T>::remove(bid_order_id);
+			Self::remove_from_bid_book(&bid.asset_id, bid.price_per_unit, bid_order_id);
+
+			if !bid.reserved.is_zero() {
+				T::Currency::transfer(
+					bid.currency_id,
+					&Self::account_id(),
+					&bid.owner,
+					bid.reserved,
+				)?;
+			}
+
+			Self::deposit_event(Event::BidOrderClosed {
+				bid_order_id,
+				units_filled: bid.units_filled,
+				refunded: bid.reserved,
+				owner: bid.owner,
+			});
+
+			Ok(())
+		}
+
+		/// Add `price` to the sorted price `levels` if it is not present
+		fn add_price_level(
+			levels: &mut PriceLevelsOf<T>,
+			price: CurrencyBalanceOf<T>,
+		) -> DispatchResult {
+			if let Err(index) = levels.binary_search(&price) {
+				levels.try_insert(index, price).map_err(|_| Error::<T>::TooManyPriceLevels)?;
 			}
+			Ok(())
 		}
+
+		/// Remove `price` from the sorted price `levels`
+		fn remove_price_level(levels: &mut PriceLevelsOf<T>, price: CurrencyBalanceOf<T>) {
+			if let Ok(index) = levels.binary_search(&price) {
+				levels.remove(index);
+			}
+		}
+
+		/// Add the sell order to its price level in the order book, if not present
+		/// The orders of a price level are sorted by order id, so an order added back keeps the
+		/// time priority it was created with
+		pub(crate) fn add_to_ask_book(
+			asset_id: &AssetIdOf<T>,
+			price: CurrencyBalanceOf<T>,
+			order_id: OrderId,
+		) -> DispatchResult {
+			let mut orders = AskBook::<T>::get(asset_id, price);
+			let index = match orders.binary_search(&order_id) {
+				Ok(_) => return Ok(()),
+				Err(index) => index,
+			};
+
+			orders.try_insert(index, order_id).map_err(|_| Error::<T>::PriceLevelFull)?;
+			AskPriceLevels::<T>::try_mutate(asset_id, |levels| {
+				Self::add_price_level(levels, price)
+			})?;
+			AskBook::<T>::insert(asset_id, price, orders);
+			Ok(())
+		}
+
+		/// Remove the sell order from the order book, empty price levels are removed
+		fn remove_from_ask_book(
+			asset_id: &AssetIdOf<T>,
+			price: CurrencyBalanceOf<T>,
+			order_id: OrderId,
+		) {
+			let mut orders = AskBook::<T>::get(asset_id, price);
+			orders.retain(|id| *id != order_id);
+			if orders.is_empty() {
+				AskBook::<T>::remove(asset_id, price);
+				AskPriceLevels::<T>::mutate(asset_id, |levels| {
+					Self::remove_price_level(levels, price)
+				});
+			} else {
+				AskBook::<T>::insert(asset_id, price, orders);
+			}
+		}
+
+		/// Add the bid order to its price level in the order book, if not present
+		/// The bids of a price level are sorted by bid order id, like the sell orders
+		fn add_to_bid_book(
+			asset_id: &AssetIdOf<T>,
+			price: CurrencyBalanceOf<T>,
+			bid_order_id: BidOrderId,
+		) -> DispatchResult {
+			let mut orders = BidBook::<T>::get(asset_id, price);
+			let index = match orders.binary_search(&bid_order_id) {
+				Ok(_) => return Ok(()),
+				Err(index) => index,
+			};
+
+			orders.try_insert(index, bid_order_id).map_err(|_| Error::<T>::PriceLevelFull)?;
+			BidPriceLevels::<T>::try_mutate(asset_id, |levels| {
+				Self::add_price_level(levels, price)
+			})?;
+			BidBook::<T>::insert(asset_id, price, orders);
+			Ok(())
+		}
+
+		/// Remove the bid order from the order book, empty price levels are removed
+		fn remove_from_bid_book(
+			asset_id: &AssetIdOf<T>,
+			price: CurrencyBalanceOf<T>,
+			bid_order_id: BidOrderId,
+		) {
+			let mut orders = BidBook::<T>::get(asset_id, price);
+			orders.retain(|id| *id != bid_order_id);
+			if orders.is_empty() {
+				BidBook::<T>::remove(asset_id, price);
+				BidPriceLevels::<T>::mutate(asset_id, |levels| {
+					Self::remove_price_level(levels, price)
+				});
+			} else {
+				BidBook::<T>::insert(asset_id, price, orders);
+			}
+		}
+
+		/// Get the open order units allowed for `level`, levels without a configured limit use
+		/// the limit of the highest configured level below them
+		pub fn open_order_units_allowed(level: &UserLevel) -> Option<AssetBalanceOf<T>> {
+			UserOpenOrderUnitsAllowed::<T>::iter()
+				.filter(|(configured_level, _)| configured_level <= level)
+				.max_by_key(|(configured_level, _)| configured_level.clone())
+				.map(|(_, limit)| limit)
+		}
+
+		/// Checks if given account is kyc approved
+		/// Returns the kyc level of the account
+		pub fn check_kyc_approval(account_id: &T::AccountId) -> Result<UserLevel, DispatchError> {
+			T::KYCProvider::kyc_level(account_id)
+				.ok_or_else(|| Error::<T>::KYCAuthorisationFailed.into())
+		}
+	}
+}
+
+/// Lists credits minted to the marketplace escrow as a sell order owned by the originator
+impl<T: Config> MarketplaceListing<T::AccountId, AssetIdOf<T>, AssetBalanceOf<T>> for Pallet<T> {
+	type Price = CurrencyBalanceOf<T>;
+
+	fn list_from_escrow(
+		escrow: &T::AccountId,
+		owner: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		units: AssetBalanceOf<T>,
+		price_per_unit: Self::Price,
+	) -> DispatchResult {
+		Self::do_create_sell_order(escrow, owner.clone(), asset_id, units, price_per_unit)?;
+		Ok(())
 	}
-}
\ No newline at end of file
+}
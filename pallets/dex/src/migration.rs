@@ -0,0 +1,68 @@
+use super::*;
+
+pub mod v1 {
+	use super::*;
+
+	#[cfg(feature = "try-runtime")]
+	use frame_support::sp_std::vec::Vec;
+	use frame_support::{
+		pallet_prelude::*,
+		sp_std::marker::PhantomData,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	/// Index the open sell orders created before the order book into `AskBook` and
+	/// `AskPriceLevels`.
+	///
+	/// The orders are added in order id order so the older orders keep their time priority, an
+	/// order that does not fit in a full price level is kept outside the book like a new order.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 1 {
+				log::info!("dex migration to v1 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let orders = Orders::<T>::iter_keys().count() as u64;
+			let mut indexed = 0_u64;
+			let mut not_indexed = 0_u64;
+			for (order_id, order) in Pallet::<T>::open_sell_orders(None, None) {
+				if Pallet::<T>::add_to_ask_book(&order.asset_id, order.price_per_unit, order_id)
+					.is_ok()
+				{
+					indexed += 1;
+				} else {
+					not_indexed += 1;
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!(
+				"dex migration to v1 done, {} orders indexed, {} orders not indexed",
+				indexed,
+				not_indexed
+			);
+
+			T::DbWeight::get()
+				.reads_writes(2 * orders + 2 * (indexed + not_indexed) + 1, 2 * indexed + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((Pallet::<T>::open_sell_orders(None, None).len() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let open_orders: u32 = Decode::decode(&mut &state[..])
+				.map_err(|_| "dex pre_upgrade state cannot be decoded")?;
+			let indexed =
+				AskBook::<T>::iter_values().map(|orders| orders.len() as u32).sum::<u32>();
+			ensure!(indexed <= open_orders, "dex order book has more orders than open orders");
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "dex storage version must be 1");
+			Ok(())
+		}
+	}
+}
@@ -10,7 +10,9 @@ use frame_support::{
 
 use frame_system::{EnsureRoot, EnsureSigned};
 use orml_traits::parameter_type_with_key;
-use primitives::{Amount, Balance, CarbonCreditsValidator, CurrencyId};
+use primitives::{
+	Amount, Balance, CarbonCreditsValidator, CurrencyId, KycLevelProvider, Royalty, UserLevel,
+};
 use sp_core::{ConstU128, ConstU64, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
@@ -129,6 +131,10 @@ impl orml_tokens::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub static MockRoyalties: Vec<Royalty<AccountId>> = vec![];
+}
+
 pub struct DummyValidator;
 impl CarbonCreditsValidator for DummyValidator {
 	type ProjectId = u32;
@@ -140,6 +146,15 @@ impl CarbonCreditsValidator for DummyValidator {
 		Some((0, 0))
 	}
 
+	fn get_royalties(_project_id: &Self::ProjectId) -> Vec<Royalty<Self::Address>> {
+		MockRoyalties::get()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_royalties(_project_id: &Self::ProjectId, royalties: Vec<Royalty<Self::Address>>) {
+		MockRoyalties::set(royalties);
+	}
+
 	fn retire_credits(
 		_sender: Self::Address,
 		_project_id: Self::ProjectId,
@@ -151,15 +166,21 @@ impl CarbonCreditsValidator for DummyValidator {
 	}
 }
 
+parameter_types! {
+	/// KYC levels of accounts above the default `KYCLevel1`
+	pub static MockKycLevels: Vec<(u64, UserLevel)> = vec![];
+}
+
 pub struct MockKycProvider;
-impl Contains<u64> for MockKycProvider {
-	fn contains(value: &u64) -> bool {
+impl KycLevelProvider<u64> for MockKycProvider {
+	fn kyc_level(value: &u64) -> Option<UserLevel> {
 		// special account to test negative kyc
 		if value == &20 {
-			return false
+			return None
 		}
 
-		true
+		let level = MockKycLevels::get().into_iter().find(|(account, _)| account == value);
+		Some(level.map(|(_, level)| level).unwrap_or_default())
 	}
 }
 
@@ -183,6 +204,19 @@ parameter_types! {
 	pub const MaxPayoutsToStore : u32 = 1000;
 	#[derive(Clone, scale_info::TypeInfo, Debug, PartialEq)]
 	pub const MaxOpenOrdersPerUser : u32 = 2;
+	pub const DexFeeAccount : u64 = 99;
+	pub const MaxPriceLevels : u32 = 10;
+	pub const MaxOrdersPerPriceLevel : u32 = 3;
+	pub const MaxMatchesPerOrder : u32 = 5;
+	pub const MaxRoyaltyRecipients : u32 = 5;
+}
+
+pub struct MockSettlementCurrencies;
+impl Contains<CurrencyId> for MockSettlementCurrencies {
+	fn contains(value: &CurrencyId) -> bool {
+		// DOT is not accepted to test negative settlement
+		matches!(value, CurrencyId::USDT | CurrencyId::USDC)
+	}
 }
 
 impl pallet_dex::Config for Test {
@@ -206,6 +240,12 @@ impl pallet_dex::Config for Test {
 	type MaxPaymentFee = MaxPaymentFee;
 	type MaxPurchaseFee = MaxPurchaseFee;
 	type MaxPayoutsToStore = MaxPayoutsToStore;
+	type FeeAccount = DexFeeAccount;
+	type SettlementCurrencies = MockSettlementCurrencies;
+	type MaxPriceLevels = MaxPriceLevels;
+	type MaxOrdersPerPriceLevel = MaxOrdersPerPriceLevel;
+	type MaxMatchesPerOrder = MaxMatchesPerOrder;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
 	type WeightInfo = ();
 }
 
@@ -225,4 +265,4 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 pub fn last_event() -> RuntimeEvent {
 	System::events().pop().expect("Event expected").event
-}
\ No newline at end of file
+}
@@ -2,12 +2,20 @@
 // Copyright (C) 2022 BitGreen.
 // This code is licensed under MIT license (see LICENSE.txt for details)
 use crate::{
-	mock::*, types::UserLevel, BuyOrders, BuyOrdersByUser, Error, Event, Orders, SellerReceivables,
+	mock::*,
+	types::{RoyaltyBasis, UserLevel},
+	AskBook, AskPriceLevels, BidBook, BidOrders, BidPriceLevels, BuyOrders, BuyOrdersByUser, Error,
+	Event, Orders, RoyaltyReceivables, SellerReceivables,
 };
 use frame_support::{
-	assert_noop, assert_ok, traits::OnIdle, weights::Weight, BoundedVec, PalletId,
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnIdle, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	BoundedVec, PalletId,
 };
 use frame_system::RawOrigin;
+use orml_traits::MultiCurrency;
+use primitives::{CurrencyId, MarketplaceListing, Royalty};
 use sp_runtime::{traits::AccountIdConversion, Percent};
 
 /// helper function to add authorised account
@@ -96,6 +104,48 @@ fn create_sell_order_should_fail_if_caller_does_not_have_asset_balance() {
 	});
 }
 
+#[test]
+fn list_from_escrow_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let escrow = 5;
+		let originator = 1;
+		let dex_account: u64 = PalletId(*b"bitg/dex").into_account_truncating();
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), asset_id, escrow, 100));
+
+		// the escrowed units are listed on behalf of the originator
+		assert_ok!(<Dex as MarketplaceListing<_, _, _>>::list_from_escrow(
+			&escrow,
+			&originator,
+			asset_id,
+			100,
+			10
+		));
+
+		let sell_order_storage = Orders::<Test>::get(0).unwrap();
+		assert_eq!(sell_order_storage.owner, originator);
+		assert_eq!(sell_order_storage.units, 100);
+		assert_eq!(sell_order_storage.price_per_unit, 10);
+
+		assert_eq!(Assets::balance(asset_id, escrow), 0);
+		assert_eq!(Assets::balance(asset_id, dex_account), 100);
+
+		// the escrow cannot cancel the order
+		assert_noop!(
+			Dex::cancel_sell_order(RuntimeOrigin::signed(escrow), 0),
+			Error::<Test>::InvalidOrderOwner
+		);
+
+		// cancelling returns the units to the originator
+		assert_ok!(Dex::cancel_sell_order(RuntimeOrigin::signed(originator), 0));
+		assert_eq!(Assets::balance(asset_id, originator), 100);
+		assert_eq!(Assets::balance(asset_id, escrow), 0);
+		assert_eq!(Assets::balance(asset_id, dex_account), 0);
+	});
+}
+
 #[test]
 fn cancel_sell_order_should_work() {
 	new_test_ext().execute_with(|| {
@@ -818,6 +868,52 @@ fn buy_order_limits_should_work() {
 	});
 }
 
+#[test]
+fn buy_order_limits_depend_on_kyc_level() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 100, 10));
+
+		assert_ok!(Dex::force_set_open_order_allowed_limits(
+			RuntimeOrigin::root(),
+			UserLevel::KYCLevel1,
+			10
+		));
+		assert_ok!(Dex::force_set_open_order_allowed_limits(
+			RuntimeOrigin::root(),
+			UserLevel::KYCLevel3,
+			50
+		));
+
+		// a level 1 buyer is held to the level 1 limit
+		assert_noop!(
+			Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 20, 1000),
+			Error::<Test>::UserOpenOrderUnitsAllowedExceeded
+		);
+
+		// a level 2 buyer has no limit configured and falls back to the level 1 limit
+		MockKycLevels::set(vec![(buyer, UserLevel::KYCLevel2)]);
+		assert_noop!(
+			Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 20, 1000),
+			Error::<Test>::UserOpenOrderUnitsAllowedExceeded
+		);
+
+		// a level 3 buyer can purchase up to the level 3 limit
+		MockKycLevels::set(vec![(buyer, UserLevel::KYCLevel3)]);
+		assert_ok!(Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 20, 1000));
+		assert_noop!(
+			Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 31, 1000),
+			Error::<Test>::UserOpenOrderUnitsAllowedExceeded
+		);
+	});
+}
+
 #[test]
 fn buy_order_limits_are_reset_correctly() {
 	new_test_ext().execute_with(|| {
@@ -1012,4 +1108,705 @@ fn purchase_is_retired_if_payment_is_stripe() {
 		assert_eq!(Assets::balance(asset_id, buyer), 1);
 		assert_eq!(Assets::balance(asset_id, dex_account), 4);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn create_buy_order_onchain_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+		let fee_account = DexFeeAccount::get();
+		let dex_account: u64 = PalletId(*b"bitg/dex").into_account_truncating();
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+
+		// set fee values
+		assert_ok!(Dex::force_set_payment_fee(RuntimeOrigin::root(), Percent::from_percent(10)));
+		assert_ok!(Dex::force_set_purchase_fee(RuntimeOrigin::root(), 10u32.into()));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+
+		// non kyc buyer should fail
+		assert_noop!(
+			Dex::create_buy_order_onchain(RuntimeOrigin::signed(20), 0, asset_id, 1, 100, USDT),
+			Error::<Test>::KYCAuthorisationFailed
+		);
+
+		// currency not accepted for settlement should fail
+		assert_noop!(
+			Dex::create_buy_order_onchain(
+				RuntimeOrigin::signed(buyer),
+				0,
+				asset_id,
+				1,
+				100,
+				CurrencyId::DOT
+			),
+			Error::<Test>::SettlementCurrencyNotAccepted
+		);
+
+		// fee more than max_fee should fail
+		assert_noop!(
+			Dex::create_buy_order_onchain(RuntimeOrigin::signed(buyer), 0, asset_id, 2, 1, USDT),
+			Error::<Test>::FeeExceedsUserLimit
+		);
+
+		// seller cannot buy from own order
+		assert_noop!(
+			Dex::create_buy_order_onchain(RuntimeOrigin::signed(seller), 0, asset_id, 2, 100, USDT),
+			Error::<Test>::SellerAndBuyerCannotBeSame
+		);
+
+		assert_ok!(Dex::create_buy_order_onchain(
+			RuntimeOrigin::signed(buyer),
+			0,
+			asset_id,
+			2,
+			100,
+			USDT
+		));
+
+		// sell order should be reduced
+		assert_eq!(Orders::<Test>::get(0).unwrap().units, 3);
+
+		// no pending buy order or receivables should be recorded
+		assert!(BuyOrders::<Test>::get(0).is_none());
+		assert!(BuyOrdersByUser::<Test>::get(buyer).is_none());
+		assert!(SellerReceivables::<Test>::get(seller).is_none());
+
+		// price is 20, fees are 10% of 20 + 10
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 68);
+		assert_eq!(Tokens::free_balance(USDT, &seller), 20);
+		assert_eq!(Tokens::free_balance(USDT, &fee_account), 12);
+
+		// Asset balance should be set correctly
+		assert_eq!(Assets::balance(asset_id, buyer), 2);
+		assert_eq!(Assets::balance(asset_id, dex_account), 3);
+
+		assert_eq!(
+			last_event(),
+			Event::BuyOrderSettled {
+				order_id: 0,
+				sell_order_id: 0,
+				currency_id: USDT,
+				amount_to_seller: 20,
+				fees_paid: 12,
+				fee_account,
+			}
+			.into()
+		);
+	});
+}
+
+#[test]
+fn create_buy_order_onchain_fails_if_buyer_cannot_pay() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 50, 10));
+
+		// buyer only has 100 USDT
+		assert_noop!(
+			Dex::create_buy_order_onchain(RuntimeOrigin::signed(buyer), 0, asset_id, 11, 100, USDT),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+
+		// buyer does not hold any USDC
+		assert_noop!(
+			Dex::create_buy_order_onchain(
+				RuntimeOrigin::signed(buyer),
+				0,
+				asset_id,
+				1,
+				100,
+				CurrencyId::USDC
+			),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn royalties_are_recorded_when_payment_is_validated() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+		let validator = 10;
+		let validator_two = 11;
+		let buy_order_id = 0;
+		let chain_id = 1u32;
+
+		MockRoyalties::set(vec![
+			Royalty { account_id: 30, percent_of_fees: Percent::from_percent(50) },
+			Royalty { account_id: 31, percent_of_fees: Percent::from_percent(25) },
+		]);
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+
+		// set fee values
+		assert_ok!(Dex::force_set_payment_fee(RuntimeOrigin::root(), Percent::from_percent(10)));
+		assert_ok!(Dex::force_set_purchase_fee(RuntimeOrigin::root(), 10u32.into()));
+
+		// configure limit to avoid failure
+		assert_ok!(Dex::force_set_open_order_allowed_limits(
+			RuntimeOrigin::root(),
+			UserLevel::KYCLevel1,
+			1000
+		));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+
+		add_validator_account(validator);
+		add_validator_account(validator_two);
+
+		assert_ok!(Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 2, 12));
+
+		let tx_proof: BoundedVec<_, _> = vec![].try_into().unwrap();
+		assert_ok!(Dex::validate_buy_order(
+			RuntimeOrigin::signed(validator),
+			buy_order_id,
+			chain_id,
+			tx_proof.clone(),
+			None
+		));
+		assert_ok!(Dex::validate_buy_order(
+			RuntimeOrigin::signed(validator_two),
+			buy_order_id,
+			chain_id,
+			tx_proof,
+			None
+		));
+
+		// royalties are a share of the fees, the seller receives the full price
+		assert_eq!(SellerReceivables::<Test>::get(seller).unwrap(), 20);
+		assert_eq!(RoyaltyReceivables::<Test>::get(30).unwrap(), 6);
+		assert_eq!(RoyaltyReceivables::<Test>::get(31).unwrap(), 3);
+
+		assert!(System::events().iter().any(|record| record.event
+			== Event::RoyaltyPaid {
+				order_id: buy_order_id,
+				project_id: 0,
+				recipient: 30,
+				amount: 6,
+				paid_onchain: false
+			}
+			.into()));
+
+		// royalty recipient can be paid out by the payout authority
+		let payment = crate::types::PayoutExecutedToSeller {
+			order_id: vec![buy_order_id].try_into().unwrap(),
+			chain_id,
+			recipient_address: vec![].try_into().unwrap(),
+			tx_hash: vec![].try_into().unwrap(),
+			amount: 6,
+		};
+		assert_ok!(Dex::force_set_seller_payout_authority(RuntimeOrigin::root(), 5));
+		assert_noop!(
+			Dex::record_payment_to_royalty_recipient(RuntimeOrigin::signed(5), 31, payment.clone()),
+			Error::<Test>::ReceivableLessThanPayment
+		);
+		assert_ok!(Dex::record_payment_to_royalty_recipient(RuntimeOrigin::signed(5), 30, payment));
+		assert_eq!(RoyaltyReceivables::<Test>::get(30).unwrap(), 0);
+	});
+}
+
+#[test]
+fn royalties_from_gross_amount_are_paid_onchain() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+		let royalty_recipient = 30;
+		let fee_account = DexFeeAccount::get();
+
+		MockRoyalties::set(vec![Royalty {
+			account_id: royalty_recipient,
+			percent_of_fees: Percent::from_percent(10),
+		}]);
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+
+		// set fee values
+		assert_ok!(Dex::force_set_payment_fee(RuntimeOrigin::root(), Percent::from_percent(10)));
+		assert_ok!(Dex::force_set_purchase_fee(RuntimeOrigin::root(), 10u32.into()));
+		assert_ok!(Dex::force_set_royalty_basis(RuntimeOrigin::root(), RoyaltyBasis::GrossAmount));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+
+		assert_ok!(Dex::create_buy_order_onchain(
+			RuntimeOrigin::signed(buyer),
+			0,
+			asset_id,
+			2,
+			100,
+			USDT
+		));
+
+		// royalties are a share of the price paid to the seller
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 68);
+		assert_eq!(Tokens::free_balance(USDT, &seller), 18);
+		assert_eq!(Tokens::free_balance(USDT, &royalty_recipient), 2);
+		assert_eq!(Tokens::free_balance(USDT, &fee_account), 12);
+
+		// nothing is left as receivables
+		assert!(RoyaltyReceivables::<Test>::get(royalty_recipient).is_none());
+
+		assert!(System::events().iter().any(|record| record.event
+			== Event::RoyaltyPaid {
+				order_id: 0,
+				project_id: 0,
+				recipient: royalty_recipient,
+				amount: 2,
+				paid_onchain: true
+			}
+			.into()));
+	});
+}
+
+#[test]
+fn royalties_exceeding_amount_should_fail() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+
+		MockRoyalties::set(vec![
+			Royalty { account_id: 30, percent_of_fees: Percent::from_percent(60) },
+			Royalty { account_id: 31, percent_of_fees: Percent::from_percent(60) },
+		]);
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+		assert_ok!(Dex::force_set_purchase_fee(RuntimeOrigin::root(), 10u32.into()));
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+
+		assert_noop!(
+			Dex::create_buy_order_onchain(RuntimeOrigin::signed(buyer), 0, asset_id, 2, 100, USDT),
+			Error::<Test>::RoyaltiesExceedAmount
+		);
+	});
+}
+
+#[test]
+fn bid_order_is_matched_by_price_and_time_priority() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let buyer = 10;
+		let fee_account = DexFeeAccount::get();
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), asset_id, 1, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), asset_id, 2, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), asset_id, 3, 100));
+
+		// set fee values
+		assert_ok!(Dex::force_set_payment_fee(RuntimeOrigin::root(), Percent::from_percent(10)));
+		assert_ok!(Dex::force_set_purchase_fee(RuntimeOrigin::root(), 10u32.into()));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(1), asset_id, 5, 10));
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(2), asset_id, 5, 8));
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(3), asset_id, 5, 8));
+		assert_eq!(AskPriceLevels::<Test>::get(asset_id).into_inner(), vec![8, 10]);
+		assert_eq!(AskBook::<Test>::get(asset_id, 8).into_inner(), vec![1, 2]);
+
+		// bid with an expiry that is not in the future should fail
+		assert_noop!(
+			Dex::create_bid_order(RuntimeOrigin::signed(buyer), asset_id, 8, 10, USDT, 1),
+			Error::<Test>::InvalidBidOrderExpiry
+		);
+
+		// reserves 80 + 10% fee + 10 purchase fee
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(buyer), asset_id, 8, 10, USDT, 10));
+
+		// the cheapest and oldest sell order is filled first
+		System::assert_has_event(
+			Event::OrdersMatched {
+				bid_order_id: 0,
+				sell_order_id: 1,
+				asset_id,
+				units: 5,
+				price_per_unit: 8,
+				currency_id: USDT,
+				fees_paid: 14,
+				seller: 2,
+				buyer,
+			}
+			.into(),
+		);
+
+		// the payment fee is charged on the total paid and the purchase fee only once
+		System::assert_has_event(
+			Event::OrdersMatched {
+				bid_order_id: 0,
+				sell_order_id: 2,
+				asset_id,
+				units: 3,
+				price_per_unit: 8,
+				currency_id: USDT,
+				fees_paid: 3,
+				seller: 3,
+				buyer,
+			}
+			.into(),
+		);
+
+		// sell orders should be reduced and the filled order removed from the book
+		assert_eq!(Orders::<Test>::get(0).unwrap().units, 5);
+		assert_eq!(Orders::<Test>::get(1).unwrap().units, 0);
+		assert_eq!(Orders::<Test>::get(2).unwrap().units, 2);
+		assert_eq!(AskBook::<Test>::get(asset_id, 8).into_inner(), vec![2]);
+
+		// balances should be set correctly, the unused reserve is refunded
+		assert_eq!(Assets::balance(asset_id, buyer), 8);
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 9919);
+		assert_eq!(Tokens::free_balance(USDT, &2), 40);
+		assert_eq!(Tokens::free_balance(USDT, &3), 24);
+		assert_eq!(Tokens::free_balance(USDT, &fee_account), 17);
+
+		// the filled bid is closed
+		assert!(BidOrders::<Test>::get(0).is_none());
+		assert!(BidPriceLevels::<Test>::get(asset_id).is_empty());
+		assert_eq!(
+			last_event(),
+			Event::BidOrderClosed { bid_order_id: 0, units_filled: 8, refunded: 17, owner: buyer }
+				.into()
+		);
+	});
+}
+
+#[test]
+fn resting_bid_order_is_matched_by_new_sell_orders() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+
+		// no sell orders, the bid rests in the book
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(buyer), asset_id, 10, 5, USDT, 10));
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 50);
+		assert_eq!(BidOrders::<Test>::get(0).unwrap().reserved, 50);
+		assert_eq!(BidBook::<Test>::get(asset_id, 5).into_inner(), vec![0]);
+		assert_eq!(BidPriceLevels::<Test>::get(asset_id).into_inner(), vec![5]);
+
+		// a sell order below the bid price is filled at the bid price
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 4, 4));
+		assert_eq!(Orders::<Test>::get(0).unwrap().units, 0);
+		assert!(AskPriceLevels::<Test>::get(asset_id).is_empty());
+		assert_eq!(Tokens::free_balance(USDT, &seller), 20);
+		assert_eq!(Assets::balance(asset_id, buyer), 4);
+
+		let bid = BidOrders::<Test>::get(0).unwrap();
+		assert_eq!(bid.units, 6);
+		assert_eq!(bid.units_filled, 4);
+		assert_eq!(bid.reserved, 30);
+
+		// a sell order above the bid price rests in the book
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 10, 6));
+		assert_eq!(Orders::<Test>::get(1).unwrap().units, 10);
+		assert_eq!(AskPriceLevels::<Test>::get(asset_id).into_inner(), vec![6]);
+
+		// a partially filled sell order rests in the book with the remaining units
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 8, 5));
+		assert_eq!(Orders::<Test>::get(2).unwrap().units, 2);
+		assert_eq!(AskPriceLevels::<Test>::get(asset_id).into_inner(), vec![5, 6]);
+		assert_eq!(AskBook::<Test>::get(asset_id, 5).into_inner(), vec![2]);
+
+		// the bid is filled and closed
+		assert!(BidOrders::<Test>::get(0).is_none());
+		assert!(BidPriceLevels::<Test>::get(asset_id).is_empty());
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 50);
+		assert_eq!(Tokens::free_balance(USDT, &seller), 50);
+		assert_eq!(Assets::balance(asset_id, buyer), 10);
+	});
+}
+
+#[test]
+fn cancel_bid_order_should_work() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let trader = 10;
+		let buyer = 4;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), asset_id, trader, 100));
+
+		// non kyc buyer should fail
+		assert_noop!(
+			Dex::create_bid_order(RuntimeOrigin::signed(20), asset_id, 2, 5, USDT, 10),
+			Error::<Test>::KYCAuthorisationFailed
+		);
+
+		// currency not accepted for settlement should fail
+		assert_noop!(
+			Dex::create_bid_order(
+				RuntimeOrigin::signed(buyer),
+				asset_id,
+				2,
+				5,
+				CurrencyId::DOT,
+				10
+			),
+			Error::<Test>::SettlementCurrencyNotAccepted
+		);
+
+		// a bid is never matched with a sell order of the same account
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(trader), asset_id, 5, 5));
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(trader), asset_id, 5, 5, USDT, 10));
+		assert_eq!(Orders::<Test>::get(0).unwrap().units, 5);
+		assert_eq!(BidOrders::<Test>::get(0).unwrap().units, 5);
+
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(buyer), asset_id, 2, 3, USDT, 5));
+		assert_eq!(BidPriceLevels::<Test>::get(asset_id).into_inner(), vec![3, 5]);
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 94);
+
+		// only the owner can cancel a bid before expiry
+		assert_noop!(
+			Dex::cancel_bid_order(RuntimeOrigin::signed(trader), 1),
+			Error::<Test>::InvalidOrderOwner
+		);
+		assert_noop!(
+			Dex::cancel_bid_order(RuntimeOrigin::signed(buyer), 2),
+			Error::<Test>::InvalidBidOrderId
+		);
+
+		// anyone can cancel an expired bid, the funds are refunded to the owner
+		System::set_block_number(6);
+		assert_ok!(Dex::cancel_bid_order(RuntimeOrigin::signed(trader), 1));
+		assert_eq!(Tokens::free_balance(USDT, &buyer), 100);
+		assert_eq!(
+			last_event(),
+			Event::BidOrderClosed { bid_order_id: 1, units_filled: 0, refunded: 6, owner: buyer }
+				.into()
+		);
+
+		assert_ok!(Dex::cancel_bid_order(RuntimeOrigin::signed(trader), 0));
+		assert_eq!(Tokens::free_balance(USDT, &trader), 10000);
+		assert!(BidOrders::<Test>::get(0).is_none());
+		assert!(BidPriceLevels::<Test>::get(asset_id).is_empty());
+
+		// cancelling the sell order removes it from the book
+		assert_ok!(Dex::cancel_sell_order(RuntimeOrigin::signed(trader), 0));
+		assert!(AskPriceLevels::<Test>::get(asset_id).is_empty());
+	});
+}
+
+#[test]
+fn runtime_api_helpers_return_open_orders_and_quotes() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let other_asset_id = 1;
+		let seller = 1;
+		let buyer = 4;
+		let validator = 10;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), other_asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), other_asset_id, 1, 100));
+
+		// set fee values
+		assert_ok!(Dex::force_set_payment_fee(RuntimeOrigin::root(), Percent::from_percent(10)));
+		assert_ok!(Dex::force_set_purchase_fee(RuntimeOrigin::root(), 10u32.into()));
+		assert_ok!(Dex::force_set_open_order_allowed_limits(
+			RuntimeOrigin::root(),
+			UserLevel::KYCLevel1,
+			1000
+		));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), other_asset_id, 5, 20));
+
+		// sell orders can be filtered by asset and project
+		assert_eq!(Dex::open_sell_orders(None, None).len(), 2);
+		let orders = Dex::open_sell_orders(Some(other_asset_id), None);
+		assert_eq!(orders.len(), 1);
+		assert_eq!(orders[0].0, 1);
+		assert_eq!(orders[0].1.price_per_unit, 20);
+		assert_eq!(Dex::open_sell_orders(None, Some(0)).len(), 2);
+		assert!(Dex::open_sell_orders(None, Some(1)).is_empty());
+
+		// the quote includes the payment and purchase fees
+		assert_eq!(Dex::quote_buy_order(0, 2), Ok((20, 12, 32)));
+		assert_noop!(Dex::quote_buy_order(0, 6), Error::<Test>::OrderUnitsOverflow);
+		assert_noop!(Dex::quote_buy_order(5, 1), Error::<Test>::InvalidOrderId);
+
+		assert_ok!(Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 1, 11));
+		add_validator_account(validator);
+		assert_ok!(Dex::validate_buy_order(
+			RuntimeOrigin::signed(validator),
+			0,
+			0u32,
+			vec![].try_into().unwrap(),
+			None
+		));
+
+		let pending = Dex::pending_buy_orders(Some(buyer));
+		assert_eq!(pending.len(), 1);
+		assert_eq!(pending[0].buy_order_id, 0);
+		assert_eq!(pending[0].order.units, 1);
+		assert_eq!(pending[0].validations, 1);
+		assert_eq!(pending[0].required_validations, 2);
+		assert_eq!(Dex::pending_buy_orders(None), pending);
+		assert!(Dex::pending_buy_orders(Some(seller)).is_empty());
+	});
+}
+
+#[test]
+fn sell_order_is_kept_outside_a_full_order_book() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 10;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+
+		// fill the price level
+		for _ in 0..MaxOrdersPerPriceLevel::get() {
+			assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+		}
+
+		// the order is created but not added to the full price level
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+		assert_eq!(Orders::<Test>::get(3).unwrap().units, 5);
+		assert_eq!(AskBook::<Test>::get(asset_id, 10).into_inner(), vec![0, 1, 2]);
+
+		// the order can still be bought directly
+		assert_ok!(Dex::create_buy_order_onchain(
+			RuntimeOrigin::signed(buyer),
+			3,
+			asset_id,
+			5,
+			100,
+			USDT
+		));
+		assert_eq!(Orders::<Test>::get(3).unwrap().units, 0);
+		assert_eq!(Assets::balance(asset_id, buyer), 5);
+	});
+}
+
+#[test]
+fn bid_order_is_kept_outside_a_full_order_book() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let buyer = 10;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+
+		// fill the price level
+		for _ in 0..MaxOrdersPerPriceLevel::get() {
+			assert_ok!(Dex::create_bid_order(
+				RuntimeOrigin::signed(buyer),
+				asset_id,
+				2,
+				5,
+				USDT,
+				10
+			));
+		}
+
+		// the bid is created but not added to the full price level
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(buyer), asset_id, 2, 5, USDT, 10));
+		let bid = BidOrders::<Test>::get(3).unwrap();
+		assert_eq!(bid.units, 2);
+		assert_eq!(BidBook::<Test>::get(asset_id, 5).into_inner(), vec![0, 1, 2]);
+
+		// the bid can still be cancelled and refunded
+		let balance_before = Tokens::free_balance(USDT, &buyer);
+		assert_ok!(Dex::cancel_bid_order(RuntimeOrigin::signed(buyer), 3));
+		assert!(BidOrders::<Test>::get(3).is_none());
+		assert_eq!(Tokens::free_balance(USDT, &buyer), balance_before + bid.reserved);
+	});
+}
+
+#[test]
+fn sell_order_keeps_its_position_when_an_expired_buy_order_returns_its_units() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let buyer = 4;
+		let trader = 10;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, 1, 100));
+		assert_ok!(Dex::force_set_open_order_allowed_limits(
+			RuntimeOrigin::root(),
+			UserLevel::KYCLevel1,
+			1000
+		));
+
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 5, 10));
+
+		// the first order is sold out by a pending buy order and leaves the book on the next match
+		assert_ok!(Dex::create_buy_order(RuntimeOrigin::signed(buyer), 0, asset_id, 5, 100));
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(trader), asset_id, 2, 10, USDT, 10));
+		assert_eq!(AskBook::<Test>::get(asset_id, 10).into_inner(), vec![1]);
+
+		// the expired buy order returns the units, the order is back ahead of the newer order
+		Dex::on_idle(6, Weight::MAX);
+		assert_eq!(Orders::<Test>::get(0).unwrap().units, 5);
+		assert_eq!(AskBook::<Test>::get(asset_id, 10).into_inner(), vec![0, 1]);
+	});
+}
+
+#[test]
+fn skipped_orders_count_towards_the_match_limit() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+		let trader = 10;
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), asset_id, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, trader, 100));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(seller), asset_id, seller, 100));
+
+		// the own orders of the trader are visited before the order of the seller
+		for price in [1, 1, 1, 2, 2] {
+			assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(trader), asset_id, 2, price));
+		}
+		assert_ok!(Dex::create_sell_order(RuntimeOrigin::signed(seller), asset_id, 2, 3));
+
+		// the bid stops matching after visiting `MaxMatchesPerOrder` orders and rests in the book
+		assert_ok!(Dex::create_bid_order(RuntimeOrigin::signed(trader), asset_id, 2, 3, USDT, 10));
+		assert_eq!(Orders::<Test>::get(5).unwrap().units, 2);
+		assert_eq!(BidOrders::<Test>::get(0).unwrap().units, 2);
+		assert_eq!(BidBook::<Test>::get(asset_id, 3).into_inner(), vec![0]);
+	});
+}
+
+#[test]
+fn migration_to_v1_indexes_open_sell_orders() {
+	new_test_ext().execute_with(|| {
+		let asset_id = 0;
+		let seller = 1;
+
+		for (order_id, units, price_per_unit) in [(0, 5, 10), (1, 0, 10), (2, 5, 8), (3, 5, 10)] {
+			Orders::<Test>::insert(
+				order_id,
+				crate::OrderInfo { owner: seller, units, price_per_unit, asset_id },
+			);
+		}
+		StorageVersion::new(0).put::<Dex>();
+
+		crate::migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		// sold out orders are not indexed and the order id keeps the time priority
+		assert_eq!(AskPriceLevels::<Test>::get(asset_id).into_inner(), vec![8, 10]);
+		assert_eq!(AskBook::<Test>::get(asset_id, 8).into_inner(), vec![2]);
+		assert_eq!(AskBook::<Test>::get(asset_id, 10).into_inner(), vec![0, 3]);
+		assert_eq!(Dex::on_chain_storage_version(), 1);
+	});
+}
@@ -9,6 +9,9 @@ use sp_runtime::traits::Get;
 pub type CurrencyBalanceOf<T> =
 	<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type CurrencyIdOf<T> =
+	<<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+
 pub type AssetBalanceOf<T> =
 	<<T as Config>::Asset as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -50,9 +53,27 @@ pub type PayoutExecutedToSellerOf<T> = PayoutExecutedToSeller<
 	<T as Config>::MaxAddressLen,
 >;
 
+pub type BidOrderInfoOf<T> = BidOrderInfo<
+	<T as frame_system::Config>::AccountId,
+	AssetIdOf<T>,
+	AssetBalanceOf<T>,
+	CurrencyBalanceOf<T>,
+	CurrencyIdOf<T>,
+	BlockNumberFor<T>,
+>;
+
+/// The price levels of one side of the order book of an asset, sorted by ascending price
+pub type PriceLevelsOf<T> = BoundedVec<CurrencyBalanceOf<T>, <T as Config>::MaxPriceLevels>;
+
+/// The orders at a price level of the order book, in the order they were placed
+pub type PriceLevelOrdersOf<T> = BoundedVec<u128, <T as Config>::MaxOrdersPerPriceLevel>;
+
+pub type PendingBuyOrderOf<T> = PendingBuyOrder<BuyOrderInfoOf<T>>;
+
 pub type SellerPayoutPreferenceOf<T> = SellerPayoutPreference<<T as Config>::MaxAddressLen>;
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderInfo<AccountId, AssetId, AssetBalance, TokenBalance> {
 	pub owner: AccountId,
 	pub units: AssetBalance,
@@ -61,6 +82,14 @@ pub struct OrderInfo<AccountId, AssetId, AssetBalance, TokenBalance> {
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "serde",
+	serde(bound(
+		serialize = "AccountId: serde::Serialize, AssetId: serde::Serialize, AssetBalance: serde::Serialize, TokenBalance: serde::Serialize, Time: serde::Serialize",
+		deserialize = "AccountId: serde::Deserialize<'de>, AssetId: serde::Deserialize<'de>, AssetBalance: serde::Deserialize<'de>, TokenBalance: serde::Deserialize<'de>, Time: serde::Deserialize<'de>"
+	))
+)]
 pub struct BuyOrderInfo<
 	AccountId,
 	AssetId,
@@ -81,7 +110,53 @@ pub struct BuyOrderInfo<
 	pub payment_info: Option<PaymentInfo<AccountId, TxProofLen, MaxValidators>>,
 }
 
+/// A standing order to buy units of an asset at or below a price, the funds to pay for the bid are
+/// reserved in the pallet account until the bid is filled, cancelled or expired
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct BidOrderInfo<AccountId, AssetId, AssetBalance, TokenBalance, CurrencyId, Time> {
+	/// The account that placed the bid
+	pub owner: AccountId,
+	/// The asset to buy
+	pub asset_id: AssetId,
+	/// The currency used to pay for the bid
+	pub currency_id: CurrencyId,
+	/// The maximum price the owner is willing to pay per unit
+	pub price_per_unit: TokenBalance,
+	/// The units that are still to be filled
+	pub units: AssetBalance,
+	/// The units filled so far
+	pub units_filled: AssetBalance,
+	/// The amount paid to sellers so far, excluding fees
+	pub amount_paid: TokenBalance,
+	/// The funds still reserved for the bid
+	pub reserved: TokenBalance,
+	/// The block after which the bid can no longer be filled
+	pub expiry_time: Time,
+}
+
+/// A buy order waiting for its payment to be validated, along with the validation progress
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingBuyOrder<BuyOrder> {
+	/// The id of the buy order
+	pub buy_order_id: BuyOrderId,
+	/// The details of the buy order
+	pub order: BuyOrder,
+	/// The number of validators that validated the payment so far
+	pub validations: u32,
+	/// The number of validations required to complete the purchase
+	pub required_validations: u32,
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "serde",
+	serde(bound(
+		serialize = "AccountId: serde::Serialize",
+		deserialize = "AccountId: serde::Deserialize<'de>"
+	))
+)]
 pub struct PaymentInfo<AccountId, TxProofLen: Get<u32> + Clone, MaxValidators: Get<u32> + Clone> {
 	pub chain_id: u32,
 	pub tx_proof: BoundedVec<u8, TxProofLen>,
@@ -130,6 +205,20 @@ pub struct PayoutExecutedToSeller<
 	pub tx_hash: BoundedVec<u8, TxHashLen>,
 }
 
+/// The amount that project royalties are calculated from on every fill
+#[derive(
+	Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, MaxEncodedLen, TypeInfo,
+)]
+pub enum RoyaltyBasis {
+	/// Royalties are a share of the fees charged by the dex, the fee account receives the rest
+	#[default]
+	Fees,
+	/// Royalties are a share of the gross amount of the purchase, the seller receives the rest
+	GrossAmount,
+}
+
 pub type OrderId = u128;
 
-pub type BuyOrderId = u128;
\ No newline at end of file
+pub type BuyOrderId = u128;
+
+pub type BidOrderId = u128;
@@ -30,11 +30,16 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_dex.
 pub trait WeightInfo {
-	fn create_sell_order() -> Weight;
+	fn create_sell_order(m: u32, ) -> Weight;
 	fn cancel_sell_order() -> Weight;
-	fn buy_order() -> Weight;
+	fn buy_order(r: u32, ) -> Weight;
 	fn force_set_payment_fee() -> Weight;
 	fn force_set_purchase_fee() -> Weight;
+	fn create_buy_order_onchain(r: u32, ) -> Weight;
+	fn create_bid_order(m: u32, ) -> Weight;
+	fn cancel_bid_order() -> Weight;
+	fn force_set_royalty_basis() -> Weight;
+	fn record_payment_to_royalty_recipient() -> Weight;
 }
 
 /// Weights for pallet_dex using the Substrate node and recommended hardware.
@@ -44,12 +49,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Assets Account (r:2 w:2)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Dex OrderCount (r:1 w:1)
+	// Storage: Dex BidPriceLevels (r:1 w:1)
+	// Storage: Dex BidBook (r:1 w:1)
+	// Storage: Dex BidOrders (r:1 w:1)
+	// Storage: Dex PaymentFees (r:1 w:0)
+	// Storage: Dex PurchaseFees (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Dex AskPriceLevels (r:1 w:1)
+	// Storage: Dex AskBook (r:1 w:1)
 	// Storage: Dex Orders (r:0 w:1)
-	fn create_sell_order() -> Weight {
+	/// The range of component `m` is `[1, 20]`.
+	fn create_sell_order(m: u32, ) -> Weight {
 		// Minimum execution time:  nanoseconds.
-		Weight::from_parts(74_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(6))
+		Weight::from_parts(81_000_000, 0)
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_parts(46_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((9_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(9))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(m.into())))
 	}
 	// Storage: Dex Orders (r:1 w:1)
 	// Storage: Assets Asset (r:1 w:1)
@@ -66,11 +84,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Dex PurchaseFees (r:1 w:0)
 	// Storage: Assets Asset (r:1 w:1)
 	// Storage: Assets Account (r:2 w:2)
-	fn buy_order() -> Weight {
+	// Storage: Dex RoyaltyReceivables (r:5 w:5)
+	/// The range of component `r` is `[0, 5]`.
+	fn buy_order(r: u32, ) -> Weight {
 		// Minimum execution time:  nanoseconds.
 		Weight::from_parts(49_000_000, 0)
+			// Placeholder for the `r` component, not generated by the benchmark CLI
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(r.into()))
 			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
 			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
 	}
 	// Storage: Dex PaymentFees (r:0 w:1)
 	fn force_set_payment_fee() -> Weight {
@@ -84,6 +108,75 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(7_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	// Storage: Dex Orders (r:1 w:1)
+	// Storage: Dex PaymentFees (r:1 w:0)
+	// Storage: Dex PurchaseFees (r:1 w:0)
+	// Storage: Dex BuyOrderCount (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Tokens Accounts (r:5 w:5)
+	/// The range of component `r` is `[0, 5]`.
+	fn create_buy_order_onchain(r: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(92_000_000, 0)
+			// Placeholder for the `r` component, not generated by the benchmark CLI
+			.saturating_add(Weight::from_parts(14_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(9))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	// Storage: Dex BidOrderCount (r:1 w:1)
+	// Storage: Dex PaymentFees (r:1 w:0)
+	// Storage: Dex PurchaseFees (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dex AskPriceLevels (r:1 w:1)
+	// Storage: Dex AskBook (r:1 w:1)
+	// Storage: Dex Orders (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Dex BidPriceLevels (r:1 w:1)
+	// Storage: Dex BidBook (r:1 w:1)
+	// Storage: Dex BidOrders (r:0 w:1)
+	/// The range of component `m` is `[1, 20]`.
+	fn create_bid_order(m: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(72_000_000, 0)
+			// Standard Error: 38_000
+			.saturating_add(Weight::from_parts(44_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(m.into())))
+	}
+	// Storage: Dex BidOrders (r:1 w:1)
+	// Storage: Dex BidBook (r:1 w:1)
+	// Storage: Dex BidPriceLevels (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_bid_order() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	// Storage: Dex RoyaltyCalculationBasis (r:0 w:1)
+	fn force_set_royalty_basis() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Dex SellerPayoutAuthority (r:1 w:0)
+	// Storage: Dex RoyaltyReceivables (r:1 w:1)
+	fn record_payment_to_royalty_recipient() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
 
 // For backwards compatibility and tests
@@ -92,12 +185,25 @@ impl WeightInfo for () {
 	// Storage: Assets Account (r:2 w:2)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Dex OrderCount (r:1 w:1)
+	// Storage: Dex BidPriceLevels (r:1 w:1)
+	// Storage: Dex BidBook (r:1 w:1)
+	// Storage: Dex BidOrders (r:1 w:1)
+	// Storage: Dex PaymentFees (r:1 w:0)
+	// Storage: Dex PurchaseFees (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Dex AskPriceLevels (r:1 w:1)
+	// Storage: Dex AskBook (r:1 w:1)
 	// Storage: Dex Orders (r:0 w:1)
-	fn create_sell_order() -> Weight {
+	/// The range of component `m` is `[1, 20]`.
+	fn create_sell_order(m: u32, ) -> Weight {
 		// Minimum execution time:  nanoseconds.
-		Weight::from_parts(74_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(5))
-			.saturating_add(RocksDbWeight::get().writes(6))
+		Weight::from_parts(81_000_000, 0)
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_parts(46_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().reads((9_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(9))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(m.into())))
 	}
 	// Storage: Dex Orders (r:1 w:1)
 	// Storage: Assets Asset (r:1 w:1)
@@ -114,11 +220,17 @@ impl WeightInfo for () {
 	// Storage: Dex PurchaseFees (r:1 w:0)
 	// Storage: Assets Asset (r:1 w:1)
 	// Storage: Assets Account (r:2 w:2)
-	fn buy_order() -> Weight {
+	// Storage: Dex RoyaltyReceivables (r:5 w:5)
+	/// The range of component `r` is `[0, 5]`.
+	fn buy_order(r: u32, ) -> Weight {
 		// Minimum execution time:  nanoseconds.
 		Weight::from_parts(49_000_000, 0)
+			// Placeholder for the `r` component, not generated by the benchmark CLI
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(r.into()))
 			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
 			.saturating_add(RocksDbWeight::get().writes(4))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
 	}
 	// Storage: Dex PaymentFees (r:0 w:1)
 	fn force_set_payment_fee() -> Weight {
@@ -132,4 +244,73 @@ impl WeightInfo for () {
 		Weight::from_parts(7_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1))
 	}
+	// Storage: Dex Orders (r:1 w:1)
+	// Storage: Dex PaymentFees (r:1 w:0)
+	// Storage: Dex PurchaseFees (r:1 w:0)
+	// Storage: Dex BuyOrderCount (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Tokens Accounts (r:5 w:5)
+	/// The range of component `r` is `[0, 5]`.
+	fn create_buy_order_onchain(r: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(92_000_000, 0)
+			// Placeholder for the `r` component, not generated by the benchmark CLI
+			.saturating_add(Weight::from_parts(14_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(11))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(9))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	// Storage: Dex BidOrderCount (r:1 w:1)
+	// Storage: Dex PaymentFees (r:1 w:0)
+	// Storage: Dex PurchaseFees (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Dex AskPriceLevels (r:1 w:1)
+	// Storage: Dex AskBook (r:1 w:1)
+	// Storage: Dex Orders (r:1 w:1)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: Dex BidPriceLevels (r:1 w:1)
+	// Storage: Dex BidBook (r:1 w:1)
+	// Storage: Dex BidOrders (r:0 w:1)
+	/// The range of component `m` is `[1, 20]`.
+	fn create_bid_order(m: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(72_000_000, 0)
+			// Standard Error: 38_000
+			.saturating_add(Weight::from_parts(44_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(6))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(m.into())))
+	}
+	// Storage: Dex BidOrders (r:1 w:1)
+	// Storage: Dex BidBook (r:1 w:1)
+	// Storage: Dex BidPriceLevels (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_bid_order() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	// Storage: Dex RoyaltyCalculationBasis (r:0 w:1)
+	fn force_set_royalty_basis() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(7_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: Dex SellerPayoutAuthority (r:1 w:0)
+	// Storage: Dex RoyaltyReceivables (r:1 w:1)
+	fn record_payment_to_royalty_recipient() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(19_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
 }
\ No newline at end of file
@@ -5,16 +5,18 @@ use super::*;
 use crate::Pallet as ForestaCollectives;
 use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
-use sp_std::vec;
+use sp_std::{boxed::Box, vec, vec::Vec};
 use pallet_carbon_credits::{BatchGroupOf, ProjectCreateParams, RegistryListOf, SDGTypesListOf, BatchOf, BatchGroupListOf};
 use primitives::{Batch, RegistryDetails, RegistryName, SDGDetails, SdgType};
-use frame_support::BoundedVec;
+use frame_support::{traits::Currency, BoundedVec};
 use sp_std::convert::TryInto;
+use sp_runtime::Percent;
 
 
 benchmarks! {
 	where_clause { where
-		T: pallet_membership::Config
+		T: pallet_membership::Config,
+		<T as Config>::RuntimeCall: From<frame_system::Call<T>>,
 	}
 	add_collective {
 		let manager : T::AccountId = account("account_id", 0, 0);
@@ -50,6 +52,133 @@ benchmarks! {
 	}
 
 
+	set_voting_policy {
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		let policy = VotingPolicy::<T> {
+			min_turnout: Percent::from_percent(30),
+			approval_threshold: Percent::from_percent(60),
+			voting_durations: vec![
+				(VoteType::ProjectApproval, 10_u32.into()),
+				(VoteType::ProjectRemoval, 10_u32.into()),
+				(VoteType::Proposal, 10_u32.into()),
+			].try_into().unwrap(),
+			weighting: VoteWeighting::LockedBalance,
+		};
+	}: _(RawOrigin::Signed(manager), collective_id, policy.clone())
+	verify {
+		assert_eq!(VotingPolicies::<T>::get(collective_id), policy);
+	}
+
+	create_proposal {
+		// the encoded remark call is a few bytes longer than the remark
+		let l in 0 .. T::MaxProposalLength::get() - 6;
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let vote_id : T::VoteId = 0_u32.into();
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		let call: <T as Config>::RuntimeCall = frame_system::Call::<T>::remark { remark: vec![0; l as usize] }.into();
+	}: _(RawOrigin::Signed(manager), collective_id, "Proposal1Hash".as_bytes().to_vec().try_into().unwrap(), Some(Box::new(call)))
+	verify {
+		assert!(ProposalCalls::<T>::get(vote_id).is_some());
+	}
+
+	cast_vote {
+		let l in 1 .. T::MaxVoteLocks::get();
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let vote_id : T::VoteId = 0_u32.into();
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		// mixed weighting reads both the balance and the retirements of the member
+		let weighting = VoteWeighting::Mixed { balance_share: Percent::from_percent(50) };
+		let policy = VotingPolicy::<T> { weighting, ..Default::default() };
+		ForestaCollectives::<T>::set_voting_policy(RawOrigin::Root.into(), collective_id, policy)?;
+		<T as Config>::NativeCurrency::make_free_balance_be(&manager, 1_000_000_u32.into());
+		ForestaCollectives::<T>::create_proposal(RawOrigin::Signed(manager.clone()).into(), collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(), None)?;
+		// the balance of the member is already locked for other votes
+		let locks: Vec<(T::VoteId, BalanceOf<T>)> = (1 .. l).map(|i| (i.into(), 1_000_u32.into())).collect();
+		VoteLocks::<T>::insert(&manager, BoundedVec::try_from(locks).unwrap());
+	}: _(RawOrigin::Signed(manager.clone()), vote_id, true)
+	verify {
+		assert_eq!(VoteLocks::<T>::get(&manager).len(), l as usize);
+	}
+
+	unlock_vote {
+		let l in 1 .. T::MaxVoteLocks::get();
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let vote_id : T::VoteId = 0_u32.into();
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		let policy = VotingPolicy::<T> { weighting: VoteWeighting::LockedBalance, ..Default::default() };
+		ForestaCollectives::<T>::set_voting_policy(RawOrigin::Root.into(), collective_id, policy)?;
+		<T as Config>::NativeCurrency::make_free_balance_be(&manager, 1_000_000_u32.into());
+		ForestaCollectives::<T>::create_proposal(RawOrigin::Signed(manager.clone()).into(), collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(), None)?;
+		// the lock of the vote comes last, after the locks of the other votes
+		let locks: Vec<(T::VoteId, BalanceOf<T>)> = (1 .. l).map(|i| (i.into(), 1_000_u32.into())).collect();
+		VoteLocks::<T>::insert(&manager, BoundedVec::try_from(locks).unwrap());
+		ForestaCollectives::<T>::cast_vote(RawOrigin::Signed(manager.clone()).into(), vote_id, true)?;
+		// end the vote
+		let mut vote = ProjectVote::<T>::get(vote_id).unwrap();
+		vote.status = VoteStatus::Passed;
+		ProjectVote::<T>::insert(vote_id, vote);
+	}: _(RawOrigin::Signed(manager.clone()), vote_id)
+	verify {
+		assert_eq!(VoteLocks::<T>::get(&manager).len(), l as usize - 1);
+	}
+
+	leave_collective {
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let member : T::AccountId = account("account_id", 0, 0);
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		ForestaCollectives::<T>::add_member(RawOrigin::Signed(manager).into(), collective_id, member.clone())?;
+	}: _(RawOrigin::Signed(member), collective_id)
+	verify {
+		assert_eq!(MembersCount::<T>::get(collective_id),1_u32);
+	}
+
+	remove_member {
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let member : T::AccountId = account("account_id", 0, 0);
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		ForestaCollectives::<T>::add_member(RawOrigin::Signed(manager.clone()).into(), collective_id, member.clone())?;
+	}: _(RawOrigin::Signed(manager), collective_id, member)
+	verify {
+		assert_eq!(MembersCount::<T>::get(collective_id),1_u32);
+	}
+
+	init_membership_vote {
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let member : T::AccountId = account("account_id", 0, 0);
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager.clone()].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+		ForestaCollectives::<T>::add_member(RawOrigin::Signed(manager.clone()).into(), collective_id, member.clone())?;
+	}: _(RawOrigin::Signed(manager), collective_id, member, VoteType::AddManager)
+	verify {
+		assert!(MembershipParamsInfo::<T>::get(T::VoteId::from(0_u32)).is_some());
+	}
+
+	archive_collective {
+		let manager : T::AccountId = account("account_id", 0, 1);
+		let collective_id : T::CollectiveId = 0_u32.into();
+		let _ = ForestaCollectives::<T>::add_collective(RawOrigin::Root.into(), "Collective1".as_bytes().to_vec().try_into().unwrap(),
+		vec![manager].try_into().unwrap(),"Coll1Hash".as_bytes().to_vec().try_into().unwrap());
+	}: _(RawOrigin::Root, collective_id)
+	verify {
+		assert!(ArchivedCollectives::<T>::contains_key(collective_id));
+	}
+
 	impl_benchmark_test_suite!(ForestaCollectives, crate::mock::new_test_ext(), crate::mock::Test);
 }
 
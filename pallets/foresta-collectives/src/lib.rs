@@ -13,6 +13,7 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod migration;
 pub mod weights;
 pub use weights::*;
 
@@ -22,18 +23,28 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 	use frame_support::{BoundedVec,PalletId};
+	use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
 	use scale_info::TypeInfo;
 	use codec::{FullCodec, MaxEncodedLen, EncodeLike};
 
 	use sp_runtime::{
-		traits::{MaybeSerializeDeserialize,CheckedAdd,AccountIdConversion}
-		,ArithmeticError, };
-	use sp_std::{fmt::Debug,cmp::{Eq, PartialEq}};
-	use frame_support::traits::Contains;
+		traits::{MaybeSerializeDeserialize,CheckedAdd,AccountIdConversion,Zero,Dispatchable,SaturatedConversion}
+		,ArithmeticError, Percent, };
+	use sp_std::{boxed::Box,fmt::Debug,cmp::{Eq, PartialEq}};
+	use frame_support::traits::{Contains, Currency, LockIdentifier, LockableCurrency, WithdrawReasons};
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	/// Lock on the native balance backing the votes of collective members
+	pub const COLLECTIVE_VOTE_LOCK: LockIdentifier = *b"forcolvt";
+
+	pub type BalanceOf<T> = <<T as Config>::NativeCurrency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 	#[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen, Debug, TypeInfo, Eq)]
 	#[scale_info(skip_type_params(T))]
 	pub struct Collective<T:Config> {
@@ -46,6 +57,10 @@ pub mod pallet {
 	pub struct Vote<T:Config> {
 		pub yes_votes: u64,
 		pub no_votes: u64,
+		/// Sum of the weights of the yes votes
+		pub yes_weight: u128,
+		/// Sum of the weights of the no votes
+		pub no_weight: u128,
 		pub end: BlockNumberFor<T>,
 		pub status: VoteStatus,
 		pub vote_type: VoteType,
@@ -99,6 +114,8 @@ pub mod pallet {
 		Deciding,
 		Passed,
 		Failed,
+		/// Not enough members of the collective took part in the vote
+		QuorumNotMet,
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen,Debug, TypeInfo, Eq, Copy)]
@@ -111,6 +128,70 @@ pub mod pallet {
 		RemoveValidator,
 		SetSellerPayoutAuthority,
 		Proposal,
+		MemberRemoval,
+		AddManager,
+		RemoveManager,
+	}
+
+	/// Origin of the calls dispatched by the passed proposals of a collective
+	#[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen, Debug, TypeInfo, Eq)]
+	pub enum CollectiveOrigin<CollectiveId> {
+		/// A proposal of the collective passed
+		Collective(CollectiveId),
+	}
+
+	#[pallet::origin]
+	pub type Origin<T> = CollectiveOrigin<<T as Config>::CollectiveId>;
+
+	/// Where the weight of the votes of the members of a collective comes from
+	#[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen, Debug, TypeInfo, Eq, Copy, Default)]
+	pub enum VoteWeighting {
+		/// Every member has a weight of one
+		#[default]
+		OneMemberOneVote,
+		/// The native balance of the member, locked until the vote ends
+		LockedBalance,
+		/// The carbon credits retired by the member
+		Retirements,
+		/// The locked native balance counted at `balance_share`, and the retired carbon credits
+		/// counted at the remaining share
+		Mixed { balance_share: Percent },
+	}
+
+	/// Voting duration per vote type
+	pub type VotingDurationsOf<T> = BoundedVec<(VoteType, BlockNumberFor<T>), ConstU32<7>>;
+
+	/// Rules applied when deciding the votes of a collective
+	#[derive(Clone, Encode, Decode, PartialEq, MaxEncodedLen, Debug, TypeInfo, Eq)]
+	#[scale_info(skip_type_params(T))]
+	pub struct VotingPolicy<T: Config> {
+		/// Minimum share of the collective members that must vote for the result to be valid
+		pub min_turnout: Percent,
+		/// Share of the votes cast that the yes votes must exceed for the vote to pass
+		pub approval_threshold: Percent,
+		/// Voting duration of each vote type, types not listed use `VotingDuration`
+		pub voting_durations: VotingDurationsOf<T>,
+		/// Source of the weight of the votes
+		pub weighting: VoteWeighting,
+	}
+
+	impl<T: Config> Default for VotingPolicy<T> {
+		/// No quorum and a simple majority of the votes cast
+		fn default() -> Self {
+			Self {
+				min_turnout: Percent::zero(),
+				approval_threshold: Percent::from_percent(50),
+				voting_durations: Default::default(),
+				weighting: Default::default(),
+			}
+		}
+	}
+
+	impl<T: Config> VotingPolicy<T> {
+		/// The voting duration set for the given vote type, if any
+		pub fn voting_duration(&self, vote_type: VoteType) -> Option<BlockNumberFor<T>> {
+			self.voting_durations.iter().find(|(t, _)| *t == vote_type).map(|(_, d)| *d)
+		}
 	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -173,7 +254,27 @@ pub mod pallet {
 		type MaxConcurrentVotes: Get<u32>;
 		type MaxProjectsPerCollective: Get<u32>;
 		type VotingDuration: Get<BlockNumberFor<Self>>;
-		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		type ForceOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+		/// The overarching origin, must include the collective origin of this pallet
+		type RuntimeOrigin: From<CollectiveOrigin<Self::CollectiveId>>;
+		/// The calls that proposals can dispatch once passed
+		type RuntimeCall: Parameter
+			+ Dispatchable<RuntimeOrigin = <Self as Config>::RuntimeOrigin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo;
+		/// Maximum encoded length of a proposal call
+		#[pallet::constant]
+		type MaxProposalLength: Get<u32>;
+		/// Maximum weight of a proposal call, and of all the proposal calls dispatched in one
+		/// `on_initialize`. Passed proposals that do not fit are dispatched in the next blocks
+		#[pallet::constant]
+		type MaxProposalWeight: Get<Weight>;
+		/// Maximum number of votes a member can have native balance locked in at once
+		#[pallet::constant]
+		type MaxVoteLocks: Get<u32>;
+		/// The native currency locked to back balance weighted votes
+		type NativeCurrency: LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>>;
+		/// Provides the balance and retirements a member votes with
+		type VoteWeight: crate::VoteWeight<Self::AccountId>;
 	}
 
 	// The pallet's runtime storage items.
@@ -250,6 +351,18 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The number of collectives each account manages, managers stay authorized in carbon
+	/// credits while they manage any collective
+	#[pallet::storage]
+	#[pallet::getter(fn get_managed_collectives_count)]
+	pub(super) type ManagedCollectivesCount<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_approved_projects)]
 	pub(super) type ApprovedProjects<T:Config> = StorageMap<
@@ -311,17 +424,110 @@ pub mod pallet {
 	>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn check_member_vote)]
-	pub(super) type CheckMemberVote<T:Config> = StorageDoubleMap<
+	#[pallet::getter(fn get_proposal_call)]
+	pub(super) type ProposalCalls<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::VoteId,
+		BoundedVec<u8, T::MaxProposalLength>,
+		OptionQuery,
+	>;
+
+	/// Passed proposals waiting to be dispatched with the collective that passed them, keyed by
+	/// their position in the dispatch queue
+	#[pallet::storage]
+	#[pallet::getter(fn get_pending_proposal)]
+	pub(super) type PendingProposals<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		(T::VoteId, T::CollectiveId),
+		OptionQuery,
+	>;
+
+	/// The position of the first pending proposal and the position of the next passed proposal
+	#[pallet::storage]
+	#[pallet::getter(fn get_pending_proposals_range)]
+	pub(super) type PendingProposalsRange<T: Config> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// The native balance locked by a member for each vote until it is unlocked
+	#[pallet::storage]
+	#[pallet::getter(fn get_vote_locks)]
+	pub(super) type VoteLocks<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
+		BoundedVec<(T::VoteId, BalanceOf<T>), T::MaxVoteLocks>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_voting_policy)]
+	pub(super) type VotingPolicies<T:Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::CollectiveId,
+		VotingPolicy<T>,
+		ValueQuery,
+	>;
+
+	/// The voting policy of a collective vote, as it was when the vote opened
+	#[pallet::storage]
+	pub(super) type VotePolicies<T:Config> = StorageMap<
+		_,
 		Blake2_128Concat,
 		T::VoteId,
+		VotingPolicy<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_membership_params_info)]
+	pub(super) type MembershipParamsInfo<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::VoteId,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_archived_at)]
+	pub(super) type ArchivedCollectives<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::CollectiveId,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// The members that voted on each vote, cleared once the vote has ended
+	#[pallet::storage]
+	#[pallet::getter(fn check_member_vote)]
+	pub(super) type MemberVotes<T:Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::VoteId,
+		Blake2_128Concat,
+		T::AccountId,
 		bool,
 		ValueQuery,
 	>;
 
+	/// Ended votes whose records in `MemberVotes` are still to be cleared
+	#[pallet::storage]
+	pub(super) type StaleVoteRecords<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::VoteId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Whether the vote records from before version 2 are still being moved to `MemberVotes`
+	#[pallet::storage]
+	pub(super) type MigratingVoteRecords<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 
 	#[pallet::storage]
 	#[pallet::getter(fn collectives_count)]
@@ -344,6 +550,26 @@ pub mod pallet {
 		ProjectApprovalInit { collective_id: T::CollectiveId, project_id: <T as pallet_carbon_credits::Config>::ProjectId},
 		ProjectApprovalVoteCast { collective_id: T::CollectiveId, project_id: <T as pallet_carbon_credits::Config>::ProjectId},
 		ProjectApprovalRemovalInit { collective_id: T::CollectiveId, project_id: <T as pallet_carbon_credits::Config>::ProjectId},
+		/// The voting policy of a collective was updated
+		VotingPolicySet { collective_id: T::CollectiveId, policy: VotingPolicy<T> },
+		/// The call of a passed proposal was dispatched
+		ProposalExecuted { collective_id: T::CollectiveId, vote_id: T::VoteId, result: DispatchResult },
+		/// A member cast a vote with the given weight
+		VoteCast { vote_id: T::VoteId, who: T::AccountId, approve: bool, weight: u128 },
+		/// The balance locked for a vote that has ended was released
+		VoteUnlocked { vote_id: T::VoteId, who: T::AccountId },
+		/// A member left a collective
+		MemberLeft { collective_id: T::CollectiveId, member: T::AccountId },
+		/// A member was removed from a collective
+		MemberRemoved { collective_id: T::CollectiveId, member: T::AccountId },
+		/// A member became a manager of a collective
+		ManagerAdded { collective_id: T::CollectiveId, manager: T::AccountId },
+		/// A manager of a collective was removed
+		ManagerRemoved { collective_id: T::CollectiveId, manager: T::AccountId },
+		/// A vote on the members or managers of a collective was started
+		MembershipVoteInit { collective_id: T::CollectiveId, vote_id: T::VoteId, account: T::AccountId, vote_type: VoteType },
+		/// A collective was archived
+		CollectiveArchived { collective_id: T::CollectiveId },
 	}
 
 	// Errors inform users that something went wrong.
@@ -385,6 +611,34 @@ pub mod pallet {
 		WrongVoteType,
 		/// Params Not Found
 		ParamsNotFound,
+		/// Not Allowed To Manage Collective
+		NotAllowedToManageCollective,
+		/// Invalid Voting Policy
+		InvalidVotingPolicy,
+		/// Proposal Call Too Long
+		ProposalTooLong,
+		/// Proposal Call Weight Too High
+		ProposalWeightTooHigh,
+		/// Proposal Call Cannot Be Decoded
+		UndecodableProposal,
+		/// No Vote Weight
+		NoVoteWeight,
+		/// Vote In Progress
+		VoteInProgress,
+		/// Vote Lock Not Found
+		VoteLockNotFound,
+		/// Max Vote Locks Exceeded
+		MaxVoteLocksExceeded,
+		/// Collective Archived
+		CollectiveArchived,
+		/// Already A Manager
+		AlreadyManager,
+		/// Not A Manager
+		NotManager,
+		/// Cannot Remove The Last Manager
+		LastManager,
+		/// Max Managers Exceeded
+		MaxManagersExceeded,
 	}
 
 	#[pallet::hooks]
@@ -395,17 +649,16 @@ pub mod pallet {
 			let approval = ActiveVoting::<T>::take(n);
 
 			for v_id in approval.iter() {
-				weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(5, 4));
 				let vote = ProjectVote::<T>::take(v_id);
+				// the votes of the members are only needed while the vote is ongoing
+				StaleVoteRecords::<T>::insert(v_id, ());
 
 				if let Some(mut vote) = vote {
-					let mut is_approved: bool = false;
-					if vote.yes_votes > vote.no_votes {
-						vote.status = VoteStatus::Passed;
-						is_approved = true;						
-					} else {
-						vote.status = VoteStatus::Failed;						
-					}
+					let policy = Self::vote_policy(*v_id, &vote);
+					VotePolicies::<T>::remove(v_id);
+					vote.status = Self::tally_vote(&vote, &policy);
+					let is_approved = vote.status == VoteStatus::Passed;
 
 					match vote.vote_type {
 						VoteType::ProjectApproval => {
@@ -426,7 +679,12 @@ pub mod pallet {
 						VoteType::SetSellerPayoutAuthority=> {
 							let _ = Self::do_add_validator(*v_id,is_approved);	
 						},
-						_ => ()
+						VoteType::Proposal => {
+							Self::do_queue_proposal(*v_id,vote.collective_id,is_approved);
+						},
+						VoteType::MemberRemoval | VoteType::AddManager | VoteType::RemoveManager => {
+							let _ = Self::do_change_membership(*v_id,vote.collective_id,vote.vote_type,is_approved);
+						},
 					}
 
 					ProjectVote::<T>::insert(v_id,&vote);
@@ -435,7 +693,12 @@ pub mod pallet {
 
 			}
 
-			weight
+			weight.saturating_add(Self::do_execute_pending_proposals())
+		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used = crate::migration::v2::migrate_vote_records::<T>(remaining_weight);
+			used.saturating_add(Self::clear_vote_records(remaining_weight.saturating_sub(used)))
 		}
 	}
 
@@ -467,7 +730,7 @@ pub mod pallet {
 			let mut mid = Self::get_membership_count(uid.clone());
 
 			for manager in managers {
-				let _ = Self::do_authorize_account(manager.clone());
+				let _ = Self::do_add_manager_role(&manager);
 				mid = mid.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 				Members::<T>::insert(uid.clone(),manager.clone(),true);
 				MembersCount::<T>::insert(uid.clone(),mid);
@@ -484,7 +747,7 @@ pub mod pallet {
 			let member = ensure_signed(origin)?;
 			Self::check_kyc_approval(&member)?;
 			ensure!(!Self::check_member(collective_id,member.clone()),Error::<T>::MemberAlreadyExists);
-			ensure!(Self::get_collective(collective_id).is_some(),Error::<T>::CollectiveDoesNotExist);
+			Self::ensure_active_collective(collective_id)?;
 			let uid = Self::get_membership_count(collective_id.clone());
 			let uid2 = uid.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 			Members::<T>::insert(collective_id.clone(),member.clone(),true);
@@ -499,25 +762,22 @@ pub mod pallet {
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_member())]
 		pub fn add_member(origin: OriginFor<T>, collective_id: T::CollectiveId, member: T::AccountId) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(Self::get_collective(collective_id).is_some(),Error::<T>::CollectiveDoesNotExist);
+			Self::ensure_active_collective(collective_id)?;
 			ensure!(!Members::<T>::contains_key(collective_id.clone(),&member.clone()), Error::<T>::MemberAlreadyExists);
-			let managers = Managers::<T>::get(collective_id.clone());
-			
-			match managers.binary_search(&who) {
-				Ok(_) => {
-					let uid = Self::get_membership_count(collective_id.clone());
-					let uid2 = uid.checked_add(1).ok_or(ArithmeticError::Overflow)?;
-					Members::<T>::insert(collective_id.clone(),member.clone(),true);
-					MembersCount::<T>::insert(collective_id.clone(),uid2);
-			
-					Self::deposit_event(Event::MemberAdded{ collective_id, member, uid });
+			// managers added by vote are appended, the list is not sorted
+			ensure!(
+				Managers::<T>::get(collective_id.clone()).contains(&who),
+				Error::<T>::NotAllowedToManageMembership
+			);
 
-					Ok(())
-				},
-				Err(_) => Err(Error::<T>::NotAllowedToManageMembership.into()),
+			let uid = Self::get_membership_count(collective_id.clone());
+			let uid2 = uid.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			Members::<T>::insert(collective_id.clone(),member.clone(),true);
+			MembersCount::<T>::insert(collective_id.clone(),uid2);
 
-			}
-			
+			Self::deposit_event(Event::MemberAdded{ collective_id, member, uid });
+
+			Ok(())
 		}
 
 		#[pallet::call_index(3)]
@@ -526,7 +786,7 @@ pub mod pallet {
 		project_id: <T as pallet_carbon_credits::Config>::ProjectId, vote_type: VoteType) -> DispatchResult {
 			
 			let who = ensure_signed(origin)?;
-			ensure!(Self::get_collective(collective_id).is_some(),Error::<T>::CollectiveDoesNotExist);
+			Self::ensure_active_collective(collective_id)?;
 			ensure!(Members::<T>::contains_key(collective_id.clone(),&who.clone()), Error::<T>::MemberDoesNotExist);
 			ensure!(vote_type == VoteType::ProjectApproval || vote_type == VoteType::ProjectRemoval,
 				Error::<T>::WrongVoteType);
@@ -541,7 +801,7 @@ pub mod pallet {
 			
 			let current_block = <frame_system::Pallet<T>>::block_number();
 
-			let final_block = current_block + T::VotingDuration::get();
+			let final_block = current_block + Self::voting_duration(Some(collective_id), vote_type);
 
 			ActiveVoting::<T>::try_mutate(final_block, |projects| {
 				projects.try_push(uid).map_err(|_| Error::<T>::MaxVotingExceeded)?;
@@ -551,6 +811,8 @@ pub mod pallet {
 			let vote_info = Vote::<T> {
 				yes_votes: 0,
 				no_votes: 0,
+				yes_weight: 0,
+				no_weight: 0,
 				end: final_block,
 				status: VoteStatus::Deciding,
 				vote_type: VoteType::ProjectApproval,
@@ -559,6 +821,7 @@ pub mod pallet {
 			};
 
 			ProjectVote::<T>::insert(uid,&vote_info);
+			VotePolicies::<T>::insert(uid, Self::get_voting_policy(collective_id));
 			let uid2 = uid.checked_add(&1u32.into()).ok_or(ArithmeticError::Overflow)?;
 			VotesCount::<T>::put(uid2);
 
@@ -568,7 +831,7 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(4)]
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_collective())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::cast_vote(T::MaxVoteLocks::get()))]
 		pub fn cast_vote(origin: OriginFor<T>,vote_id: T::VoteId, 
 		vote_cast: bool) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -586,16 +849,23 @@ pub mod pallet {
 			// Check if vote is in progress
 			ensure!(vote.status == VoteStatus::Deciding, Error::<T>::VoteNotInProgress);
 			// Check if member has already voted
-			ensure!(!Self::check_member_vote(who.clone(),vote_id), Error::<T>::AlreadyVoted);
+			ensure!(!Self::has_voted(vote_id, &who), Error::<T>::AlreadyVoted);
+
+			let weighting = Self::vote_policy(vote_id, &vote).weighting;
+			let weight = Self::lock_vote_weight(&who, vote_id, weighting)?;
 
 			if vote_cast {
 				vote.yes_votes = vote.yes_votes + 1;
+				vote.yes_weight = vote.yes_weight.saturating_add(weight);
 			} else {
 				vote.no_votes = vote.no_votes + 1;
+				vote.no_weight = vote.no_weight.saturating_add(weight);
 			}
 
 			ProjectVote::<T>::insert(vote_id,vote);
-			CheckMemberVote::<T>::insert(who.clone(),vote_id,true);
+			MemberVotes::<T>::insert(vote_id,who.clone(),true);
+
+			Self::deposit_event(Event::VoteCast { vote_id, who, approve: vote_cast, weight });
 
 			//Self::deposit_event(Event::ProjectApprovalVoteCast{ collective_id, project_id });
 			
@@ -628,6 +898,8 @@ pub mod pallet {
 			let vote_info = Vote::<T> {
 				yes_votes: 0,
 				no_votes: 0,
+				yes_weight: 0,
+				no_weight: 0,
 				end: final_block,
 				status: VoteStatus::Deciding,
 				vote_type: VoteType::PoolCreation,
@@ -675,6 +947,8 @@ pub mod pallet {
 			let vote_info = Vote::<T> {
 				yes_votes: 0,
 				no_votes: 0,
+				yes_weight: 0,
+				no_weight: 0,
 				end: final_block,
 				status: VoteStatus::Deciding,
 				vote_type: vote_type,
@@ -695,19 +969,22 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(7)]
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::add_collective())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::create_proposal(
+			call.as_ref().map_or(0, |call| call.encoded_size() as u32)
+		))]
 		pub fn create_proposal(origin: OriginFor<T>, collective_id: T::CollectiveId,
-			proposal_hash: BoundedVec<u8, T::MaxStringLength>) -> DispatchResult {
+			proposal_hash: BoundedVec<u8, T::MaxStringLength>,
+			call: Option<Box<<T as Config>::RuntimeCall>>) -> DispatchResult {
 			
 			let who = ensure_signed(origin)?;
-			ensure!(Self::get_collective(collective_id).is_some(),Error::<T>::CollectiveDoesNotExist);
+			Self::ensure_active_collective(collective_id)?;
 			ensure!(Members::<T>::contains_key(collective_id.clone(),&who.clone()), Error::<T>::MemberDoesNotExist);
 
 			let uid = Self::votes_count();
 		
 			let current_block = <frame_system::Pallet<T>>::block_number();
 
-			let final_block = current_block + T::VotingDuration::get();
+			let final_block = current_block + Self::voting_duration(Some(collective_id), VoteType::Proposal);
 
 			ActiveVoting::<T>::try_mutate(final_block, |projects| {
 				projects.try_push(uid).map_err(|_| Error::<T>::MaxVotingExceeded)?;
@@ -717,6 +994,8 @@ pub mod pallet {
 			let vote_info = Vote::<T> {
 				yes_votes: 0,
 				no_votes: 0,
+				yes_weight: 0,
+				no_weight: 0,
 				end: final_block,
 				status: VoteStatus::Deciding,
 				vote_type: VoteType::Proposal,
@@ -724,6 +1003,17 @@ pub mod pallet {
 				project_id: None,
 			};
 
+			// the call is dispatched with the collective origin once the proposal passes
+			if let Some(call) = call {
+				ensure!(
+					call.get_dispatch_info().weight.all_lte(T::MaxProposalWeight::get()),
+					Error::<T>::ProposalWeightTooHigh
+				);
+				let encoded_call: BoundedVec<u8, T::MaxProposalLength> =
+					call.encode().try_into().map_err(|_| Error::<T>::ProposalTooLong)?;
+				ProposalCalls::<T>::insert(uid, encoded_call);
+			}
+
 			let proposal_info = Proposal::<T> {
 				creator: who.clone(),
 				hash: proposal_hash,
@@ -734,6 +1024,7 @@ pub mod pallet {
 
 
 			ProjectVote::<T>::insert(uid,&vote_info);
+			VotePolicies::<T>::insert(uid, Self::get_voting_policy(collective_id));
 			Proposals::<T>::insert(collective_id,proposal_count,&proposal_info);
 
 			let uid2 = uid.checked_add(&1u32.into()).ok_or(ArithmeticError::Overflow)?;
@@ -747,6 +1038,183 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Set the voting policy of a collective, it applies to the votes created afterwards, votes
+		/// in progress are still decided with the policy they opened with.
+		/// Can be called by a manager of the collective or by `ForceOrigin`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_voting_policy())]
+		pub fn set_voting_policy(
+			origin: OriginFor<T>,
+			collective_id: T::CollectiveId,
+			policy: VotingPolicy<T>,
+		) -> DispatchResult {
+			if <T as pallet::Config>::ForceOrigin::ensure_origin(origin.clone()).is_err() {
+				let who = ensure_signed(origin)?;
+				ensure!(
+					Managers::<T>::get(collective_id).contains(&who),
+					Error::<T>::NotAllowedToManageCollective
+				);
+			}
+			ensure!(Self::get_collective(collective_id).is_some(), Error::<T>::CollectiveDoesNotExist);
+
+			// a threshold of 100% could never be exceeded
+			ensure!(
+				policy.approval_threshold < Percent::from_percent(100),
+				Error::<T>::InvalidVotingPolicy
+			);
+			for (index, (vote_type, duration)) in policy.voting_durations.iter().enumerate() {
+				ensure!(
+					matches!(
+						vote_type,
+						VoteType::ProjectApproval |
+							VoteType::ProjectRemoval |
+							VoteType::Proposal |
+							VoteType::MemberRemoval |
+							VoteType::AddManager |
+							VoteType::RemoveManager
+					),
+					Error::<T>::WrongVoteType
+				);
+				ensure!(!duration.is_zero(), Error::<T>::InvalidVotingPolicy);
+				ensure!(
+					!policy.voting_durations[..index].iter().any(|(t, _)| t == vote_type),
+					Error::<T>::InvalidVotingPolicy
+				);
+			}
+
+			VotingPolicies::<T>::insert(collective_id, &policy);
+			Self::deposit_event(Event::VotingPolicySet { collective_id, policy });
+			Ok(())
+		}
+
+		/// Release the native balance locked by the caller for a vote that has ended
+		#[pallet::call_index(9)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unlock_vote(T::MaxVoteLocks::get()))]
+		pub fn unlock_vote(origin: OriginFor<T>, vote_id: T::VoteId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let vote = Self::get_project_vote(vote_id).ok_or(Error::<T>::VoteNotFound)?;
+			ensure!(vote.status != VoteStatus::Deciding, Error::<T>::VoteInProgress);
+			let mut locks = VoteLocks::<T>::get(&who);
+			let index = locks
+				.iter()
+				.position(|(id, _)| *id == vote_id)
+				.ok_or(Error::<T>::VoteLockNotFound)?;
+			locks.remove(index);
+
+			// the lock keeps covering the largest amount locked by the other votes of the member
+			match locks.iter().map(|(_, amount)| *amount).max() {
+				Some(amount) => <T as Config>::NativeCurrency::set_lock(
+					COLLECTIVE_VOTE_LOCK,
+					&who,
+					amount,
+					WithdrawReasons::all(),
+				),
+				None => <T as Config>::NativeCurrency::remove_lock(COLLECTIVE_VOTE_LOCK, &who),
+			}
+			VoteLocks::<T>::insert(&who, locks);
+
+			Self::deposit_event(Event::VoteUnlocked { vote_id, who });
+			Ok(())
+		}
+
+		/// Leave a collective, a manager can only leave if another manager remains
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::leave_collective())]
+		pub fn leave_collective(origin: OriginFor<T>, collective_id: T::CollectiveId) -> DispatchResult {
+			let member = ensure_signed(origin)?;
+			Self::do_remove_member(collective_id, &member)?;
+			Self::deposit_event(Event::MemberLeft { collective_id, member });
+			Ok(())
+		}
+
+		/// Remove a member from a collective, can be called by a manager of the collective or by
+		/// `ForceOrigin`. Managers can only be removed by `ForceOrigin` or by a collective vote.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_member())]
+		pub fn remove_member(
+			origin: OriginFor<T>,
+			collective_id: T::CollectiveId,
+			member: T::AccountId,
+		) -> DispatchResult {
+			if <T as pallet::Config>::ForceOrigin::ensure_origin(origin.clone()).is_err() {
+				let who = ensure_signed(origin)?;
+				let managers = Managers::<T>::get(collective_id);
+				ensure!(managers.contains(&who), Error::<T>::NotAllowedToManageMembership);
+				ensure!(!managers.contains(&member), Error::<T>::NotAllowedToManageMembership);
+			}
+			Self::do_remove_member(collective_id, &member)?;
+			Self::deposit_event(Event::MemberRemoved { collective_id, member });
+			Ok(())
+		}
+
+		/// Start a vote of the collective to remove a member, to make a member a manager or to
+		/// remove a manager
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::init_membership_vote())]
+		pub fn init_membership_vote(
+			origin: OriginFor<T>,
+			collective_id: T::CollectiveId,
+			account: T::AccountId,
+			vote_type: VoteType,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_active_collective(collective_id)?;
+			ensure!(Members::<T>::contains_key(collective_id, &who), Error::<T>::MemberDoesNotExist);
+			ensure!(
+				Members::<T>::contains_key(collective_id, &account),
+				Error::<T>::MemberDoesNotExist
+			);
+			let is_manager = Managers::<T>::get(collective_id).contains(&account);
+			match vote_type {
+				VoteType::MemberRemoval => (),
+				VoteType::AddManager => ensure!(!is_manager, Error::<T>::AlreadyManager),
+				VoteType::RemoveManager => ensure!(is_manager, Error::<T>::NotManager),
+				_ => return Err(Error::<T>::WrongVoteType.into()),
+			}
+
+			let uid = Self::votes_count();
+			let current_block = <frame_system::Pallet<T>>::block_number();
+			let final_block = current_block + Self::voting_duration(Some(collective_id), vote_type);
+
+			ActiveVoting::<T>::try_mutate(final_block, |votes| {
+				votes.try_push(uid).map_err(|_| Error::<T>::MaxVotingExceeded)?;
+				Ok::<(), DispatchError>(())
+			})?;
+
+			let vote_info = Vote::<T> {
+				yes_votes: 0,
+				no_votes: 0,
+				yes_weight: 0,
+				no_weight: 0,
+				end: final_block,
+				status: VoteStatus::Deciding,
+				vote_type,
+				collective_id: Some(collective_id),
+				project_id: None,
+			};
+
+			ProjectVote::<T>::insert(uid, &vote_info);
+			VotePolicies::<T>::insert(uid, Self::get_voting_policy(collective_id));
+			MembershipParamsInfo::<T>::insert(uid, &account);
+			let uid2 = uid.checked_add(&1u32.into()).ok_or(ArithmeticError::Overflow)?;
+			VotesCount::<T>::put(uid2);
+
+			Self::deposit_event(Event::MembershipVoteInit { collective_id, vote_id: uid, account, vote_type });
+			Ok(())
+		}
+
+		/// Archive a collective, it can no longer gain members or start votes. Ongoing votes are
+		/// still decided.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::archive_collective())]
+		pub fn archive_collective(origin: OriginFor<T>, collective_id: T::CollectiveId) -> DispatchResult {
+			<T as pallet::Config>::ForceOrigin::ensure_origin(origin)?;
+			Self::ensure_active_collective(collective_id)?;
+			ArchivedCollectives::<T>::insert(collective_id, <frame_system::Pallet<T>>::block_number());
+			Self::deposit_event(Event::CollectiveArchived { collective_id });
+			Ok(())
+		}
+
 	}
 
 	impl<T:Config> Pallet<T> {
@@ -851,6 +1319,150 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Ensure the collective exists and has not been archived
+		pub fn ensure_active_collective(collective_id: T::CollectiveId) -> DispatchResult {
+			ensure!(Self::get_collective(collective_id).is_some(), Error::<T>::CollectiveDoesNotExist);
+			ensure!(
+				!ArchivedCollectives::<T>::contains_key(collective_id),
+				Error::<T>::CollectiveArchived
+			);
+			Ok(())
+		}
+
+		/// Remove `member` from the collective and from its managers, at least one manager must
+		/// remain. The vote records of the member for the ended votes of the collective are
+		/// cleaned, the ones of ongoing votes are kept so the member cannot rejoin and vote twice.
+		pub fn do_remove_member(collective_id: T::CollectiveId, member: &T::AccountId) -> DispatchResult {
+			ensure!(Members::<T>::contains_key(collective_id, member), Error::<T>::MemberDoesNotExist);
+
+			let mut managers = Managers::<T>::get(collective_id);
+			if managers.contains(member) {
+				ensure!(managers.len() > 1, Error::<T>::LastManager);
+				managers.retain(|manager| manager != member);
+				Managers::<T>::insert(collective_id, managers);
+				Self::do_remove_manager_role(member)?;
+			}
+
+			Members::<T>::remove(collective_id, member);
+			MembersCount::<T>::mutate(collective_id, |count| *count = count.saturating_sub(1));
+
+			Ok(())
+		}
+
+		/// Apply a passed vote on the members or managers of a collective
+		pub fn do_change_membership(vote_id: T::VoteId, coll_id: Option<T::CollectiveId>,
+			vote_type: VoteType, is_approved: bool) -> DispatchResult {
+			let account = MembershipParamsInfo::<T>::take(vote_id).ok_or(Error::<T>::ParamsNotFound)?;
+			let collective_id = match coll_id {
+				Some(x) if is_approved => x,
+				_ => return Ok(()),
+			};
+
+			match vote_type {
+				VoteType::MemberRemoval => {
+					Self::do_remove_member(collective_id, &account)?;
+					Self::deposit_event(Event::MemberRemoved { collective_id, member: account });
+				},
+				VoteType::AddManager => {
+					// the member may have left while the vote was ongoing
+					ensure!(
+						Members::<T>::contains_key(collective_id, &account),
+						Error::<T>::MemberDoesNotExist
+					);
+					Managers::<T>::try_mutate(collective_id, |managers| {
+						ensure!(!managers.contains(&account), Error::<T>::AlreadyManager);
+						managers.try_push(account.clone()).map_err(|_| Error::<T>::MaxManagersExceeded)?;
+						Ok::<(), DispatchError>(())
+					})?;
+					let _ = Self::do_add_manager_role(&account);
+					Self::deposit_event(Event::ManagerAdded { collective_id, manager: account });
+				},
+				VoteType::RemoveManager => {
+					Managers::<T>::try_mutate(collective_id, |managers| {
+						ensure!(managers.contains(&account), Error::<T>::NotManager);
+						ensure!(managers.len() > 1, Error::<T>::LastManager);
+						managers.retain(|manager| *manager != account);
+						Ok::<(), DispatchError>(())
+					})?;
+					Self::do_remove_manager_role(&account)?;
+					Self::deposit_event(Event::ManagerRemoved { collective_id, manager: account });
+				},
+				_ => return Err(Error::<T>::WrongVoteType.into()),
+			}
+
+			Ok(())
+		}
+
+		/// Queue the call of a passed proposal for dispatch after the proposals that passed
+		/// before it, drop the call of a failed one
+		pub fn do_queue_proposal(vote_id: T::VoteId, coll_id: Option<T::CollectiveId>,
+			is_approved: bool) {
+			match coll_id {
+				Some(collective_id) if is_approved && ProposalCalls::<T>::contains_key(vote_id) => {
+					PendingProposalsRange::<T>::mutate(|(_, next)| {
+						PendingProposals::<T>::insert(*next, (vote_id, collective_id));
+						*next = next.wrapping_add(1);
+					});
+				},
+				_ => ProposalCalls::<T>::remove(vote_id),
+			}
+		}
+
+		/// Dispatch the pending proposal calls in the order they passed until the next call would
+		/// exceed `MaxProposalWeight` for this block, the rest stays pending for the next blocks
+		pub fn do_execute_pending_proposals() -> Weight {
+			let budget = T::MaxProposalWeight::get();
+			let mut used = Weight::zero();
+			let mut weight = T::DbWeight::get().reads(1);
+			let (start, next) = PendingProposalsRange::<T>::get();
+			let mut first = start;
+
+			while first != next {
+				weight = weight.saturating_add(T::DbWeight::get().reads(2));
+				let (vote_id, collective_id) = match PendingProposals::<T>::get(first) {
+					Some(pending) => pending,
+					None => {
+						first = first.wrapping_add(1);
+						continue
+					},
+				};
+				let call = ProposalCalls::<T>::get(vote_id)
+					.map(|encoded_call| <T as Config>::RuntimeCall::decode(&mut &encoded_call[..]));
+
+				let call_weight = match &call {
+					Some(Ok(call)) => call.get_dispatch_info().weight,
+					_ => Weight::zero(),
+				};
+				// the first call of a block always goes through, so a call heavier than a lowered
+				// `MaxProposalWeight` cannot hold up the queue
+				if used != Weight::zero() && !used.saturating_add(call_weight).all_lte(budget) {
+					break
+				}
+				used = used.saturating_add(call_weight);
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
+				PendingProposals::<T>::remove(first);
+				ProposalCalls::<T>::remove(vote_id);
+				first = first.wrapping_add(1);
+
+				let result = match call {
+					Some(Ok(call)) => call.dispatch(CollectiveOrigin::Collective(collective_id).into())
+						.map(|_| ())
+						.map_err(|e| e.error),
+					Some(Err(_)) => Err(Error::<T>::UndecodableProposal.into()),
+					None => continue,
+				};
+
+				Self::deposit_event(Event::ProposalExecuted { collective_id, vote_id, result });
+			}
+
+			if first != start {
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+				PendingProposalsRange::<T>::put((first, next));
+			}
+
+			weight.saturating_add(used)
+		}
+
 		pub fn do_authorize_account(account: T::AccountId) -> DispatchResult {
 			if !Self::check_authorized_account(&account.clone()) {
 				pallet_carbon_credits::Pallet::<T>::force_add_authorized_account(frame_system::RawOrigin::Root.into(),account)?;
@@ -859,6 +1471,28 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Count one more collective managed by `manager` and authorize it in carbon credits
+		pub fn do_add_manager_role(manager: &T::AccountId) -> DispatchResult {
+			ManagedCollectivesCount::<T>::mutate(manager, |count| *count = count.saturating_add(1));
+			Self::do_authorize_account(manager.clone())
+		}
+
+		/// Count one less collective managed by `manager`, its authorization in carbon credits
+		/// is removed once it manages no collective
+		pub fn do_remove_manager_role(manager: &T::AccountId) -> DispatchResult {
+			let count = Self::get_managed_collectives_count(manager).saturating_sub(1);
+			if count > 0 {
+				ManagedCollectivesCount::<T>::insert(manager, count);
+				return Ok(())
+			}
+
+			ManagedCollectivesCount::<T>::remove(manager);
+			pallet_carbon_credits::Pallet::<T>::force_remove_authorized_account(
+				frame_system::RawOrigin::Root.into(),
+				manager.clone(),
+			)
+		}
+
 		pub fn check_authorized_account(account_id: &T::AccountId) -> bool {
 			let authorized_accounts = pallet_carbon_credits::AuthorizedAccounts::<T>::get();
 			if authorized_accounts.contains(account_id) {
@@ -868,6 +1502,125 @@ pub mod pallet {
 			}
 		}
 
+		/// The voting duration of a new vote, taken from the policy of the collective when set
+		pub fn voting_duration(
+			collective_id: Option<T::CollectiveId>,
+			vote_type: VoteType,
+		) -> BlockNumberFor<T> {
+			collective_id
+				.and_then(|id| Self::get_voting_policy(id).voting_duration(vote_type))
+				.unwrap_or_else(T::VotingDuration::get)
+		}
+
+		/// The voting policy a vote is decided with. Collective votes keep the policy of the
+		/// collective from when they opened, so a new policy only applies to later votes.
+		pub fn vote_policy(vote_id: T::VoteId, vote: &Vote<T>) -> VotingPolicy<T> {
+			VotePolicies::<T>::get(vote_id)
+				.or_else(|| vote.collective_id.map(Self::get_voting_policy))
+				.unwrap_or_default()
+		}
+
+		/// Decide the outcome of a vote that has ended with its voting policy.
+		/// Votes of a collective must reach the minimum turnout of the policy, measured in members
+		/// against the current number of members, then the weight of the yes votes must exceed the
+		/// approval threshold of the total weight cast. Votes outside a collective only need a
+		/// simple majority.
+		pub fn tally_vote(vote: &Vote<T>, policy: &VotingPolicy<T>) -> VoteStatus {
+			let turnout = vote.yes_votes.saturating_add(vote.no_votes) as u128;
+
+			if let Some(collective_id) = vote.collective_id {
+				let members = MembersCount::<T>::get(collective_id) as u128;
+				if turnout * 100 < policy.min_turnout.deconstruct() as u128 * members {
+					return VoteStatus::QuorumNotMet
+				}
+			}
+
+			let total_weight = vote.yes_weight.saturating_add(vote.no_weight);
+			if vote.yes_weight.saturating_mul(100) >
+				(policy.approval_threshold.deconstruct() as u128).saturating_mul(total_weight)
+			{
+				VoteStatus::Passed
+			} else {
+				VoteStatus::Failed
+			}
+		}
+
+		/// Whether `who` voted on the vote, also checks the records not yet moved to version 2
+		pub fn has_voted(vote_id: T::VoteId, who: &T::AccountId) -> bool {
+			Self::check_member_vote(vote_id, who) ||
+				(MigratingVoteRecords::<T>::get() &&
+					crate::migration::v2::CheckMemberVote::<T>::get(who, vote_id))
+		}
+
+		/// Clear the records of the members that voted on the ended votes, within `limit`
+		pub fn clear_vote_records(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// reading the next ended vote and removing it once cleared
+			let per_vote = db_weight.reads_writes(1, 1);
+			// reading and removing the record of a member
+			let per_record = db_weight.reads_writes(1, 1);
+			let mut used = Weight::zero();
+
+			'votes: loop {
+				if used.saturating_add(per_vote).any_gt(limit) {
+					break
+				}
+				used.saturating_accrue(per_vote);
+				let Some(vote_id) = StaleVoteRecords::<T>::iter_keys().next() else { break };
+
+				let mut voters = MemberVotes::<T>::iter_key_prefix(vote_id);
+				loop {
+					if used.saturating_add(per_record).any_gt(limit) {
+						break 'votes
+					}
+					let Some(who) = voters.next() else { break };
+					used.saturating_accrue(per_record);
+					MemberVotes::<T>::remove(vote_id, &who);
+				}
+				StaleVoteRecords::<T>::remove(vote_id);
+			}
+
+			used
+		}
+
+		/// The weight of the vote of `who` under `weighting`, the native balance counted is locked
+		/// until the member unlocks it once the vote has ended
+		pub fn lock_vote_weight(
+			who: &T::AccountId,
+			vote_id: T::VoteId,
+			weighting: VoteWeighting,
+		) -> Result<u128, DispatchError> {
+			let balance = match weighting {
+				VoteWeighting::LockedBalance | VoteWeighting::Mixed { .. } =>
+					<T as Config>::VoteWeight::lockable_balance(who),
+				_ => 0,
+			};
+			let weight = match weighting {
+				VoteWeighting::OneMemberOneVote => 1,
+				VoteWeighting::LockedBalance => balance,
+				VoteWeighting::Retirements => <T as Config>::VoteWeight::retired_credits(who),
+				VoteWeighting::Mixed { balance_share } => (balance_share * balance).saturating_add(
+					balance_share.left_from_one() * <T as Config>::VoteWeight::retired_credits(who),
+				),
+			};
+			ensure!(!weight.is_zero(), Error::<T>::NoVoteWeight);
+
+			// the whole balance backing the vote is locked, also when only a share of it counts
+			if !balance.is_zero() {
+				let amount: BalanceOf<T> = balance.saturated_into();
+				VoteLocks::<T>::try_mutate(who, |locks| locks.try_push((vote_id, amount)))
+					.map_err(|_| Error::<T>::MaxVoteLocksExceeded)?;
+				<T as Config>::NativeCurrency::extend_lock(
+					COLLECTIVE_VOTE_LOCK,
+					who,
+					amount,
+					WithdrawReasons::all(),
+				);
+			}
+
+			Ok(weight)
+		}
+
 		/// The account ID of the ForestaCollectives pallet
 		pub fn account_id() -> T::AccountId {
 			<T as pallet::Config>::PalletId::get().into_account_truncating()
@@ -876,3 +1629,85 @@ pub mod pallet {
 	}
 }
 
+/// Ensure the origin is any collective, returns the id of the collective
+pub struct EnsureCollective<O, CollectiveId>(sp_std::marker::PhantomData<(O, CollectiveId)>);
+
+impl<O, CollectiveId> frame_support::traits::EnsureOrigin<O> for EnsureCollective<O, CollectiveId>
+where
+	O: Into<Result<CollectiveOrigin<CollectiveId>, O>> + From<CollectiveOrigin<CollectiveId>>,
+	CollectiveId: Default,
+{
+	type Success = CollectiveId;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|CollectiveOrigin::Collective(collective_id)| collective_id)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		Ok(O::from(CollectiveOrigin::Collective(Default::default())))
+	}
+}
+
+/// Ensure the origin is the collective with the given id
+pub struct EnsureCollectiveId<O, CollectiveId, Id>(
+	sp_std::marker::PhantomData<(O, CollectiveId, Id)>,
+);
+
+impl<O, CollectiveId, Id> frame_support::traits::EnsureOrigin<O>
+	for EnsureCollectiveId<O, CollectiveId, Id>
+where
+	O: Into<Result<CollectiveOrigin<CollectiveId>, O>> + From<CollectiveOrigin<CollectiveId>>,
+	CollectiveId: PartialEq,
+	Id: frame_support::traits::Get<CollectiveId>,
+{
+	type Success = CollectiveId;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			CollectiveOrigin::Collective(collective_id) if collective_id == Id::get() =>
+				Ok(collective_id),
+			o => Err(O::from(o)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		Ok(O::from(CollectiveOrigin::Collective(Id::get())))
+	}
+}
+
+/// Provides the sources of the voting weight of collective members
+pub trait VoteWeight<AccountId> {
+	/// The native balance of `who` that can be locked to back a vote
+	fn lockable_balance(who: &AccountId) -> u128;
+	/// The amount of carbon credits retired by `who`
+	fn retired_credits(who: &AccountId) -> u128;
+}
+
+impl<AccountId> VoteWeight<AccountId> for () {
+	fn lockable_balance(_who: &AccountId) -> u128 {
+		0
+	}
+
+	fn retired_credits(_who: &AccountId) -> u128 {
+		0
+	}
+}
+
+/// Weighs votes with the free native balance and the carbon credits retired in
+/// pallet-carbon-credits
+pub struct NativeBalanceAndRetirements<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> VoteWeight<T::AccountId> for NativeBalanceAndRetirements<T> {
+	fn lockable_balance(who: &T::AccountId) -> u128 {
+		use frame_support::traits::Currency;
+		use sp_runtime::SaturatedConversion;
+		<T as Config>::NativeCurrency::free_balance(who).saturated_into()
+	}
+
+	fn retired_credits(who: &T::AccountId) -> u128 {
+		use sp_runtime::SaturatedConversion;
+		pallet_carbon_credits::RetiredCreditsByAccount::<T>::get(who).saturated_into()
+	}
+}
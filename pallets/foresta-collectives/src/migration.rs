@@ -0,0 +1,183 @@
+use super::*;
+
+pub mod v1 {
+	use super::*;
+
+	use codec::{Decode, Encode};
+	use frame_support::{
+		pallet_prelude::*,
+		sp_std::marker::PhantomData,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+	use frame_system::pallet_prelude::BlockNumberFor;
+
+	/// The vote before version 1, without the weight of the votes cast
+	#[derive(Encode, Decode)]
+	pub struct OldVote<T: Config> {
+		pub yes_votes: u64,
+		pub no_votes: u64,
+		pub end: BlockNumberFor<T>,
+		pub status: VoteStatus,
+		pub vote_type: VoteType,
+		pub collective_id: Option<T::CollectiveId>,
+		pub project_id: Option<<T as pallet_carbon_credits::Config>::ProjectId>,
+	}
+
+	/// Add the weight of the votes cast to the existing votes.
+	///
+	/// The votes were cast with one vote per member, so the weights are the vote counts.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 1 {
+				log::info!("foresta-collectives migration to v1 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut votes = 0_u64;
+			ProjectVote::<T>::translate_values::<OldVote<T>, _>(|old| {
+				votes += 1;
+				Some(Vote::<T> {
+					yes_votes: old.yes_votes,
+					no_votes: old.no_votes,
+					yes_weight: old.yes_votes.into(),
+					no_weight: old.no_votes.into(),
+					end: old.end,
+					status: old.status,
+					vote_type: old.vote_type,
+					collective_id: old.collective_id,
+					project_id: old.project_id,
+				})
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!("foresta-collectives migration to v1 done, {} votes", votes);
+
+			T::DbWeight::get().reads_writes(votes + 1, votes + 1)
+		}
+	}
+}
+
+pub mod v2 {
+	use super::*;
+
+	use frame_support::{
+		pallet_prelude::*,
+		sp_std::marker::PhantomData,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	/// The records of the votes before version 2, keyed by member first
+	#[frame_support::storage_alias]
+	pub(crate) type CheckMemberVote<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		<T as Config>::VoteId,
+		bool,
+		ValueQuery,
+	>;
+
+	/// Key the records of the votes by vote first, so they can be cleared once the vote ends.
+	///
+	/// Only starts the migration, the records are moved in bounded steps on idle by
+	/// [`migrate_vote_records`]. Until they are all moved `cast_vote` also checks the old records.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 2 {
+				log::info!("foresta-collectives migration to v2 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			MigratingVoteRecords::<T>::put(true);
+			StorageVersion::new(2).put::<Pallet<T>>();
+			log::info!(
+				"foresta-collectives migration to v2 started, vote records are moved on idle"
+			);
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+	}
+
+	/// Move the records of the votes to `MemberVotes` within `limit`. The records of the ongoing
+	/// votes are kept, the records of the ended votes are dropped.
+	pub fn migrate_vote_records<T: Config>(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// reading whether the migration is still running
+		let mut used = db_weight.reads(1);
+		if used.any_gt(limit) {
+			return Weight::zero();
+		}
+		if !MigratingVoteRecords::<T>::get() {
+			return used;
+		}
+		// draining an old record, reading its vote and writing the new record
+		let per_record = db_weight.reads_writes(2, 2);
+
+		let mut records = CheckMemberVote::<T>::drain();
+		loop {
+			if used.saturating_add(per_record).any_gt(limit) {
+				return used;
+			}
+			let Some((who, vote_id, _)) = records.next() else { break };
+			used.saturating_accrue(per_record);
+			let ongoing = ProjectVote::<T>::get(vote_id)
+				.map_or(false, |vote| vote.status == VoteStatus::Deciding);
+			if ongoing {
+				MemberVotes::<T>::insert(vote_id, who, true);
+			}
+		}
+
+		MigratingVoteRecords::<T>::kill();
+		log::info!("foresta-collectives vote records moved to the layout of v2");
+		used.saturating_add(db_weight.writes(1))
+	}
+}
+
+pub mod v3 {
+	use super::*;
+
+	use frame_support::{
+		pallet_prelude::*,
+		sp_std::marker::PhantomData,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	/// Count the collectives each manager manages, so managers are deauthorized in carbon credits
+	/// once they no longer manage any collective.
+	///
+	/// Collectives are only created by `ForceOrigin` and have at most `MaxNumManagers` managers,
+	/// so all managers are counted at once.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 3 {
+				log::info!("foresta-collectives migration to v3 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut reads = 1_u64;
+			let mut writes = 1_u64;
+			for (_, managers) in Managers::<T>::iter() {
+				reads += 1;
+				for manager in managers {
+					ManagedCollectivesCount::<T>::mutate(manager, |count| {
+						*count = count.saturating_add(1)
+					});
+					reads += 1;
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+			log::info!("foresta-collectives migration to v3 done, {} managers counted", writes - 1);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+	}
+}
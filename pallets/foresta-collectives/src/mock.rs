@@ -3,10 +3,14 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	pallet_prelude::DispatchResult,
 	parameter_types,
-	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, Contains, Nothing, OnFinalize, OnInitialize},
+	traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, Contains, GetDefault, Nothing, OnFinalize, OnInitialize},
+	weights::Weight,
 	PalletId,
 };
-use primitives::{Amount, Balance, CarbonCreditsValidator, CurrencyId};
+use primitives::{
+	Amount, Balance, CarbonCreditsValidator, CurrencyId, KycLevelOfMembers, KycLevelProvider, Royalty,
+	UserLevel,
+};
 use crate::SubstrateWeight;
 
 use frame_system::{EnsureRoot, EnsureSigned};
@@ -137,8 +141,11 @@ impl pallet_carbon_credits::Config for Test {
 	type ItemId = u32;
 	type ProjectId = u32;
 	type GroupId = u32;
-	type KYCProvider = KYCMembership;
+	type KYCProvider = KycLevelOfMembers<KYCMembership, GetDefault>;
+	type MinKycLevelToCreate = GetDefault;
+	type MinKycLevelToRetire = GetDefault;
 	type MarketplaceEscrow = MarketplaceEscrowAccount;
+	type Marketplace = ();
 	type MaxAuthorizedAccountCount = ConstU32<2>;
 	type MaxDocumentCount = ConstU32<2>;
 	type MaxGroupSize = MaxGroupSize;
@@ -148,13 +155,14 @@ impl pallet_carbon_credits::Config for Test {
 	type MaxRoyaltyRecipients = ConstU32<5>;
 	type MaxShortStringLength = ConstU32<20>;
 	type MinProjectId = ConstU32<1000>;
-	type NFTHandler = Uniques;
+	type NFTHandler = pallet_carbon_credits::UniquesCertificateHandler<Test>;
 	type PalletId = CarbonCreditsPalletId;
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const CarbonCreditPoolsPalletId: PalletId = PalletId(*b"bit/ccpp");
+	pub const MaxRedemptionFee: Percent = Percent::from_percent(10);
 }
 
 impl pallet_carbon_credits_pool::Config for Test {
@@ -166,6 +174,8 @@ impl pallet_carbon_credits_pool::Config for Test {
 	type MaxProjectIdList = ConstU32<100>;
 	type MaxRegistryListCount = ConstU32<2>;
 	type MinPoolId = ConstU32<10000>;
+	type MinKycLevelToDeposit = GetDefault;
+	type MaxRedemptionFee = MaxRedemptionFee;
 	type PalletId = CarbonCreditPoolsPalletId;
 	type PoolId = u32;
 	type WeightInfo = ();
@@ -202,6 +212,10 @@ impl CarbonCreditsValidator for DummyValidator {
 		Some((0, 0))
 	}
 
+	fn get_royalties(_project_id: &Self::ProjectId) -> Vec<Royalty<Self::Address>> {
+		vec![]
+	}
+
 	fn retire_credits(
 		_sender: Self::Address,
 		_project_id: Self::ProjectId,
@@ -214,14 +228,14 @@ impl CarbonCreditsValidator for DummyValidator {
 }
 
 pub struct MockKycProvider;
-impl Contains<u64> for MockKycProvider {
-	fn contains(value: &u64) -> bool {
+impl KycLevelProvider<u64> for MockKycProvider {
+	fn kyc_level(value: &u64) -> Option<UserLevel> {
 		// special account to test negative kyc
 		if value == &20 {
-			return false
+			return None
 		}
 
-		true
+		Some(UserLevel::KYCLevel1)
 	}
 }
 
@@ -245,6 +259,17 @@ parameter_types! {
 	pub const MaxPayoutsToStore : u32 = 1000;
 	#[derive(Clone, scale_info::TypeInfo, Debug, PartialEq)]
 	pub const MaxOpenOrdersPerUser : u32 = 2;
+	pub const DexFeeAccount : u64 = 99;
+	pub const MaxPriceLevels : u32 = 10;
+	pub const MaxOrdersPerPriceLevel : u32 = 3;
+	pub const MaxMatchesPerOrder : u32 = 5;
+}
+
+pub struct MockSettlementCurrencies;
+impl Contains<CurrencyId> for MockSettlementCurrencies {
+	fn contains(value: &CurrencyId) -> bool {
+		matches!(value, CurrencyId::USDT | CurrencyId::USDC)
+	}
 }
 
 impl pallet_dex::Config for Test {
@@ -268,6 +293,12 @@ impl pallet_dex::Config for Test {
 	type MaxPaymentFee = MaxPaymentFee;
 	type MaxPurchaseFee = MaxPurchaseFee;
 	type MaxPayoutsToStore = MaxPayoutsToStore;
+	type FeeAccount = DexFeeAccount;
+	type SettlementCurrencies = MockSettlementCurrencies;
+	type MaxPriceLevels = MaxPriceLevels;
+	type MaxOrdersPerPriceLevel = MaxOrdersPerPriceLevel;
+	type MaxMatchesPerOrder = MaxMatchesPerOrder;
+	type MaxRoyaltyRecipients = ConstU32<5>;
 	type WeightInfo = ();
 }
 
@@ -306,6 +337,7 @@ impl pallet_membership::Config for Test {
 parameter_types! {
 	pub const blocknumbers : BlockNumber = 100;
 	pub const ForestaCollectivesPalletId: PalletId = PalletId(*b"foresta/c");
+	pub static MaxProposalWeight: Weight = Weight::from_parts(1_000_000_000, 1_000_000);
   }
 
 impl pallet_foresta_collectives::Config for Test {
@@ -322,6 +354,13 @@ impl pallet_foresta_collectives::Config for Test {
     type MaxProjectsPerCollective = ConstU32<5>;
     type VotingDuration = blocknumbers;
     type ForceOrigin =  frame_system::EnsureRoot<u64>;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type MaxProposalLength = ConstU32<256>;
+	type MaxProposalWeight = MaxProposalWeight;
+	type MaxVoteLocks = ConstU32<5>;
+	type NativeCurrency = Balances;
+	type VoteWeight = pallet_foresta_collectives::NativeBalanceAndRetirements<Test>;
 }
 
 // Build genesis storage according to the mock runtime.
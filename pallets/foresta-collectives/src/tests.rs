@@ -1,7 +1,12 @@
-use crate::{mock::*, Config, Error, VoteType, Vote, VoteStatus, MembersCount};
+use crate::{mock::*, Config, Error, VoteType, Vote, VoteStatus, MembersCount, VotingPolicy, VotePolicies, VoteWeighting};
 use frame_support::{
 	assert_noop, assert_ok,
-	traits::{tokens::fungibles::{metadata::Inspect as MetadataInspect, Inspect}, OnFinalize, OnInitialize},
+	dispatch::GetDispatchInfo,
+	traits::{
+		tokens::fungibles::{metadata::Inspect as MetadataInspect, Inspect}, Currency, EnsureOrigin, GetStorageVersion,
+		OnFinalize, OnIdle, OnInitialize, OnRuntimeUpgrade, StorageVersion,
+	},
+	weights::Weight,
 	BoundedVec,
 };
 use frame_system::RawOrigin;
@@ -325,6 +330,8 @@ fn it_works_for_init_project_approval_vote() {
 		let mut vote = Vote::<Test> {
 			yes_votes: 0,
 			no_votes: 0,
+			yes_weight: 0,
+			no_weight: 0,
 			end: 101,
 			status: VoteStatus::Deciding,
 			vote_type: VoteType::ProjectApproval,
@@ -343,6 +350,8 @@ fn it_works_for_init_project_approval_vote() {
 		vote = Vote::<Test> {
 			yes_votes: 1,
 			no_votes: 0,
+			yes_weight: 1,
+			no_weight: 0,
 			end: 101,
 			status: VoteStatus::Deciding,
 			vote_type: VoteType::ProjectApproval,
@@ -357,6 +366,8 @@ fn it_works_for_init_project_approval_vote() {
 		vote = Vote::<Test> {
 			yes_votes: 1,
 			no_votes: 0,
+			yes_weight: 1,
+			no_weight: 0,
 			end: 101,
 			status: VoteStatus::Passed,
 			vote_type: VoteType::ProjectApproval,
@@ -373,7 +384,8 @@ fn it_works_for_init_project_approval_vote() {
 			project_id,
 			group_id,
 			project_tokens_to_mint.into(),
-			false
+			false,
+			None
 		));
 
 	});
@@ -402,11 +414,13 @@ fn it_works_for_create_proposal() {
 
 		// member creates proposal
 		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
-		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap()));
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None));
 
 		let mut vote = Vote::<Test> {
 			yes_votes: 0,
 			no_votes: 0,
+			yes_weight: 0,
+			no_weight: 0,
 			end: 101,
 			status: VoteStatus::Deciding,
 			vote_type: VoteType::Proposal,
@@ -425,6 +439,8 @@ fn it_works_for_create_proposal() {
 		vote = Vote::<Test> {
 			yes_votes: 1,
 			no_votes: 0,
+			yes_weight: 1,
+			no_weight: 0,
 			end: 101,
 			status: VoteStatus::Deciding,
 			vote_type: VoteType::Proposal,
@@ -439,6 +455,8 @@ fn it_works_for_create_proposal() {
 		vote = Vote::<Test> {
 			yes_votes: 1,
 			no_votes: 0,
+			yes_weight: 1,
+			no_weight: 0,
 			end: 101,
 			status: VoteStatus::Passed,
 			vote_type: VoteType::Proposal,
@@ -456,3 +474,734 @@ fn it_works_for_create_proposal() {
 	Error::<Test>::VoteNotInProgress);
 	});
 }
+
+#[test]
+fn it_works_for_set_voting_policy() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let not_manager = 2;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+
+		// collectives without a policy use a simple majority and the default duration
+		assert_eq!(ForestaCollectives::get_voting_policy(collective_id), VotingPolicy::<Test>::default());
+
+		let policy = VotingPolicy::<Test> {
+			min_turnout: Percent::from_percent(50),
+			approval_threshold: Percent::from_percent(60),
+			voting_durations: vec![(VoteType::ProjectApproval, 10)].try_into().unwrap(),
+			weighting: VoteWeighting::LockedBalance,
+		};
+
+		assert_noop!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(not_manager).into(),collective_id,policy.clone()),
+		Error::<Test>::NotAllowedToManageCollective);
+
+		assert_noop!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),1,policy.clone()),
+		Error::<Test>::CollectiveDoesNotExist);
+
+		let mut invalid_policy = policy.clone();
+		invalid_policy.approval_threshold = Percent::from_percent(100);
+		assert_noop!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(manager).into(),collective_id,invalid_policy),
+		Error::<Test>::InvalidVotingPolicy);
+
+		let mut invalid_policy = policy.clone();
+		invalid_policy.voting_durations = vec![(VoteType::Proposal, 0)].try_into().unwrap();
+		assert_noop!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(manager).into(),collective_id,invalid_policy),
+		Error::<Test>::InvalidVotingPolicy);
+
+		let mut invalid_policy = policy.clone();
+		invalid_policy.voting_durations = vec![(VoteType::Proposal, 5), (VoteType::Proposal, 10)].try_into().unwrap();
+		assert_noop!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(manager).into(),collective_id,invalid_policy),
+		Error::<Test>::InvalidVotingPolicy);
+
+		let mut invalid_policy = policy.clone();
+		invalid_policy.voting_durations = vec![(VoteType::PoolCreation, 10)].try_into().unwrap();
+		assert_noop!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(manager).into(),collective_id,invalid_policy),
+		Error::<Test>::WrongVoteType);
+
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(manager).into(),collective_id,policy.clone()));
+		assert_eq!(ForestaCollectives::get_voting_policy(collective_id), policy.clone());
+		System::assert_last_event(ForestaCollectivesEvent::VotingPolicySet { collective_id, policy }.into());
+
+		// ForceOrigin can always update the policy
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test>::default()));
+		assert_eq!(ForestaCollectives::get_voting_policy(collective_id), VotingPolicy::<Test>::default());
+	});
+}
+
+#[test]
+fn vote_below_min_turnout_fails_quorum() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		let project_id = 0;
+		let vote_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for member in [2, 3, 4] {
+			assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+		}
+
+		// at least half of the four members must vote
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test> {
+			min_turnout: Percent::from_percent(50),
+			approval_threshold: Percent::from_percent(50),
+			voting_durations: vec![(VoteType::ProjectApproval, 10)].try_into().unwrap(),
+			weighting: VoteWeighting::OneMemberOneVote,
+		}));
+
+		create_project::<Test>(manager, false);
+
+		assert_ok!(ForestaCollectives::init_project_approval_removal(RawOrigin::Signed(2).into(),collective_id,
+		project_id,VoteType::ProjectApproval));
+		assert_eq!(ForestaCollectives::get_project_vote(vote_id).unwrap().end, 11);
+
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),vote_id,true));
+
+		run_to_block(11);
+
+		assert_eq!(ForestaCollectives::get_project_vote(vote_id).unwrap().status, VoteStatus::QuorumNotMet);
+		assert!(ForestaCollectives::get_approved_projects(collective_id).is_empty());
+	});
+}
+
+#[test]
+fn vote_must_exceed_approval_threshold() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		let project_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for member in [2, 3, 4] {
+			assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+		}
+
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Signed(manager).into(),collective_id,VotingPolicy::<Test> {
+			min_turnout: Percent::from_percent(75),
+			approval_threshold: Percent::from_percent(70),
+			voting_durations: vec![(VoteType::ProjectApproval, 10), (VoteType::Proposal, 20)].try_into().unwrap(),
+			weighting: VoteWeighting::OneMemberOneVote,
+		}));
+
+		// a proposal with two yes and one no votes does not exceed 70%
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(2).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None));
+		assert_eq!(ForestaCollectives::get_project_vote(0).unwrap().end, 21);
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),0,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(3).into(),0,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(4).into(),0,false));
+
+		// the project approval gets three yes votes out of four
+		create_project::<Test>(manager, false);
+		assert_ok!(ForestaCollectives::init_project_approval_removal(RawOrigin::Signed(2).into(),collective_id,
+		project_id,VoteType::ProjectApproval));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(1).into(),1,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),1,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(3).into(),1,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(4).into(),1,false));
+
+		run_to_block(11);
+		assert_eq!(ForestaCollectives::get_project_vote(1).unwrap().status, VoteStatus::Passed);
+		assert_eq!(ForestaCollectives::get_approved_projects(collective_id).into_inner(), vec![project_id]);
+
+		run_to_block(21);
+		assert_eq!(ForestaCollectives::get_project_vote(0).unwrap().status, VoteStatus::Failed);
+	});
+}
+
+#[test]
+fn vote_in_progress_keeps_its_voting_policy() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		let project_id = 0;
+		let vote_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for member in [2, 3, 4] {
+			assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+		}
+
+		create_project::<Test>(manager, false);
+		assert_ok!(ForestaCollectives::init_project_approval_removal(RawOrigin::Signed(2).into(),collective_id,
+		project_id,VoteType::ProjectApproval));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),vote_id,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(3).into(),vote_id,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(4).into(),vote_id,false));
+
+		// the stricter policy only applies to the votes opened from now on
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test> {
+			min_turnout: Percent::from_percent(100),
+			approval_threshold: Percent::from_percent(90),
+			voting_durations: Default::default(),
+			weighting: VoteWeighting::OneMemberOneVote,
+		}));
+
+		run_to_block(101);
+
+		assert_eq!(ForestaCollectives::get_project_vote(vote_id).unwrap().status, VoteStatus::Passed);
+		assert_eq!(ForestaCollectives::get_approved_projects(collective_id).into_inner(), vec![project_id]);
+		assert!(VotePolicies::<Test>::get(vote_id).is_none());
+	});
+}
+
+#[test]
+fn passed_proposal_dispatches_call_with_collective_origin() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let member = 2;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+
+		// calls longer than MaxProposalLength are rejected
+		let too_long: Box<RuntimeCall> = Box::new(frame_system::Call::remark { remark: vec![0; 300] }.into());
+		assert_noop!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),Some(too_long)),
+		Error::<Test>::ProposalTooLong);
+
+		// remark can be dispatched by any origin, remark_with_event needs a signed origin
+		let remark: Box<RuntimeCall> = Box::new(frame_system::Call::remark { remark: vec![1] }.into());
+		let signed_remark: Box<RuntimeCall> =
+			Box::new(frame_system::Call::remark_with_event { remark: vec![1] }.into());
+		let rejected_remark = remark.clone();
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),Some(remark)));
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
+		"Proposal2Hash".as_bytes().to_vec().try_into().unwrap(),Some(signed_remark)));
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
+		"Proposal3Hash".as_bytes().to_vec().try_into().unwrap(),Some(rejected_remark)));
+		assert!(ForestaCollectives::get_proposal_call(0).is_some());
+
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(member).into(),0,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(member).into(),1,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(member).into(),2,false));
+
+		run_to_block(101);
+
+		System::assert_has_event(ForestaCollectivesEvent::ProposalExecuted { collective_id, vote_id: 0, result: Ok(()) }.into());
+		System::assert_has_event(ForestaCollectivesEvent::ProposalExecuted {
+			collective_id,
+			vote_id: 1,
+			result: Err(sp_runtime::DispatchError::BadOrigin),
+		}.into());
+		// the rejected proposal is not dispatched
+		assert_eq!(ForestaCollectives::get_project_vote(2).unwrap().status, VoteStatus::Failed);
+		assert!(!System::events().iter().any(|record| matches!(record.event,
+			RuntimeEvent::ForestaCollectives(ForestaCollectivesEvent::ProposalExecuted { vote_id: 2, .. }))));
+		for vote_id in 0..3 {
+			assert!(ForestaCollectives::get_proposal_call(vote_id).is_none());
+		}
+	});
+}
+
+#[test]
+fn passed_proposals_exceeding_the_block_weight_are_carried_over() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let member = 2;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+
+		// only one remark fits in the proposal weight of a block
+		let remark: Box<RuntimeCall> = Box::new(frame_system::Call::remark { remark: vec![1] }.into());
+		MaxProposalWeight::set(remark.get_dispatch_info().weight);
+		for vote_id in 0..3 {
+			assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
+			"ProposalHash".as_bytes().to_vec().try_into().unwrap(),Some(remark.clone())));
+			assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(member).into(),vote_id,true));
+		}
+
+		// events are reset every block, one proposal is dispatched per block in the order they passed
+		let executed = || System::events().iter().filter(|record| matches!(record.event,
+			RuntimeEvent::ForestaCollectives(ForestaCollectivesEvent::ProposalExecuted { result: Ok(()), .. }))).count();
+		let pending = || (0..3).filter(|position| ForestaCollectives::get_pending_proposal(position).is_some()).count();
+		for (block, vote_id, still_pending) in [(101, 0, 2), (102, 1, 1), (103, 2, 0)] {
+			run_to_block(block);
+			assert_eq!(executed(), 1);
+			System::assert_has_event(ForestaCollectivesEvent::ProposalExecuted { collective_id, vote_id, result: Ok(()) }.into());
+			assert_eq!(pending(), still_pending);
+		}
+		assert_eq!(ForestaCollectives::get_pending_proposals_range(), (3, 3));
+		for vote_id in 0..3 {
+			assert!(ForestaCollectives::get_proposal_call(vote_id).is_none());
+		}
+	});
+}
+
+#[test]
+fn ensure_collective_accepts_only_collective_origin() {
+	new_test_ext().execute_with(|| {
+		frame_support::parameter_types! {
+			pub const FirstCollective: u32 = 0;
+		}
+		let origin: RuntimeOrigin = crate::CollectiveOrigin::Collective(0).into();
+		assert_eq!(crate::EnsureCollective::<RuntimeOrigin, u32>::try_origin(origin.clone()).ok(), Some(0));
+		assert_eq!(crate::EnsureCollectiveId::<RuntimeOrigin, u32, FirstCollective>::try_origin(origin).ok(), Some(0));
+
+		let other: RuntimeOrigin = crate::CollectiveOrigin::Collective(1).into();
+		assert!(crate::EnsureCollectiveId::<RuntimeOrigin, u32, FirstCollective>::try_origin(other).is_err());
+		assert!(crate::EnsureCollective::<RuntimeOrigin, u32>::try_origin(RawOrigin::Root.into()).is_err());
+	});
+}
+
+#[test]
+fn balance_weighted_votes_lock_balance_until_unlocked() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		let vote_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for member in [2, 3, 4] {
+			assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+		}
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test> {
+			weighting: VoteWeighting::LockedBalance,
+			..Default::default()
+		}));
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 300);
+		Balances::make_free_balance_be(&3, 50);
+
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None));
+
+		// two members approve but the member voting no has more balance
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(1).into(),vote_id,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(3).into(),vote_id,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),vote_id,false));
+		System::assert_last_event(ForestaCollectivesEvent::VoteCast { vote_id, who: 2, approve: false, weight: 300 }.into());
+
+		// members without balance have no weight
+		assert_noop!(ForestaCollectives::cast_vote(RawOrigin::Signed(4).into(),vote_id,true),
+		Error::<Test>::NoVoteWeight);
+
+		let vote = ForestaCollectives::get_project_vote(vote_id).unwrap();
+		assert_eq!((vote.yes_votes, vote.no_votes), (2, 1));
+		assert_eq!((vote.yes_weight, vote.no_weight), (150, 300));
+		assert_eq!(Balances::locks(2)[0].amount, 300);
+		assert_eq!(ForestaCollectives::get_vote_locks(2).into_inner(), vec![(vote_id, 300)]);
+
+		assert_noop!(ForestaCollectives::unlock_vote(RawOrigin::Signed(2).into(),vote_id),
+		Error::<Test>::VoteInProgress);
+
+		run_to_block(101);
+		assert_eq!(ForestaCollectives::get_project_vote(vote_id).unwrap().status, VoteStatus::Failed);
+
+		assert_ok!(ForestaCollectives::unlock_vote(RawOrigin::Signed(2).into(),vote_id));
+		assert!(Balances::locks(2).is_empty());
+		assert!(ForestaCollectives::get_vote_locks(2).is_empty());
+		assert_noop!(ForestaCollectives::unlock_vote(RawOrigin::Signed(2).into(),vote_id),
+		Error::<Test>::VoteLockNotFound);
+	});
+}
+
+#[test]
+fn retirement_weighted_votes() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,2));
+		pallet_carbon_credits::RetiredCreditsByAccount::<Test>::insert(1, 40);
+		pallet_carbon_credits::RetiredCreditsByAccount::<Test>::insert(2, 10);
+		Balances::make_free_balance_be(&2, 100);
+
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test> {
+			weighting: VoteWeighting::Retirements,
+			..Default::default()
+		}));
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(1).into(),0,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),0,false));
+		let vote = ForestaCollectives::get_project_vote(0).unwrap();
+		assert_eq!((vote.yes_weight, vote.no_weight), (40, 10));
+		// retirements are not locked
+		assert!(Balances::locks(2).is_empty());
+		assert!(ForestaCollectives::get_vote_locks(2).is_empty());
+
+		run_to_block(101);
+		assert_eq!(ForestaCollectives::get_project_vote(0).unwrap().status, VoteStatus::Passed);
+	});
+}
+
+#[test]
+fn mixed_weighted_votes() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		let other_collective_id = 1;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective2".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll2Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,2));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),other_collective_id,2));
+		pallet_carbon_credits::RetiredCreditsByAccount::<Test>::insert(2, 10);
+		Balances::make_free_balance_be(&2, 100);
+
+		// each collective picks its own share of balance and retirements
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test> {
+			weighting: VoteWeighting::Mixed { balance_share: Percent::from_percent(50) },
+			..Default::default()
+		}));
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),other_collective_id,VotingPolicy::<Test> {
+			weighting: VoteWeighting::Mixed { balance_share: Percent::from_percent(0) },
+			..Default::default()
+		}));
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None));
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),other_collective_id,
+		"Proposal2Hash".as_bytes().to_vec().try_into().unwrap(),None));
+
+		// half of the balance and half of the retirements
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),0,true));
+		assert_eq!(ForestaCollectives::get_project_vote(0).unwrap().yes_weight, 55);
+		// only the retirements, the balance is still locked for the vote
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),1,true));
+		assert_eq!(ForestaCollectives::get_project_vote(1).unwrap().yes_weight, 10);
+		assert_eq!(Balances::locks(2)[0].amount, 100);
+		assert_eq!(ForestaCollectives::get_vote_locks(2).into_inner(), vec![(0, 100), (1, 100)]);
+
+		// members without balance and retirements have no weight
+		assert_noop!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),0,true),
+		Error::<Test>::NoVoteWeight);
+
+		run_to_block(101);
+		assert_eq!(ForestaCollectives::get_project_vote(0).unwrap().status, VoteStatus::Passed);
+		assert_ok!(ForestaCollectives::unlock_vote(RawOrigin::Signed(2).into(),0));
+		assert_ok!(ForestaCollectives::unlock_vote(RawOrigin::Signed(2).into(),1));
+		assert!(Balances::locks(2).is_empty());
+	});
+}
+
+#[test]
+fn balance_locks_are_limited_per_member() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::set_voting_policy(RawOrigin::Root.into(),collective_id,VotingPolicy::<Test> {
+			weighting: VoteWeighting::LockedBalance,
+			..Default::default()
+		}));
+		Balances::make_free_balance_be(&manager, 100);
+
+		// MaxVoteLocks is 5, the sixth vote cannot lock balance
+		for vote_id in 0..6 {
+			if vote_id == 5 {
+				run_to_block(2);
+			}
+			assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),collective_id,
+			"ProposalHash".as_bytes().to_vec().try_into().unwrap(),None));
+		}
+		for vote_id in 0..5 {
+			assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),vote_id,true));
+		}
+		assert_noop!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),5,true),
+		Error::<Test>::MaxVoteLocksExceeded);
+
+		// unlocking an ended vote frees a slot
+		run_to_block(101);
+		assert_ok!(ForestaCollectives::unlock_vote(RawOrigin::Signed(manager).into(),0));
+		assert_eq!(ForestaCollectives::get_vote_locks(manager).len(), 4);
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),5,true));
+		assert_eq!(Balances::locks(manager)[0].amount, 100);
+	});
+}
+
+#[test]
+fn migration_to_v1_adds_vote_weights() {
+	new_test_ext().execute_with(|| {
+		let old = crate::migration::v1::OldVote::<Test> {
+			yes_votes: 3,
+			no_votes: 1,
+			end: 10,
+			status: VoteStatus::Deciding,
+			vote_type: VoteType::Proposal,
+			collective_id: Some(0),
+			project_id: None,
+		};
+		frame_support::storage::unhashed::put(&crate::ProjectVote::<Test>::hashed_key_for(0), &old);
+		StorageVersion::new(0).put::<ForestaCollectives>();
+
+		crate::migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		let vote = ForestaCollectives::get_project_vote(0).unwrap();
+		assert_eq!((vote.yes_votes, vote.no_votes), (3, 1));
+		assert_eq!((vote.yes_weight, vote.no_weight), (3, 1));
+		assert_eq!((vote.end, vote.vote_type, vote.collective_id), (10, VoteType::Proposal, Some(0)));
+		assert_eq!(ForestaCollectives::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn members_can_leave_and_be_removed() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for member in [2, 3, 4] {
+			assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+		}
+		assert_eq!(MembersCount::<Test>::get(collective_id),4);
+
+		assert_ok!(ForestaCollectives::leave_collective(RawOrigin::Signed(2).into(),collective_id));
+		System::assert_last_event(ForestaCollectivesEvent::MemberLeft { collective_id, member: 2 }.into());
+		assert!(!ForestaCollectives::check_member(collective_id, 2));
+		assert_eq!(MembersCount::<Test>::get(collective_id),3);
+		assert_noop!(ForestaCollectives::leave_collective(RawOrigin::Signed(2).into(),collective_id),
+		Error::<Test>::MemberDoesNotExist);
+
+		// the last manager cannot leave
+		assert_noop!(ForestaCollectives::leave_collective(RawOrigin::Signed(manager).into(),collective_id),
+		Error::<Test>::LastManager);
+
+		// only managers or ForceOrigin can remove members
+		assert_noop!(ForestaCollectives::remove_member(RawOrigin::Signed(4).into(),collective_id,3),
+		Error::<Test>::NotAllowedToManageMembership);
+		assert_noop!(ForestaCollectives::remove_member(RawOrigin::Signed(manager).into(),collective_id,manager),
+		Error::<Test>::NotAllowedToManageMembership);
+		assert_ok!(ForestaCollectives::remove_member(RawOrigin::Signed(manager).into(),collective_id,3));
+		System::assert_last_event(ForestaCollectivesEvent::MemberRemoved { collective_id, member: 3 }.into());
+		assert_ok!(ForestaCollectives::remove_member(RawOrigin::Root.into(),collective_id,4));
+		assert_eq!(MembersCount::<Test>::get(collective_id),1);
+
+		// removed members can join again
+		assert_ok!(ForestaCollectives::join_collective(RawOrigin::Signed(3).into(),collective_id));
+		assert_eq!(MembersCount::<Test>::get(collective_id),2);
+	});
+}
+
+#[test]
+fn vote_records_are_cleared_once_votes_end() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let member = 2;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+
+		assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(member).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),0,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(member).into(),0,true));
+
+		// the vote of a member that left is kept so rejoining does not allow voting twice
+		assert_ok!(ForestaCollectives::leave_collective(RawOrigin::Signed(member).into(),collective_id));
+		assert!(ForestaCollectives::check_member_vote(0, member));
+		assert_ok!(ForestaCollectives::join_collective(RawOrigin::Signed(member).into(),collective_id));
+		assert_noop!(ForestaCollectives::cast_vote(RawOrigin::Signed(member).into(),0,true),
+		Error::<Test>::AlreadyVoted);
+
+		run_to_block(101);
+		assert!(ForestaCollectives::check_member_vote(0, manager));
+
+		// the records are cleared with the idle weight of the block
+		ForestaCollectives::on_idle(101, Weight::MAX);
+		assert!(!ForestaCollectives::check_member_vote(0, manager));
+		assert!(!ForestaCollectives::check_member_vote(0, member));
+		assert!(crate::StaleVoteRecords::<Test>::get(0).is_none());
+	});
+}
+
+#[test]
+fn migration_to_v2_keys_vote_records_by_vote() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for proposal in ["Proposal1Hash", "Proposal2Hash"] {
+			assert_ok!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),collective_id,
+			proposal.as_bytes().to_vec().try_into().unwrap(),None));
+		}
+		let mut ended = ForestaCollectives::get_project_vote(0).unwrap();
+		ended.status = VoteStatus::Passed;
+		crate::ProjectVote::<Test>::insert(0, ended);
+
+		// records keyed by member first
+		for vote_id in [0_u32, 1] {
+			crate::migration::v2::CheckMemberVote::<Test>::insert(manager, vote_id, true);
+		}
+		StorageVersion::new(1).put::<ForestaCollectives>();
+
+		crate::migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(ForestaCollectives::on_chain_storage_version(), 2);
+
+		// the records are moved on idle, until then the old records still count
+		assert!(!ForestaCollectives::check_member_vote(1, manager));
+		assert_noop!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),1,true),
+		Error::<Test>::AlreadyVoted);
+
+		ForestaCollectives::on_idle(1, Weight::MAX);
+
+		assert!(!ForestaCollectives::check_member_vote(0, manager));
+		assert!(ForestaCollectives::check_member_vote(1, manager));
+		assert_eq!(crate::MemberVotes::<Test>::iter().count(), 1);
+		assert_eq!(crate::migration::v2::CheckMemberVote::<Test>::iter().count(), 0);
+		assert!(!crate::MigratingVoteRecords::<Test>::get());
+		assert_noop!(ForestaCollectives::cast_vote(RawOrigin::Signed(manager).into(),1,true),
+		Error::<Test>::AlreadyVoted);
+	});
+}
+
+#[test]
+fn membership_votes_change_members_and_managers() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+		for member in [2, 3] {
+			assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,member));
+		}
+
+		assert_noop!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,manager,VoteType::AddManager),
+		Error::<Test>::AlreadyManager);
+		assert_noop!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,3,VoteType::RemoveManager),
+		Error::<Test>::NotManager);
+		assert_noop!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,5,VoteType::MemberRemoval),
+		Error::<Test>::MemberDoesNotExist);
+		assert_noop!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,3,VoteType::Proposal),
+		Error::<Test>::WrongVoteType);
+
+		// member 2 becomes a manager, then the first manager and member 3 are removed
+		assert_ok!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,2,VoteType::AddManager));
+		System::assert_last_event(ForestaCollectivesEvent::MembershipVoteInit {
+			collective_id, vote_id: 0, account: 2, vote_type: VoteType::AddManager
+		}.into());
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),0,true));
+		run_to_block(101);
+		assert_eq!(ForestaCollectives::get_managers(collective_id).into_inner(), vec![manager, 2]);
+
+		assert_ok!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,manager,VoteType::RemoveManager));
+		assert_ok!(ForestaCollectives::init_membership_vote(RawOrigin::Signed(2).into(),collective_id,3,VoteType::MemberRemoval));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),1,true));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(),2,true));
+		run_to_block(201);
+		assert_eq!(ForestaCollectives::get_managers(collective_id).into_inner(), vec![2]);
+		// the removed manager is still a member
+		assert!(ForestaCollectives::check_member(collective_id, manager));
+		assert!(!ForestaCollectives::check_member(collective_id, 3));
+		assert_eq!(MembersCount::<Test>::get(collective_id),2);
+		assert!(ForestaCollectives::get_membership_params_info(1).is_none());
+	});
+}
+
+#[test]
+fn managers_changed_by_vote_are_authorized_in_carbon_credits() {
+	new_test_ext().execute_with(|| {
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(
+			RawOrigin::Root.into(),
+			"Collective1".as_bytes().to_vec().try_into().unwrap(),
+			sp_core::bounded_vec![3],
+			"Coll1Hash".as_bytes().to_vec().try_into().unwrap()
+		));
+		assert_ok!(ForestaCollectives::add_collective(
+			RawOrigin::Root.into(),
+			"Collective2".as_bytes().to_vec().try_into().unwrap(),
+			sp_core::bounded_vec![2],
+			"Coll2Hash".as_bytes().to_vec().try_into().unwrap()
+		));
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(3).into(), collective_id, 2));
+
+		assert_ok!(ForestaCollectives::init_membership_vote(
+			RawOrigin::Signed(2).into(),
+			collective_id,
+			2,
+			VoteType::AddManager
+		));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(), 0, true));
+		run_to_block(101);
+
+		// the new manager is appended, it can still add members
+		assert_eq!(ForestaCollectives::get_managers(collective_id).into_inner(), vec![3, 2]);
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(2).into(), collective_id, 4));
+		assert!(ForestaCollectives::check_authorized_account(&2));
+		assert_eq!(ForestaCollectives::get_managed_collectives_count(2), 2);
+
+		assert_ok!(ForestaCollectives::init_membership_vote(
+			RawOrigin::Signed(2).into(),
+			collective_id,
+			3,
+			VoteType::RemoveManager
+		));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(), 1, true));
+		run_to_block(201);
+
+		// the removed manager no longer manages any collective
+		assert_eq!(ForestaCollectives::get_managers(collective_id).into_inner(), vec![2]);
+		assert!(!ForestaCollectives::check_authorized_account(&3));
+		assert_eq!(ForestaCollectives::get_managed_collectives_count(3), 0);
+		assert!(ForestaCollectives::check_authorized_account(&2));
+
+		// leaving the collective also removes the manager role
+		assert_ok!(ForestaCollectives::add_member(RawOrigin::Signed(2).into(), 1, 3));
+		assert_ok!(ForestaCollectives::init_membership_vote(
+			RawOrigin::Signed(2).into(),
+			1,
+			3,
+			VoteType::AddManager
+		));
+		assert_ok!(ForestaCollectives::cast_vote(RawOrigin::Signed(2).into(), 2, true));
+		run_to_block(301);
+		assert_ok!(ForestaCollectives::leave_collective(RawOrigin::Signed(2).into(), 1));
+		assert_eq!(ForestaCollectives::get_managed_collectives_count(2), 1);
+		assert!(ForestaCollectives::check_authorized_account(&2));
+	});
+}
+
+#[test]
+fn migration_to_v3_counts_managed_collectives() {
+	new_test_ext().execute_with(|| {
+		for (collective_id, managers) in [(0_u32, vec![1, 2]), (1, vec![2])] {
+			crate::Managers::<Test>::insert(collective_id, BoundedVec::try_from(managers).unwrap());
+		}
+		StorageVersion::new(2).put::<ForestaCollectives>();
+
+		crate::migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+		assert_eq!(ForestaCollectives::get_managed_collectives_count(1), 1);
+		assert_eq!(ForestaCollectives::get_managed_collectives_count(2), 2);
+		assert_eq!(ForestaCollectives::on_chain_storage_version(), 3);
+	});
+}
+
+#[test]
+fn archived_collectives_cannot_grow_or_vote() {
+	new_test_ext().execute_with(|| {
+		let manager = 1;
+		let collective_id = 0;
+		assert_ok!(ForestaCollectives::add_collective(RawOrigin::Root.into(),"Collective1".as_bytes().to_vec().try_into().unwrap(),
+		sp_core::bounded_vec![manager],"Coll1Hash".as_bytes().to_vec().try_into().unwrap()));
+
+		assert_noop!(ForestaCollectives::archive_collective(RawOrigin::Signed(manager).into(),collective_id),
+		sp_runtime::DispatchError::BadOrigin);
+		assert_ok!(ForestaCollectives::archive_collective(RawOrigin::Root.into(),collective_id));
+		System::assert_last_event(ForestaCollectivesEvent::CollectiveArchived { collective_id }.into());
+		assert_eq!(ForestaCollectives::get_archived_at(collective_id), Some(1));
+
+		assert_noop!(ForestaCollectives::archive_collective(RawOrigin::Root.into(),collective_id),
+		Error::<Test>::CollectiveArchived);
+		assert_noop!(ForestaCollectives::join_collective(RawOrigin::Signed(2).into(),collective_id),
+		Error::<Test>::CollectiveArchived);
+		assert_noop!(ForestaCollectives::add_member(RawOrigin::Signed(manager).into(),collective_id,2),
+		Error::<Test>::CollectiveArchived);
+		assert_noop!(ForestaCollectives::create_proposal(RawOrigin::Signed(manager).into(),collective_id,
+		"Proposal1Hash".as_bytes().to_vec().try_into().unwrap(),None),
+		Error::<Test>::CollectiveArchived);
+	});
+}
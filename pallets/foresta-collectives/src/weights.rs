@@ -53,6 +53,14 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn add_collective() -> Weight;
 	fn add_member() -> Weight;
+	fn set_voting_policy() -> Weight;
+	fn create_proposal(l: u32, ) -> Weight;
+	fn cast_vote(l: u32, ) -> Weight;
+	fn unlock_vote(l: u32, ) -> Weight;
+	fn leave_collective() -> Weight;
+	fn remove_member() -> Weight;
+	fn init_membership_vote() -> Weight;
+	fn archive_collective() -> Weight;
 }
 
 /// Weights for pallet_foresta_collectives using the Substrate node and recommended hardware.
@@ -64,6 +72,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `ForestaCollectives::MembersCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
 	/// Storage: `CarbonCredits::AuthorizedAccounts` (r:1 w:1)
 	/// Proof: `CarbonCredits::AuthorizedAccounts` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ManagedCollectivesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ManagedCollectivesCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	/// Storage: `ForestaCollectives::Managers` (r:0 w:1)
 	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
 	/// Storage: `ForestaCollectives::CollectivesMap` (r:0 w:1)
@@ -76,8 +86,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		//  Estimated: `3489`
 		// Minimum execution time: 21_000_000 picoseconds.
 		Weight::from_parts(21_000_000, 3489)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	/// Storage: `ForestaCollectives::Members` (r:1 w:1)
 	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
@@ -94,6 +104,183 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotingPolicies` (r:0 w:1)
+	/// Proof: `ForestaCollectives::VotingPolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn set_voting_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `338`
+		//  Estimated: `3646`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3646)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ArchivedCollectives` (r:1 w:0)
+	/// Proof: `ForestaCollectives::ArchivedCollectives` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Members` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VotesCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotingPolicies` (r:1 w:0)
+	/// Proof: `ForestaCollectives::VotingPolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ActiveVoting` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ActiveVoting` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProposalsCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ProposalsCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProposalCalls` (r:0 w:1)
+	/// Proof: `ForestaCollectives::ProposalCalls` (`max_values`: None, `max_size`: Some(278), added: 2753, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProjectVote` (r:0 w:1)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotePolicies` (r:0 w:1)
+	/// Proof: `ForestaCollectives::VotePolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Proposals` (r:0 w:1)
+	/// Proof: `ForestaCollectives::Proposals` (`max_values`: None, `max_size`: Some(240), added: 2715, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 1018]`.
+	fn create_proposal(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `486`
+		//  Estimated: `3646`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(37_214_000, 3646)
+			// Standard Error: 412
+			.saturating_add(Weight::from_parts(1_873, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `ForestaCollectives::ProjectVote` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Members` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MemberVotes` (r:1 w:1)
+	/// Proof: `ForestaCollectives::MemberVotes` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MigratingVoteRecords` (r:1 w:0)
+	/// Proof: `ForestaCollectives::MigratingVoteRecords` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotePolicies` (r:1 w:0)
+	/// Proof: `ForestaCollectives::VotePolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VoteLocks` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VoteLocks` (`max_values`: None, `max_size`: Some(369), added: 2844, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[1, 16]`.
+	fn cast_vote(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `712 + l * (20 ±0)`
+		//  Estimated: `4764`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(53_174_000, 4764)
+			// Standard Error: 3_516
+			.saturating_add(Weight::from_parts(171_402, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::ProjectVote` (r:1 w:0)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VoteLocks` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VoteLocks` (`max_values`: None, `max_size`: Some(369), added: 2844, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[1, 16]`.
+	fn unlock_vote(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `648 + l * (20 ±0)`
+		//  Estimated: `4764`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(42_386_000, 4764)
+			// Standard Error: 2_874
+			.saturating_add(Weight::from_parts(148_917, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `ForestaCollectives::Members` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ManagedCollectivesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ManagedCollectivesCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `CarbonCredits::AuthorizedAccounts` (r:1 w:1)
+	/// Proof: `CarbonCredits::AuthorizedAccounts` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MembersCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::MembersCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn leave_collective() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `410`
+		//  Estimated: `3646`
+		// Minimum execution time: 23_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 3646)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::Members` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ManagedCollectivesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ManagedCollectivesCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `CarbonCredits::AuthorizedAccounts` (r:1 w:1)
+	/// Proof: `CarbonCredits::AuthorizedAccounts` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MembersCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::MembersCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn remove_member() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `410`
+		//  Estimated: `3646`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 3646)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ArchivedCollectives` (r:1 w:0)
+	/// Proof: `ForestaCollectives::ArchivedCollectives` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Members` (r:2 w:0)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VotesCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotingPolicies` (r:1 w:0)
+	/// Proof: `ForestaCollectives::VotingPolicies` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ActiveVoting` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ActiveVoting` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotePolicies` (r:0 w:1)
+	/// Proof: `ForestaCollectives::VotePolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MembershipParamsInfo` (r:0 w:1)
+	/// Proof: `ForestaCollectives::MembershipParamsInfo` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProjectVote` (r:0 w:1)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	fn init_membership_vote() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `486`
+		//  Estimated: `3646`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(34_000_000, 3646)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ArchivedCollectives` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ArchivedCollectives` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn archive_collective() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `186`
+		//  Estimated: `3617`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 3617)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -104,6 +291,8 @@ impl WeightInfo for () {
 	/// Proof: `ForestaCollectives::MembersCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
 	/// Storage: `CarbonCredits::AuthorizedAccounts` (r:1 w:1)
 	/// Proof: `CarbonCredits::AuthorizedAccounts` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ManagedCollectivesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ManagedCollectivesCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
 	/// Storage: `ForestaCollectives::Managers` (r:0 w:1)
 	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
 	/// Storage: `ForestaCollectives::CollectivesMap` (r:0 w:1)
@@ -116,8 +305,8 @@ impl WeightInfo for () {
 		//  Estimated: `3489`
 		// Minimum execution time: 21_000_000 picoseconds.
 		Weight::from_parts(21_000_000, 3489)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `ForestaCollectives::Members` (r:1 w:1)
 	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
@@ -134,4 +323,181 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotingPolicies` (r:0 w:1)
+	/// Proof: `ForestaCollectives::VotingPolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	fn set_voting_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `338`
+		//  Estimated: `3646`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 3646)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ArchivedCollectives` (r:1 w:0)
+	/// Proof: `ForestaCollectives::ArchivedCollectives` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Members` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VotesCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotingPolicies` (r:1 w:0)
+	/// Proof: `ForestaCollectives::VotingPolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ActiveVoting` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ActiveVoting` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProposalsCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ProposalsCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProposalCalls` (r:0 w:1)
+	/// Proof: `ForestaCollectives::ProposalCalls` (`max_values`: None, `max_size`: Some(278), added: 2753, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProjectVote` (r:0 w:1)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotePolicies` (r:0 w:1)
+	/// Proof: `ForestaCollectives::VotePolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Proposals` (r:0 w:1)
+	/// Proof: `ForestaCollectives::Proposals` (`max_values`: None, `max_size`: Some(240), added: 2715, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 1018]`.
+	fn create_proposal(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `486`
+		//  Estimated: `3646`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(37_214_000, 3646)
+			// Standard Error: 412
+			.saturating_add(Weight::from_parts(1_873, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `ForestaCollectives::ProjectVote` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Members` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MemberVotes` (r:1 w:1)
+	/// Proof: `ForestaCollectives::MemberVotes` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MigratingVoteRecords` (r:1 w:0)
+	/// Proof: `ForestaCollectives::MigratingVoteRecords` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotePolicies` (r:1 w:0)
+	/// Proof: `ForestaCollectives::VotePolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VoteLocks` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VoteLocks` (`max_values`: None, `max_size`: Some(369), added: 2844, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[1, 16]`.
+	fn cast_vote(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `712 + l * (20 ±0)`
+		//  Estimated: `4764`
+		// Minimum execution time: 52_000_000 picoseconds.
+		Weight::from_parts(53_174_000, 4764)
+			// Standard Error: 3_516
+			.saturating_add(Weight::from_parts(171_402, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::ProjectVote` (r:1 w:0)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VoteLocks` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VoteLocks` (`max_values`: None, `max_size`: Some(369), added: 2844, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[1, 16]`.
+	fn unlock_vote(l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `648 + l * (20 ±0)`
+		//  Estimated: `4764`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(42_386_000, 4764)
+			// Standard Error: 2_874
+			.saturating_add(Weight::from_parts(148_917, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `ForestaCollectives::Members` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ManagedCollectivesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ManagedCollectivesCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `CarbonCredits::AuthorizedAccounts` (r:1 w:1)
+	/// Proof: `CarbonCredits::AuthorizedAccounts` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MembersCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::MembersCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn leave_collective() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `410`
+		//  Estimated: `3646`
+		// Minimum execution time: 23_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 3646)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::Members` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:1)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ManagedCollectivesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ManagedCollectivesCount` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `CarbonCredits::AuthorizedAccounts` (r:1 w:1)
+	/// Proof: `CarbonCredits::AuthorizedAccounts` (`max_values`: Some(1), `max_size`: Some(321), added: 816, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MembersCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::MembersCount` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	fn remove_member() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `410`
+		//  Estimated: `3646`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 3646)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ArchivedCollectives` (r:1 w:0)
+	/// Proof: `ForestaCollectives::ArchivedCollectives` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Members` (r:2 w:0)
+	/// Proof: `ForestaCollectives::Members` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::Managers` (r:1 w:0)
+	/// Proof: `ForestaCollectives::Managers` (`max_values`: None, `max_size`: Some(181), added: 2656, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotesCount` (r:1 w:1)
+	/// Proof: `ForestaCollectives::VotesCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotingPolicies` (r:1 w:0)
+	/// Proof: `ForestaCollectives::VotingPolicies` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ActiveVoting` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ActiveVoting` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::VotePolicies` (r:0 w:1)
+	/// Proof: `ForestaCollectives::VotePolicies` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::MembershipParamsInfo` (r:0 w:1)
+	/// Proof: `ForestaCollectives::MembershipParamsInfo` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ProjectVote` (r:0 w:1)
+	/// Proof: `ForestaCollectives::ProjectVote` (`max_values`: None, `max_size`: Some(94), added: 2569, mode: `MaxEncodedLen`)
+	fn init_membership_vote() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `486`
+		//  Estimated: `3646`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(34_000_000, 3646)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `ForestaCollectives::CollectivesMap` (r:1 w:0)
+	/// Proof: `ForestaCollectives::CollectivesMap` (`max_values`: None, `max_size`: Some(152), added: 2627, mode: `MaxEncodedLen`)
+	/// Storage: `ForestaCollectives::ArchivedCollectives` (r:1 w:1)
+	/// Proof: `ForestaCollectives::ArchivedCollectives` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
+	fn archive_collective() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `186`
+		//  Estimated: `3617`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 3617)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -0,0 +1,67 @@
+//! Benchmarking setup for pallet-foresta-fulfillment
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::Pallet as ForestaFulfillment;
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_std::vec;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Fill the processors up to one below the limit so the new processor is checked against all of
+/// them
+fn fill_processors<T: Config>() {
+	for i in 0..T::MaxProcessors::get().saturating_sub(1) {
+		let processor: T::AccountId = account("processor", i, 0);
+		Processors::<T>::mutate(|processors| processors.try_push(processor).unwrap());
+	}
+}
+
+benchmarks! {
+	add_processor {
+		fill_processors::<T>();
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| "no force origin")?;
+		let processor: T::AccountId = account("new_processor", 0, 0);
+	}: _<T::RuntimeOrigin>(origin, processor.clone())
+	verify {
+		assert!(ForestaFulfillment::<T>::processors().contains(&processor));
+	}
+
+	remove_processor {
+		fill_processors::<T>();
+		let origin = T::ForceOrigin::try_successful_origin().map_err(|_| "no force origin")?;
+		let processor: T::AccountId = account("new_processor", 0, 0);
+		Processors::<T>::mutate(|processors| processors.try_push(processor.clone()).unwrap());
+	}: _<T::RuntimeOrigin>(origin, processor.clone())
+	verify {
+		assert!(!ForestaFulfillment::<T>::processors().contains(&processor));
+	}
+
+	// the handlers are weighed by the runtime, only the decoding of the largest payload is measured
+	// here, with a trailing byte so that it is not handled
+	handle_fulfillment {
+		fill_processors::<T>();
+		let processor: T::AccountId = account("new_processor", 0, 0);
+		Processors::<T>::mutate(|processors| processors.try_push(processor.clone()).unwrap());
+		let mut payload = FulfillmentMessage::<T>::PaymentProof {
+			order_id: u128::MAX,
+			chain_id: u32::MAX,
+			tx_proof: vec![1_u8; T::MaxProofLength::get() as usize].try_into().unwrap(),
+		}
+		.encode();
+		payload.push(0);
+	}: {
+		ForestaFulfillment::<T>::handle_fulfillment(processor.clone(), &payload);
+	}
+	verify {
+		assert_last_event::<T>(
+			Event::FulfillmentFailed { processor, reason: FailureReason::InvalidPayload }.into(),
+		);
+	}
+
+	impl_benchmark_test_suite!(ForestaFulfillment, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,332 @@
+//! # Foresta Fulfillment Pallet
+//!
+//! Interprets the payloads fulfilled by Acurast processors as Foresta messages and routes them to
+//! the pallets consuming them:
+//!
+//! - price updates of the oracle feeds, see [`PriceUpdateHandler`]
+//! - satellite based MRV readings of a project, see [`MrvReadingHandler`]
+//! - payment proofs of marketplace buy orders, see [`PaymentProofHandler`]
+//!
+//! A payload is the SCALE encoding of a [`FulfillmentMessage`]. Only the processors added by
+//! `ForceOrigin` are trusted. Fulfillments from other processors, payloads that cannot be decoded
+//! and messages rejected by their handler do not fail the fulfillment, they are reported with a
+//! `FulfillmentFailed` event and the changes of the handler are reverted.
+//!
+//! Every handler returns the weight it used and declares the most it can use, the runtime charges
+//! the latter upfront through [`Pallet::max_fulfillment_weight`] and refunds the difference with
+//! the weight returned by [`Pallet::handle_fulfillment`].
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+use frame_support::{pallet_prelude::DispatchError, weights::Weight};
+use sp_std::vec::Vec;
+
+/// Consumes the prices of the oracle feeds fulfilled by a processor
+pub trait PriceUpdateHandler<AccountId, FeedId> {
+	/// Record `prices`, given as `(feed, price)` pairs, submitted by `processor`, returning the
+	/// weight used
+	fn handle_price_update(
+		processor: &AccountId,
+		prices: Vec<(FeedId, u128)>,
+	) -> Result<Weight, DispatchError>;
+
+	/// The weight of the largest price update
+	fn max_weight() -> Weight;
+}
+
+/// Consumes the MRV readings of projects fulfilled by a processor
+pub trait MrvReadingHandler<AccountId, ProjectId> {
+	/// Record the `reading` of `project_id` taken at `timestamp` and submitted by `processor`,
+	/// returning the weight used
+	fn handle_mrv_reading(
+		processor: &AccountId,
+		project_id: ProjectId,
+		timestamp: u64,
+		reading: Vec<u8>,
+	) -> Result<Weight, DispatchError>;
+
+	/// The weight of the largest MRV reading
+	fn max_weight() -> Weight;
+}
+
+/// Consumes the payment proofs of buy orders fulfilled by a processor
+pub trait PaymentProofHandler<AccountId> {
+	/// Validate the payment of `order_id` made on `chain_id` with `tx_proof`, returning the weight
+	/// used
+	fn handle_payment_proof(
+		processor: &AccountId,
+		order_id: u128,
+		chain_id: u32,
+		tx_proof: Vec<u8>,
+	) -> Result<Weight, DispatchError>;
+
+	/// The weight of the largest payment proof
+	fn max_weight() -> Weight;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use codec::DecodeAll;
+	use frame_support::{
+		pallet_prelude::*, storage::with_storage_layer, CloneNoBound, EqNoBound, PartialEqNoBound,
+		RuntimeDebugNoBound,
+	};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A message fulfilled by an Acurast processor
+	#[derive(
+		CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub enum FulfillmentMessage<T: Config> {
+		/// New prices for the oracle feeds
+		#[codec(index = 0)]
+		PriceUpdate { prices: BoundedVec<(T::FeedId, u128), T::MaxPrices> },
+		/// A satellite based MRV reading of a project
+		#[codec(index = 1)]
+		MrvReading {
+			project_id: T::ProjectId,
+			/// Unix time in milliseconds at which the reading was taken
+			timestamp: u64,
+			reading: BoundedVec<u8, T::MaxReadingLength>,
+		},
+		/// Proof of the payment of a marketplace buy order
+		#[codec(index = 2)]
+		PaymentProof { order_id: u128, chain_id: u32, tx_proof: BoundedVec<u8, T::MaxProofLength> },
+	}
+
+	impl<T: Config> FulfillmentMessage<T> {
+		pub fn kind(&self) -> MessageKind {
+			match self {
+				Self::PriceUpdate { .. } => MessageKind::PriceUpdate,
+				Self::MrvReading { .. } => MessageKind::MrvReading,
+				Self::PaymentProof { .. } => MessageKind::PaymentProof,
+			}
+		}
+	}
+
+	impl MessageKind {
+		/// The most a handler can use to process a message of this kind
+		pub fn max_weight<T: Config>(&self) -> Weight {
+			match self {
+				Self::PriceUpdate => T::PriceHandler::max_weight(),
+				Self::MrvReading => T::MrvHandler::max_weight(),
+				Self::PaymentProof => T::PaymentHandler::max_weight(),
+			}
+		}
+	}
+
+	/// The kind of a fulfillment message
+	#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum MessageKind {
+		PriceUpdate,
+		MrvReading,
+		PaymentProof,
+	}
+
+	/// The reason a fulfillment was not processed
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+	pub enum FailureReason {
+		/// The processor is not trusted
+		UnknownProcessor,
+		/// The payload is not a valid message
+		InvalidPayload,
+		/// The handler of the message rejected it
+		Rejected { kind: MessageKind, error: DispatchError },
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to add and remove processors
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The key of an oracle price feed
+		type FeedId: Parameter + Member + MaxEncodedLen + Copy;
+
+		/// The identifier of the projects the MRV readings belong to
+		type ProjectId: Parameter + Member + MaxEncodedLen + Copy;
+
+		/// Maximum number of prices in a price update
+		#[pallet::constant]
+		type MaxPrices: Get<u32>;
+
+		/// Maximum length of an MRV reading
+		#[pallet::constant]
+		type MaxReadingLength: Get<u32>;
+
+		/// Maximum length of the transaction proof of a payment
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// Maximum number of trusted processors
+		#[pallet::constant]
+		type MaxProcessors: Get<u32>;
+
+		/// Handler of the price updates
+		type PriceHandler: PriceUpdateHandler<Self::AccountId, Self::FeedId>;
+
+		/// Handler of the MRV readings
+		type MrvHandler: MrvReadingHandler<Self::AccountId, Self::ProjectId>;
+
+		/// Handler of the payment proofs
+		type PaymentHandler: PaymentProofHandler<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The Acurast processors whose fulfillments are processed
+	#[pallet::storage]
+	#[pallet::getter(fn processors)]
+	pub type Processors<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxProcessors>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A processor was added to the trusted processors
+		ProcessorAdded { processor: T::AccountId },
+		/// A processor was removed from the trusted processors
+		ProcessorRemoved { processor: T::AccountId },
+		/// A fulfillment was decoded and accepted by its handler
+		FulfillmentProcessed { processor: T::AccountId, kind: MessageKind },
+		/// A fulfillment was not processed
+		FulfillmentFailed { processor: T::AccountId, reason: FailureReason },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The processor is already trusted
+		ProcessorAlreadyExists,
+		/// The processor is not trusted
+		ProcessorNotFound,
+		/// Too many trusted processors
+		TooManyProcessors,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Trust the fulfillments of `processor`
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::add_processor())]
+		pub fn add_processor(origin: OriginFor<T>, processor: T::AccountId) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			Processors::<T>::try_mutate(|processors| -> DispatchResult {
+				ensure!(!processors.contains(&processor), Error::<T>::ProcessorAlreadyExists);
+				processors
+					.try_push(processor.clone())
+					.map_err(|_| Error::<T>::TooManyProcessors)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ProcessorAdded { processor });
+			Ok(())
+		}
+
+		/// Stop trusting the fulfillments of `processor`
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::remove_processor())]
+		pub fn remove_processor(origin: OriginFor<T>, processor: T::AccountId) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			Processors::<T>::try_mutate(|processors| -> DispatchResult {
+				let index = processors
+					.iter()
+					.position(|p| p == &processor)
+					.ok_or(Error::<T>::ProcessorNotFound)?;
+				processors.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ProcessorRemoved { processor });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Decode the `payload` fulfilled by `processor` and route the message to its handler, the
+		/// outcome is reported with an event. Returns the weight used, at most
+		/// `max_fulfillment_weight`.
+		pub fn handle_fulfillment(processor: T::AccountId, payload: &[u8]) -> Weight {
+			let (handler_weight, event) = match Self::do_handle_fulfillment(&processor, payload) {
+				Ok((kind, weight)) => (weight, Event::FulfillmentProcessed { processor, kind }),
+				Err(reason) => {
+					// the weight used by a rejecting handler is unknown, the most is charged
+					let weight = match reason {
+						FailureReason::Rejected { kind, .. } => kind.max_weight::<T>(),
+						_ => Weight::zero(),
+					};
+					(weight, Event::FulfillmentFailed { processor, reason })
+				},
+			};
+			Self::deposit_event(event);
+
+			T::WeightInfo::handle_fulfillment().saturating_add(handler_weight)
+		}
+
+		/// The most a fulfillment can use, to be charged before handling it
+		pub fn max_fulfillment_weight() -> Weight {
+			let max_handler_weight = T::PriceHandler::max_weight()
+				.max(T::MrvHandler::max_weight())
+				.max(T::PaymentHandler::max_weight());
+			T::WeightInfo::handle_fulfillment().saturating_add(max_handler_weight)
+		}
+
+		fn do_handle_fulfillment(
+			processor: &T::AccountId,
+			payload: &[u8],
+		) -> Result<(MessageKind, Weight), FailureReason> {
+			ensure!(Self::processors().contains(processor), FailureReason::UnknownProcessor);
+
+			let message = FulfillmentMessage::<T>::decode_all(&mut &payload[..])
+				.map_err(|_| FailureReason::InvalidPayload)?;
+			let kind = message.kind();
+
+			// revert the changes of a handler rejecting the message
+			let weight = with_storage_layer(|| match message {
+				FulfillmentMessage::PriceUpdate { prices } => {
+					T::PriceHandler::handle_price_update(processor, prices.into_inner())
+				},
+				FulfillmentMessage::MrvReading { project_id, timestamp, reading } => {
+					T::MrvHandler::handle_mrv_reading(
+						processor,
+						project_id,
+						timestamp,
+						reading.into_inner(),
+					)
+				},
+				FulfillmentMessage::PaymentProof { order_id, chain_id, tx_proof } => {
+					T::PaymentHandler::handle_payment_proof(
+						processor,
+						order_id,
+						chain_id,
+						tx_proof.into_inner(),
+					)
+				},
+			})
+			.map_err(|error| FailureReason::Rejected { kind, error })?;
+
+			Ok((kind, weight))
+		}
+	}
+}
@@ -0,0 +1,145 @@
+use crate as pallet_foresta_fulfillment;
+use crate::{MrvReadingHandler, PaymentProofHandler, PriceUpdateHandler};
+use frame_support::{
+	ensure, parameter_types,
+	storage::unhashed,
+	traits::{ConstU16, ConstU32, ConstU64},
+	weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, DispatchError,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		ForestaFulfillment: pallet_foresta_fulfillment,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// Storage keys where the mock handlers record the messages they accept
+pub const PRICES_KEY: &[u8] = b":test:prices";
+pub const MRV_KEY: &[u8] = b":test:mrv";
+pub const PAYMENT_KEY: &[u8] = b":test:payment";
+
+/// Weight used by the mock handlers per price or per byte of reading and proof
+pub const HANDLER_WEIGHT_PER_ITEM: u64 = 1_000;
+
+/// Records the prices, prices of zero are rejected after being recorded
+pub struct MockPriceHandler;
+impl PriceUpdateHandler<u64, u32> for MockPriceHandler {
+	fn handle_price_update(
+		processor: &u64,
+		prices: Vec<(u32, u128)>,
+	) -> Result<Weight, DispatchError> {
+		unhashed::put(PRICES_KEY, &(processor, &prices));
+		ensure!(prices.iter().all(|(_, price)| *price > 0), DispatchError::Other("zero price"));
+		Ok(Weight::from_parts(HANDLER_WEIGHT_PER_ITEM * prices.len() as u64, 0))
+	}
+
+	fn max_weight() -> Weight {
+		Weight::from_parts(HANDLER_WEIGHT_PER_ITEM * MaxPrices::get() as u64, 0)
+	}
+}
+
+/// Records the readings, empty readings are rejected
+pub struct MockMrvHandler;
+impl MrvReadingHandler<u64, u32> for MockMrvHandler {
+	fn handle_mrv_reading(
+		processor: &u64,
+		project_id: u32,
+		timestamp: u64,
+		reading: Vec<u8>,
+	) -> Result<Weight, DispatchError> {
+		ensure!(!reading.is_empty(), DispatchError::Other("empty reading"));
+		let weight = Weight::from_parts(HANDLER_WEIGHT_PER_ITEM * reading.len() as u64, 0);
+		unhashed::put(MRV_KEY, &(processor, project_id, timestamp, reading));
+		Ok(weight)
+	}
+
+	fn max_weight() -> Weight {
+		Weight::from_parts(HANDLER_WEIGHT_PER_ITEM * MaxReadingLength::get() as u64, 0)
+	}
+}
+
+/// Records the payment proofs
+pub struct MockPaymentHandler;
+impl PaymentProofHandler<u64> for MockPaymentHandler {
+	fn handle_payment_proof(
+		processor: &u64,
+		order_id: u128,
+		chain_id: u32,
+		tx_proof: Vec<u8>,
+	) -> Result<Weight, DispatchError> {
+		let weight = Weight::from_parts(HANDLER_WEIGHT_PER_ITEM * tx_proof.len() as u64, 0);
+		unhashed::put(PAYMENT_KEY, &(processor, order_id, chain_id, tx_proof));
+		Ok(weight)
+	}
+
+	fn max_weight() -> Weight {
+		Weight::from_parts(HANDLER_WEIGHT_PER_ITEM * MaxProofLength::get() as u64, 0)
+	}
+}
+
+parameter_types! {
+	pub const MaxPrices: u32 = 2;
+	pub const MaxReadingLength: u32 = 8;
+	pub const MaxProofLength: u32 = 8;
+	pub const MaxProcessors: u32 = 2;
+}
+
+impl pallet_foresta_fulfillment::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ForceOrigin = EnsureRoot<u64>;
+	type FeedId = u32;
+	type ProjectId = u32;
+	type MaxPrices = MaxPrices;
+	type MaxReadingLength = MaxReadingLength;
+	type MaxProofLength = MaxProofLength;
+	type MaxProcessors = MaxProcessors;
+	type PriceHandler = MockPriceHandler;
+	type MrvHandler = MockMrvHandler;
+	type PaymentHandler = MockPaymentHandler;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,192 @@
+use crate::{mock::*, Error, Event, FailureReason, FulfillmentMessage, MessageKind, WeightInfo};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, storage::unhashed, weights::Weight};
+use sp_runtime::DispatchError;
+
+const PROCESSOR: u64 = 1;
+
+fn setup_processor() {
+	assert_ok!(ForestaFulfillment::add_processor(RuntimeOrigin::root(), PROCESSOR));
+}
+
+fn price_update(prices: Vec<(u32, u128)>) -> Vec<u8> {
+	FulfillmentMessage::<Test>::PriceUpdate { prices: prices.try_into().unwrap() }.encode()
+}
+
+#[test]
+fn add_and_remove_processor_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ForestaFulfillment::add_processor(RuntimeOrigin::signed(1), PROCESSOR),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ForestaFulfillment::add_processor(RuntimeOrigin::root(), PROCESSOR));
+		System::assert_last_event(Event::ProcessorAdded { processor: PROCESSOR }.into());
+		assert_eq!(ForestaFulfillment::processors().into_inner(), vec![PROCESSOR]);
+
+		assert_noop!(
+			ForestaFulfillment::add_processor(RuntimeOrigin::root(), PROCESSOR),
+			Error::<Test>::ProcessorAlreadyExists
+		);
+		assert_ok!(ForestaFulfillment::add_processor(RuntimeOrigin::root(), 2));
+		assert_noop!(
+			ForestaFulfillment::add_processor(RuntimeOrigin::root(), 3),
+			Error::<Test>::TooManyProcessors
+		);
+
+		assert_noop!(
+			ForestaFulfillment::remove_processor(RuntimeOrigin::signed(1), PROCESSOR),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ForestaFulfillment::remove_processor(RuntimeOrigin::root(), PROCESSOR));
+		System::assert_last_event(Event::ProcessorRemoved { processor: PROCESSOR }.into());
+		assert_eq!(ForestaFulfillment::processors().into_inner(), vec![2]);
+		assert_noop!(
+			ForestaFulfillment::remove_processor(RuntimeOrigin::root(), PROCESSOR),
+			Error::<Test>::ProcessorNotFound
+		);
+	});
+}
+
+#[test]
+fn fulfillments_are_routed_to_their_handler() {
+	new_test_ext().execute_with(|| {
+		setup_processor();
+
+		ForestaFulfillment::handle_fulfillment(PROCESSOR, &price_update(vec![(0, 100), (1, 5)]));
+		System::assert_last_event(
+			Event::FulfillmentProcessed { processor: PROCESSOR, kind: MessageKind::PriceUpdate }
+				.into(),
+		);
+		assert_eq!(
+			unhashed::get::<(u64, Vec<(u32, u128)>)>(PRICES_KEY),
+			Some((PROCESSOR, vec![(0, 100), (1, 5)]))
+		);
+
+		let reading = FulfillmentMessage::<Test>::MrvReading {
+			project_id: 1000,
+			timestamp: 1_700_000_000_000,
+			reading: b"ndvi:0.7".to_vec().try_into().unwrap(),
+		};
+		ForestaFulfillment::handle_fulfillment(PROCESSOR, &reading.encode());
+		System::assert_last_event(
+			Event::FulfillmentProcessed { processor: PROCESSOR, kind: MessageKind::MrvReading }
+				.into(),
+		);
+		assert_eq!(
+			unhashed::get::<(u64, u32, u64, Vec<u8>)>(MRV_KEY),
+			Some((PROCESSOR, 1000, 1_700_000_000_000, b"ndvi:0.7".to_vec()))
+		);
+
+		let proof = FulfillmentMessage::<Test>::PaymentProof {
+			order_id: 7,
+			chain_id: 0,
+			tx_proof: b"pi_123".to_vec().try_into().unwrap(),
+		};
+		ForestaFulfillment::handle_fulfillment(PROCESSOR, &proof.encode());
+		System::assert_last_event(
+			Event::FulfillmentProcessed { processor: PROCESSOR, kind: MessageKind::PaymentProof }
+				.into(),
+		);
+		assert_eq!(
+			unhashed::get::<(u64, u128, u32, Vec<u8>)>(PAYMENT_KEY),
+			Some((PROCESSOR, 7, 0, b"pi_123".to_vec()))
+		);
+	});
+}
+
+#[test]
+fn failed_fulfillments_are_reported_in_events() {
+	new_test_ext().execute_with(|| {
+		// fulfillments of unknown processors are ignored
+		ForestaFulfillment::handle_fulfillment(PROCESSOR, &price_update(vec![(0, 100)]));
+		System::assert_last_event(
+			Event::FulfillmentFailed {
+				processor: PROCESSOR,
+				reason: FailureReason::UnknownProcessor,
+			}
+			.into(),
+		);
+		assert_eq!(unhashed::get_raw(PRICES_KEY), None);
+
+		setup_processor();
+
+		// payloads must decode to a message entirely
+		let mut trailing = price_update(vec![(0, 100)]);
+		trailing.push(0);
+		// empty, unknown message, trailing bytes and truncated
+		for payload in [vec![], vec![3], trailing, vec![0]] {
+			ForestaFulfillment::handle_fulfillment(PROCESSOR, &payload);
+			System::assert_last_event(
+				Event::FulfillmentFailed {
+					processor: PROCESSOR,
+					reason: FailureReason::InvalidPayload,
+				}
+				.into(),
+			);
+		}
+
+		// messages exceeding the bounds are invalid
+		let mut too_many_prices = vec![0_u8];
+		too_many_prices.extend(vec![(0_u32, 1_u128); 3].encode());
+		ForestaFulfillment::handle_fulfillment(PROCESSOR, &too_many_prices);
+		System::assert_last_event(
+			Event::FulfillmentFailed {
+				processor: PROCESSOR,
+				reason: FailureReason::InvalidPayload,
+			}
+			.into(),
+		);
+		assert_eq!(unhashed::get_raw(PRICES_KEY), None);
+
+		// the changes of a handler rejecting the message are reverted
+		ForestaFulfillment::handle_fulfillment(PROCESSOR, &price_update(vec![(0, 100), (1, 0)]));
+		System::assert_last_event(
+			Event::FulfillmentFailed {
+				processor: PROCESSOR,
+				reason: FailureReason::Rejected {
+					kind: MessageKind::PriceUpdate,
+					error: DispatchError::Other("zero price"),
+				},
+			}
+			.into(),
+		);
+		assert_eq!(unhashed::get_raw(PRICES_KEY), None);
+	});
+}
+
+#[test]
+fn fulfillment_weight_includes_the_handler_weight() {
+	new_test_ext().execute_with(|| {
+		let base = <() as WeightInfo>::handle_fulfillment();
+		let per_item = Weight::from_parts(HANDLER_WEIGHT_PER_ITEM, 0);
+
+		// the largest handler is the MRV one with readings of 8 bytes
+		assert_eq!(ForestaFulfillment::max_fulfillment_weight(), base + per_item.saturating_mul(8));
+
+		// nothing is handled for unknown processors
+		assert_eq!(ForestaFulfillment::handle_fulfillment(PROCESSOR, &price_update(vec![])), base);
+
+		setup_processor();
+		assert_eq!(
+			ForestaFulfillment::handle_fulfillment(PROCESSOR, &price_update(vec![(0, 100)])),
+			base + per_item
+		);
+		let proof = FulfillmentMessage::<Test>::PaymentProof {
+			order_id: 7,
+			chain_id: 0,
+			tx_proof: b"pi_123".to_vec().try_into().unwrap(),
+		};
+		assert_eq!(
+			ForestaFulfillment::handle_fulfillment(PROCESSOR, &proof.encode()),
+			base + per_item.saturating_mul(6)
+		);
+
+		// a rejected message is charged the most its handler can use
+		assert_eq!(
+			ForestaFulfillment::handle_fulfillment(PROCESSOR, &price_update(vec![(0, 0)])),
+			base + per_item.saturating_mul(2)
+		);
+	});
+}
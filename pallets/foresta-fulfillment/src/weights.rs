@@ -0,0 +1,67 @@
+//! Weights for pallet_foresta_fulfillment
+//!
+//! The storage accesses are taken from the benchmarks in `benchmarking.rs`, regenerate this file
+//! with the benchmark CLI to refresh the execution times.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use frame_support::sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_foresta_fulfillment.
+pub trait WeightInfo {
+	fn add_processor() -> Weight;
+	fn remove_processor() -> Weight;
+	fn handle_fulfillment() -> Weight;
+}
+
+/// Weights for pallet_foresta_fulfillment using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ForestaFulfillment Processors (r:1 w:1)
+	fn add_processor() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ForestaFulfillment Processors (r:1 w:1)
+	fn remove_processor() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ForestaFulfillment Processors (r:1 w:0)
+	fn handle_fulfillment() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: ForestaFulfillment Processors (r:1 w:1)
+	fn add_processor() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ForestaFulfillment Processors (r:1 w:1)
+	fn remove_processor() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ForestaFulfillment Processors (r:1 w:0)
+	fn handle_fulfillment() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+	}
+}
@@ -0,0 +1,144 @@
+//! Benchmarking setup for pallet-foresta-ocw
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::Pallet as ForestaOcw;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{traits::UnfilteredDispatchable, unsigned::ValidateUnsigned};
+use frame_system::RawOrigin;
+use sp_runtime::RuntimeAppPublic;
+use sp_std::{vec, vec::Vec};
+
+const PRICE: Price = 1_000;
+
+/// A feed with the longest URL and JSON path
+fn max_feed<T: Config>() -> FeedDefinition<T> {
+	FeedDefinition {
+		url: vec![b'u'; T::MaxUrlLength::get() as usize].try_into().unwrap(),
+		json_path: vec![b'p'; T::MaxJsonPathLength::get() as usize].try_into().unwrap(),
+		decimals: 6,
+		heartbeat: 100_u32.into(),
+	}
+}
+
+/// The authorities other than the caller of the benchmarks
+fn other_authorities<T: Config>(count: u32) -> Vec<T::AccountId> {
+	(0..count).map(|i| account("authority", i, 0)).collect()
+}
+
+/// Fill the authorities with `caller` and other accounts, and create `feeds` feeds where every
+/// other authority has a fresh submission
+fn setup_feeds<T: Config>(caller: &T::AccountId, feeds: u32)
+where
+	T::FeedId: From<u32>,
+{
+	let others = other_authorities::<T>(T::MaxAuthorities::get() - 1);
+	let mut authorities = others.clone();
+	authorities.push(caller.clone());
+	Authorities::<T>::put(BoundedVec::truncate_from(authorities));
+
+	let now = frame_system::Pallet::<T>::block_number();
+	for feed_id in 0..feeds {
+		Feeds::<T>::insert(T::FeedId::from(feed_id), max_feed::<T>());
+		let submissions: Vec<Submission<T>> = others
+			.iter()
+			.map(|who| Submission { who: who.clone(), price: PRICE, submitted_at: now })
+			.collect();
+		Submissions::<T>::insert(T::FeedId::from(feed_id), BoundedVec::truncate_from(submissions));
+	}
+}
+
+/// Generate an authority key in the keystore
+fn generate_authority_key<T: Config>() -> T::Public {
+	let public: <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic =
+		<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(
+			None,
+		)
+		.into();
+	public.into()
+}
+
+benchmarks! {
+	where_clause { where T::FeedId: From<u32> }
+
+	submit_prices {
+		let n in 1 .. T::MaxFeeds::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_feeds::<T>(&caller, n);
+		let prices: Vec<(T::FeedId, Price)> = (0..n).map(|i| (T::FeedId::from(i), PRICE)).collect();
+		let prices: BoundedVec<_, T::MaxFeeds> = prices.try_into().unwrap();
+	}: _(RawOrigin::Signed(caller), prices)
+	verify {
+		for i in 0..n {
+			assert!(AggregatedPrices::<T>::contains_key(T::FeedId::from(i)));
+		}
+	}
+
+	add_authority {
+		let authorities = other_authorities::<T>(T::MaxAuthorities::get() - 1);
+		Authorities::<T>::put(BoundedVec::truncate_from(authorities));
+		let authority: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Root, authority.clone())
+	verify {
+		assert!(Authorities::<T>::get().contains(&authority));
+	}
+
+	remove_authority {
+		let authority: T::AccountId = whitelisted_caller();
+		setup_feeds::<T>(&authority, 0);
+	}: _(RawOrigin::Root, authority.clone())
+	verify {
+		assert!(!Authorities::<T>::get().contains(&authority));
+	}
+
+	set_feed {
+		for feed_id in 1..T::MaxFeeds::get() {
+			Feeds::<T>::insert(T::FeedId::from(feed_id), max_feed::<T>());
+		}
+	}: _(RawOrigin::Root, T::FeedId::from(0), max_feed::<T>())
+	verify {
+		assert_eq!(Feeds::<T>::count(), T::MaxFeeds::get());
+	}
+
+	remove_feed {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_feeds::<T>(&caller, 1);
+		ForestaOcw::<T>::submit_prices(
+			RawOrigin::Signed(caller).into(),
+			BoundedVec::truncate_from(vec![(T::FeedId::from(0), PRICE)]),
+		)?;
+	}: _(RawOrigin::Root, T::FeedId::from(0))
+	verify {
+		assert!(!Feeds::<T>::contains_key(T::FeedId::from(0)));
+		assert!(!AggregatedPrices::<T>::contains_key(T::FeedId::from(0)));
+	}
+
+	submit_prices_unsigned_with_signed_payload {
+		let n in 1 .. T::MaxFeeds::get();
+		let public = generate_authority_key::<T>();
+		setup_feeds::<T>(&public.clone().into_account(), n);
+		let prices: Vec<(T::FeedId, Price)> = (0..n).map(|i| (T::FeedId::from(i), PRICE)).collect();
+		let prices_payload = PricesPayload::<T> {
+			block_number: frame_system::Pallet::<T>::block_number(),
+			prices: prices.try_into().unwrap(),
+			public: public.clone(),
+		};
+		let signature = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::sign(
+			&prices_payload.encode(),
+			public,
+		)
+		.ok_or("cannot sign the payload")?;
+		let call = Call::<T>::submit_prices_unsigned_with_signed_payload { prices_payload, signature };
+	}: {
+		// unsigned transactions are validated again before dispatch, verifying the signature
+		ForestaOcw::<T>::pre_dispatch(&call).map_err(|_| "invalid unsigned transaction")?;
+		call.dispatch_bypass_filter(RawOrigin::None.into())?;
+	}
+	verify {
+		for i in 0..n {
+			assert!(AggregatedPrices::<T>::contains_key(T::FeedId::from(i)));
+		}
+	}
+
+	impl_benchmark_test_suite!(ForestaOcw, crate::tests::new_benchmark_ext(), crate::tests::Test);
+}
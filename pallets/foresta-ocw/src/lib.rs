@@ -25,15 +25,19 @@
 //!
 //! ## Overview
 //!
-//! In this example we are going to build a very simplistic, naive and definitely NOT
-//! production-ready oracle for BTC/USD price. The main goal is to showcase how to use off-chain
-//! workers to fetch data from external sources via HTTP and feed it back on-chain.
+//! The pallet maintains a set of keyed price feeds, e.g. carbon credit benchmark prices per
+//! registry and vintage or FX rates of stablecoins. Every feed is defined on-chain by root with the
+//! URL to query, the path of the price in the JSON response, the number of decimals of the price
+//! and a heartbeat after which a price is considered stale.
 //!
-//! The OCW will be triggered after every block, fetch the current price and prepare either signed
-//! or unsigned transaction to feed the result back on chain. The on-chain logic will simply
-//! aggregate the results and store last `64` values to compute the average price.
+//! The OCW will be triggered after every block, fetch the price of every feed and prepare either a
+//! signed transaction or an unsigned transaction with a signed payload, as selected by
+//! `PriceSubmission`, to feed the results back on chain. The on-chain logic keeps the latest price
+//! submitted by every authority and aggregates the fresh ones into their median, which is exposed
+//! to other pallets through `orml_traits::DataProvider` once `MinSubmissions` authorities submitted
+//! a fresh price.
 //!
-//! Only authorized keys are allowed to submit the price. The authorization key should be rotated.
+//! Only authorized keys are allowed to submit prices. The authorization key should be rotated.
 //!
 //! Here's an example of how a node admin can inject some keys into the keystore:
 //!
@@ -61,20 +65,42 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::Get;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 use frame_system::{
 	self as system,
-	offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+	offchain::{
+		AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+		SignedPayload, Signer,
+	},
+	pallet_prelude::BlockNumberFor,
 };
-use lite_json::json::JsonValue;
+use lite_json::json::{JsonValue, NumberValue};
+use orml_traits::{data_provider::median, DataProvider, DataProviderExtended, TimestampedValue};
+use scale_info::{prelude::string::String, TypeInfo};
 use sp_core::crypto::KeyTypeId;
-use sp_runtime::offchain::{
-	http,
-	storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
-	Duration,
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
+		Duration,
+	},
+	traits::{IdentifyAccount, Saturating, UniqueSaturatedInto},
+	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
+	RuntimeDebug,
 };
 use sp_std::vec::Vec;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migration;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::WeightInfo;
+
 /// Defines application identifier for crypto keys of this module.
 ///
 /// Every module that deals with signatures needs to declare its unique identifier for its crypto
@@ -119,11 +145,44 @@ pub mod crypto {
 
 pub use pallet::*;
 
-#[frame_support::pallet(dev_mode)]
+/// Prices are fixed point numbers with the number of decimals of their feed
+pub type Price = u128;
+
+/// How the offchain workers submit the fetched prices.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SubmissionMode {
+	/// Signed transactions from every local authority key, fees are refunded.
+	Signed,
+	/// A single unsigned transaction with a payload signed by one local authority key.
+	UnsignedSignedPayload,
+}
+
+/// Payload used by the unsigned transactions to submit prices.
+#[derive(
+	CloneNoBound, Encode, Decode, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct PricesPayload<T: Config> {
+	/// The block at which the prices were fetched.
+	pub block_number: BlockNumberFor<T>,
+	/// The fetched prices.
+	pub prices: BoundedVec<(T::FeedId, Price), T::MaxFeeds>,
+	/// The authority key that signed the payload.
+	pub public: T::Public,
+}
+
+impl<T: Config> SignedPayload<T> for PricesPayload<T> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+#[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Zero;
 
 	/// This pallet's configuration trait
 	#[pallet::config(with_default)]
@@ -145,33 +204,142 @@ pub mod pallet {
 		#[pallet::constant]
 		type GracePeriod: Get<BlockNumberFor<Self>>;
 
-		/// Maximum number of prices.
+		/// The key of a price feed.
+		#[pallet::no_default]
+		type FeedId: Parameter + Member + MaxEncodedLen + Copy + Ord;
+
+		/// How the offchain workers submit the prices.
+		#[pallet::no_default]
+		#[pallet::constant]
+		type PriceSubmission: Get<SubmissionMode>;
+
+		/// A configuration for base priority of unsigned transactions.
+		///
+		/// This is exposed so that it can be tuned for particular runtime, when multiple pallets
+		/// send unsigned transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// Maximum number of feeds.
+		#[pallet::constant]
+		type MaxFeeds: Get<u32>;
+
+		/// Maximum length of the URL of a feed.
+		#[pallet::constant]
+		type MaxUrlLength: Get<u32>;
+
+		/// Maximum length of the JSON path of a feed.
 		#[pallet::constant]
-		type MaxPrices: Get<u32>;
+		type MaxJsonPathLength: Get<u32>;
 
 		/// Maximum number of authorities.
 		#[pallet::constant]
 		type MaxAuthorities: Get<u32>;
+
+		/// Minimum number of fresh submissions of a feed needed to update its aggregated price.
+		#[pallet::constant]
+		type MinSubmissions: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		#[pallet::no_default]
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Definition of a price feed.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct FeedDefinition<T: Config> {
+		/// The URL queried by the offchain workers.
+		pub url: BoundedVec<u8, T::MaxUrlLength>,
+		/// Dot separated path of the price in the JSON response, array items are selected by
+		/// their index, e.g. `data.0.price`.
+		pub json_path: BoundedVec<u8, T::MaxJsonPathLength>,
+		/// Number of decimals of the prices of the feed.
+		pub decimals: u8,
+		/// Number of blocks after which a price of the feed is stale.
+		pub heartbeat: BlockNumberFor<T>,
+	}
+
+	/// Latest price submitted by an authority for a feed.
+	#[derive(
+		CloneNoBound,
+		Encode,
+		Decode,
+		EqNoBound,
+		PartialEqNoBound,
+		RuntimeDebugNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Submission<T: Config> {
+		pub who: T::AccountId,
+		pub price: Price,
+		pub submitted_at: BlockNumberFor<T>,
+	}
+
+	/// Median of the fresh submissions of a feed.
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AggregatedPrice<BlockNumber> {
+		pub value: Price,
+		pub updated_at: BlockNumber,
 	}
 
 	/// Events for the pallet.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event generated when new price is accepted to contribute to the average.
-		NewPrice { price: u32, maybe_who: Option<T::AccountId> },
+		/// Event generated when a new price is submitted for a feed.
+		NewPrice { feed_id: T::FeedId, price: Price, who: T::AccountId },
+		/// Event generated when the aggregated price of a feed is updated.
+		AggregatedPriceUpdated { feed_id: T::FeedId, value: Price },
 		/// Event generated when a new authority is added.
 		AuthorityAdded { authority: T::AccountId },
 		/// Event generated when an authority is removed.
 		AuthorityRemoved { authority: T::AccountId },
+		/// Event generated when a feed is created or updated.
+		FeedSet { feed_id: T::FeedId, definition: FeedDefinition<T> },
+		/// Event generated when a feed is removed.
+		FeedRemoved { feed_id: T::FeedId },
 	}
 
-	/// A vector of recently submitted prices.
-	///
-	/// This is used to calculate average price, should have bounded size.
+	/// The price feeds queried by the offchain workers.
 	#[pallet::storage]
-	#[pallet::getter(fn prices)]
-	pub(super) type Prices<T: Config> = StorageValue<_, BoundedVec<u32, T::MaxPrices>, ValueQuery>;
+	#[pallet::getter(fn feeds)]
+	pub(super) type Feeds<T: Config> =
+		CountedStorageMap<_, Twox64Concat, T::FeedId, FeedDefinition<T>>;
+
+	/// The latest price submitted by every authority for a feed.
+	#[pallet::storage]
+	#[pallet::getter(fn submissions)]
+	pub(super) type Submissions<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::FeedId,
+		BoundedVec<Submission<T>, T::MaxAuthorities>,
+		ValueQuery,
+	>;
+
+	/// The median of the fresh submissions of a feed.
+	#[pallet::storage]
+	#[pallet::getter(fn aggregated_price)]
+	pub(super) type AggregatedPrices<T: Config> =
+		StorageMap<_, Twox64Concat, T::FeedId, AggregatedPrice<BlockNumberFor<T>>>;
+
+	/// The block at which the prices of the last unsigned submission of an authority were fetched.
+	#[pallet::storage]
+	#[pallet::getter(fn last_unsigned_submission)]
+	pub(super) type LastUnsignedSubmission<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BlockNumberFor<T>>;
 
 	/// Authorities allowed to submit the price.
 	#[pallet::storage]
@@ -184,9 +352,19 @@ pub mod pallet {
 		NotAuthority,
 		AlreadyAuthority,
 		TooManyAuthorities,
+		/// The feed does not exist.
+		FeedNotFound,
+		/// The URL and the JSON path must be set and the heartbeat must not be zero.
+		InvalidFeed,
+		/// The maximum number of feeds has been reached.
+		TooManyFeeds,
 	}
 
+	/// The current storage version, the prices are kept per feed since version 1
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
@@ -216,12 +394,6 @@ pub mod pallet {
 			let parent_hash = <system::Pallet<T>>::block_hash(block_number - 1u32.into());
 			log::debug!("Current block: {:?} (parent hash: {:?})", block_number, parent_hash);
 
-			// It's a good practice to keep `fn offchain_worker()` function minimal, and move most
-			// of the code to separate `impl` block. Here we call a helper function to calculate
-			// current average price. This function reads storage entries of the current state.
-			let average: Option<u32> = Self::average_price();
-			log::debug!("Current price: {:?}", average);
-
 			// Start off by creating a reference to Local Storage value. Since the local storage is
 			// common for all offchain workers, it's a good practice to prepend your entry with the
 			// module name.
@@ -254,7 +426,13 @@ pub mod pallet {
 				// The value has been set correctly, which means we can safely send a transaction
 				// now.
 				Ok(_) => {
-					if let Err(e) = Self::fetch_price_and_send_signed() {
+					let res = match T::PriceSubmission::get() {
+						SubmissionMode::Signed => Self::fetch_prices_and_send_signed(),
+						SubmissionMode::UnsignedSignedPayload => {
+							Self::fetch_prices_and_send_unsigned_with_signed_payload(block_number)
+						},
+					};
+					if let Err(e) = res {
 						log::error!("Error: {}", e);
 					}
 				},
@@ -277,22 +455,27 @@ pub mod pallet {
 	/// A public part of the pallet.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Submit new price to the list.
+		/// Submit new prices for the feeds.
 		///
 		/// This method is a public function of the module and can be called from within a
-		/// transaction. It appends given `price` to current list of prices. In our example the
-		/// `offchain worker` will create, sign & submit a transaction that calls this function
-		/// passing the price.
+		/// transaction. It records the given prices as the latest ones of the caller and updates
+		/// the aggregated price of the feeds. In our example the `offchain worker` will create,
+		/// sign & submit a transaction that calls this function passing the prices.
 		///
 		/// This only works if the caller is in `Authorities`.
 		#[pallet::call_index(0)]
-		pub fn submit_price(origin: OriginFor<T>, price: u32) -> DispatchResultWithPostInfo {
+		#[pallet::weight(T::WeightInfo::submit_prices(prices.len() as u32))]
+		pub fn submit_prices(
+			origin: OriginFor<T>,
+			prices: BoundedVec<(T::FeedId, Price), T::MaxFeeds>,
+		) -> DispatchResultWithPostInfo {
 			// Retrieve sender of the transaction.
 			let who = ensure_signed(origin)?;
 
-			match Self::is_authority(&who) {
-				true => Self::add_price(Some(who), price),
-				false => return Err(Error::<T>::NotAuthority.into()),
+			ensure!(Self::is_authority(&who), Error::<T>::NotAuthority);
+
+			for (feed_id, price) in prices {
+				Self::add_price(&who, feed_id, price)?;
 			}
 
 			// Authorized OCWs don't need to pay fees
@@ -300,6 +483,7 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::add_authority())]
 		pub fn add_authority(
 			origin: OriginFor<T>,
 			authority: T::AccountId,
@@ -322,6 +506,7 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::remove_authority())]
 		pub fn remove_authority(
 			origin: OriginFor<T>,
 			authority: T::AccountId,
@@ -337,11 +522,117 @@ pub mod pallet {
 			};
 
 			Authorities::<T>::set(authorities);
+			LastUnsignedSubmission::<T>::remove(&authority);
 
-			Self::deposit_event(Event::AuthorityAdded { authority });
+			Self::deposit_event(Event::AuthorityRemoved { authority });
 
 			Ok(().into())
 		}
+
+		/// Create or update a price feed.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_feed())]
+		pub fn set_feed(
+			origin: OriginFor<T>,
+			feed_id: T::FeedId,
+			definition: FeedDefinition<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(
+				!definition.url.is_empty() &&
+					!definition.json_path.is_empty() &&
+					!definition.heartbeat.is_zero(),
+				Error::<T>::InvalidFeed
+			);
+			ensure!(
+				Feeds::<T>::contains_key(feed_id) || Feeds::<T>::count() < T::MaxFeeds::get(),
+				Error::<T>::TooManyFeeds
+			);
+
+			Feeds::<T>::insert(feed_id, definition.clone());
+
+			Self::deposit_event(Event::FeedSet { feed_id, definition });
+
+			Ok(().into())
+		}
+
+		/// Remove a price feed along with its submissions and aggregated price.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::remove_feed())]
+		pub fn remove_feed(origin: OriginFor<T>, feed_id: T::FeedId) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(Feeds::<T>::contains_key(feed_id), Error::<T>::FeedNotFound);
+
+			Feeds::<T>::remove(feed_id);
+			Submissions::<T>::remove(feed_id);
+			AggregatedPrices::<T>::remove(feed_id);
+
+			Self::deposit_event(Event::FeedRemoved { feed_id });
+
+			Ok(().into())
+		}
+
+		/// Submit new prices for the feeds using an unsigned transaction.
+		///
+		/// The payload is signed by an authority key, the signature and the authority are checked
+		/// when validating the transaction, see `ValidateUnsigned`. An authority can submit once
+		/// every `GracePeriod` blocks.
+		///
+		/// The weight scales with the number of prices and covers the verification of the
+		/// signature, which is done again when the transaction is validated before dispatch.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			T::WeightInfo::submit_prices_unsigned_with_signed_payload(
+				prices_payload.prices.len() as u32,
+			)
+		)]
+		pub fn submit_prices_unsigned_with_signed_payload(
+			origin: OriginFor<T>,
+			prices_payload: PricesPayload<T>,
+			_signature: T::Signature,
+		) -> DispatchResultWithPostInfo {
+			// This ensures that the function can only be called via unsigned transaction.
+			ensure_none(origin)?;
+
+			let who = prices_payload.public.into_account();
+			ensure!(Self::is_authority(&who), Error::<T>::NotAuthority);
+
+			for (feed_id, price) in prices_payload.prices {
+				Self::add_price(&who, feed_id, price)?;
+			}
+			LastUnsignedSubmission::<T>::insert(&who, prices_payload.block_number);
+
+			Ok(Pays::No.into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Validate unsigned call to this module.
+		///
+		/// By default unsigned transactions are disallowed, but implementing the validator here we
+		/// make sure that the payload is signed by an authority that did not submit prices during
+		/// the last `GracePeriod` blocks.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::submit_prices_unsigned_with_signed_payload {
+				prices_payload: ref payload,
+				ref signature,
+			} = call
+			{
+				let signature_valid =
+					SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone());
+				if !signature_valid {
+					return InvalidTransaction::BadProof.into();
+				}
+				Self::validate_transaction_parameters(&payload.block_number, &payload.public)
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
 	}
 
 }
@@ -351,31 +642,57 @@ impl<T: Config> Pallet<T> {
 		<Authorities<T>>::get().contains(who)
 	}
 
-	/// A helper function to fetch the price and send signed transaction.
-	fn fetch_price_and_send_signed() -> Result<(), &'static str> {
+	/// Whether a price recorded at `recorded_at` is stale at block `now`.
+	fn is_stale(
+		recorded_at: BlockNumberFor<T>,
+		heartbeat: BlockNumberFor<T>,
+		now: BlockNumberFor<T>,
+	) -> bool {
+		now > recorded_at.saturating_add(heartbeat)
+	}
+
+	/// Fetch the prices of all the feeds, the feeds whose price cannot be fetched are skipped.
+	fn fetch_prices() -> Result<BoundedVec<(T::FeedId, Price), T::MaxFeeds>, &'static str> {
+		// Make an external HTTP request for every feed to fetch its current price. Note these
+		// calls will block until the responses are received.
+		let prices: BoundedVec<(T::FeedId, Price), T::MaxFeeds> = Feeds::<T>::iter()
+			.filter_map(|(feed_id, feed)| match Self::fetch_price(&feed) {
+				Ok(price) => Some((feed_id, price)),
+				Err(e) => {
+					log::warn!("Failed to fetch price of feed {:?}: {:?}", feed_id, e);
+					None
+				},
+			})
+			.take(T::MaxFeeds::get() as usize)
+			.collect::<Vec<_>>()
+			.try_into()
+			.map_err(|_| "Too many feeds")?;
+		if prices.is_empty() {
+			return Err("No price fetched");
+		}
+
+		Ok(prices)
+	}
+
+	/// A helper function to fetch the prices of all the feeds and send signed transaction.
+	fn fetch_prices_and_send_signed() -> Result<(), &'static str> {
 		let signer = Signer::<T, T::AuthorityId>::all_accounts();
 		if !signer.can_sign() {
 			return Err(
 				"No local accounts available. Consider adding one via `author_insertKey` RPC.",
 			);
 		}
-		// Make an external HTTP request to fetch the current price. Note this call will block until
-		// response is received.
-		let price = Self::fetch_price().map_err(|_| "Failed to fetch price")?;
+		let prices = Self::fetch_prices()?;
 
 		// Using `send_signed_transaction` associated type we create and submit a transaction
 		// representing the call, we've just created. Submit signed will return a vector of results
 		// for all accounts that were found in the local keystore with expected `KEY_TYPE`.
-		let results = signer.send_signed_transaction(|_account| {
-			// Received price is wrapped into a call to `submit_price` public function of this
-			// pallet. This means that the transaction, when executed, will simply call that
-			// function passing `price` as an argument.
-			Call::submit_price { price }
-		});
+		let results = signer
+			.send_signed_transaction(|_account| Call::submit_prices { prices: prices.clone() });
 
 		for (acc, res) in &results {
 			match res {
-				Ok(()) => log::info!("[{:?}] Submitted price of {} cents", acc.id, price),
+				Ok(()) => log::info!("[{:?}] Submitted prices of {} feeds", acc.id, prices.len()),
 				Err(e) => log::error!("[{:?}] Failed to submit transaction: {:?}", acc.id, e),
 			}
 		}
@@ -383,18 +700,91 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Fetch current price and return the result in cents.
-	fn fetch_price() -> Result<u32, http::Error> {
+	/// A helper function to fetch the prices of all the feeds and send an unsigned transaction
+	/// with a payload signed by one of the local authority keys.
+	fn fetch_prices_and_send_unsigned_with_signed_payload(
+		block_number: BlockNumberFor<T>,
+	) -> Result<(), &'static str> {
+		let prices = Self::fetch_prices()?;
+
+		// -- Sign using any account
+		let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+			.send_unsigned_transaction(
+				|account| PricesPayload {
+					block_number,
+					prices: prices.clone(),
+					public: account.public.clone(),
+				},
+				|payload, signature| Call::submit_prices_unsigned_with_signed_payload {
+					prices_payload: payload,
+					signature,
+				},
+			)
+			.ok_or("No local accounts available.")?;
+		result.map_err(|()| "Unable to submit transaction")?;
+
+		Ok(())
+	}
+
+	/// Check that the payload is signed by an authority, was fetched during the last grace period
+	/// and is newer than the last submission of the authority by at least the grace period, then
+	/// build the validity of the transaction.
+	fn validate_transaction_parameters(
+		block_number: &BlockNumberFor<T>,
+		public: &T::Public,
+	) -> TransactionValidity {
+		let who = public.clone().into_account();
+		if !Self::is_authority(&who) {
+			return InvalidTransaction::BadSigner.into();
+		}
+		// Let's make sure to reject transactions from the future.
+		let current_block = <system::Pallet<T>>::block_number();
+		if &current_block < block_number {
+			return InvalidTransaction::Future.into();
+		}
+		// Payloads fetched before the last grace period are too old to be replayed.
+		if *block_number < current_block.saturating_sub(T::GracePeriod::get()) {
+			return InvalidTransaction::Stale.into();
+		}
+		// One submission per authority every `GracePeriod` blocks, counted from the block the
+		// prices were fetched at, so an older payload cannot be submitted again.
+		if let Some(last) = LastUnsignedSubmission::<T>::get(&who) {
+			if *block_number <= last || *block_number < last.saturating_add(T::GracePeriod::get()) {
+				return InvalidTransaction::Stale.into();
+			}
+		}
+
+		ValidTransaction::with_tag_prefix("ForestaOcw")
+			// We set base priority for the transactions, it can be tuned per runtime when several
+			// pallets send unsigned transactions.
+			.priority(T::UnsignedPriority::get())
+			// We set the `provides` tag to the authority and the block of the payload, so that a
+			// single transaction per authority and payload is kept in the pool.
+			.and_provides((who, *block_number))
+			// The transaction is only valid for the grace period, after which the offchain worker
+			// sends a new one.
+			.longevity(T::GracePeriod::get().unique_saturated_into())
+			// It's fine to propagate that transaction to other peers, which means it can be
+			// created even by nodes that don't produce blocks.
+			.propagate(true)
+			.build()
+	}
+
+	/// Fetch the current price of a feed and return it with the decimals of the feed.
+	fn fetch_price(feed: &FeedDefinition<T>) -> Result<Price, http::Error> {
 		// We want to keep the offchain worker execution time reasonable, so we set a hard-coded
 		// deadline to 2s to complete the external call. You can also wait indefinitely for the
 		// response, however you may still get a timeout coming from the host machine.
 		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(2_000));
+		let url = sp_std::str::from_utf8(&feed.url).map_err(|_| {
+			log::warn!("No UTF8 url");
+			http::Error::Unknown
+		})?;
 		// Initiate an external HTTP GET request. This is using high-level wrappers from
 		// `sp_runtime`, for the low-level calls that you can find in `sp_io`. The API is trying to
 		// be similar to `request`, but since we are running in a custom WASM execution environment
 		// we can't simply import the library here.
-		let request =
-			http::Request::get("https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD");
+		let request = http::Request::get(url);
 		// We set the deadline for sending of the request, note that awaiting response can have a
 		// separate deadline. Next we send the request, before that it's also possible to alter
 		// request headers or stream body content in case of non-GET requests.
@@ -423,7 +813,7 @@ impl<T: Config> Pallet<T> {
 			http::Error::Unknown
 		})?;
 
-		let price = match Self::parse_price(body_str) {
+		let price = match Self::parse_price(body_str, &feed.json_path, feed.decimals) {
 			Some(price) => Ok(price),
 			None => {
 				log::warn!("Unable to extract price from the response: {:?}", body_str);
@@ -431,20 +821,31 @@ impl<T: Config> Pallet<T> {
 			},
 		}?;
 
-		log::warn!("Got price: {} cents", price);
+		log::info!("Got price: {} with {} decimals", price, feed.decimals);
 
 		Ok(price)
 	}
 
-	/// Parse the price from the given JSON string using `lite-json`.
+	/// Parse the price at `json_path` in the given JSON string using `lite-json`.
 	///
-	/// Returns `None` when parsing failed or `Some(price in cents)` when parsing is successful.
-	fn parse_price(price_str: &str) -> Option<u32> {
-		let val = lite_json::parse_json(price_str);
-		let price = match val.ok()? {
-			JsonValue::Object(obj) => {
-				let (_, v) = obj.into_iter().find(|(k, _)| k.iter().copied().eq("USD".chars()))?;
-				match v {
+	/// Returns `None` when parsing failed or `Some(price with decimals)` when parsing is
+	/// successful. Prices given as JSON strings are accepted too.
+	fn parse_price(price_str: &str, json_path: &[u8], decimals: u8) -> Option<Price> {
+		let mut val = lite_json::parse_json(price_str).ok()?;
+		for segment in sp_std::str::from_utf8(json_path).ok()?.split('.') {
+			val = match val {
+				JsonValue::Object(obj) => {
+					obj.into_iter().find(|(k, _)| k.iter().copied().eq(segment.chars()))?.1
+				},
+				JsonValue::Array(items) => items.into_iter().nth(segment.parse().ok()?)?,
+				_ => return None,
+			};
+		}
+
+		let number = match val {
+			JsonValue::Number(number) => number,
+			JsonValue::String(chars) => {
+				match lite_json::parse_json(&chars.into_iter().collect::<String>()).ok()? {
 					JsonValue::Number(number) => number,
 					_ => return None,
 				}
@@ -452,33 +853,85 @@ impl<T: Config> Pallet<T> {
 			_ => return None,
 		};
 
-		let exp = price.fraction_length.saturating_sub(2);
-		Some(price.integer as u32 * 100 + (price.fraction / 10_u64.pow(exp)) as u32)
+		Self::to_fixed_point(number, decimals)
 	}
 
-	/// Add new price to the list.
-	fn add_price(maybe_who: Option<T::AccountId>, price: u32) {
-		log::info!("Adding to the average: {}", price);
-		<Prices<T>>::mutate(|prices| {
-			if prices.try_push(price).is_err() {
-				prices[(price % T::MaxPrices::get()) as usize] = price;
-			}
-		});
+	/// Convert a JSON number to a fixed point number with `decimals` decimals, the extra digits
+	/// are truncated.
+	fn to_fixed_point(number: NumberValue, decimals: u8) -> Option<Price> {
+		if number.negative {
+			return None;
+		}
 
-		let average = Self::average_price()
-			.expect("The average is not empty, because it was just mutated; qed");
-		log::info!("Current average price is: {}", average);
-		// here we are raising the NewPrice event
-		Self::deposit_event(Event::NewPrice { price, maybe_who });
+		let digits = Price::from(number.integer)
+			.checked_mul(10_u128.checked_pow(number.fraction_length)?)?
+			.checked_add(number.fraction.into())?;
+		let shift = decimals as i32 + number.exponent - number.fraction_length as i32;
+		if shift >= 0 {
+			digits.checked_mul(10_u128.checked_pow(shift as u32)?)
+		} else {
+			Some(10_u128.checked_pow(shift.unsigned_abs()).map_or(0, |divisor| digits / divisor))
+		}
 	}
 
-	/// Calculate current average price.
-	fn average_price() -> Option<u32> {
-		let prices = <Prices<T>>::get();
-		if prices.is_empty() {
-			None
-		} else {
-			Some(prices.iter().fold(0_u32, |a, b| a.saturating_add(*b)) / prices.len() as u32)
+	/// Record the price submitted by an authority and update the aggregated price of the feed.
+	fn add_price(
+		who: &T::AccountId,
+		feed_id: T::FeedId,
+		price: Price,
+	) -> frame_support::dispatch::DispatchResult {
+		let feed = Feeds::<T>::get(feed_id).ok_or(Error::<T>::FeedNotFound)?;
+		let now = <system::Pallet<T>>::block_number();
+		let authorities = <Authorities<T>>::get();
+
+		let prices = Submissions::<T>::mutate(feed_id, |submissions| {
+			// only the fresh submissions of the current authorities are kept
+			submissions.retain(|s| {
+				&s.who != who &&
+					authorities.contains(&s.who) &&
+					!Self::is_stale(s.submitted_at, feed.heartbeat, now)
+			});
+			// every authority has at most one submission so the bound cannot be exceeded
+			let _ = submissions.try_push(Submission { who: who.clone(), price, submitted_at: now });
+			submissions.iter().map(|s| s.price).collect::<Vec<_>>()
+		});
+
+		log::info!("Adding price {} to feed {:?}", price, feed_id);
+		Self::deposit_event(Event::NewPrice { feed_id, price, who: who.clone() });
+
+		// the aggregated price is only updated once enough authorities agree on it
+		if prices.len() < T::MinSubmissions::get() as usize {
+			return Ok(());
 		}
+		if let Some(value) = median(prices) {
+			AggregatedPrices::<T>::insert(feed_id, AggregatedPrice { value, updated_at: now });
+			Self::deposit_event(Event::AggregatedPriceUpdated { feed_id, value });
+		}
+
+		Ok(())
+	}
+}
+
+/// The aggregated prices, `None` is returned for stale prices.
+impl<T: Config> DataProvider<T::FeedId, Price> for Pallet<T> {
+	fn get(feed_id: &T::FeedId) -> Option<Price> {
+		let feed = Feeds::<T>::get(feed_id)?;
+		let aggregated = AggregatedPrices::<T>::get(feed_id)?;
+		let now = <system::Pallet<T>>::block_number();
+		(!Self::is_stale(aggregated.updated_at, feed.heartbeat, now)).then_some(aggregated.value)
+	}
+}
+
+/// The aggregated prices with the block they were updated at, stale prices included.
+impl<T: Config> DataProviderExtended<T::FeedId, TimestampedValue<Price, BlockNumberFor<T>>>
+	for Pallet<T>
+{
+	fn get_no_op(feed_id: &T::FeedId) -> Option<TimestampedValue<Price, BlockNumberFor<T>>> {
+		AggregatedPrices::<T>::get(feed_id)
+			.map(|price| TimestampedValue { value: price.value, timestamp: price.updated_at })
+	}
+
+	fn get_all_values() -> Vec<(T::FeedId, Option<TimestampedValue<Price, BlockNumberFor<T>>>)> {
+		Feeds::<T>::iter_keys().map(|feed_id| (feed_id, Self::get_no_op(&feed_id))).collect()
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+use super::*;
+
+pub mod v1 {
+	use super::*;
+
+	use frame_support::{
+		pallet_prelude::*,
+		sp_std::marker::PhantomData,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	/// The flat vector of BTC prices before version 1
+	#[frame_support::storage_alias]
+	type Prices<T: Config> = StorageValue<Pallet<T>, Vec<u32>, ValueQuery>;
+
+	/// Remove the BTC prices kept before the keyed feeds, the feeds are set by root after the
+	/// upgrade.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 1 {
+				log::info!("foresta-ocw migration to v1 skipped, already applied");
+				return T::DbWeight::get().reads(1);
+			}
+
+			Prices::<T>::kill();
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!("foresta-ocw migration to v1 done, prices removed");
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+	}
+}
@@ -0,0 +1,645 @@
+use crate as pallet_foresta_ocw;
+use crate::*;
+use codec::Decode;
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{ConstU32, ConstU64, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	BoundedVec,
+};
+use orml_traits::{DataProvider, DataProviderExtended, TimestampedValue};
+use sp_core::{
+	offchain::{testing, OffchainWorkerExt, TransactionPoolExt},
+	sr25519::Signature,
+	H256,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{
+	testing::TestXt,
+	traits::{
+		BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, ValidateUnsigned,
+		Verify,
+	},
+	transaction_validity::TransactionSource,
+	BuildStorage, DispatchError, RuntimeAppPublic,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// For testing the module, we construct a mock runtime.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		ForestaOcw: pallet_foresta_ocw,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Block = Block;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = sp_core::sr25519::Public;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+type Extrinsic = TestXt<RuntimeCall, ()>;
+type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub const GracePeriod: u64 = 5;
+	pub const PriceSubmission: SubmissionMode = SubmissionMode::Signed;
+	pub const UnsignedPriority: u64 = 1 << 20;
+	pub static MinSubmissions: u32 = 1;
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AuthorityId = crypto::TestAuthId;
+	type GracePeriod = GracePeriod;
+	type FeedId = u32;
+	type PriceSubmission = PriceSubmission;
+	type UnsignedPriority = UnsignedPriority;
+	type MaxFeeds = ConstU32<2>;
+	type MaxUrlLength = ConstU32<128>;
+	type MaxJsonPathLength = ConstU32<32>;
+	type MaxAuthorities = ConstU32<5>;
+	type MinSubmissions = MinSubmissions;
+	type WeightInfo = ();
+}
+
+const BTC_FEED: u32 = 0;
+const USDC_FEED: u32 = 1;
+const BTC_URL: &str = "https://min-api.cryptocompare.com/data/price?fsym=BTC&tsyms=USD";
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// The benchmarks sign payloads with keys generated in the keystore
+#[cfg(feature = "runtime-benchmarks")]
+pub(crate) fn new_benchmark_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+	ext
+}
+
+fn account(seed: u8) -> AccountId {
+	sp_core::sr25519::Public::from_raw([seed; 32])
+}
+
+fn feed(url: &str, json_path: &str, decimals: u8, heartbeat: u64) -> FeedDefinition<Test> {
+	FeedDefinition {
+		url: url.as_bytes().to_vec().try_into().unwrap(),
+		json_path: json_path.as_bytes().to_vec().try_into().unwrap(),
+		decimals,
+		heartbeat,
+	}
+}
+
+fn prices(prices: Vec<(u32, Price)>) -> BoundedVec<(u32, Price), ConstU32<2>> {
+	prices.try_into().unwrap()
+}
+
+fn setup_authorities(authorities: &[AccountId]) {
+	for authority in authorities {
+		assert_ok!(ForestaOcw::add_authority(RuntimeOrigin::root(), *authority));
+	}
+}
+
+#[test]
+fn parse_price_works() {
+	let test_data = [
+		("{\"USD\":6536.92}", "USD", 2, Some(653692)),
+		("{\"USD\":6536.924565}", "USD", 2, Some(653692)),
+		("{\"USD\":65.92}", "USD", 4, Some(659200)),
+		("{\"USD\":\"1.0002\"}", "USD", 6, Some(1000200)),
+		("{\"data\":[{\"price\":12.5},{\"price\":13}]}", "data.1.price", 1, Some(130)),
+		("{\"rate\":1.5e3}", "rate", 0, Some(1500)),
+		("{\"rate\":25e-1}", "rate", 2, Some(250)),
+		("{\"USD\":-1.5}", "USD", 2, None),
+		("{\"USD\":\"abc\"}", "USD", 2, None),
+		("{\"USD\":6536.92}", "EUR", 2, None),
+		("{\"data\":[1,2]}", "data.2", 2, None),
+		("{\"data\":[1,2]}", "data.x", 2, None),
+		("{\"USD\":}", "USD", 2, None),
+	];
+
+	for (json, path, decimals, expected) in test_data {
+		assert_eq!(
+			expected,
+			ForestaOcw::parse_price(json, path.as_bytes(), decimals),
+			"{} at {}",
+			json,
+			path
+		);
+	}
+}
+
+#[test]
+fn set_and_remove_feed_works() {
+	new_test_ext().execute_with(|| {
+		let definition = feed(BTC_URL, "USD", 2, 10);
+
+		assert_noop!(
+			ForestaOcw::set_feed(RuntimeOrigin::signed(account(1)), BTC_FEED, definition.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ForestaOcw::set_feed(RuntimeOrigin::root(), BTC_FEED, feed("", "USD", 2, 10)),
+			Error::<Test>::InvalidFeed
+		);
+		assert_noop!(
+			ForestaOcw::set_feed(RuntimeOrigin::root(), BTC_FEED, feed(BTC_URL, "USD", 2, 0)),
+			Error::<Test>::InvalidFeed
+		);
+
+		assert_ok!(ForestaOcw::set_feed(RuntimeOrigin::root(), BTC_FEED, definition.clone()));
+		System::assert_last_event(
+			Event::FeedSet { feed_id: BTC_FEED, definition: definition.clone() }.into(),
+		);
+		assert_eq!(ForestaOcw::feeds(BTC_FEED), Some(definition.clone()));
+
+		// updating an existing feed does not count against the limit
+		assert_ok!(ForestaOcw::set_feed(RuntimeOrigin::root(), USDC_FEED, definition.clone()));
+		assert_ok!(ForestaOcw::set_feed(RuntimeOrigin::root(), USDC_FEED, definition.clone()));
+		assert_noop!(
+			ForestaOcw::set_feed(RuntimeOrigin::root(), 2, definition),
+			Error::<Test>::TooManyFeeds
+		);
+
+		setup_authorities(&[account(1)]);
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(1)),
+			prices(vec![(BTC_FEED, 100)])
+		));
+
+		assert_ok!(ForestaOcw::remove_feed(RuntimeOrigin::root(), BTC_FEED));
+		System::assert_last_event(Event::FeedRemoved { feed_id: BTC_FEED }.into());
+		assert_eq!(ForestaOcw::feeds(BTC_FEED), None);
+		assert!(ForestaOcw::submissions(BTC_FEED).is_empty());
+		assert_eq!(ForestaOcw::aggregated_price(BTC_FEED), None);
+		assert_noop!(
+			ForestaOcw::remove_feed(RuntimeOrigin::root(), BTC_FEED),
+			Error::<Test>::FeedNotFound
+		);
+	});
+}
+
+#[test]
+fn submitted_prices_are_aggregated_into_their_median() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			BTC_FEED,
+			feed(BTC_URL, "USD", 2, 10)
+		));
+		setup_authorities(&[account(1), account(2), account(3)]);
+
+		assert_noop!(
+			ForestaOcw::submit_prices(
+				RuntimeOrigin::signed(account(4)),
+				prices(vec![(BTC_FEED, 100)])
+			),
+			Error::<Test>::NotAuthority
+		);
+		assert_noop!(
+			ForestaOcw::submit_prices(
+				RuntimeOrigin::signed(account(1)),
+				prices(vec![(USDC_FEED, 100)])
+			),
+			Error::<Test>::FeedNotFound
+		);
+
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(1)),
+			prices(vec![(BTC_FEED, 100)])
+		));
+		System::assert_has_event(
+			Event::NewPrice { feed_id: BTC_FEED, price: 100, who: account(1) }.into(),
+		);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(100));
+
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(2)),
+			prices(vec![(BTC_FEED, 300)])
+		));
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(3)),
+			prices(vec![(BTC_FEED, 120)])
+		));
+		System::assert_last_event(
+			Event::AggregatedPriceUpdated { feed_id: BTC_FEED, value: 120 }.into(),
+		);
+
+		// a new submission replaces the previous one of the authority
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(1)),
+			prices(vec![(BTC_FEED, 200)])
+		));
+		assert_eq!(ForestaOcw::submissions(BTC_FEED).len(), 3);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(200));
+
+		// removed authorities no longer count
+		assert_ok!(ForestaOcw::remove_authority(RuntimeOrigin::root(), account(2)));
+		System::assert_last_event(Event::AuthorityRemoved { authority: account(2) }.into());
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(3)),
+			prices(vec![(BTC_FEED, 120)])
+		));
+		assert_eq!(ForestaOcw::submissions(BTC_FEED).len(), 2);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(200));
+	});
+}
+
+#[test]
+fn stale_prices_are_detected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			BTC_FEED,
+			feed(BTC_URL, "USD", 2, 10)
+		));
+		setup_authorities(&[account(1), account(2)]);
+
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(1)),
+			prices(vec![(BTC_FEED, 100)])
+		));
+
+		System::set_block_number(11);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(100));
+
+		// past the heartbeat the price is stale but remains available with its timestamp
+		System::set_block_number(12);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), None);
+		assert_eq!(
+			<ForestaOcw as DataProviderExtended<u32, TimestampedValue<Price, u64>>>::get_no_op(
+				&BTC_FEED
+			),
+			Some(TimestampedValue { value: 100, timestamp: 1 })
+		);
+		assert_eq!(
+			<ForestaOcw as DataProviderExtended<u32, TimestampedValue<Price, u64>>>::get_all_values(
+			),
+			vec![(BTC_FEED, Some(TimestampedValue { value: 100, timestamp: 1 }))]
+		);
+
+		// stale submissions are left out of the median
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(2)),
+			prices(vec![(BTC_FEED, 300)])
+		));
+		assert_eq!(ForestaOcw::submissions(BTC_FEED).len(), 1);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(300));
+	});
+}
+
+#[test]
+fn aggregated_price_needs_the_minimum_submissions() {
+	new_test_ext().execute_with(|| {
+		MinSubmissions::set(2);
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			BTC_FEED,
+			feed(BTC_URL, "USD", 2, 10)
+		));
+		setup_authorities(&[account(1), account(2), account(3)]);
+
+		// a single authority cannot set the price
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(1)),
+			prices(vec![(BTC_FEED, 100)])
+		));
+		assert_eq!(ForestaOcw::aggregated_price(BTC_FEED), None);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), None);
+
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(2)),
+			prices(vec![(BTC_FEED, 120)])
+		));
+		assert!(ForestaOcw::aggregated_price(BTC_FEED).is_some());
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(3)),
+			prices(vec![(BTC_FEED, 110)])
+		));
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(110));
+
+		// once the other submissions are stale the price is no longer updated
+		System::set_block_number(12);
+		assert_ok!(ForestaOcw::submit_prices(
+			RuntimeOrigin::signed(account(1)),
+			prices(vec![(BTC_FEED, 500)])
+		));
+		assert_eq!(ForestaOcw::submissions(BTC_FEED).len(), 1);
+		assert_eq!(
+			ForestaOcw::aggregated_price(BTC_FEED),
+			Some(AggregatedPrice { value: 110, updated_at: 1 })
+		);
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), None);
+	});
+}
+
+#[test]
+fn migration_to_v1_removes_the_old_prices() {
+	new_test_ext().execute_with(|| {
+		let prices_key = frame_support::storage::storage_prefix(b"ForestaOcw", b"Prices");
+		frame_support::storage::unhashed::put(&prices_key, &vec![6536_u32, 6540]);
+		StorageVersion::new(0).put::<ForestaOcw>();
+
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(ForestaOcw::on_chain_storage_version(), 1);
+		assert!(!frame_support::storage::unhashed::exists(&prices_key));
+	});
+}
+
+#[test]
+fn should_make_http_call_and_parse_result() {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let mut t = sp_io::TestExternalities::default();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+
+	price_oracle_response(&mut state.write(), BTC_URL, "{\"USD\": 155.23}");
+
+	t.execute_with(|| {
+		// when
+		let price = ForestaOcw::fetch_price(&feed(BTC_URL, "USD", 2, 10)).unwrap();
+		// then
+		assert_eq!(price, 15523);
+	});
+}
+
+#[test]
+fn should_submit_signed_transaction_on_chain() {
+	const PHRASE: &str =
+		"news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	keystore
+		.sr25519_generate_new(crate::crypto::Public::ID, Some(&format!("{}/hunter1", PHRASE)))
+		.unwrap();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	t.register_extension(KeystoreExt::new(keystore));
+
+	let usdc_url = "https://api.example.com/fx/usdc";
+	price_oracle_response(&mut offchain_state.write(), BTC_URL, "{\"USD\": 155.23}");
+	price_oracle_response(
+		&mut offchain_state.write(),
+		usdc_url,
+		"{\"data\":{\"rate\":\"0.9998\"}}",
+	);
+
+	t.execute_with(|| {
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			BTC_FEED,
+			feed(BTC_URL, "USD", 2, 10)
+		));
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			USDC_FEED,
+			feed(usdc_url, "data.rate", 6, 10)
+		));
+
+		// when
+		ForestaOcw::fetch_prices_and_send_signed().unwrap();
+		// then
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature.unwrap().0, 0);
+		match tx.call {
+			RuntimeCall::ForestaOcw(crate::Call::submit_prices { prices }) => {
+				let mut prices = prices.into_inner();
+				prices.sort();
+				assert_eq!(prices, vec![(BTC_FEED, 15523), (USDC_FEED, 999800)]);
+			},
+			_ => panic!("submit_prices call expected"),
+		}
+	});
+}
+
+#[test]
+fn should_submit_unsigned_transaction_with_signed_payload_on_chain() {
+	const PHRASE: &str =
+		"news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+	let (offchain, offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	keystore
+		.sr25519_generate_new(crate::crypto::Public::ID, Some(&format!("{}/hunter1", PHRASE)))
+		.unwrap();
+	let public_key = *keystore.sr25519_public_keys(crate::crypto::Public::ID).get(0).unwrap();
+
+	let mut t = new_test_ext();
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	t.register_extension(KeystoreExt::new(keystore));
+
+	price_oracle_response(&mut offchain_state.write(), BTC_URL, "{\"USD\": 155.23}");
+
+	t.execute_with(|| {
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			BTC_FEED,
+			feed(BTC_URL, "USD", 2, 10)
+		));
+
+		// when
+		ForestaOcw::fetch_prices_and_send_unsigned_with_signed_payload(1).unwrap();
+		// then
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		match tx.call {
+			RuntimeCall::ForestaOcw(crate::Call::submit_prices_unsigned_with_signed_payload {
+				prices_payload: body,
+				signature,
+			}) => {
+				assert_eq!(body.prices.into_inner(), vec![(BTC_FEED, 15523)]);
+				assert_eq!(body.public, public_key.into());
+				assert_eq!(body.block_number, 1);
+
+				let signature_valid = <PricesPayload<Test> as SignedPayload<Test>>::verify::<
+					crypto::TestAuthId,
+				>(&body, signature);
+				assert!(signature_valid);
+			},
+			_ => panic!("submit_prices_unsigned_with_signed_payload call expected"),
+		}
+	});
+}
+
+#[test]
+fn unsigned_submissions_are_validated() {
+	const PHRASE: &str =
+		"news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+	let keystore = MemoryKeystore::new();
+	let public = keystore
+		.sr25519_generate_new(crate::crypto::Public::ID, Some(&format!("{}/hunter1", PHRASE)))
+		.unwrap();
+	let authority: AccountId = public.into();
+
+	let mut t = new_test_ext();
+	t.register_extension(KeystoreExt::new(keystore));
+
+	t.execute_with(|| {
+		assert_ok!(ForestaOcw::set_feed(
+			RuntimeOrigin::root(),
+			BTC_FEED,
+			feed(BTC_URL, "USD", 2, 10)
+		));
+		let payload = PricesPayload::<Test> {
+			block_number: 1,
+			prices: prices(vec![(BTC_FEED, 15523)]),
+			public: public.into(),
+		};
+		let signature: Signature = payload
+			.using_encoded(|bytes| crypto::Public::from(public).sign(&bytes))
+			.unwrap()
+			.into();
+		let call = crate::Call::submit_prices_unsigned_with_signed_payload {
+			prices_payload: payload.clone(),
+			signature: signature.clone(),
+		};
+		let validate = |call: &crate::Call<Test>| {
+			<ForestaOcw as ValidateUnsigned>::validate_unsigned(TransactionSource::External, call)
+		};
+
+		// the signer must be an authority
+		assert_eq!(validate(&call), InvalidTransaction::BadSigner.into());
+		setup_authorities(&[authority]);
+
+		let valid = validate(&call).unwrap();
+		assert_eq!(valid.priority, UnsignedPriority::get());
+		assert_eq!(valid.longevity, GracePeriod::get());
+		assert!(valid.propagate);
+
+		// the signature must match the payload
+		let mut forged = payload.clone();
+		forged.prices = prices(vec![(BTC_FEED, 1)]);
+		assert_eq!(
+			validate(&crate::Call::submit_prices_unsigned_with_signed_payload {
+				prices_payload: forged,
+				signature: signature.clone(),
+			}),
+			InvalidTransaction::BadProof.into()
+		);
+
+		// the submission must not come from the future
+		System::set_block_number(0);
+		assert_eq!(validate(&call), InvalidTransaction::Future.into());
+		System::set_block_number(1);
+
+		assert_noop!(
+			ForestaOcw::submit_prices_unsigned_with_signed_payload(
+				RuntimeOrigin::signed(authority),
+				payload.clone(),
+				signature.clone(),
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ForestaOcw::submit_prices_unsigned_with_signed_payload(
+			RuntimeOrigin::none(),
+			payload.clone(),
+			signature,
+		));
+		assert_eq!(ForestaOcw::last_unsigned_submission(authority), Some(1));
+		assert_eq!(<ForestaOcw as DataProvider<u32, Price>>::get(&BTC_FEED), Some(15523));
+
+		let call_at = |block_number: u64| {
+			let payload = PricesPayload::<Test> { block_number, ..payload.clone() };
+			let signature: Signature = payload
+				.using_encoded(|bytes| crypto::Public::from(public).sign(&bytes))
+				.unwrap()
+				.into();
+			crate::Call::submit_prices_unsigned_with_signed_payload {
+				prices_payload: payload,
+				signature,
+			}
+		};
+
+		// a submitted payload cannot be replayed
+		System::set_block_number(6);
+		assert_eq!(validate(&call), InvalidTransaction::Stale.into());
+
+		// one submission per authority every grace period
+		assert_eq!(validate(&call_at(5)), InvalidTransaction::Stale.into());
+		let valid = validate(&call_at(6)).unwrap();
+		assert_eq!(valid.provides, vec![("ForestaOcw", (authority, 6_u64)).encode()]);
+
+		// payloads fetched before the last grace period are rejected
+		System::set_block_number(12);
+		assert_eq!(validate(&call_at(6)), InvalidTransaction::Stale.into());
+		assert!(validate(&call_at(7)).is_ok());
+
+		assert_ok!(ForestaOcw::remove_authority(RuntimeOrigin::root(), authority));
+		assert_eq!(ForestaOcw::last_unsigned_submission(authority), None);
+	});
+}
+
+fn price_oracle_response(state: &mut testing::OffchainState, uri: &str, body: &str) {
+	state.expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: uri.into(),
+		response: Some(body.as_bytes().to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
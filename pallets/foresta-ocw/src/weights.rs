@@ -0,0 +1,156 @@
+//! Weights for pallet_foresta_ocw
+//!
+//! The storage accesses are taken from the benchmarks in `benchmarking.rs`, regenerate this file
+//! with the benchmark CLI to refresh the execution times.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_foresta_ocw.
+pub trait WeightInfo {
+	fn submit_prices(n: u32, ) -> Weight;
+	fn add_authority() -> Weight;
+	fn remove_authority() -> Weight;
+	fn set_feed() -> Weight;
+	fn remove_feed() -> Weight;
+	fn submit_prices_unsigned_with_signed_payload(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_foresta_ocw using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ForestaOCW Authorities (r:1 w:0)
+	// Storage: ForestaOCW Feeds (r:32 w:0)
+	// Storage: ForestaOCW Submissions (r:32 w:32)
+	// Storage: ForestaOCW AggregatedPrices (r:0 w:32)
+	/// The range of component `n` is `[1, 32]`.
+	fn submit_prices(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(14_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	// Storage: ForestaOCW Authorities (r:1 w:1)
+	fn add_authority() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: ForestaOCW Authorities (r:1 w:1)
+	// Storage: ForestaOCW LastUnsignedSubmission (r:0 w:1)
+	fn remove_authority() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: ForestaOCW Feeds (r:1 w:1)
+	// Storage: ForestaOCW CounterForFeeds (r:1 w:1)
+	fn set_feed() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: ForestaOCW Feeds (r:1 w:1)
+	// Storage: ForestaOCW CounterForFeeds (r:1 w:1)
+	// Storage: ForestaOCW Submissions (r:0 w:1)
+	// Storage: ForestaOCW AggregatedPrices (r:0 w:1)
+	fn remove_feed() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: ForestaOCW Authorities (r:1 w:0)
+	// Storage: ForestaOCW LastUnsignedSubmission (r:1 w:1)
+	// Storage: ForestaOCW Feeds (r:32 w:0)
+	// Storage: ForestaOCW Submissions (r:32 w:32)
+	// Storage: ForestaOCW AggregatedPrices (r:0 w:32)
+	/// The range of component `n` is `[1, 32]`.
+	fn submit_prices_unsigned_with_signed_payload(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(68_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: ForestaOCW Authorities (r:1 w:0)
+	// Storage: ForestaOCW Feeds (r:32 w:0)
+	// Storage: ForestaOCW Submissions (r:32 w:32)
+	// Storage: ForestaOCW AggregatedPrices (r:0 w:32)
+	/// The range of component `n` is `[1, 32]`.
+	fn submit_prices(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(14_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	// Storage: ForestaOCW Authorities (r:1 w:1)
+	fn add_authority() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// Storage: ForestaOCW Authorities (r:1 w:1)
+	// Storage: ForestaOCW LastUnsignedSubmission (r:0 w:1)
+	fn remove_authority() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: ForestaOCW Feeds (r:1 w:1)
+	// Storage: ForestaOCW CounterForFeeds (r:1 w:1)
+	fn set_feed() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: ForestaOCW Feeds (r:1 w:1)
+	// Storage: ForestaOCW CounterForFeeds (r:1 w:1)
+	// Storage: ForestaOCW Submissions (r:0 w:1)
+	// Storage: ForestaOCW AggregatedPrices (r:0 w:1)
+	fn remove_feed() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	// Storage: ForestaOCW Authorities (r:1 w:0)
+	// Storage: ForestaOCW LastUnsignedSubmission (r:1 w:1)
+	// Storage: ForestaOCW Feeds (r:32 w:0)
+	// Storage: ForestaOCW Submissions (r:32 w:32)
+	// Storage: ForestaOCW AggregatedPrices (r:0 w:32)
+	/// The range of component `n` is `[1, 32]`.
+	fn submit_prices_unsigned_with_signed_payload(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(68_000_000, 0)
+			// Standard Error: 6_000
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+}
@@ -0,0 +1,167 @@
+//! Benchmarking setup for pallet-general-storage
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::Pallet as GeneralStorage;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+	sp_runtime::traits::{Bounded, Zero},
+	sp_std::{vec, vec::Vec},
+	traits::{Currency, ReservableCurrency},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+
+const PREFIX: &[u8] = b"mrv/";
+
+fn fund<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, CurrencyBalanceOf::<T>::max_value() / 2_u32.into());
+}
+
+/// A key of the maximum length starting with `PREFIX`
+fn max_key<T: Config>(index: u32) -> KeyOf<T> {
+	let mut key = vec![0_u8; T::MaxKeyLength::get() as usize];
+	let len = key.len();
+	key[..PREFIX.len()].copy_from_slice(PREFIX);
+	key[len - 4..].copy_from_slice(&index.to_be_bytes());
+	key.try_into().unwrap()
+}
+
+fn max_value<T: Config>() -> ValueOf<T> {
+	vec![1_u8; T::MaxValueLength::get() as usize].try_into().unwrap()
+}
+
+fn prefix<T: Config>() -> KeyOf<T> {
+	PREFIX.to_vec().try_into().unwrap()
+}
+
+benchmarks! {
+	store_data {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let key = max_key::<T>(0);
+	}: _(RawOrigin::Signed(caller.clone()), key.clone(), max_value::<T>())
+	verify {
+		assert!(StoredData::<T>::contains_key(&caller, &key));
+	}
+
+	clear_data {
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let key = max_key::<T>(0);
+		GeneralStorage::<T>::store_data(RawOrigin::Signed(caller.clone()).into(), key.clone(), max_value::<T>())?;
+	}: _(RawOrigin::Signed(caller.clone()), key.clone())
+	verify {
+		assert!(!StoredData::<T>::contains_key(&caller, &key));
+	}
+
+	grant_access {
+		let owner: T::AccountId = whitelisted_caller();
+		let grantee: T::AccountId = account("grantee", 0, 0);
+		fund::<T>(&owner);
+	}: _(RawOrigin::Signed(owner.clone()), grantee.clone(), prefix::<T>(), true, true, None)
+	verify {
+		assert_eq!(AccessGrants::<T>::get(&owner, &grantee).len(), 1);
+	}
+
+	revoke_access {
+		let owner: T::AccountId = whitelisted_caller();
+		let grantee: T::AccountId = account("grantee", 0, 0);
+		fund::<T>(&owner);
+		GeneralStorage::<T>::grant_access(RawOrigin::Signed(owner.clone()).into(), grantee.clone(), prefix::<T>(), true, true, None)?;
+	}: _(RawOrigin::Signed(owner.clone()), owner.clone(), grantee.clone(), prefix::<T>())
+	verify {
+		assert!(!AccessGrants::<T>::contains_key(&owner, &grantee));
+	}
+
+	store_data_for {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let writer: T::AccountId = whitelisted_caller();
+		fund::<T>(&owner);
+		fund::<T>(&writer);
+		let key = max_key::<T>(0);
+		GeneralStorage::<T>::grant_access(RawOrigin::Signed(owner.clone()).into(), writer.clone(), prefix::<T>(), true, false, None)?;
+		// overwriting an entry of the owner also releases the deposit of the owner
+		GeneralStorage::<T>::store_data(RawOrigin::Signed(owner.clone()).into(), key.clone(), max_value::<T>())?;
+	}: _(RawOrigin::Signed(writer.clone()), owner.clone(), key.clone(), max_value::<T>())
+	verify {
+		assert_eq!(DataDepositors::<T>::get(&owner, &key), Some(writer));
+	}
+
+	clear_data_for {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let writer: T::AccountId = whitelisted_caller();
+		fund::<T>(&owner);
+		fund::<T>(&writer);
+		let key = max_key::<T>(0);
+		GeneralStorage::<T>::grant_access(RawOrigin::Signed(owner.clone()).into(), writer.clone(), prefix::<T>(), true, false, None)?;
+		GeneralStorage::<T>::store_data_for(RawOrigin::Signed(writer.clone()).into(), owner.clone(), key.clone(), max_value::<T>())?;
+	}: _(RawOrigin::Signed(writer), owner.clone(), key.clone())
+	verify {
+		assert!(!StoredData::<T>::contains_key(&owner, &key));
+	}
+
+	attest_data {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let attester: T::AccountId = whitelisted_caller();
+		fund::<T>(&owner);
+		fund::<T>(&attester);
+		let key = max_key::<T>(0);
+		GeneralStorage::<T>::grant_access(RawOrigin::Signed(owner.clone()).into(), attester.clone(), prefix::<T>(), false, true, None)?;
+		GeneralStorage::<T>::store_data(RawOrigin::Signed(owner.clone()).into(), key.clone(), max_value::<T>())?;
+	}: _(RawOrigin::Signed(attester.clone()), owner.clone(), key.clone())
+	verify {
+		assert!(Attestations::<T>::contains_key((&owner, &key, &attester)));
+	}
+
+	store_many {
+		let n in 1 .. T::MaxEntriesPerCall::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		let entries: Vec<(KeyOf<T>, ValueOf<T>)> = (0..n).map(|i| (max_key::<T>(i), max_value::<T>())).collect();
+		let entries: BoundedVec<_, T::MaxEntriesPerCall> = entries.try_into().unwrap();
+	}: _(RawOrigin::Signed(caller.clone()), entries)
+	verify {
+		assert_eq!(StoredData::<T>::iter_prefix(&caller).count() as u32, n);
+	}
+
+	clear_prefix {
+		let n in 1 .. T::MaxEntriesPerCall::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T>(&caller);
+		for i in 0..n {
+			GeneralStorage::<T>::store_data(RawOrigin::Signed(caller.clone()).into(), max_key::<T>(i), max_value::<T>())?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), prefix::<T>(), n, None)
+	verify {
+		assert_eq!(StoredData::<T>::iter_prefix(&caller).count(), 0);
+	}
+
+	reserve_deposit_shortfall {
+		let caller: T::AccountId = whitelisted_caller();
+		let depositor: T::AccountId = account("depositor", 0, 0);
+		fund::<T>(&depositor);
+		let amount = T::DepositPerByte::get() * 10_u32.into();
+		DepositShortfalls::<T>::insert(&depositor, amount);
+	}: _(RawOrigin::Signed(caller), depositor.clone())
+	verify {
+		assert_eq!(T::Currency::reserved_balance(&depositor), amount);
+	}
+
+	revoke_attestation {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let attester: T::AccountId = whitelisted_caller();
+		fund::<T>(&owner);
+		fund::<T>(&attester);
+		let key = max_key::<T>(0);
+		GeneralStorage::<T>::grant_access(RawOrigin::Signed(owner.clone()).into(), attester.clone(), prefix::<T>(), false, true, None)?;
+		GeneralStorage::<T>::store_data(RawOrigin::Signed(owner.clone()).into(), key.clone(), max_value::<T>())?;
+		GeneralStorage::<T>::attest_data(RawOrigin::Signed(attester.clone()).into(), owner.clone(), key.clone())?;
+	}: _(RawOrigin::Signed(attester.clone()), owner.clone(), key.clone())
+	verify {
+		assert!(!Attestations::<T>::contains_key((&owner, &key, &attester)));
+		assert!(T::Currency::reserved_balance(&attester).is_zero());
+	}
+
+	impl_benchmark_test_suite!(GeneralStorage, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -11,17 +11,31 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migration;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
+	use codec::{EncodeLike, Input, Output};
 	use frame_support::{
 		pallet_prelude::*,
-		sp_runtime::traits::Zero,
+		sp_runtime::traits::{Hash, Saturating, Zero},
+		sp_std::{vec, vec::Vec},
 		traits::{Currency, ReservableCurrency},
 	};
 	use frame_system::pallet_prelude::*;
+	use scale_info::{build::Fields, Path, Type};
 
-	#[pallet::pallet]
+	use crate::WeightInfo;
+
+	/// The current storage version, deposits cover the key bytes since version 1
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -35,8 +49,14 @@ pub mod pallet {
 		type MaxKeyLength: Get<u32>;
 		/// Maximum length of value
 		type MaxValueLength: Get<u32>;
-		/// The deposit charged to store data
+		/// The deposit charged per byte to store data, access grants and attestations
 		type DepositPerByte: Get<CurrencyBalanceOf<Self>>;
+		/// Maximum number of access grants an owner can give to a single account
+		type MaxGrantsPerAccount: Get<u32>;
+		/// Maximum number of entries stored or visited by a single bulk call
+		type MaxEntriesPerCall: Get<u32>;
+		/// Weight information for extrinsics in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
 	// -- Types for representing key and value in pallet -- //
@@ -49,6 +69,73 @@ pub mod pallet {
 	pub type CurrencyBalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	/// Access given by the owner of a namespace to another account on the keys starting with
+	/// `prefix`
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	pub struct AccessGrant<T: Config> {
+		/// The keys covered by the grant
+		pub prefix: KeyOf<T>,
+		/// The grantee can store and clear data under the prefix, paying the deposits
+		pub can_write: bool,
+		/// The grantee can attest the data stored under the prefix
+		pub can_attest: bool,
+		/// The block after which the grant can no longer be used
+		pub expires_at: Option<BlockNumberFor<T>>,
+	}
+
+	impl<T: Config> AccessGrant<T> {
+		/// Whether the grant covers `key` at block `now`
+		pub fn covers(&self, key: &KeyOf<T>, now: BlockNumberFor<T>) -> bool {
+			key.starts_with(&self.prefix) && self.expires_at.map_or(true, |expiry| now <= expiry)
+		}
+	}
+
+	pub type AccessGrantsOf<T> = BoundedVec<AccessGrant<T>, <T as Config>::MaxGrantsPerAccount>;
+
+	/// A key encoded as its bytes, without the length prefix. Used unhashed in storage keys, the
+	/// keys sharing a prefix are then next to each other in the key order.
+	pub struct OrderedKey<S: Get<u32>>(pub BoundedVec<u8, S>);
+
+	impl<S: Get<u32>> Encode for OrderedKey<S> {
+		fn size_hint(&self) -> usize {
+			self.0.len()
+		}
+
+		fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+			dest.write(&self.0)
+		}
+	}
+
+	impl<S: Get<u32>> EncodeLike for OrderedKey<S> {}
+
+	impl<S: Get<u32>> Decode for OrderedKey<S> {
+		fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+			// the key is the last part of the storage key, it takes the remaining bytes
+			let len = input.remaining_len()?.ok_or("OrderedKey needs the input length")?;
+			let mut bytes = vec![0_u8; len];
+			input.read(&mut bytes)?;
+			bytes.try_into().map(Self).map_err(|_| "OrderedKey is too long".into())
+		}
+	}
+
+	impl<S: Get<u32>> MaxEncodedLen for OrderedKey<S> {
+		fn max_encoded_len() -> usize {
+			S::get() as usize
+		}
+	}
+
+	impl<S: Get<u32> + 'static> TypeInfo for OrderedKey<S> {
+		type Identity = Self;
+
+		fn type_info() -> Type {
+			Type::builder()
+				.path(Path::new("OrderedKey", module_path!()))
+				.docs(&["The bytes of a key, without the length prefix"])
+				.composite(Fields::unnamed().field(|f| f.ty::<[u8]>()))
+		}
+	}
+
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/main-docs/build/runtime-storage/
 	#[pallet::storage]
@@ -58,6 +145,66 @@ pub mod pallet {
 	pub type StoredData<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, KeyOf<T>, ValueOf<T>>;
 
+	/// The keys of the entries of each namespace, unhashed so the keys starting with a prefix are
+	/// visited in order by `clear_prefix`
+	#[pallet::storage]
+	pub type OrderedKeys<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Identity,
+		OrderedKey<T::MaxKeyLength>,
+		(),
+	>;
+
+	/// The account that paid the deposit of an entry, when it is not the owner of the namespace
+	#[pallet::storage]
+	pub type DataDepositors<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		KeyOf<T>,
+		T::AccountId,
+	>;
+
+	/// The access grants given by an owner (first key) to a grantee (second key), the owner pays
+	/// a deposit for each grant
+	#[pallet::storage]
+	pub type AccessGrants<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		AccessGrantsOf<T>,
+		ValueQuery,
+	>;
+
+	/// The hash of the value attested by an account and the block of the attestation, the
+	/// attester pays a deposit for each attestation
+	#[pallet::storage]
+	pub type Attestations<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, KeyOf<T>>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		(T::Hash, BlockNumberFor<T>),
+	>;
+
+	/// The raw storage key of the last `StoredData` entry visited by the deposit migration to
+	/// version 1, only set while it runs in `on_idle`
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type DepositMigration<T: Config> = StorageValue<_, Vec<u8>>;
+
+	/// The part of the deposit of an account the deposit migration could not reserve
+	#[pallet::storage]
+	pub type DepositShortfalls<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CurrencyBalanceOf<T>, ValueQuery>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -67,6 +214,37 @@ pub mod pallet {
 		DataStored { key: KeyOf<T>, who: T::AccountId },
 		/// An existing data has been removed
 		DataCleared { key: KeyOf<T>, who: T::AccountId },
+		/// Access to the keys starting with `prefix` has been granted
+		AccessGranted {
+			owner: T::AccountId,
+			grantee: T::AccountId,
+			prefix: KeyOf<T>,
+			can_write: bool,
+			can_attest: bool,
+			expires_at: Option<BlockNumberFor<T>>,
+		},
+		/// An access grant has been revoked
+		AccessRevoked { owner: T::AccountId, grantee: T::AccountId, prefix: KeyOf<T> },
+		/// A grantee has stored data in the namespace of the owner
+		DataStoredByGrantee { owner: T::AccountId, key: KeyOf<T>, writer: T::AccountId },
+		/// A grantee has removed data from the namespace of the owner
+		DataClearedByGrantee { owner: T::AccountId, key: KeyOf<T>, writer: T::AccountId },
+		/// The current value of an entry has been attested
+		DataAttested { owner: T::AccountId, key: KeyOf<T>, attester: T::AccountId, hash: T::Hash },
+		/// An attestation has been revoked by its attester
+		AttestationRevoked { owner: T::AccountId, key: KeyOf<T>, attester: T::AccountId },
+		/// Entries starting with `prefix` have been removed, `last_key` is set when the limit was
+		/// reached and is the cursor to continue from
+		PrefixCleared {
+			who: T::AccountId,
+			prefix: KeyOf<T>,
+			removed: u32,
+			last_key: Option<KeyOf<T>>,
+		},
+		/// The deposit migration could not reserve `amount` of the deposit of `who`
+		DepositShortfall { who: T::AccountId, amount: CurrencyBalanceOf<T> },
+		/// The deposit shortfall of `who` has been reserved
+		DepositShortfallReserved { who: T::AccountId, amount: CurrencyBalanceOf<T> },
 	}
 
 	// Errors inform users that something went wrong.
@@ -76,6 +254,31 @@ pub mod pallet {
 		NoDataStored,
 		/// cannot pass empty key
 		EmptyInput,
+		/// The caller has no valid grant covering the key
+		AccessDenied,
+		/// The owner cannot grant access to itself
+		CannotGrantToSelf,
+		/// The grant expiry is in the past
+		GrantAlreadyExpired,
+		/// The grantee already has the maximum number of grants from the owner
+		TooManyGrants,
+		/// No grant exists for the prefix
+		GrantNotFound,
+		/// Only the owner, the grantee or anyone once expired can revoke a grant
+		NotAllowedToRevoke,
+		/// The limit is above `MaxEntriesPerCall`
+		TooManyEntries,
+		/// The account has no deposit shortfall
+		NoDepositShortfall,
+		/// The caller has not attested the entry
+		AttestationNotFound,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			crate::migration::v1::migrate_deposits::<T>(remaining_weight)
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -86,7 +289,7 @@ pub mod pallet {
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
 		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
 		#[pallet::call_index(0)]
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1).ref_time())]
+		#[pallet::weight(T::WeightInfo::store_data())]
 		pub fn store_data(
 			origin: OriginFor<T>,
 			key: KeyOf<T>,
@@ -94,16 +297,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			ensure!(!key.len().is_zero() && !value.len().is_zero(), Error::<T>::EmptyInput);
-
-			// calculate deposit fee
-			let deposit_amount = T::DepositPerByte::get() * (value.len() as u32).into();
-
-			// reserve deposit fee
-			T::Currency::reserve(&who, deposit_amount)?;
-
-			// Update storage.
-			<StoredData<T>>::insert(who.clone(), key.clone(), value);
+			Self::do_store(&who, &who, &key, value)?;
 
 			// Emit an event.
 			Self::deposit_event(Event::DataStored { key, who });
@@ -113,31 +307,460 @@ pub mod pallet {
 
 		/// An example dispatchable that may throw a custom error.
 		#[pallet::call_index(1)]
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1).ref_time())]
+		#[pallet::weight(T::WeightInfo::clear_data())]
 		pub fn clear_data(origin: OriginFor<T>, key: KeyOf<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			ensure!(!key.len().is_zero(), Error::<T>::EmptyInput);
+			Self::do_clear(&who, &key)?;
 
-			let data = <StoredData<T>>::get(who.clone(), key.clone());
+			// Emit an event.
+			Self::deposit_event(Event::DataCleared { key, who });
 
-			// does some data exist
-			ensure!(data.is_some(), Error::<T>::NoDataStored);
+			Ok(())
+		}
 
-			// calculate deposit fee
-			let deposit_amount =
-				T::DepositPerByte::get() * (data.expect("already checked!").len() as u32).into();
+		/// Grant `grantee` access to the keys of the caller starting with `prefix`, replacing any
+		/// existing grant on the same prefix. An empty prefix covers all the keys.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::grant_access())]
+		pub fn grant_access(
+			origin: OriginFor<T>,
+			grantee: T::AccountId,
+			prefix: KeyOf<T>,
+			can_write: bool,
+			can_attest: bool,
+			expires_at: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			ensure!(owner != grantee, Error::<T>::CannotGrantToSelf);
+			ensure!(can_write || can_attest, Error::<T>::EmptyInput);
+			if let Some(expiry) = expires_at {
+				ensure!(
+					expiry >= frame_system::Pallet::<T>::block_number(),
+					Error::<T>::GrantAlreadyExpired
+				);
+			}
+
+			let grant =
+				AccessGrant::<T> { prefix: prefix.clone(), can_write, can_attest, expires_at };
+			let deposit = Self::grant_deposit(&grantee, &grant);
+			AccessGrants::<T>::try_mutate(&owner, &grantee, |grants| -> DispatchResult {
+				match grants.iter_mut().find(|g| g.prefix == prefix) {
+					Some(existing) => {
+						// only the difference with the deposit of the replaced grant is reserved
+						let previous_deposit = Self::grant_deposit(&grantee, existing);
+						if deposit > previous_deposit {
+							T::Currency::reserve(&owner, deposit - previous_deposit)?;
+						} else {
+							T::Currency::unreserve(&owner, previous_deposit - deposit);
+						}
+						*existing = grant;
+					},
+					None => {
+						grants.try_push(grant).map_err(|_| Error::<T>::TooManyGrants)?;
+						T::Currency::reserve(&owner, deposit)?;
+					},
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AccessGranted {
+				owner,
+				grantee,
+				prefix,
+				can_write,
+				can_attest,
+				expires_at,
+			});
+
+			Ok(())
+		}
+
+		/// Revoke the grant given by `owner` to `grantee` on `prefix`, the deposit is returned to
+		/// the owner. Can be called by the owner, by the grantee, or by anyone once the grant has
+		/// expired.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::revoke_access())]
+		pub fn revoke_access(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			grantee: T::AccountId,
+			prefix: KeyOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			AccessGrants::<T>::try_mutate_exists(&owner, &grantee, |maybe_grants| {
+				let grants = maybe_grants.as_mut().ok_or(Error::<T>::GrantNotFound)?;
+				let index = grants
+					.iter()
+					.position(|g| g.prefix == prefix)
+					.ok_or(Error::<T>::GrantNotFound)?;
+				let expired = grants[index].expires_at.map_or(false, |expiry| now > expiry);
+				ensure!(who == owner || who == grantee || expired, Error::<T>::NotAllowedToRevoke);
+
+				let grant = grants.remove(index);
+				T::Currency::unreserve(&owner, Self::grant_deposit(&grantee, &grant));
+				if grants.is_empty() {
+					*maybe_grants = None;
+				}
+				Ok::<(), DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::AccessRevoked { owner, grantee, prefix });
+
+			Ok(())
+		}
+
+		/// Store data in the namespace of `owner` under a write grant, the caller pays the deposit
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::store_data_for())]
+		pub fn store_data_for(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			key: KeyOf<T>,
+			value: ValueOf<T>,
+		) -> DispatchResult {
+			let writer = ensure_signed(origin)?;
+
+			Self::ensure_access(&owner, &writer, &key, |grant| grant.can_write)?;
+			Self::do_store(&owner, &writer, &key, value)?;
+
+			Self::deposit_event(Event::DataStoredByGrantee { owner, key, writer });
+
+			Ok(())
+		}
+
+		/// Remove data from the namespace of `owner` under a write grant, the deposit is returned
+		/// to the account that paid it
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::clear_data_for())]
+		pub fn clear_data_for(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			key: KeyOf<T>,
+		) -> DispatchResult {
+			let writer = ensure_signed(origin)?;
 
-			// reserve deposit fee
-			T::Currency::unreserve(&who, deposit_amount);
+			Self::ensure_access(&owner, &writer, &key, |grant| grant.can_write)?;
+			Self::do_clear(&owner, &key)?;
+
+			Self::deposit_event(Event::DataClearedByGrantee { owner, key, writer });
+
+			Ok(())
+		}
+
+		/// Attest the current value stored by `owner` under `key`, the hash of the value is
+		/// recorded so later changes of the value are detectable. The attester pays a deposit
+		/// on its first attestation of the entry.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::attest_data())]
+		pub fn attest_data(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			key: KeyOf<T>,
+		) -> DispatchResult {
+			let attester = ensure_signed(origin)?;
+
+			Self::ensure_access(&owner, &attester, &key, |grant| grant.can_attest)?;
+			let value = StoredData::<T>::get(&owner, &key).ok_or(Error::<T>::NoDataStored)?;
+			let hash = T::Hashing::hash(&value);
+			if !Attestations::<T>::contains_key((&owner, &key, &attester)) {
+				T::Currency::reserve(&attester, Self::attestation_deposit(&attester, &key))?;
+			}
+			Attestations::<T>::insert(
+				(&owner, &key, &attester),
+				(hash, frame_system::Pallet::<T>::block_number()),
+			);
+
+			Self::deposit_event(Event::DataAttested { owner, key, attester, hash });
+
+			Ok(())
+		}
+
+		/// Store several entries in the namespace of the caller, a `DataStored` event is emitted
+		/// for every entry
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::store_many(entries.len() as u32))]
+		pub fn store_many(
+			origin: OriginFor<T>,
+			entries: BoundedVec<(KeyOf<T>, ValueOf<T>), T::MaxEntriesPerCall>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(!entries.is_empty(), Error::<T>::EmptyInput);
+
+			for (key, value) in entries {
+				Self::do_store(&who, &who, &key, value)?;
+				Self::deposit_event(Event::DataStored { key, who: who.clone() });
+			}
+
+			Ok(())
+		}
+
+		/// Remove the entries of the caller starting with `prefix`, in key order. At most `limit`
+		/// entries are removed, starting after `start_after` when given. When the limit is reached
+		/// the last removed key is returned in the event so the call can be repeated.
+		///
+		/// While the deposit migration runs the entries written before version 1 are not in
+		/// `OrderedKeys` yet, so the whole namespace is visited in storage order instead and
+		/// `limit` counts the entries visited.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::clear_prefix(*limit))]
+		pub fn clear_prefix(
+			origin: OriginFor<T>,
+			prefix: KeyOf<T>,
+			limit: u32,
+			start_after: Option<KeyOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(limit <= T::MaxEntriesPerCall::get(), Error::<T>::TooManyEntries);
+
+			let visited: Vec<KeyOf<T>> = if DepositMigration::<T>::exists() {
+				let keys = match start_after {
+					Some(cursor) => StoredData::<T>::iter_key_prefix_from(
+						&who,
+						StoredData::<T>::hashed_key_for(&who, &cursor),
+					),
+					None => StoredData::<T>::iter_key_prefix(&who),
+				};
+				keys.take(limit as usize).collect()
+			} else {
+				Self::keys_with_prefix(&who, &prefix, start_after, limit)
+			};
+			let visited_count = visited.len() as u32;
+			let last_key = if visited_count == limit { visited.last().cloned() } else { None };
+
+			let mut removed = 0_u32;
+			for key in visited.iter().filter(|key| key.starts_with(&prefix)) {
+				Self::do_clear(&who, key)?;
+				removed.saturating_inc();
+			}
+
+			Self::deposit_event(Event::PrefixCleared { who, prefix, removed, last_key });
+
+			Ok(Some(T::WeightInfo::clear_prefix(visited_count)).into())
+		}
+
+		/// Reserve the deposit shortfall of `who` left by the deposit migration, anyone can call
+		/// it once `who` can pay
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::reserve_deposit_shortfall())]
+		pub fn reserve_deposit_shortfall(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let amount = DepositShortfalls::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NoDepositShortfall);
+			T::Currency::reserve(&who, amount)?;
+
+			Self::deposit_event(Event::DepositShortfallReserved { who, amount });
+
+			Ok(())
+		}
+
+		/// Revoke the attestation of the caller on the entry of `owner` under `key`, the deposit
+		/// is returned to the caller
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::revoke_attestation())]
+		pub fn revoke_attestation(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			key: KeyOf<T>,
+		) -> DispatchResult {
+			let attester = ensure_signed(origin)?;
+
+			Attestations::<T>::take((&owner, &key, &attester))
+				.ok_or(Error::<T>::AttestationNotFound)?;
+			T::Currency::unreserve(&attester, Self::attestation_deposit(&attester, &key));
+
+			Self::deposit_event(Event::AttestationRevoked { owner, key, attester });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Ensure `who` holds an unexpired grant from `owner` covering `key` with the permission
+		/// checked by `allowed`
+		pub fn ensure_access(
+			owner: &T::AccountId,
+			who: &T::AccountId,
+			key: &KeyOf<T>,
+			allowed: impl Fn(&AccessGrant<T>) -> bool,
+		) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				AccessGrants::<T>::get(owner, who)
+					.iter()
+					.any(|grant| allowed(grant) && grant.covers(key, now)),
+				Error::<T>::AccessDenied
+			);
+			Ok(())
+		}
+
+		/// The deposit for an entry, both the key and the value bytes are charged
+		pub fn deposit_for(key: &KeyOf<T>, value: &ValueOf<T>) -> CurrencyBalanceOf<T> {
+			Self::bytes_deposit(key.len() + value.len())
+		}
+
+		/// The deposit for a grant, the grantee and the grant bytes are charged
+		pub fn grant_deposit(
+			grantee: &T::AccountId,
+			grant: &AccessGrant<T>,
+		) -> CurrencyBalanceOf<T> {
+			Self::bytes_deposit(grantee.encoded_size() + grant.encoded_size())
+		}
+
+		/// The deposit for an attestation, the attester, the key and the recorded hash and block
+		/// bytes are charged
+		pub fn attestation_deposit(
+			attester: &T::AccountId,
+			key: &KeyOf<T>,
+		) -> CurrencyBalanceOf<T> {
+			Self::bytes_deposit(
+				attester.encoded_size()
+					+ key.len() + <(T::Hash, BlockNumberFor<T>)>::max_encoded_len(),
+			)
+		}
+
+		fn bytes_deposit(bytes: usize) -> CurrencyBalanceOf<T> {
+			T::DepositPerByte::get().saturating_mul((bytes as u32).into())
+		}
+
+		/// Whether the deposit migration has visited the entry under `key` in the namespace of
+		/// `owner`, the migration visits the entries in storage key order
+		pub fn deposit_migrated(owner: &T::AccountId, key: &KeyOf<T>) -> bool {
+			DepositMigration::<T>::get()
+				.map_or(true, |cursor| StoredData::<T>::hashed_key_for(owner, key) <= cursor)
+		}
+
+		/// The deposit for an entry, the entries not visited by the deposit migration yet keep
+		/// the deposit of version 0 that only covers the value bytes
+		fn entry_deposit(
+			key: &KeyOf<T>,
+			value: &ValueOf<T>,
+			migrated: bool,
+		) -> CurrencyBalanceOf<T> {
+			if migrated {
+				Self::deposit_for(key, value)
+			} else {
+				Self::bytes_deposit(value.len())
+			}
+		}
+
+		/// At most `limit` keys of the namespace of `owner` starting with `prefix`, in key order
+		/// and after `start_after` when given
+		fn keys_with_prefix(
+			owner: &T::AccountId,
+			prefix: &KeyOf<T>,
+			start_after: Option<KeyOf<T>>,
+			limit: u32,
+		) -> Vec<KeyOf<T>> {
+			let mut keys = Vec::new();
+			let start = match start_after {
+				Some(cursor) if cursor.as_slice() >= prefix.as_slice() => cursor,
+				_ => {
+					// the iteration starts after the given key, so the prefix itself is read apart
+					if OrderedKeys::<T>::contains_key(owner, OrderedKey(prefix.clone())) {
+						keys.push(prefix.clone());
+					}
+					prefix.clone()
+				},
+			};
+			keys.truncate(limit as usize);
+			let raw_key = OrderedKeys::<T>::hashed_key_for(owner, OrderedKey(start));
+			keys.extend(
+				OrderedKeys::<T>::iter_key_prefix_from(owner, raw_key)
+					.map(|key| key.0)
+					.take_while(|key| key.starts_with(prefix))
+					.take(limit as usize - keys.len()),
+			);
+			keys
+		}
+
+		/// Store `value` under `key` in the namespace of `owner`, reserving the deposit from
+		/// `depositor`. On overwrites only the difference with the current deposit is reserved or
+		/// released when the depositor does not change.
+		pub fn do_store(
+			owner: &T::AccountId,
+			depositor: &T::AccountId,
+			key: &KeyOf<T>,
+			value: ValueOf<T>,
+		) -> DispatchResult {
+			ensure!(!key.len().is_zero() && !value.len().is_zero(), Error::<T>::EmptyInput);
+
+			let migrated = Self::deposit_migrated(owner, key);
+			let deposit = Self::entry_deposit(key, &value, migrated);
+			match <StoredData<T>>::get(owner, key) {
+				Some(previous) => {
+					let previous_deposit = Self::entry_deposit(key, &previous, migrated);
+					let previous_depositor =
+						DataDepositors::<T>::get(owner, key).unwrap_or_else(|| owner.clone());
+					if &previous_depositor == depositor {
+						if deposit > previous_deposit {
+							T::Currency::reserve(depositor, deposit - previous_deposit)?;
+						} else {
+							Self::release_deposit(depositor, previous_deposit - deposit);
+						}
+					} else {
+						// the previous writer of the entry gets its deposit back
+						T::Currency::reserve(depositor, deposit)?;
+						Self::release_deposit(&previous_depositor, previous_deposit);
+					}
+				},
+				None => {
+					T::Currency::reserve(depositor, deposit)?;
+				},
+			}
 
 			// Update storage.
-			<StoredData<T>>::take(who.clone(), key.clone());
+			<StoredData<T>>::insert(owner, key, value);
+			OrderedKeys::<T>::insert(owner, OrderedKey(key.clone()), ());
+			if depositor == owner {
+				DataDepositors::<T>::remove(owner, key);
+			} else {
+				DataDepositors::<T>::insert(owner, key, depositor);
+			}
 
-			// Emit an event.
-			Self::deposit_event(Event::DataCleared { key, who });
+			Ok(())
+		}
+
+		/// Remove the data stored under `key` in the namespace of `owner`, returning the deposit
+		/// to the account that paid it
+		pub fn do_clear(owner: &T::AccountId, key: &KeyOf<T>) -> DispatchResult {
+			ensure!(!key.len().is_zero(), Error::<T>::EmptyInput);
+
+			// does some data exist
+			let data = <StoredData<T>>::take(owner, key).ok_or(Error::<T>::NoDataStored)?;
+			OrderedKeys::<T>::remove(owner, OrderedKey(key.clone()));
+
+			// calculate deposit fee
+			let deposit_amount =
+				Self::entry_deposit(key, &data, Self::deposit_migrated(owner, key));
+
+			// unreserve deposit fee
+			let depositor = DataDepositors::<T>::take(owner, key).unwrap_or_else(|| owner.clone());
+			Self::release_deposit(&depositor, deposit_amount);
 
 			Ok(())
 		}
+
+		/// Unreserve `amount` of the deposits of `who`, the part the deposit migration could not
+		/// reserve is written off the shortfall instead
+		pub fn release_deposit(who: &T::AccountId, amount: CurrencyBalanceOf<T>) {
+			let shortfall = DepositShortfalls::<T>::get(who);
+			let written_off = shortfall.min(amount);
+			if shortfall > written_off {
+				DepositShortfalls::<T>::insert(who, shortfall - written_off);
+			} else if !written_off.is_zero() {
+				DepositShortfalls::<T>::remove(who);
+			}
+			T::Currency::unreserve(who, amount - written_off);
+		}
 	}
 }
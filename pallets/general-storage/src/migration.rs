@@ -0,0 +1,95 @@
+use super::*;
+
+pub mod v1 {
+	use super::*;
+
+	use frame_support::{
+		pallet_prelude::Weight,
+		sp_runtime::traits::Saturating,
+		sp_std::marker::PhantomData,
+		storage::StoragePrefixedMap,
+		traits::{
+			Currency, Get, GetStorageVersion, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+		},
+	};
+
+	/// Recompute the deposits of the existing entries now that the key bytes are charged too.
+	///
+	/// Before version 1 each write reserved `DepositPerByte * value.len()`. The upgrade only
+	/// starts the migration, the entries are then visited in `on_idle` by [`migrate_deposits`] in
+	/// storage key order: the deposit of the key bytes is reserved from the depositor of each
+	/// entry and the key is added to `OrderedKeys`. Deposits that cannot be reserved are recorded
+	/// in `DepositShortfalls` and reserved later with `reserve_deposit_shortfall`. Data can be
+	/// stored and cleared while the migration runs, the entries not visited yet keep the deposit
+	/// of version 0 until they are.
+	///
+	/// Overwrites by the same depositor before version 1 reserved again without releasing the
+	/// previous deposit. Those leaked reserves cannot be told apart from the reserves of other
+	/// pallets, so the migration only accounts for the deposits of the stored entries.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version >= 1 {
+				log::info!("general-storage migration to v1 skipped, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+
+			// the raw keys of all the entries are after the prefix of the map
+			DepositMigration::<T>::put(StoredData::<T>::final_prefix().to_vec());
+			StorageVersion::new(1).put::<Pallet<T>>();
+			log::info!("general-storage migration to v1 started, deposits are migrated on idle");
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(
+			_state: frame_support::sp_std::vec::Vec<u8>,
+		) -> Result<(), frame_support::sp_runtime::TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"general-storage storage version must be 1"
+			);
+			Ok(())
+		}
+	}
+
+	/// Run the deposit migration within `limit`, returning the weight used
+	pub fn migrate_deposits<T: Config>(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut used = db_weight.reads(1);
+		let Some(cursor) = DepositMigration::<T>::get() else { return used };
+
+		// reading the key of an entry and its depositor, reserving the deposit of the key bytes
+		// or recording the shortfall, and indexing the key
+		let per_entry = db_weight.reads_writes(4, 3);
+
+		let mut keys = StoredData::<T>::iter_keys_from(cursor);
+		loop {
+			if used.saturating_add(per_entry).any_gt(limit) {
+				DepositMigration::<T>::put(keys.last_raw_key().to_vec());
+				return used.saturating_add(db_weight.writes(1));
+			}
+			let Some((owner, key)) = keys.next() else {
+				DepositMigration::<T>::kill();
+				log::info!("general-storage migration to v1 done");
+				return used.saturating_add(db_weight.writes(1));
+			};
+			used.saturating_accrue(per_entry);
+
+			let depositor = DataDepositors::<T>::get(&owner, &key).unwrap_or_else(|| owner.clone());
+			let key_deposit = T::DepositPerByte::get().saturating_mul((key.len() as u32).into());
+			if T::Currency::reserve(&depositor, key_deposit).is_err() {
+				DepositShortfalls::<T>::mutate(&depositor, |shortfall| {
+					shortfall.saturating_accrue(key_deposit)
+				});
+				Pallet::<T>::deposit_event(Event::DepositShortfall {
+					who: depositor,
+					amount: key_deposit,
+				});
+			}
+			OrderedKeys::<T>::insert(&owner, OrderedKey(key), ());
+		}
+	}
+}
@@ -71,6 +71,8 @@ parameter_types! {
 	pub const MaxKeyLength : u32 = 10;
 	pub const MaxValueLength : u32 = 100;
 	pub const DepositPerByte : u128 = 1;
+	pub const MaxGrantsPerAccount : u32 = 2;
+	pub const MaxEntriesPerCall : u32 = 10;
 }
 impl pallet_general_storage::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
@@ -78,6 +80,9 @@ impl pallet_general_storage::Config for Test {
 	type MaxKeyLength = MaxKeyLength;
 	type MaxValueLength = MaxValueLength;
 	type DepositPerByte = DepositPerByte;
+	type MaxGrantsPerAccount = MaxGrantsPerAccount;
+	type MaxEntriesPerCall = MaxEntriesPerCall;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,5 +1,16 @@
-use crate::{mock::*, Error, KeyOf, StoredData};
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use crate::{
+	migration, mock::*, AccessGrants, Attestations, DataDepositors, DepositMigration,
+	DepositShortfalls, Error, Event, KeyOf, OrderedKeys, StoredData, ValueOf,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		Currency, GetStorageVersion, OnIdle, OnRuntimeUpgrade, ReservableCurrency, StorageVersion,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
 #[test]
 fn store_data_should_work() {
@@ -102,3 +113,667 @@ fn clear_data_should_work() {
 		);
 	});
 }
+
+fn bounded_key(key: &str) -> KeyOf<Test> {
+	key.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn bounded_value(value: &str) -> ValueOf<Test> {
+	value.as_bytes().to_vec().try_into().unwrap()
+}
+
+#[test]
+fn grant_and_revoke_access_should_work() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let auditor = 2;
+		let prefix = bounded_key("mrv/");
+		Balances::make_free_balance_be(&owner, 1000);
+
+		// cannot grant to self or without any permission
+		assert_noop!(
+			GeneralStorage::grant_access(
+				RuntimeOrigin::signed(owner),
+				owner,
+				prefix.clone(),
+				true,
+				false,
+				None
+			),
+			Error::<Test>::CannotGrantToSelf
+		);
+		assert_noop!(
+			GeneralStorage::grant_access(
+				RuntimeOrigin::signed(owner),
+				auditor,
+				prefix.clone(),
+				false,
+				false,
+				None
+			),
+			Error::<Test>::EmptyInput
+		);
+
+		System::set_block_number(5);
+		assert_noop!(
+			GeneralStorage::grant_access(
+				RuntimeOrigin::signed(owner),
+				auditor,
+				prefix.clone(),
+				true,
+				false,
+				Some(4)
+			),
+			Error::<Test>::GrantAlreadyExpired
+		);
+
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			prefix.clone(),
+			true,
+			false,
+			Some(10)
+		));
+		assert_eq!(
+			last_event(),
+			Event::AccessGranted {
+				owner,
+				grantee: auditor,
+				prefix: prefix.clone(),
+				can_write: true,
+				can_attest: false,
+				expires_at: Some(10)
+			}
+			.into()
+		);
+		// the owner pays a deposit for the grant
+		let deposit =
+			GeneralStorage::grant_deposit(&auditor, &AccessGrants::<Test>::get(owner, auditor)[0]);
+		assert!(deposit > 0);
+		assert_eq!(Balances::reserved_balance(owner), deposit);
+
+		// granting again on the same prefix replaces the grant
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			prefix.clone(),
+			true,
+			true,
+			Some(10)
+		));
+		let grants = AccessGrants::<Test>::get(owner, auditor);
+		assert_eq!(grants.len(), 1);
+		assert!(grants[0].can_attest);
+		assert_eq!(Balances::reserved_balance(owner), deposit);
+
+		// the number of grants per account is bounded
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			bounded_key("docs/"),
+			true,
+			false,
+			None
+		));
+		assert_noop!(
+			GeneralStorage::grant_access(
+				RuntimeOrigin::signed(owner),
+				auditor,
+				bounded_key("other/"),
+				true,
+				false,
+				None
+			),
+			Error::<Test>::TooManyGrants
+		);
+		let docs_deposit =
+			GeneralStorage::grant_deposit(&auditor, &AccessGrants::<Test>::get(owner, auditor)[1]);
+		assert_eq!(Balances::reserved_balance(owner), deposit + docs_deposit);
+
+		// third parties can only revoke expired grants
+		assert_noop!(
+			GeneralStorage::revoke_access(RuntimeOrigin::signed(3), owner, auditor, prefix.clone()),
+			Error::<Test>::NotAllowedToRevoke
+		);
+		System::set_block_number(11);
+		assert_ok!(GeneralStorage::revoke_access(
+			RuntimeOrigin::signed(3),
+			owner,
+			auditor,
+			prefix.clone()
+		));
+		assert_eq!(
+			last_event(),
+			Event::AccessRevoked { owner, grantee: auditor, prefix: prefix.clone() }.into()
+		);
+		// the deposit goes back to the owner
+		assert_eq!(Balances::reserved_balance(owner), docs_deposit);
+		assert_noop!(
+			GeneralStorage::revoke_access(RuntimeOrigin::signed(owner), owner, auditor, prefix),
+			Error::<Test>::GrantNotFound
+		);
+
+		// the grantee can renounce its grant
+		assert_ok!(GeneralStorage::revoke_access(
+			RuntimeOrigin::signed(auditor),
+			owner,
+			auditor,
+			bounded_key("docs/")
+		));
+		assert!(!AccessGrants::<Test>::contains_key(owner, auditor));
+		assert_eq!(Balances::reserved_balance(owner), 0);
+	});
+}
+
+#[test]
+fn grantee_can_write_in_owner_namespace() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let auditor = 2;
+		let key = bounded_key("mrv/2023");
+		let value = bounded_value("monitoring");
+		Balances::make_free_balance_be(&owner, 1000);
+		Balances::make_free_balance_be(&auditor, 1000);
+
+		assert_noop!(
+			GeneralStorage::store_data_for(
+				RuntimeOrigin::signed(auditor),
+				owner,
+				key.clone(),
+				value.clone()
+			),
+			Error::<Test>::AccessDenied
+		);
+
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			bounded_key("mrv/"),
+			true,
+			false,
+			Some(10)
+		));
+		let grant_deposit =
+			GeneralStorage::grant_deposit(&auditor, &AccessGrants::<Test>::get(owner, auditor)[0]);
+
+		// keys outside the prefix are not covered
+		assert_noop!(
+			GeneralStorage::store_data_for(
+				RuntimeOrigin::signed(auditor),
+				owner,
+				bounded_key("docs/1"),
+				value.clone()
+			),
+			Error::<Test>::AccessDenied
+		);
+
+		assert_ok!(GeneralStorage::store_data_for(
+			RuntimeOrigin::signed(auditor),
+			owner,
+			key.clone(),
+			value.clone()
+		));
+		assert_eq!(
+			last_event(),
+			Event::DataStoredByGrantee { owner, key: key.clone(), writer: auditor }.into()
+		);
+		assert_eq!(StoredData::<Test>::get(owner, key.clone()), Some(value.clone()));
+		assert_eq!(DataDepositors::<Test>::get(owner, key.clone()), Some(auditor));
+
+		// the writer pays the deposit
+		assert_eq!(Balances::reserved_balance(auditor), (key.len() + value.len()) as u128);
+		assert_eq!(Balances::reserved_balance(owner), grant_deposit);
+
+		// the owner can clear the data, the deposit goes back to the writer
+		assert_ok!(GeneralStorage::clear_data(RuntimeOrigin::signed(owner), key.clone()));
+		assert_eq!(Balances::reserved_balance(auditor), 0);
+		assert_eq!(DataDepositors::<Test>::get(owner, key.clone()), None);
+
+		assert_ok!(GeneralStorage::store_data_for(
+			RuntimeOrigin::signed(auditor),
+			owner,
+			key.clone(),
+			value.clone()
+		));
+		assert_ok!(GeneralStorage::clear_data_for(
+			RuntimeOrigin::signed(auditor),
+			owner,
+			key.clone()
+		));
+		assert_eq!(
+			last_event(),
+			Event::DataClearedByGrantee { owner, key: key.clone(), writer: auditor }.into()
+		);
+		assert_eq!(Balances::reserved_balance(auditor), 0);
+
+		// expired grants cannot be used
+		System::set_block_number(11);
+		assert_noop!(
+			GeneralStorage::store_data_for(RuntimeOrigin::signed(auditor), owner, key, value),
+			Error::<Test>::AccessDenied
+		);
+	});
+}
+
+#[test]
+fn grantee_can_attest_data() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let auditor = 2;
+		let key = bounded_key("mrv/2023");
+		let value = bounded_value("monitoring");
+		Balances::make_free_balance_be(&owner, 1000);
+		Balances::make_free_balance_be(&auditor, 1000);
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			key.clone(),
+			value.clone()
+		));
+
+		// a write grant does not allow attesting
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			bounded_key("mrv/"),
+			true,
+			false,
+			None
+		));
+		assert_noop!(
+			GeneralStorage::attest_data(RuntimeOrigin::signed(auditor), owner, key.clone()),
+			Error::<Test>::AccessDenied
+		);
+
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			bounded_key("mrv/"),
+			false,
+			true,
+			None
+		));
+		assert_noop!(
+			GeneralStorage::attest_data(
+				RuntimeOrigin::signed(auditor),
+				owner,
+				bounded_key("mrv/x")
+			),
+			Error::<Test>::NoDataStored
+		);
+		// attest-only grants cannot write
+		assert_noop!(
+			GeneralStorage::store_data_for(
+				RuntimeOrigin::signed(auditor),
+				owner,
+				key.clone(),
+				value.clone()
+			),
+			Error::<Test>::AccessDenied
+		);
+
+		assert_ok!(GeneralStorage::attest_data(RuntimeOrigin::signed(auditor), owner, key.clone()));
+		let hash = BlakeTwo256::hash(&value);
+		assert_eq!(
+			last_event(),
+			Event::DataAttested { owner, key: key.clone(), attester: auditor, hash }.into()
+		);
+		assert_eq!(Attestations::<Test>::get((owner, key.clone(), auditor)), Some((hash, 1)));
+
+		// the attester pays a deposit once per entry
+		let deposit = GeneralStorage::attestation_deposit(&auditor, &key);
+		assert!(deposit > 0);
+		assert_eq!(Balances::reserved_balance(auditor), deposit);
+		assert_ok!(GeneralStorage::attest_data(RuntimeOrigin::signed(auditor), owner, key.clone()));
+		assert_eq!(Balances::reserved_balance(auditor), deposit);
+
+		// only the attester can revoke its attestation and gets the deposit back
+		assert_noop!(
+			GeneralStorage::revoke_attestation(RuntimeOrigin::signed(owner), owner, key.clone()),
+			Error::<Test>::AttestationNotFound
+		);
+		assert_ok!(GeneralStorage::revoke_attestation(
+			RuntimeOrigin::signed(auditor),
+			owner,
+			key.clone()
+		));
+		assert_eq!(
+			last_event(),
+			Event::AttestationRevoked { owner, key: key.clone(), attester: auditor }.into()
+		);
+		assert!(!Attestations::<Test>::contains_key((owner, key, auditor)));
+		assert_eq!(Balances::reserved_balance(auditor), 0);
+	});
+}
+
+#[test]
+fn overwrites_only_reserve_the_deposit_difference() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let auditor = 2;
+		let key = bounded_key("mrv/2023");
+		Balances::make_free_balance_be(&owner, 1000);
+		Balances::make_free_balance_be(&auditor, 1000);
+
+		// the key bytes are charged too
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			key.clone(),
+			bounded_value("0123456789")
+		));
+		assert_eq!(Balances::reserved_balance(owner), 18);
+
+		// a longer value only reserves the extra bytes
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			key.clone(),
+			bounded_value("0123456789abcde")
+		));
+		assert_eq!(Balances::reserved_balance(owner), 23);
+
+		// a shorter value releases the difference
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			key.clone(),
+			bounded_value("01")
+		));
+		assert_eq!(Balances::reserved_balance(owner), 10);
+
+		// overwriting the same value does not change the deposit
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			key.clone(),
+			bounded_value("01")
+		));
+		assert_eq!(Balances::reserved_balance(owner), 10);
+
+		// a grantee overwriting the entry takes over the deposit
+		assert_ok!(GeneralStorage::grant_access(
+			RuntimeOrigin::signed(owner),
+			auditor,
+			bounded_key("mrv/"),
+			true,
+			false,
+			None
+		));
+		assert_ok!(GeneralStorage::store_data_for(
+			RuntimeOrigin::signed(auditor),
+			owner,
+			key.clone(),
+			bounded_value("0123")
+		));
+		let grant_deposit =
+			GeneralStorage::grant_deposit(&auditor, &AccessGrants::<Test>::get(owner, auditor)[0]);
+		assert_eq!(Balances::reserved_balance(owner), grant_deposit);
+		assert_eq!(Balances::reserved_balance(auditor), 12);
+
+		assert_ok!(GeneralStorage::clear_data(RuntimeOrigin::signed(owner), key));
+		assert_eq!(Balances::reserved_balance(auditor), 0);
+	});
+}
+
+#[test]
+fn store_many_should_work() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let entries: BoundedVec<_, MaxEntriesPerCall> = vec![
+			(bounded_key("mrv/1"), bounded_value("one")),
+			(bounded_key("mrv/2"), bounded_value("two")),
+			(bounded_key("mrv/3"), bounded_value("three")),
+		]
+		.try_into()
+		.unwrap();
+
+		assert_noop!(
+			GeneralStorage::store_many(RuntimeOrigin::signed(owner), Default::default()),
+			Error::<Test>::EmptyInput
+		);
+		assert_noop!(
+			GeneralStorage::store_many(RuntimeOrigin::signed(owner), entries.clone()),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		Balances::make_free_balance_be(&owner, 1000);
+		assert_ok!(GeneralStorage::store_many(RuntimeOrigin::signed(owner), entries));
+		assert_eq!(
+			last_event(),
+			Event::DataStored { key: bounded_key("mrv/3"), who: owner }.into()
+		);
+		assert_eq!(
+			StoredData::<Test>::get(owner, bounded_key("mrv/2")),
+			Some(bounded_value("two"))
+		);
+		assert_eq!(StoredData::<Test>::iter_prefix(owner).count(), 3);
+		assert_eq!(Balances::reserved_balance(owner), 15 + 11);
+
+		// a failing entry reverts the whole batch
+		let entries: BoundedVec<_, MaxEntriesPerCall> = vec![
+			(bounded_key("mrv/4"), bounded_value("four")),
+			(Default::default(), bounded_value("five")),
+		]
+		.try_into()
+		.unwrap();
+		assert_noop!(
+			GeneralStorage::store_many(RuntimeOrigin::signed(owner), entries),
+			Error::<Test>::EmptyInput
+		);
+	});
+}
+
+#[test]
+fn clear_prefix_should_work() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		Balances::make_free_balance_be(&owner, 1000);
+		for i in 0..6 {
+			assert_ok!(GeneralStorage::store_data(
+				RuntimeOrigin::signed(owner),
+				bounded_key(&format!("mrv/{}", i)),
+				bounded_value("record")
+			));
+		}
+		// a key equal to the prefix is removed too
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			bounded_key("mrv/"),
+			bounded_value("index")
+		));
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			bounded_key("docs/pdd"),
+			bounded_value("document")
+		));
+
+		assert_noop!(
+			GeneralStorage::clear_prefix(
+				RuntimeOrigin::signed(owner),
+				bounded_key("mrv/"),
+				MaxEntriesPerCall::get() + 1,
+				None
+			),
+			Error::<Test>::TooManyEntries
+		);
+
+		// remove the entries four at a time, in key order
+		let mut cursor = None;
+		let mut cursors = Vec::new();
+		let mut removed = 0;
+		loop {
+			assert_ok!(GeneralStorage::clear_prefix(
+				RuntimeOrigin::signed(owner),
+				bounded_key("mrv/"),
+				4,
+				cursor.clone()
+			));
+			match last_event() {
+				RuntimeEvent::GeneralStorage(Event::PrefixCleared {
+					who,
+					removed: count,
+					last_key,
+					..
+				}) => {
+					assert_eq!(who, owner);
+					removed += count;
+					cursor = last_key;
+					cursors.push(cursor.clone());
+				},
+				_ => panic!("PrefixCleared event expected"),
+			}
+			if cursor.is_none() {
+				break
+			}
+		}
+
+		assert_eq!(removed, 7);
+		assert_eq!(cursors, vec![Some(bounded_key("mrv/2")), None]);
+		assert_eq!(StoredData::<Test>::iter_prefix(owner).count(), 1);
+		assert_eq!(
+			OrderedKeys::<Test>::iter_key_prefix(owner).map(|key| key.0).collect::<Vec<_>>(),
+			vec![bounded_key("docs/pdd")]
+		);
+		assert_eq!(
+			StoredData::<Test>::get(owner, bounded_key("docs/pdd")),
+			Some(bounded_value("document"))
+		);
+		assert_eq!(Balances::reserved_balance(owner), 16);
+	});
+}
+
+#[test]
+fn migration_reserves_key_bytes_of_existing_entries() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let auditor = 2;
+		let poor = 3;
+		Balances::make_free_balance_be(&owner, 1000);
+		Balances::make_free_balance_be(&auditor, 1000);
+		Balances::make_free_balance_be(&poor, 10);
+
+		// entries written before version 1 only reserved the value bytes, and overwrites by the
+		// same depositor leaked the previous deposit
+		StorageVersion::new(0).put::<GeneralStorage>();
+		StoredData::<Test>::insert(owner, bounded_key("mrv/1"), bounded_value("record"));
+		StoredData::<Test>::insert(owner, bounded_key("mrv/2"), bounded_value("record"));
+		StoredData::<Test>::insert(owner, bounded_key("mrv/3"), bounded_value("record"));
+		DataDepositors::<Test>::insert(owner, bounded_key("mrv/3"), auditor);
+		StoredData::<Test>::insert(poor, bounded_key("mrv/1"), bounded_value("record"));
+		assert_ok!(Balances::reserve(&owner, 12 + 5));
+		assert_ok!(Balances::reserve(&auditor, 6));
+		assert_ok!(Balances::reserve(&poor, 6));
+
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(GeneralStorage::on_chain_storage_version(), 1);
+		assert!(DepositMigration::<Test>::exists());
+		assert!(!GeneralStorage::deposit_migrated(&owner, &bounded_key("mrv/1")));
+
+		// data can be stored and cleared while the deposits are migrated, the entries not
+		// visited yet keep the deposit of version 0
+		assert_ok!(GeneralStorage::store_data(
+			RuntimeOrigin::signed(owner),
+			bounded_key("mrv/4"),
+			bounded_value("record")
+		));
+		assert_eq!(Balances::reserved_balance(owner), 17 + 6);
+		assert_ok!(GeneralStorage::clear_data(RuntimeOrigin::signed(owner), bounded_key("mrv/2")));
+		assert_eq!(Balances::reserved_balance(owner), 17);
+
+		GeneralStorage::on_idle(1, Weight::MAX);
+
+		assert!(!DepositMigration::<Test>::exists());
+		assert!(GeneralStorage::deposit_migrated(&owner, &bounded_key("mrv/1")));
+		assert_eq!(Balances::reserved_balance(auditor), 11);
+		assert_eq!(Balances::reserved_balance(owner), 22 + 5);
+
+		// the keys are indexed, clearing them only leaves the leaked deposit reserved
+		assert_ok!(GeneralStorage::clear_prefix(
+			RuntimeOrigin::signed(owner),
+			bounded_key("mrv/"),
+			MaxEntriesPerCall::get(),
+			None
+		));
+		assert_eq!(
+			last_event(),
+			Event::PrefixCleared {
+				who: owner,
+				prefix: bounded_key("mrv/"),
+				removed: 3,
+				last_key: None
+			}
+			.into()
+		);
+		assert_eq!(Balances::reserved_balance(owner), 5);
+		assert_eq!(Balances::reserved_balance(auditor), 0);
+
+		// accounts that cannot pay the difference keep their reserve and can pay it later
+		assert_eq!(Balances::reserved_balance(poor), 6);
+		assert_eq!(DepositShortfalls::<Test>::get(poor), 5);
+		assert_noop!(
+			GeneralStorage::reserve_deposit_shortfall(RuntimeOrigin::signed(owner), poor),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		Balances::make_free_balance_be(&poor, 20);
+		assert_ok!(GeneralStorage::reserve_deposit_shortfall(RuntimeOrigin::signed(owner), poor));
+		assert_eq!(Balances::reserved_balance(poor), 11);
+		assert_noop!(
+			GeneralStorage::reserve_deposit_shortfall(RuntimeOrigin::signed(owner), poor),
+			Error::<Test>::NoDepositShortfall
+		);
+
+		// running it again does nothing
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert!(!DepositMigration::<Test>::exists());
+		assert_eq!(Balances::reserved_balance(poor), 11);
+	});
+}
+
+#[test]
+fn clear_prefix_visits_the_namespace_while_deposits_are_migrated() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		Balances::make_free_balance_be(&owner, 1000);
+
+		// the entries written before version 1 are not in `OrderedKeys` yet
+		StorageVersion::new(0).put::<GeneralStorage>();
+		StoredData::<Test>::insert(owner, bounded_key("mrv/1"), bounded_value("record"));
+		StoredData::<Test>::insert(owner, bounded_key("docs/1"), bounded_value("record"));
+		assert_ok!(Balances::reserve(&owner, 12));
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_ok!(GeneralStorage::clear_prefix(
+			RuntimeOrigin::signed(owner),
+			bounded_key("mrv/"),
+			2,
+			None
+		));
+		// the limit counts the entries visited, whether they start with the prefix or not
+		match last_event() {
+			RuntimeEvent::GeneralStorage(Event::PrefixCleared { removed, last_key, .. }) => {
+				assert_eq!(removed, 1);
+				assert!(last_key.is_some());
+			},
+			_ => panic!("PrefixCleared event expected"),
+		}
+		assert_eq!(StoredData::<Test>::get(owner, bounded_key("mrv/1")), None);
+		assert_eq!(Balances::reserved_balance(owner), 6);
+	});
+}
+
+#[test]
+fn clearing_data_writes_off_the_deposit_shortfall() {
+	new_test_ext().execute_with(|| {
+		let poor = 3;
+		Balances::make_free_balance_be(&poor, 10);
+
+		StorageVersion::new(0).put::<GeneralStorage>();
+		StoredData::<Test>::insert(poor, bounded_key("mrv/1"), bounded_value("record"));
+		assert_ok!(Balances::reserve(&poor, 6));
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		GeneralStorage::on_idle(1, Weight::MAX);
+		assert_eq!(DepositShortfalls::<Test>::get(poor), 5);
+
+		// only the reserved part of the deposit is released
+		assert_ok!(GeneralStorage::clear_data(RuntimeOrigin::signed(poor), bounded_key("mrv/1")));
+		assert_eq!(Balances::reserved_balance(poor), 0);
+		assert!(!DepositShortfalls::<Test>::contains_key(poor));
+	});
+}
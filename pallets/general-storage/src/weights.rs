@@ -0,0 +1,287 @@
+//! Weights for pallet_general_storage
+//!
+//! The storage accesses are taken from the benchmarks in `benchmarking.rs`, regenerate this file
+//! with the benchmark CLI to refresh the execution times.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use frame_support::sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_general_storage.
+pub trait WeightInfo {
+	fn store_data() -> Weight;
+	fn clear_data() -> Weight;
+	fn grant_access() -> Weight;
+	fn revoke_access() -> Weight;
+	fn store_data_for() -> Weight;
+	fn clear_data_for() -> Weight;
+	fn attest_data() -> Weight;
+	fn store_many(n: u32, ) -> Weight;
+	fn clear_prefix(n: u32, ) -> Weight;
+	fn reserve_deposit_shortfall() -> Weight;
+	fn revoke_attestation() -> Weight;
+}
+
+/// Weights for pallet_general_storage using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn store_data() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(41_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_data() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(37_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: GeneralStorage AccessGrants (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn grant_access() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage AccessGrants (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn revoke_access() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage AccessGrants (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn store_data_for() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage AccessGrants (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_data_for() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(43_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: GeneralStorage AccessGrants (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:0)
+	// Storage: GeneralStorage Attestations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn attest_data() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(36_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:100 w:100)
+	// Storage: GeneralStorage DataDepositors (r:100 w:100)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:100)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[1, 100]`.
+	fn store_many(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(23_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage OrderedKeys (r:102 w:100)
+	// Storage: GeneralStorage StoredData (r:100 w:100)
+	// Storage: GeneralStorage DataDepositors (r:100 w:100)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[1, 100]`.
+	fn clear_prefix(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(25_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(23_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reserve_deposit_shortfall() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage Attestations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn revoke_attestation() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn store_data() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(41_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_data() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(37_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	// Storage: GeneralStorage AccessGrants (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn grant_access() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(24_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage AccessGrants (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn revoke_access() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(22_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage AccessGrants (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn store_data_for() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage AccessGrants (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:1)
+	// Storage: GeneralStorage DataDepositors (r:1 w:1)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:1)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_data_for() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(43_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	// Storage: GeneralStorage AccessGrants (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:1 w:0)
+	// Storage: GeneralStorage Attestations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn attest_data() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(36_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage StoredData (r:100 w:100)
+	// Storage: GeneralStorage DataDepositors (r:100 w:100)
+	// Storage: GeneralStorage OrderedKeys (r:0 w:100)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[1, 100]`.
+	fn store_many(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(23_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	// Storage: GeneralStorage DepositMigration (r:1 w:0)
+	// Storage: GeneralStorage OrderedKeys (r:102 w:100)
+	// Storage: GeneralStorage StoredData (r:100 w:100)
+	// Storage: GeneralStorage DataDepositors (r:100 w:100)
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	/// The range of component `n` is `[1, 100]`.
+	fn clear_prefix(n: u32, ) -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(25_000_000, 0)
+			// Standard Error: 4_000
+			.saturating_add(Weight::from_parts(23_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	// Storage: GeneralStorage DepositShortfalls (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reserve_deposit_shortfall() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	// Storage: GeneralStorage Attestations (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn revoke_attestation() -> Weight {
+		// Minimum execution time:  nanoseconds.
+		Weight::from_parts(26_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+}
@@ -83,6 +83,9 @@ pub mod pallet {
         /// Maximum number of invulnerables.
         type MaxInvulnerables: Get<u32>;
 
+        /// Minimum number of invulnerables kept when removing repeat offenders.
+        type MinInvulnerables: Get<u32>;
+
         /// A stable ID for a collator.
         type CollatorId: Member + Parameter;
 
@@ -331,3 +334,33 @@ where
         Ok(Some(total_weight).into())
     }
 }
+
+/// Invulnerables that repeatedly fail to produce their container chain blocks lose their
+/// invulnerable status, they will not be selected as collators from the next session.
+///
+/// Offenders keep their status if there would be less than `MinInvulnerables` left.
+impl<T: Config> tp_traits::HandleRepeatOffender<T::AccountId> for Pallet<T> {
+    fn handle_repeat_offender(collator: &T::AccountId, _offences: u32) -> Weight {
+        let removed = <Invulnerables<T>>::mutate(|invulnerables| {
+            if invulnerables.len() as u32 <= T::MinInvulnerables::get() {
+                return false;
+            }
+
+            match invulnerables.iter().position(|x| x == collator) {
+                Some(pos) => {
+                    invulnerables.remove(pos);
+                    true
+                }
+                None => false,
+            }
+        });
+
+        if removed {
+            Self::deposit_event(Event::InvulnerableRemoved {
+                account_id: collator.clone(),
+            });
+        }
+
+        T::WeightInfo::remove_invulnerable(T::MaxInvulnerables::get())
+    }
+}
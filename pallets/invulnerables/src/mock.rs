@@ -111,6 +111,7 @@ impl Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type UpdateOrigin = EnsureSignedBy<RootAccount, u64>;
     type MaxInvulnerables = ConstU32<20>;
+    type MinInvulnerables = ConstU32<1>;
     type CollatorId = <Self as frame_system::Config>::AccountId;
     type CollatorIdOf = IdentityCollator;
     type CollatorRegistration = IsRegistered;
@@ -23,6 +23,7 @@ use {
     },
     frame_support::{assert_noop, assert_ok},
     sp_runtime::traits::BadOrigin,
+    tp_traits::HandleRepeatOffender,
 };
 
 #[test]
@@ -160,3 +161,26 @@ fn remove_invulnerable_works() {
         );
     });
 }
+
+#[test]
+fn repeat_offender_loses_invulnerable_status() {
+    new_test_ext().execute_with(|| {
+        initialize_to_block(1);
+        assert_eq!(Invulnerables::invulnerables(), vec![1, 2]);
+
+        <Invulnerables as HandleRepeatOffender<_>>::handle_repeat_offender(&2, 3);
+
+        System::assert_last_event(RuntimeEvent::Invulnerables(
+            crate::Event::InvulnerableRemoved { account_id: 2 },
+        ));
+        assert_eq!(Invulnerables::invulnerables(), vec![1]);
+
+        // offenders that are not invulnerable are ignored
+        <Invulnerables as HandleRepeatOffender<_>>::handle_repeat_offender(&3, 3);
+        assert_eq!(Invulnerables::invulnerables(), vec![1]);
+
+        // offenders are kept rather than going below the minimum
+        <Invulnerables as HandleRepeatOffender<_>>::handle_repeat_offender(&1, 3);
+        assert_eq!(Invulnerables::invulnerables(), vec![1]);
+    });
+}
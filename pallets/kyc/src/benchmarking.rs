@@ -0,0 +1,88 @@
+//! Benchmarking setup for pallet-kyc
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use crate::Pallet as Kyc;
+use frame_benchmarking::{account, benchmarks_instance_pallet, whitelisted_caller};
+use frame_support::{
+	sp_runtime::traits::{Bounded, Zero},
+	traits::{Currency, ReservableCurrency},
+};
+use frame_system::RawOrigin;
+
+fn fund<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T, I>::max_value() / 2_u32.into());
+}
+
+/// Fill the queue with `q` applicants
+fn fill_queue<T: Config<I>, I: 'static>(q: u32) {
+	for i in 0..q {
+		let applicant: T::AccountId = account("applicant", i, 0);
+		fund::<T, I>(&applicant);
+		Kyc::<T, I>::apply_for_membership(RawOrigin::Signed(applicant).into(), Default::default())
+			.unwrap();
+	}
+}
+
+/// An authorized account to process the applications
+fn authorized<T: Config<I>, I: 'static>() -> T::AccountId {
+	let authorized: T::AccountId = account("authorized", 0, 0);
+	AuthorizedAccounts::<T, I>::mutate(|accounts| accounts.try_push(authorized.clone()).unwrap());
+	authorized
+}
+
+benchmarks_instance_pallet! {
+	apply_for_membership {
+		let q in 0 .. T::MaxQueueLength::get() - 1;
+		fill_queue::<T, I>(q);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T, I>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), Default::default())
+	verify {
+		assert!(Applicants::<T, I>::contains_key(&caller));
+		assert_eq!(Queue::<T, I>::get().len() as u32, q + 1);
+	}
+
+	reject_member {
+		let q in 0 .. T::MaxQueueLength::get() - 1;
+		fill_queue::<T, I>(q);
+		let applicant: T::AccountId = whitelisted_caller();
+		fund::<T, I>(&applicant);
+		Kyc::<T, I>::apply_for_membership(RawOrigin::Signed(applicant.clone()).into(), Default::default())?;
+		fund::<T, I>(&Kyc::<T, I>::account_id());
+		let authorized = authorized::<T, I>();
+		let lookup = T::Lookup::unlookup(applicant.clone());
+	}: _(RawOrigin::Signed(authorized), lookup)
+	verify {
+		assert!(!Applicants::<T, I>::contains_key(&applicant));
+		assert!(T::Currency::reserved_balance(&applicant).is_zero());
+	}
+
+	withdraw_application {
+		let q in 0 .. T::MaxQueueLength::get() - 1;
+		fill_queue::<T, I>(q);
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T, I>(&caller);
+		Kyc::<T, I>::apply_for_membership(RawOrigin::Signed(caller.clone()).into(), Default::default())?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!Applicants::<T, I>::contains_key(&caller));
+		assert_eq!(Queue::<T, I>::get().len() as u32, q);
+	}
+
+	renew_membership {
+		let member: T::AccountId = account("member", 0, 0);
+		Kyc::<T, I>::do_add_member(&member, UserLevel::KYCLevel1);
+		let before = MembershipExpiry::<T, I>::get(&member).unwrap();
+		frame_system::Pallet::<T>::set_block_number(before);
+		let authorized = authorized::<T, I>();
+		let lookup = T::Lookup::unlookup(member.clone());
+	}: _(RawOrigin::Signed(authorized), lookup)
+	verify {
+		let expires_at = MembershipExpiry::<T, I>::get(&member).unwrap();
+		assert!(expires_at > before);
+		assert!(!Expiries::<T, I>::contains_key(before, &member));
+	}
+
+	impl_benchmark_test_suite!(Kyc, crate::mock::new_test_ext(), crate::mock::Test);
+}
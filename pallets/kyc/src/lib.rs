@@ -6,18 +6,34 @@
 //!
 //! Allows control of membership of a set of `AccountId`s, useful for managing membership of a
 //! collective.
+//!
+//! Accounts apply with a commitment to their KYC data, the data itself stays with the KYC provider,
+//! and reserve `ApplicationDeposit` until the application is processed by an authorized account.
+//! The deposit is refunded when the application is accepted or withdrawn and moved to the pallet
+//! account, which funds the KYC airdrops, when it is rejected. An account receives the airdrop
+//! only once, on its first membership.
+//!
+//! Memberships expire `MembershipPeriod` blocks after being granted or renewed, expired members
+//! no longer have a KYC level and have to be verified again. Expired memberships are removed in
+//! `on_idle`.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::{
-	traits::{Contains, Currency, ExistenceRequirement, Get},
-	BoundedVec, PalletId,
+	traits::{BalanceStatus, Contains, Currency, ExistenceRequirement, Get, ReservableCurrency},
+	weights::Weight,
+	PalletId,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, StaticLookup},
+	DispatchError, DispatchResult,
 };
-use sp_runtime::traits::{AccountIdConversion, StaticLookup};
 use sp_std::prelude::*;
+pub mod migration;
 pub mod weights;
-pub use bitgreen_primitives::UserLevel;
+pub use bitgreen_primitives::{KycLevelProvider, UserLevel};
 
 #[cfg(test)]
 mod mock;
@@ -25,8 +41,8 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-// #[cfg(feature = "runtime-benchmarks")]
-// mod benchmarking;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 pub use pallet::*;
 pub use weights::WeightInfo;
@@ -40,7 +56,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -49,6 +65,16 @@ pub mod pallet {
 	pub type BalanceOf<T, I> =
 		<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	/// A pending membership application
+	#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Application<Hash, Balance> {
+		/// Commitment to the KYC data of the applicant, e.g. the salted hash of the data
+		pub commitment: Hash,
+		/// The deposit reserved from the applicant
+		pub deposit: Balance,
+	}
+
+	pub type ApplicationOf<T, I> = Application<<T as frame_system::Config>::Hash, BalanceOf<T, I>>;
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
@@ -62,12 +88,18 @@ pub mod pallet {
 		/// Maximum amount of authorised accounts permitted
 		type MaxAuthorizedAccountCount: Get<u32>;
 
-		type MaxStringLength: Get<u32>;
-
 		type MaxQueueLength: Get<u32>;
 
 		/// The currency used for the pallet
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from an applicant until the application is processed
+		#[pallet::constant]
+		type ApplicationDeposit: Get<BalanceOf<Self, I>>;
+
+		/// Number of blocks after which a membership expires unless renewed
+		#[pallet::constant]
+		type MembershipPeriod: Get<BlockNumberFor<Self>>;
 
 		/// The KYC pallet id
 		#[pallet::constant]
@@ -94,16 +126,39 @@ pub mod pallet {
 	// Amount to airdrop on every kyc success
 	pub type AirdropAmount<T: Config<I>, I: 'static = ()> = StorageValue<_, BalanceOf<T, I>>;
 
+	/// The accounts that received the KYC airdrop, it is not paid again when they are readmitted
 	#[pallet::storage]
-	#[pallet::getter(fn get_applicant)]
-	pub type Applicants<T: Config<I>, I: 'static = ()> = StorageMap<
+	pub type AirdropReceived<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The block at which the membership of an account expires
+	#[pallet::storage]
+	#[pallet::getter(fn membership_expiry)]
+	pub type MembershipExpiry<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The memberships expiring at a block, processed in `on_idle`
+	#[pallet::storage]
+	pub type Expiries<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
 		Blake2_128Concat,
 		T::AccountId,
-		(BoundedVec<u8,T::MaxStringLength>,BoundedVec<u8,T::MaxStringLength>),
-		ValueQuery,
+		(),
+		OptionQuery,
 	>;
 
+	/// The next block whose expiries have not been processed yet
+	#[pallet::storage]
+	pub type NextExpiryCheck<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_applicant)]
+	pub type Applicants<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ApplicationOf<T, I>, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_queue)]
 	pub type Queue<T: Config<I>, I: 'static = ()> =
@@ -125,7 +180,7 @@ pub mod pallet {
 	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
 		fn build(&self) {
 			for (member, level) in self.members.iter() {
-				Members::<T, I>::insert(member, level);
+				Pallet::<T, I>::do_add_member(member, level.clone());
 			}
 		}
 	}
@@ -134,7 +189,7 @@ pub mod pallet {
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// The given member was added
-		MemberAdded { who: T::AccountId, kyc_level: UserLevel },
+		MemberAdded { who: T::AccountId, kyc_level: UserLevel, expires_at: BlockNumberFor<T> },
 		/// The given member was removed
 		MemberRemoved { who: T::AccountId },
 		/// Two members were swapped; see the transaction for who.
@@ -145,6 +200,22 @@ pub mod pallet {
 		AuthorizedAccountRemoved { account_id: T::AccountId },
 		/// User has received airdrop for kyc approval
 		KYCAirdrop { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// An account applied for membership
+		ApplicationSubmitted { who: T::AccountId, commitment: T::Hash, deposit: BalanceOf<T, I> },
+		/// An application was accepted and its deposit refunded
+		ApplicationAccepted {
+			who: T::AccountId,
+			kyc_level: UserLevel,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// An application was rejected and its deposit slashed
+		ApplicationRejected { who: T::AccountId, slashed: BalanceOf<T, I> },
+		/// An application was withdrawn by the applicant and its deposit refunded
+		ApplicationWithdrawn { who: T::AccountId },
+		/// The membership was verified again and extended
+		MembershipRenewed { who: T::AccountId, expires_at: BlockNumberFor<T> },
+		/// The membership expired, the account has to be verified again
+		MembershipExpired { who: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -169,6 +240,13 @@ pub mod pallet {
 		TooManyApplicants,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::expire_memberships(now, remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Add a member `who` to the set.
@@ -186,14 +264,14 @@ pub mod pallet {
 			let who = T::Lookup::lookup(who)?;
 
 			// ensure the user kyc does not already exist
-			ensure!(Members::<T, I>::get(who.clone()).is_none(), Error::<T, I>::AlreadyMember);
+			ensure!(Self::kyc_level(&who).is_none(), Error::<T, I>::AlreadyMember);
 
 			// insert new kyc
-			Members::<T, I>::insert(who.clone(), kyc_level.clone());
+			let expires_at = Self::do_add_member(&who, kyc_level.clone());
 
 			let _ = Self::transfer_kyc_airdrop(who.clone());
 
-			Self::deposit_event(Event::MemberAdded { who, kyc_level });
+			Self::deposit_event(Event::MemberAdded { who, kyc_level, expires_at });
 			Ok(())
 		}
 
@@ -212,7 +290,7 @@ pub mod pallet {
 			ensure!(Members::<T, I>::get(who.clone()).is_some(), Error::<T, I>::NotMember);
 
 			// remove kyc
-			Members::<T, I>::remove(who.clone());
+			Self::do_remove_member(&who);
 
 			Self::deposit_event(Event::MemberRemoved { who });
 			Ok(())
@@ -305,82 +383,110 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Apply for membership with a `commitment` to the KYC data of the caller, the data itself
+		/// is submitted to the KYC provider. `ApplicationDeposit` is reserved until the application
+		/// is processed or withdrawn.
 		#[pallet::call_index(8)]
-		#[pallet::weight(T::WeightInfo::add_member(1))]
-		pub fn apply_for_membership(origin: OriginFor<T>, name: BoundedVec<u8,T::MaxStringLength>, email: BoundedVec<u8,T::MaxStringLength>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::apply_for_membership(T::MaxQueueLength::get()))]
+		pub fn apply_for_membership(origin: OriginFor<T>, commitment: T::Hash) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(Members::<T, I>::get(who.clone()).is_none(), Error::<T, I>::AlreadyMember);
-
+			ensure!(Self::kyc_level(&who).is_none(), Error::<T, I>::AlreadyMember);
 
-			Applicants::<T,I>::insert(who.clone(),(name,email));
-			Queue::<T,I>::try_mutate(|queue| -> DispatchResult {
-				ensure!(
-					!queue.contains(&who),
-					Error::<T, I>::ApplicantAlreadyExists
-				);
+			Queue::<T, I>::try_mutate(|queue| -> DispatchResult {
+				ensure!(!queue.contains(&who), Error::<T, I>::ApplicantAlreadyExists);
 
-				queue
-					.try_push(who.clone())
-					.map_err(|_| Error::<T, I>::TooManyApplicants)?;
+				queue.try_push(who.clone()).map_err(|_| Error::<T, I>::TooManyApplicants)?;
 				Ok(())
 			})?;
-			//let mut queue = Queue::<T,I>::get();
-			//queue.push(who.clone());
-			//Queue::<T,I>::put(queue);
 
+			let deposit = T::ApplicationDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			Applicants::<T, I>::insert(&who, Application { commitment, deposit });
+
+			Self::deposit_event(Event::ApplicationSubmitted { who, commitment, deposit });
 			Ok(())
 		}
 
+		/// Accept the application of `who` at `kyc_level`, the deposit is refunded
 		#[pallet::call_index(9)]
 		#[pallet::weight(T::WeightInfo::add_member(1))]
-		pub fn accept_member(origin: OriginFor<T>, who: AccountIdLookupOf<T>, kyc_level: UserLevel) -> DispatchResult {
+		pub fn accept_member(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			kyc_level: UserLevel,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			Self::check_authorized_account(&sender)?;
 
 			let who = T::Lookup::lookup(who)?;
 
-
 			// ensure the user kyc does not already exist
-			ensure!(Members::<T, I>::get(who.clone()).is_none(), Error::<T, I>::AlreadyMember);
+			ensure!(Self::kyc_level(&who).is_none(), Error::<T, I>::AlreadyMember);
 
-			let mut queue = Queue::<T,I>::get();
+			let application = Self::take_application(&who)?;
+			T::Currency::unreserve(&who, application.deposit);
 
-			match queue.binary_search(&who) {
-				Err(_) => Err(Error::<T,I>::ApplicantNotFound.into()),
-				Ok(index) => {
-					// insert new kyc
-					Members::<T, I>::insert(who.clone(), kyc_level.clone());
+			// insert new kyc
+			let expires_at = Self::do_add_member(&who, kyc_level.clone());
 
-					let _ = Self::transfer_kyc_airdrop(who.clone());
+			let _ = Self::transfer_kyc_airdrop(who.clone());
 
-					queue.remove(index);
-					Queue::<T,I>::put(queue);
-					Ok(())
-				},
-			}
-			
+			Self::deposit_event(Event::ApplicationAccepted { who, kyc_level, expires_at });
+			Ok(())
 		}
 
+		/// Reject the application of `who`, the deposit is moved to the pallet account
 		#[pallet::call_index(10)]
-		#[pallet::weight(T::WeightInfo::add_member(1))]
+		#[pallet::weight(T::WeightInfo::reject_member(T::MaxQueueLength::get()))]
 		pub fn reject_member(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			Self::check_authorized_account(&sender)?;
 
 			let who = T::Lookup::lookup(who)?;
 
+			let application = Self::take_application(&who)?;
+			// the remaining amount could not be repatriated and stays reserved
+			let remaining = T::Currency::repatriate_reserved(
+				&who,
+				&Self::account_id(),
+				application.deposit,
+				BalanceStatus::Free,
+			)?;
+
+			Self::deposit_event(Event::ApplicationRejected {
+				who,
+				slashed: application.deposit.saturating_sub(remaining),
+			});
+			Ok(())
+		}
+
+		/// Verify the member `who` again and extend the membership by `MembershipPeriod` from now
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::renew_membership())]
+		pub fn renew_membership(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::check_authorized_account(&sender)?;
+
+			let who = T::Lookup::lookup(who)?;
+			let kyc_level = Self::kyc_level(&who).ok_or(Error::<T, I>::NotMember)?;
+
+			let expires_at = Self::do_add_member(&who, kyc_level);
 
-			let mut queue = Queue::<T,I>::get();
+			Self::deposit_event(Event::MembershipRenewed { who, expires_at });
+			Ok(())
+		}
 
-			match queue.binary_search(&who) {
-				Err(_) => Err(Error::<T,I>::ApplicantNotFound.into()),
-				Ok(index) => {
-					queue.remove(index);
-					Queue::<T,I>::put(queue);
-					Ok(())
-				},
-			}			
+		/// Withdraw the pending application of the caller, the deposit is refunded
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::withdraw_application(T::MaxQueueLength::get()))]
+		pub fn withdraw_application(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
+			let application = Self::take_application(&who)?;
+			T::Currency::unreserve(&who, application.deposit);
+
+			Self::deposit_event(Event::ApplicationWithdrawn { who });
+			Ok(())
 		}
 	}
 }
@@ -398,15 +504,104 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Returns true if the membership of `who` has expired, expired memberships are only removed
+	/// in `on_idle`
+	pub fn membership_expired(who: &T::AccountId) -> bool {
+		MembershipExpiry::<T, I>::get(who)
+			.map_or(false, |expires_at| expires_at <= frame_system::Pallet::<T>::block_number())
+	}
+
+	/// Insert or update the membership of `who` at `kyc_level` and schedule its expiry
+	/// `MembershipPeriod` blocks from now, returns the expiry block
+	pub(crate) fn do_add_member(who: &T::AccountId, kyc_level: UserLevel) -> BlockNumberFor<T> {
+		if let Some(expires_at) = MembershipExpiry::<T, I>::get(who) {
+			Expiries::<T, I>::remove(expires_at, who);
+		}
+
+		let expires_at =
+			frame_system::Pallet::<T>::block_number().saturating_add(T::MembershipPeriod::get());
+		Members::<T, I>::insert(who, kyc_level);
+		MembershipExpiry::<T, I>::insert(who, expires_at);
+		Expiries::<T, I>::insert(expires_at, who, ());
+		expires_at
+	}
+
+	/// Remove the membership of `who` and its scheduled expiry
+	pub(crate) fn do_remove_member(who: &T::AccountId) {
+		Members::<T, I>::remove(who);
+		if let Some(expires_at) = MembershipExpiry::<T, I>::take(who) {
+			Expiries::<T, I>::remove(expires_at, who);
+		}
+	}
+
+	/// Remove the application of `who` from the queue and return it
+	fn take_application(who: &T::AccountId) -> Result<ApplicationOf<T, I>, DispatchError> {
+		Queue::<T, I>::try_mutate(|queue| -> DispatchResult {
+			let index =
+				queue.iter().position(|a| a == who).ok_or(Error::<T, I>::ApplicantNotFound)?;
+			queue.remove(index);
+			Ok(())
+		})?;
+		Applicants::<T, I>::take(who).ok_or(Error::<T, I>::ApplicantNotFound.into())
+	}
+
+	/// Remove the memberships that expired up to `now`, within `limit`
+	///
+	/// The blocks are processed in order from `NextExpiryCheck`, the expiries left when the limit
+	/// is reached are processed in the next calls. Returns the consumed weight.
+	pub fn expire_memberships(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// reading and writing the cursor
+		let mut used = db_weight.reads_writes(1, 1);
+		if used.any_gt(limit) {
+			return Weight::zero()
+		}
+		// reading the next expiry of the block
+		let per_block = db_weight.reads(1);
+		// reading the next expiry and removing the membership
+		let per_expiry = db_weight.reads_writes(1, 3);
+
+		let mut block = NextExpiryCheck::<T, I>::get();
+		'blocks: while block <= now {
+			if used.saturating_add(per_block).any_gt(limit) {
+				break
+			}
+			used.saturating_accrue(per_block);
+
+			let mut expiries = Expiries::<T, I>::iter_key_prefix(block);
+			loop {
+				if used.saturating_add(per_expiry).any_gt(limit) {
+					break 'blocks
+				}
+				let Some(who) = expiries.next() else { break };
+				used.saturating_accrue(per_expiry);
+
+				Members::<T, I>::remove(&who);
+				MembershipExpiry::<T, I>::remove(&who);
+				Expiries::<T, I>::remove(block, &who);
+				Self::deposit_event(Event::MembershipExpired { who });
+			}
+
+			block.saturating_inc();
+		}
+		NextExpiryCheck::<T, I>::put(block);
+
+		used
+	}
+
 	/// The account ID of the KYC pallet
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account_truncating()
 	}
 
-	/// Airdrop native tokens to user
+	/// Airdrop native tokens to user, unless the user already received the airdrop
 	pub fn transfer_kyc_airdrop(
 		who: T::AccountId,
 	) -> frame_support::pallet_prelude::DispatchResult {
+		if AirdropReceived::<T, I>::contains_key(&who) {
+			return Ok(());
+		}
+
 		// transfer airdrop if the amount is set
 		if let Some(amount) = Self::airdrop_amount() {
 			let airdrop_executed = T::Currency::transfer(
@@ -417,6 +612,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			);
 
 			if airdrop_executed.is_ok() {
+				AirdropReceived::<T, I>::insert(&who, ());
 				Self::deposit_event(Event::KYCAirdrop { who, amount });
 			}
 		}
@@ -426,6 +622,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 impl<T: Config<I>, I: 'static> Contains<T::AccountId> for Pallet<T, I> {
 	fn contains(t: &T::AccountId) -> bool {
-		Members::<T, I>::get(t).is_some()
+		Self::kyc_level(t).is_some()
+	}
+}
+
+impl<T: Config<I>, I: 'static> KycLevelProvider<T::AccountId> for Pallet<T, I> {
+	fn kyc_level(who: &T::AccountId) -> Option<UserLevel> {
+		Members::<T, I>::get(who).filter(|_| !Self::membership_expired(who))
 	}
 }
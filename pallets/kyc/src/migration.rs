@@ -0,0 +1,87 @@
+use super::*;
+
+pub mod v5 {
+	use super::*;
+
+	use frame_support::{
+		pallet_prelude::*,
+		sp_runtime::traits::Saturating,
+		sp_std::{marker::PhantomData, vec::Vec},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	/// The applications before version 5, with the plaintext name and email of the applicant
+	#[frame_support::storage_alias]
+	type Applicants<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		(Vec<u8>, Vec<u8>),
+		ValueQuery,
+	>;
+
+	/// Drop the pending applications holding the plaintext data of the applicants and schedule the
+	/// expiry of the existing memberships.
+	///
+	/// The applications are removed with the queue, including the ones left behind by rejections,
+	/// and the applicants apply again with a commitment. No deposit was reserved for these
+	/// applications. The existing members expire `MembershipPeriod` blocks after the upgrade and
+	/// the expiries are processed from the upgrade block. They are recorded as having received the
+	/// airdrop, which was paid when they were added.
+	pub struct MigrateToV5<T, I = ()>(PhantomData<(T, I)>);
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV5<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version >= 5 {
+				log::info!("kyc migration to v5 skipped, already applied");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let applications = Applicants::<T, I>::drain().count() as u64;
+			Queue::<T, I>::kill();
+
+			let mut members = 0_u64;
+			let now = frame_system::Pallet::<T>::block_number();
+			let expires_at = now.saturating_add(T::MembershipPeriod::get());
+			for who in Members::<T, I>::iter_keys() {
+				members += 1;
+				MembershipExpiry::<T, I>::insert(&who, expires_at);
+				Expiries::<T, I>::insert(expires_at, &who, ());
+				AirdropReceived::<T, I>::insert(&who, ());
+			}
+			NextExpiryCheck::<T, I>::put(now);
+
+			StorageVersion::new(5).put::<Pallet<T, I>>();
+			log::info!(
+				"kyc migration to v5 done, {} applications, {} members",
+				applications,
+				members
+			);
+
+			T::DbWeight::get()
+				.reads_writes(applications + members + 1, applications + 3 * members + 3)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				crate::Applicants::<T, I>::iter_keys().next().is_none()
+					&& Queue::<T, I>::get().is_empty(),
+				"kyc legacy applications left"
+			);
+			ensure!(
+				Members::<T, I>::iter_keys().all(|who| MembershipExpiry::<T, I>::contains_key(who)),
+				"kyc members without expiry"
+			);
+			ensure!(
+				Members::<T, I>::iter_keys().all(|who| AirdropReceived::<T, I>::contains_key(who)),
+				"kyc members without airdrop record"
+			);
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 5,
+				"kyc storage version must be 5"
+			);
+			Ok(())
+		}
+	}
+}
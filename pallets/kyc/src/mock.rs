@@ -3,7 +3,8 @@ use crate as pallet_membership;
 
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{ConstU16, ConstU32, ConstU64},
+	traits::{ConstU128, ConstU16, ConstU32, ConstU64},
+	weights::constants::RocksDbWeight,
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -59,7 +60,7 @@ impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Nonce = u64;
@@ -81,6 +82,9 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+pub const APPLICATION_DEPOSIT: u128 = 10;
+pub const MEMBERSHIP_PERIOD: u64 = 100;
+
 ord_parameter_types! {
 pub const One: u64 = 1;
 pub const Two: u64 = 2;
@@ -93,10 +97,11 @@ impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type AddOrigin = frame_system::EnsureRoot<u64>;
 	type MaxAuthorizedAccountCount = ConstU32<10>;
-	type MaxStringLength = ConstU32<512>;
 	type MaxQueueLength = ConstU32<100>;
 	type PalletId = KycPalletId;
 	type Currency = Balances;
+	type ApplicationDeposit = ConstU128<APPLICATION_DEPOSIT>;
+	type MembershipPeriod = ConstU64<MEMBERSHIP_PERIOD>;
 	type WeightInfo = ();
 }
 
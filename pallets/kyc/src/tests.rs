@@ -1,7 +1,12 @@
 use super::*;
 use crate::mock::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_runtime::traits::{BlakeTwo256, Hash};
 
 #[test]
 fn add_member_works() {
@@ -61,6 +66,17 @@ fn add_member_airdrop_works() {
 			UserLevel::KYCLevel1
 		));
 		assert_eq!(Balances::free_balance(15), balance_before_kyc + airdrop_amount);
+		assert!(AirdropReceived::<Test>::contains_key(15));
+
+		// the airdrop is not paid again when the member is readmitted
+		assert_ok!(Membership::remove_member(RuntimeOrigin::signed(authorised_account), 15));
+		assert_ok!(Membership::add_member(
+			RuntimeOrigin::signed(authorised_account),
+			15,
+			UserLevel::KYCLevel1
+		));
+		assert_eq!(Balances::free_balance(15), balance_before_kyc + airdrop_amount);
+		assert_eq!(Balances::free_balance(kyc_pallet_account), 100 - airdrop_amount);
 	});
 }
 
@@ -121,3 +137,257 @@ fn modify_member_works() {
 		assert_eq!(Membership::members(10), Some(UserLevel::KYCLevel2));
 	});
 }
+
+#[test]
+fn kyc_level_provider_works() {
+	new_test_ext().execute_with(|| {
+		let authorised_account = 1;
+		assert_ok!(Membership::force_add_authorized_account(
+			RawOrigin::Root.into(),
+			authorised_account,
+		));
+
+		assert_ok!(Membership::modify_member(
+			RuntimeOrigin::signed(authorised_account),
+			10,
+			UserLevel::KYCLevel3
+		));
+
+		assert_eq!(
+			<Membership as KycLevelProvider<u64>>::kyc_level(&10),
+			Some(UserLevel::KYCLevel3)
+		);
+		assert_eq!(
+			<Membership as KycLevelProvider<u64>>::kyc_level(&20),
+			Some(UserLevel::KYCLevel1)
+		);
+		assert_eq!(<Membership as KycLevelProvider<u64>>::kyc_level(&100), None);
+
+		// higher levels satisfy lower minimums but not the other way round
+		assert!(Membership::has_kyc_level(&10, &UserLevel::KYCLevel2));
+		assert!(!Membership::has_kyc_level(&20, &UserLevel::KYCLevel2));
+		assert!(!Membership::has_kyc_level(&100, &UserLevel::KYCLevel1));
+	});
+}
+
+#[test]
+fn application_lifecycle_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let authorised_account = 1;
+		assert_ok!(Membership::force_add_authorized_account(
+			RawOrigin::Root.into(),
+			authorised_account,
+		));
+		let kyc_pallet_account: u64 = PalletId(*b"bitg/kyc").into_account_truncating();
+		Balances::make_free_balance_be(&kyc_pallet_account, 100);
+		for applicant in [15, 16, 17] {
+			Balances::make_free_balance_be(&applicant, 100);
+		}
+		let commitment = BlakeTwo256::hash_of(&(b"name", b"email", b"salt"));
+
+		// members cannot apply and applicants need the deposit
+		assert_noop!(
+			Membership::apply_for_membership(RuntimeOrigin::signed(10), commitment),
+			Error::<Test, _>::AlreadyMember
+		);
+		assert_noop!(
+			Membership::apply_for_membership(RuntimeOrigin::signed(18), commitment),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(Membership::apply_for_membership(RuntimeOrigin::signed(15), commitment));
+		System::assert_last_event(
+			Event::ApplicationSubmitted { who: 15, commitment, deposit: APPLICATION_DEPOSIT }
+				.into(),
+		);
+		assert_eq!(
+			Membership::get_applicant(15),
+			Some(Application { commitment, deposit: APPLICATION_DEPOSIT })
+		);
+		assert_eq!(Balances::reserved_balance(15), APPLICATION_DEPOSIT);
+		assert_noop!(
+			Membership::apply_for_membership(RuntimeOrigin::signed(15), commitment),
+			Error::<Test, _>::ApplicantAlreadyExists
+		);
+		assert_ok!(Membership::apply_for_membership(RuntimeOrigin::signed(16), commitment));
+		assert_ok!(Membership::apply_for_membership(RuntimeOrigin::signed(17), commitment));
+
+		// accepted applications are refunded
+		assert_noop!(
+			Membership::accept_member(RuntimeOrigin::signed(5), 16, UserLevel::KYCLevel2),
+			Error::<Test, _>::NotAuthorised
+		);
+		assert_ok!(Membership::accept_member(
+			RuntimeOrigin::signed(authorised_account),
+			16,
+			UserLevel::KYCLevel2
+		));
+		System::assert_last_event(
+			Event::ApplicationAccepted {
+				who: 16,
+				kyc_level: UserLevel::KYCLevel2,
+				expires_at: 1 + MEMBERSHIP_PERIOD,
+			}
+			.into(),
+		);
+		assert_eq!(Membership::kyc_level(&16), Some(UserLevel::KYCLevel2));
+		assert_eq!(Membership::membership_expiry(16), Some(1 + MEMBERSHIP_PERIOD));
+		assert_eq!(Membership::get_applicant(16), None);
+		assert_eq!(Balances::reserved_balance(16), 0);
+		assert_eq!(Balances::free_balance(16), 100);
+
+		// rejected applications are slashed to the pallet account
+		assert_ok!(Membership::reject_member(RuntimeOrigin::signed(authorised_account), 15));
+		System::assert_last_event(
+			Event::ApplicationRejected { who: 15, slashed: APPLICATION_DEPOSIT }.into(),
+		);
+		assert_eq!(Membership::get_applicant(15), None);
+		assert_eq!(Balances::total_balance(&15), 100 - APPLICATION_DEPOSIT);
+		assert_eq!(Balances::free_balance(kyc_pallet_account), 100 + APPLICATION_DEPOSIT);
+		assert_noop!(
+			Membership::reject_member(RuntimeOrigin::signed(authorised_account), 15),
+			Error::<Test, _>::ApplicantNotFound
+		);
+
+		// withdrawn applications are refunded
+		assert_ok!(Membership::withdraw_application(RuntimeOrigin::signed(17)));
+		System::assert_last_event(Event::ApplicationWithdrawn { who: 17 }.into());
+		assert_eq!(Balances::free_balance(17), 100);
+		assert_noop!(
+			Membership::withdraw_application(RuntimeOrigin::signed(17)),
+			Error::<Test, _>::ApplicantNotFound
+		);
+		assert!(Membership::get_queue().is_empty());
+	});
+}
+
+#[test]
+fn memberships_expire_and_are_cleaned_up_on_idle() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let authorised_account = 1;
+		assert_ok!(Membership::force_add_authorized_account(
+			RawOrigin::Root.into(),
+			authorised_account,
+		));
+		assert_ok!(Membership::add_member(
+			RuntimeOrigin::signed(authorised_account),
+			15,
+			UserLevel::KYCLevel1
+		));
+		System::assert_last_event(
+			Event::MemberAdded {
+				who: 15,
+				kyc_level: UserLevel::KYCLevel1,
+				expires_at: 1 + MEMBERSHIP_PERIOD,
+			}
+			.into(),
+		);
+
+		// the genesis members expire first
+		System::set_block_number(MEMBERSHIP_PERIOD);
+		assert!(!Membership::contains(&10));
+		assert_eq!(Membership::kyc_level(&10), None);
+		assert_eq!(Membership::members(10), Some(UserLevel::KYCLevel1));
+		assert!(Membership::contains(&15));
+
+		// renewing moves the expiry
+		assert_noop!(
+			Membership::renew_membership(RuntimeOrigin::signed(authorised_account), 10),
+			Error::<Test, _>::NotMember
+		);
+		assert_ok!(Membership::renew_membership(RuntimeOrigin::signed(authorised_account), 20));
+		System::assert_last_event(
+			Event::MembershipRenewed { who: 20, expires_at: 2 * MEMBERSHIP_PERIOD }.into(),
+		);
+
+		// expired members are verified again through a new application
+		Balances::make_free_balance_be(&10, 100);
+		assert_ok!(Membership::apply_for_membership(
+			RuntimeOrigin::signed(10),
+			BlakeTwo256::hash_of(&10)
+		));
+		assert_ok!(Membership::accept_member(
+			RuntimeOrigin::signed(authorised_account),
+			10,
+			UserLevel::KYCLevel2
+		));
+		assert_eq!(Membership::membership_expiry(10), Some(2 * MEMBERSHIP_PERIOD));
+
+		// the expiries of the previous blocks were processed
+		Membership::on_idle(MEMBERSHIP_PERIOD - 1, Weight::MAX);
+		assert_eq!(NextExpiryCheck::<Test>::get(), MEMBERSHIP_PERIOD);
+
+		// the expiries are processed within the weight limit
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let limit = db_weight.reads_writes(1, 1) + db_weight.reads(1);
+		assert_eq!(Membership::on_idle(MEMBERSHIP_PERIOD, limit), limit);
+		assert_eq!(Membership::members(30), Some(UserLevel::KYCLevel1));
+
+		let limit = limit + db_weight.reads_writes(1, 3);
+		assert_eq!(Membership::on_idle(MEMBERSHIP_PERIOD, limit), limit);
+		System::assert_last_event(Event::MembershipExpired { who: 30 }.into());
+		assert_eq!(Membership::members(30), None);
+		assert_eq!(Membership::membership_expiry(30), None);
+		assert_eq!(NextExpiryCheck::<Test>::get(), MEMBERSHIP_PERIOD);
+
+		Membership::on_idle(MEMBERSHIP_PERIOD, Weight::MAX);
+		assert_eq!(NextExpiryCheck::<Test>::get(), MEMBERSHIP_PERIOD + 1);
+		for member in [10, 20] {
+			assert!(Membership::contains(&member));
+		}
+
+		System::set_block_number(1 + MEMBERSHIP_PERIOD);
+		Membership::on_idle(1 + MEMBERSHIP_PERIOD, Weight::MAX);
+		System::assert_last_event(Event::MembershipExpired { who: 15 }.into());
+		assert_eq!(Membership::members(15), None);
+		for member in [10, 20] {
+			assert!(Membership::contains(&member));
+		}
+	});
+}
+
+#[test]
+fn migration_to_v5_drops_applications_and_schedules_expiries() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		StorageVersion::new(4).put::<Membership>();
+		for who in [10, 20, 30] {
+			MembershipExpiry::<Test>::remove(who);
+		}
+		let _ = Expiries::<Test>::clear(u32::MAX, None);
+		// a queued application and one left behind by a rejection
+		let old_application = (b"name".to_vec(), b"email".to_vec());
+		for who in [15, 16] {
+			frame_support::storage::unhashed::put(
+				&Applicants::<Test>::hashed_key_for(who),
+				&old_application,
+			);
+		}
+		Queue::<Test>::put(BoundedVec::truncate_from(vec![15]));
+
+		migration::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Membership::on_chain_storage_version(), 5);
+		for who in [15, 16] {
+			assert!(!frame_support::storage::unhashed::exists(
+				&Applicants::<Test>::hashed_key_for(who)
+			));
+		}
+		assert!(Membership::get_queue().is_empty());
+
+		// the applicants apply again with a commitment
+		Balances::make_free_balance_be(&15, 100);
+		assert_ok!(Membership::apply_for_membership(
+			RuntimeOrigin::signed(15),
+			BlakeTwo256::hash_of(&old_application)
+		));
+		for who in [10, 20, 30] {
+			assert_eq!(Membership::membership_expiry(who), Some(5 + MEMBERSHIP_PERIOD));
+			assert!(Expiries::<Test>::contains_key(5 + MEMBERSHIP_PERIOD, who));
+			assert!(AirdropReceived::<Test>::contains_key(who));
+		}
+		assert_eq!(NextExpiryCheck::<Test>::get(), 5);
+	});
+}
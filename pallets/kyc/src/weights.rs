@@ -58,6 +58,10 @@ pub trait WeightInfo {
 	fn change_key(m: u32, ) -> Weight;
 	fn set_prime(m: u32, ) -> Weight;
 	fn clear_prime(m: u32, ) -> Weight;
+	fn apply_for_membership(q: u32, ) -> Weight;
+	fn reject_member(q: u32, ) -> Weight;
+	fn withdraw_application(q: u32, ) -> Weight;
+	fn renew_membership() -> Weight;
 }
 
 /// Weights for pallet_membership using the Substrate node and recommended hardware.
@@ -210,6 +214,55 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(1_298, 0).saturating_mul(m.into()))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: Membership Members (r:1 w:0)
+	/// Storage: Membership MembershipExpiry (r:1 w:0)
+	/// Storage: Membership Queue (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Membership Applicants (r:0 w:1)
+	/// The range of component `q` is `[0, 99]`.
+	fn apply_for_membership(q: u32, ) -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(27_000_000, 4_000)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(q.into()))
+	}
+	/// Storage: Membership AuthorizedAccounts (r:1 w:0)
+	/// Storage: Membership Queue (r:1 w:1)
+	/// Storage: Membership Applicants (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `q` is `[0, 99]`.
+	fn reject_member(q: u32, ) -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(38_000_000, 6_000)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(q.into()))
+	}
+	/// Storage: Membership Queue (r:1 w:1)
+	/// Storage: Membership Applicants (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `q` is `[0, 99]`.
+	fn withdraw_application(q: u32, ) -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(26_000_000, 4_000)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(q.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(q.into()))
+	}
+	/// Storage: Membership AuthorizedAccounts (r:1 w:0)
+	/// Storage: Membership Members (r:1 w:1)
+	/// Storage: Membership MembershipExpiry (r:1 w:1)
+	/// Storage: Membership Expiries (r:0 w:2)
+	fn renew_membership() -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(24_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -361,4 +414,53 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(1_298, 0).saturating_mul(m.into()))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Membership Members (r:1 w:0)
+	/// Storage: Membership MembershipExpiry (r:1 w:0)
+	/// Storage: Membership Queue (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Membership Applicants (r:0 w:1)
+	/// The range of component `q` is `[0, 99]`.
+	fn apply_for_membership(q: u32, ) -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(27_000_000, 4_000)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(q.into()))
+	}
+	/// Storage: Membership AuthorizedAccounts (r:1 w:0)
+	/// Storage: Membership Queue (r:1 w:1)
+	/// Storage: Membership Applicants (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
+	/// The range of component `q` is `[0, 99]`.
+	fn reject_member(q: u32, ) -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(38_000_000, 6_000)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(q.into()))
+	}
+	/// Storage: Membership Queue (r:1 w:1)
+	/// Storage: Membership Applicants (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `q` is `[0, 99]`.
+	fn withdraw_application(q: u32, ) -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(26_000_000, 4_000)
+			.saturating_add(Weight::from_parts(45_000, 0).saturating_mul(q.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 32).saturating_mul(q.into()))
+	}
+	/// Storage: Membership AuthorizedAccounts (r:1 w:0)
+	/// Storage: Membership Members (r:1 w:1)
+	/// Storage: Membership MembershipExpiry (r:1 w:1)
+	/// Storage: Membership Expiries (r:0 w:2)
+	fn renew_membership() -> Weight {
+		// Not generated yet: storage accesses counted from the code, execution time estimated.
+		Weight::from_parts(24_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
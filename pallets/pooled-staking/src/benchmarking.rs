@@ -33,8 +33,10 @@ use {
             tokens::{fungible::Balanced, Precision},
             Get,
         },
+        weights::Weight,
     },
     frame_system::EventRecord,
+    sp_runtime::Perbill,
     sp_std::prelude::*,
 };
 
@@ -592,6 +594,92 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn slash() -> Result<(), BenchmarkError> {
+        const USER_SEED: u32 = 1000;
+        let (caller, _deposit_amount) =
+            create_funded_user::<T>("caller", USER_SEED, min_candidate_stk::<T>());
+
+        T::EligibleCandidatesFilter::make_candidate_eligible(&caller, true);
+
+        // self delegation
+        PooledStaking::<T>::request_delegate(
+            RawOrigin::Signed(caller.clone()).into(),
+            caller.clone(),
+            TargetPool::AutoCompounding,
+            min_candidate_stk::<T>(),
+        )?;
+
+        let timer = T::JoiningRequestTimer::now();
+
+        T::JoiningRequestTimer::skip_to_elapsed();
+
+        PooledStaking::<T>::execute_pending_operations(
+            RawOrigin::Signed(caller.clone()).into(),
+            vec![PendingOperationQuery {
+                delegator: caller.clone(),
+                operation: JoiningAutoCompounding {
+                    candidate: caller.clone(),
+                    at: timer.clone(),
+                },
+            }],
+        )?;
+
+        #[block]
+        {
+            crate::pools::slash::<T>(&caller, Perbill::from_percent(10))?;
+        }
+
+        assert!(crate::PendingSlashes::<T>::contains_key(&caller));
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn settle_slashed_holds(b: Linear<0, 100>) -> Result<(), BenchmarkError> {
+        const USER_SEED: u32 = 1000;
+        let (caller, _deposit_amount) =
+            create_funded_user::<T>("caller", USER_SEED, min_candidate_stk::<T>());
+
+        // Every delegator has a hold that must be lowered
+        for i in 0..b {
+            let (delegator, _deposit) =
+                create_funded_user::<T>("delegator", USER_SEED - 1 - i, min_candidate_stk::<T>());
+
+            PooledStaking::<T>::request_delegate(
+                RawOrigin::Signed(delegator).into(),
+                caller.clone(),
+                TargetPool::ManualRewards,
+                min_candidate_stk::<T>(),
+            )?;
+        }
+
+        if b == 0 {
+            // Without delegators the whole slash is taken from the staking account.
+            let slashed = min_candidate_stk::<T>();
+            T::Currency::set_balance(&T::StakingAccount::get(), slashed + slashed);
+            crate::PendingSlashes::<T>::insert(
+                &caller,
+                crate::PendingSlash {
+                    slashed,
+                    collected: 0u32.into(),
+                    cursor: None,
+                },
+            );
+        } else {
+            crate::pools::slash::<T>(&caller, Perbill::from_percent(10))?;
+        }
+
+        #[block]
+        {
+            crate::pools::settle_pending_slashes::<T>(Weight::MAX);
+        }
+
+        assert!(!crate::PendingSlashes::<T>::contains_key(&caller));
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         PooledStaking,
         crate::mock::ExtBuilder::default().build(),
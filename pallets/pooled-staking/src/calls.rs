@@ -44,6 +44,9 @@ impl<T: Config> Calls<T> {
         delegator: Delegator<T>,
         pool: AllTargetPool,
     ) -> DispatchResultWithPostInfo {
+        // Funds still to be slashed must not be released to the staking account.
+        pools::settle_slashed_hold::<T>(&candidate, &delegator, pool)?;
+
         let (held, stake) = match pool {
             AllTargetPool::Joining => {
                 let held = pools::Joining::<T>::hold(&candidate, &delegator);
@@ -278,6 +281,8 @@ impl<T: Config> Calls<T> {
         pool: TargetPool,
         joining_shares: Shares<T::Balance>,
     ) -> DispatchResultWithPostInfo {
+        pools::settle_slashed_hold::<T>(&candidate, &delegator, AllTargetPool::Joining)?;
+
         // Convert joining shares into stake.
         let stake = pools::Joining::<T>::sub_shares(&candidate, &delegator, joining_shares)?;
 
@@ -378,6 +383,8 @@ impl<T: Config> Calls<T> {
         delegator: Delegator<T>,
         leavinig_shares: Shares<T::Balance>,
     ) -> DispatchResultWithPostInfo {
+        pools::settle_slashed_hold::<T>(&candidate, &delegator, AllTargetPool::Leaving)?;
+
         // Convert leaving shares into stake.
         let stake = pools::Leaving::<T>::sub_shares(&candidate, &delegator, leavinig_shares)?;
 
@@ -533,6 +540,8 @@ impl<T: Config> Calls<T> {
         pool: TargetPool,
         shares: Shares<T::Balance>,
     ) -> Result<Stake<T::Balance>, DispatchErrorWithPostInfo> {
+        pools::settle_slashed_hold::<T>(candidate, delegator, pool.into())?;
+
         match pool {
             TargetPool::AutoCompounding => {
                 let stake = pools::AutoCompounding::<T>::shares_to_stake(&candidate, shares)?;
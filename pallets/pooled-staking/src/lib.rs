@@ -30,6 +30,11 @@
 //! id dedicated to the staking pallet, and delegators can call an extrinsic to transfer their rewards
 //! to their own account (but as reserved). Keeping funds reserved in user accounts allow them to
 //! participate in other processes such as gouvernance.
+//!
+//! A candidate can be slashed with [`Pallet::slash`] or through the [`tp_traits::SlashCollator`]
+//! implementation, which removes the same fraction of the stake of all its pools (including the
+//! leaving pool). As a candidate can have any number of delegators, their holds are lowered
+//! accordingly in `on_idle`, or before any operation changing one of them.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -66,7 +71,7 @@ pub mod pallet {
         frame_support::{
             pallet_prelude::*,
             storage::types::{StorageDoubleMap, StorageValue, ValueQuery},
-            traits::{fungible, tokens::Balance, IsType},
+            traits::{fungible, tokens::Balance, IsType, OnUnbalanced},
             Blake2_128Concat,
         },
         frame_system::pallet_prelude::*,
@@ -227,6 +232,17 @@ pub mod pallet {
     #[derive(RuntimeDebug, Default, PartialEq, Eq, Encode, Decode, Copy, Clone, TypeInfo)]
     pub struct Stake<T>(pub T);
 
+    /// Slash of a candidate whose delegator holds are not all lowered yet.
+    #[derive(RuntimeDebug, Default, PartialEq, Eq, Encode, Decode, Clone, TypeInfo)]
+    pub struct PendingSlash<B> {
+        /// Stake removed from the pools, to be taken from the holds or the staking account.
+        pub slashed: B,
+        /// Amount already taken from the holds of the delegators.
+        pub collected: B,
+        /// Raw key of the next `Pools` entry to check, starting from the first one if `None`.
+        pub cursor: Option<Vec<u8>>,
+    }
+
     /// Pooled Staking pallet.
     #[pallet::pallet]
     #[pallet::without_storage_info]
@@ -241,7 +257,8 @@ pub mod pallet {
         type Currency: fungible::Inspect<Self::AccountId, Balance = Self::Balance>
             + fungible::Mutate<Self::AccountId>
             + fungible::Balanced<Self::AccountId>
-            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+            + fungible::BalancedHold<Self::AccountId>;
 
         /// Same as Currency::Balance. Must impl `MulDiv` which perform
         /// multiplication followed by division using a bigger type to avoid
@@ -282,6 +299,9 @@ pub mod pallet {
         type EligibleCandidatesBufferSize: Get<u32>;
         /// Additional filter for candidates to be eligible.
         type EligibleCandidatesFilter: IsCandidateEligible<Self::AccountId>;
+        /// Handler for the funds slashed from candidates and their delegators.
+        /// Dropping them burns them.
+        type OnSlash: OnUnbalanced<CreditOf<Self>>;
 
         type WeightInfo: WeightInfo;
     }
@@ -324,6 +344,11 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Slashes whose delegator holds are being lowered in `on_idle`.
+    #[pallet::storage]
+    pub type PendingSlashes<T: Config> =
+        StorageMap<_, Blake2_128Concat, Candidate<T>, PendingSlash<T::Balance>, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -440,6 +465,12 @@ pub mod pallet {
             pending_leaving: T::Balance,
             released: T::Balance,
         },
+        /// Stake of a pool of that candidate was slashed, decreasing the value of its shares.
+        SlashedPool {
+            candidate: Candidate<T>,
+            pool: AllTargetPool,
+            stake: T::Balance,
+        },
     }
 
     #[pallet::error]
@@ -474,6 +505,10 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            pools::settle_pending_slashes::<T>(remaining_weight)
+        }
+
         #[cfg(feature = "try-runtime")]
         fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
             use sp_std::collections::btree_set::BTreeSet;
@@ -621,6 +656,15 @@ pub mod pallet {
             .ok()
             .map(|x| x.0)
         }
+
+        /// Slash `fraction` of the stake of all the pools of `candidate`, the holds of its
+        /// delegators are lowered later on. Returns the amount of stake slashed.
+        pub fn slash(
+            candidate: &Candidate<T>,
+            fraction: Perbill,
+        ) -> Result<T::Balance, DispatchError> {
+            pools::slash::<T>(candidate, fraction)
+        }
     }
 
     impl<T: Config> tp_traits::DistributeRewards<Candidate<T>, CreditOf<T>> for Pallet<T> {
@@ -631,4 +675,14 @@ pub mod pallet {
             pools::distribute_rewards::<T>(&candidate, rewards)
         }
     }
+
+    impl<T: Config> tp_traits::SlashCollator<Candidate<T>> for Pallet<T> {
+        fn slash_collator(collator: &Candidate<T>, fraction: Perbill) -> Weight {
+            if let Err(e) = pools::slash::<T>(collator, fraction) {
+                log::warn!("Failed to slash collator {:?}: {:?}", collator, e);
+            }
+
+            T::WeightInfo::slash()
+        }
+    }
 }
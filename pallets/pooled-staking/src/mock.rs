@@ -169,6 +169,7 @@ impl pallet_pooled_staking::Config for Runtime {
     // low value so we can test vec bounding, in practice it should be bigger
     type EligibleCandidatesBufferSize = ConstU32<3>;
     type EligibleCandidatesFilter = ();
+    type OnSlash = ();
     type WeightInfo = ();
     type RuntimeHoldReason = RuntimeHoldReason;
 }
@@ -16,17 +16,26 @@
 
 use {
     crate::{
-        candidate::Candidates, weights::WeightInfo, Candidate, Config, CreditOf, Delegator, Error,
-        Event, Pallet, Pools, PoolsKey, Shares, Stake,
+        candidate::Candidates, weights::WeightInfo, AllTargetPool, Candidate, Config, CreditOf,
+        Delegator, Error, Event, HoldReason, Pallet, PendingSlashes, Pools, PoolsKey, Shares,
+        Stake,
     },
     core::marker::PhantomData,
     frame_support::{
         ensure,
         pallet_prelude::*,
-        traits::{fungible::Balanced, Imbalance},
+        traits::{
+            fungible::{Balanced, BalancedHold},
+            tokens::{Fortitude, Precision, Preservation},
+            Imbalance, OnUnbalanced,
+        },
     },
     sp_core::Get,
-    sp_runtime::traits::{CheckedAdd, CheckedDiv, Zero},
+    sp_runtime::{
+        traits::{CheckedAdd, CheckedDiv, Saturating, Zero},
+        Perbill,
+    },
+    sp_std::vec::Vec,
     tp_maths::{ErrAdd, ErrMul, ErrSub, MulDiv},
 };
 
@@ -561,3 +570,247 @@ fn distribute_rewards_inner<T: Config>(
 
     Ok(candidate_manual_rewards)
 }
+
+/// Slash `fraction` of the stake of all the pools of the provided candidate.
+///
+/// Slashing a pool decreases its total stake without changing the amount of shares, which
+/// decreases the value of each share. The Leaving pool is slashed too as leaving funds are
+/// still at stake until the request is executed, but it doesn't impact the candidate total
+/// stake.
+///
+/// As the candidate can have any number of delegators, their holds are not lowered here. The
+/// slash is recorded in `PendingSlashes` and the holds are lowered to the new value of their
+/// shares by [`settle_pending_slashes`], or by [`settle_slashed_hold`] before an operation
+/// changes one of them.
+///
+/// Returns the amount of stake slashed.
+#[frame_support::transactional]
+pub fn slash<T: Config>(
+    candidate: &Candidate<T>,
+    fraction: Perbill,
+) -> Result<T::Balance, DispatchError> {
+    let joining = slash_pool::<T, Joining<T>>(candidate, AllTargetPool::Joining, fraction)?;
+    let auto =
+        slash_pool::<T, AutoCompounding<T>>(candidate, AllTargetPool::AutoCompounding, fraction)?;
+    let manual =
+        slash_pool::<T, ManualRewards<T>>(candidate, AllTargetPool::ManualRewards, fraction)?;
+    let leaving = slash_pool::<T, Leaving<T>>(candidate, AllTargetPool::Leaving, fraction)?;
+
+    let staked = joining
+        .err_add(&auto)
+        .and_then(|stake| stake.err_add(&manual))
+        .map_err(Error::<T>::from)?;
+    let slashed = staked.err_add(&leaving).map_err(Error::<T>::from)?;
+    if slashed.is_zero() {
+        return Ok(Zero::zero());
+    }
+
+    PendingSlashes::<T>::mutate(candidate, |pending| {
+        let pending = pending.get_or_insert_with(Default::default);
+        pending.slashed = pending.slashed.saturating_add(slashed);
+        // The holds already lowered must be lowered again to the new value of their shares.
+        pending.cursor = None;
+    });
+
+    Candidates::<T>::sub_total_stake(candidate, Stake(staked))?;
+    check_candidate_consistency::<T>(candidate)?;
+
+    Ok(slashed)
+}
+
+/// Lower the holds of the delegators of the pending slashes as long as `remaining_weight`
+/// allows it, resuming from the `Pools` entry the previous call stopped at.
+///
+/// Once all the holds of a candidate are lowered, the part of the slash they didn't cover is
+/// taken from the rewards kept in the staking account, as AutoCompounding shares can be worth
+/// more than their hold. All the slashed funds are handed over to `OnSlash`.
+///
+/// Returns the weight consumed.
+pub fn settle_pending_slashes<T: Config>(remaining_weight: Weight) -> Weight {
+    // Reading the next pending slash
+    let mut used = T::DbWeight::get().reads(1);
+    if used.any_gt(remaining_weight) {
+        return Weight::zero();
+    }
+    let per_slash = T::WeightInfo::settle_slashed_holds(0);
+    // Every entry is charged as a lowered hold, even if most of them are not holds.
+    let per_entry = T::WeightInfo::settle_slashed_holds(1).saturating_sub(per_slash);
+
+    while !used.saturating_add(per_slash).any_gt(remaining_weight) {
+        let Some((candidate, mut pending)) = PendingSlashes::<T>::iter().next() else {
+            break;
+        };
+        used.saturating_accrue(per_slash);
+
+        let mut entries = match pending.cursor.take() {
+            Some(raw_key) => Pools::<T>::iter_prefix_from(&candidate, raw_key),
+            None => Pools::<T>::iter_prefix(&candidate),
+        };
+
+        // Holds are collected first as the map cannot be modified while iterating over it.
+        let mut holds = Vec::new();
+        let done = loop {
+            if used.saturating_add(per_entry).any_gt(remaining_weight) {
+                break false;
+            }
+            let Some((key, hold)) = entries.next() else {
+                break true;
+            };
+            used.saturating_accrue(per_entry);
+
+            if hold.is_zero() {
+                continue;
+            }
+            match key {
+                PoolsKey::JoiningSharesHeldStake { delegator } => {
+                    holds.push((AllTargetPool::Joining, delegator))
+                }
+                PoolsKey::AutoCompoundingSharesHeldStake { delegator } => {
+                    holds.push((AllTargetPool::AutoCompounding, delegator))
+                }
+                PoolsKey::ManualRewardsSharesHeldStake { delegator } => {
+                    holds.push((AllTargetPool::ManualRewards, delegator))
+                }
+                PoolsKey::LeavingSharesHeldStake { delegator } => {
+                    holds.push((AllTargetPool::Leaving, delegator))
+                }
+                _ => (),
+            }
+        };
+        let cursor = entries.last_raw_key().to_vec();
+
+        for (pool, delegator) in holds {
+            match slash_delegator_hold::<T>(&candidate, &delegator, pool) {
+                Ok(lowered) => pending.collected = pending.collected.saturating_add(lowered),
+                Err(e) => log::warn!(
+                    "Failed to slash the hold of {:?} towards {:?}: {:?}",
+                    delegator,
+                    candidate,
+                    e
+                ),
+            }
+        }
+
+        if !done {
+            pending.cursor = Some(cursor);
+            PendingSlashes::<T>::insert(&candidate, pending);
+            break;
+        }
+
+        PendingSlashes::<T>::remove(&candidate);
+
+        // Rounding can make the holds lose a bit more than the pools, in which case there is
+        // nothing left to take from the staking account.
+        let from_rewards = pending.slashed.saturating_sub(pending.collected);
+        if !from_rewards.is_zero() {
+            let slashed_rewards = T::Currency::withdraw(
+                &T::StakingAccount::get(),
+                from_rewards,
+                Precision::BestEffort,
+                Preservation::Preserve,
+                Fortitude::Polite,
+            )
+            .unwrap_or(CreditOf::<T>::zero());
+            T::OnSlash::on_unbalanced(slashed_rewards);
+        }
+    }
+
+    used
+}
+
+/// Lower the hold of a delegator towards a candidate with a pending slash, so that an operation
+/// changing the hold doesn't release funds that must be slashed.
+pub fn settle_slashed_hold<T: Config>(
+    candidate: &Candidate<T>,
+    delegator: &Delegator<T>,
+    pool: AllTargetPool,
+) -> Result<(), DispatchError> {
+    let Some(mut pending) = PendingSlashes::<T>::get(candidate) else {
+        return Ok(());
+    };
+
+    let lowered = slash_delegator_hold::<T>(candidate, delegator, pool)?;
+    if !lowered.is_zero() {
+        pending.collected = pending.collected.saturating_add(lowered);
+        PendingSlashes::<T>::insert(candidate, pending);
+    }
+
+    Ok(())
+}
+
+/// Lower the hold of a delegator in a pool to the value of its shares and hand over the
+/// difference to `OnSlash`. Returns the amount slashed.
+fn slash_delegator_hold<T: Config>(
+    candidate: &Candidate<T>,
+    delegator: &Delegator<T>,
+    pool: AllTargetPool,
+) -> Result<T::Balance, DispatchError> {
+    let lowered = match pool {
+        AllTargetPool::Joining => slash_hold::<T, Joining<T>>(candidate, delegator)?,
+        AllTargetPool::AutoCompounding => {
+            slash_hold::<T, AutoCompounding<T>>(candidate, delegator)?
+        }
+        AllTargetPool::ManualRewards => slash_hold::<T, ManualRewards<T>>(candidate, delegator)?,
+        AllTargetPool::Leaving => slash_hold::<T, Leaving<T>>(candidate, delegator)?,
+    };
+
+    if lowered.is_zero() {
+        return Ok(lowered);
+    }
+
+    let (credit, _missing) = <T::Currency as BalancedHold<T::AccountId>>::slash(
+        &HoldReason::PooledStake.into(),
+        delegator,
+        lowered,
+    );
+    let slashed = credit.peek();
+    T::OnSlash::on_unbalanced(credit);
+
+    Ok(slashed)
+}
+
+/// Slash `fraction` of the total stake of a pool, emitting an event if anything was slashed.
+fn slash_pool<T: Config, P: Pool<T>>(
+    candidate: &Candidate<T>,
+    pool: AllTargetPool,
+    fraction: Perbill,
+) -> Result<T::Balance, Error<T>> {
+    let Stake(total_staked) = P::total_staked(candidate);
+    let slashed = fraction * total_staked;
+
+    if slashed.is_zero() {
+        return Ok(slashed);
+    }
+
+    P::slash_stake_among_holders(candidate, Stake(slashed))?;
+
+    Pallet::<T>::deposit_event(Event::<T>::SlashedPool {
+        candidate: candidate.clone(),
+        pool,
+        stake: slashed,
+    });
+
+    Ok(slashed)
+}
+
+/// Lower the hold of a delegator to the value of its shares if it is now worth less.
+/// Returns the amount by which the hold was lowered.
+fn slash_hold<T: Config, P: Pool<T>>(
+    candidate: &Candidate<T>,
+    delegator: &Delegator<T>,
+) -> Result<T::Balance, Error<T>> {
+    // Holds without shares are rounding leftovers that are not at stake anymore.
+    if P::shares(candidate, delegator).0.is_zero() {
+        return Ok(Zero::zero());
+    }
+
+    let Stake(hold) = P::hold(candidate, delegator);
+    let Stake(stake) = P::computed_stake(candidate, delegator)?;
+
+    if stake >= hold {
+        return Ok(Zero::zero());
+    }
+
+    P::set_hold(candidate, delegator, Stake(stake));
+    Ok(hold.err_sub(&stake)?)
+}
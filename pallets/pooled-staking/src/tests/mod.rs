@@ -19,6 +19,7 @@ mod delegator_flow;
 mod manual_rewards;
 mod rebalance;
 mod rewards;
+mod slashing;
 
 use {
     crate::{
@@ -0,0 +1,370 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::{
+        assert_eq_last_events,
+        pools::{AutoCompounding, ManualRewards},
+        weights::WeightInfo,
+        PendingSlashes,
+    },
+    frame_support::{traits::Hooks, weights::Weight},
+    sp_runtime::Perbill,
+    tp_traits::SlashCollator,
+};
+
+#[test]
+fn slash_decreases_all_pools_and_holds() {
+    ExtBuilder::default().build().execute_with(|| {
+        // Preparation:
+        // Delegator 1 is in the AutoCompounding pool and joining the ManualRewards pool,
+        // delegator 2 is in the ManualRewards pool and leaving part of it.
+        assert_ok!(Staking::request_delegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::AutoCompounding,
+            10 * MEGA,
+        ));
+        assert_ok!(Staking::request_delegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_2),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::ManualRewards,
+            10 * MEGA,
+        ));
+
+        roll_to(1 + BLOCKS_TO_WAIT);
+
+        assert_ok!(Staking::execute_pending_operations(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            vec![
+                PendingOperationQuery {
+                    delegator: ACCOUNT_DELEGATOR_1,
+                    operation: PendingOperationKey::JoiningAutoCompounding {
+                        candidate: ACCOUNT_CANDIDATE_1,
+                        at: 1,
+                    },
+                },
+                PendingOperationQuery {
+                    delegator: ACCOUNT_DELEGATOR_2,
+                    operation: PendingOperationKey::JoiningManualRewards {
+                        candidate: ACCOUNT_CANDIDATE_1,
+                        at: 1,
+                    },
+                },
+            ]
+        ));
+
+        let now = block_number();
+        assert_ok!(Staking::request_delegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::ManualRewards,
+            4 * MEGA,
+        ));
+        assert_ok!(Staking::request_undelegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_2),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::ManualRewards,
+            SharesOrStake::Stake(3 * MEGA),
+        ));
+
+        // We then artificialy distribute AutoCompounding rewards, which are kept in the
+        // staking account until the hold is rebalanced.
+        assert_ok!(Balances::mint_into(&ACCOUNT_STAKING, MEGA));
+        assert_ok!(AutoCompounding::<Runtime>::share_stake_among_holders(
+            &ACCOUNT_CANDIDATE_1,
+            Stake(MEGA)
+        ));
+        assert_ok!(Candidates::<Runtime>::add_total_stake(
+            &ACCOUNT_CANDIDATE_1,
+            &Stake(MEGA)
+        ));
+
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_1),
+            Stake(22 * MEGA)
+        );
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_1), 14 * MEGA);
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_2), 10 * MEGA);
+        let issuance_before = Balances::total_issuance();
+
+        // Slash 10% of the candidate stake.
+        assert_eq!(
+            Staking::slash(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(10)),
+            Ok(2_500 * KILO)
+        );
+
+        assert_eq_last_events!(vec![
+            Event::<Runtime>::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::Joining,
+                stake: 400 * KILO,
+            },
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::AutoCompounding,
+                stake: 1_100 * KILO,
+            },
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::ManualRewards,
+                stake: 700 * KILO,
+            },
+            Event::SlashedPool {
+                candidate: ACCOUNT_CANDIDATE_1,
+                pool: AllTargetPool::Leaving,
+                stake: 300 * KILO,
+            },
+            Event::DecreasedStake {
+                candidate: ACCOUNT_CANDIDATE_1,
+                stake_diff: 2_200 * KILO,
+            },
+            Event::UpdatedCandidatePosition {
+                candidate: ACCOUNT_CANDIDATE_1,
+                stake: 19_800 * KILO,
+                self_delegation: 0,
+                before: None,
+                after: None,
+            },
+        ]);
+
+        // Leaving stake doesn't count in the candidate total stake.
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_1),
+            Stake(19_800 * KILO)
+        );
+        assert_ok!(pools::check_candidate_consistency::<Runtime>(
+            &ACCOUNT_CANDIDATE_1
+        ));
+
+        // Holds are only lowered in `on_idle`.
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_1), 14 * MEGA);
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_2), 10 * MEGA);
+        assert_eq!(Balances::total_issuance(), issuance_before);
+
+        Staking::on_idle(block_number(), Weight::MAX);
+        assert!(!PendingSlashes::<Runtime>::contains_key(
+            &ACCOUNT_CANDIDATE_1
+        ));
+
+        // Holds are lowered to the new value of the shares, the AutoCompounding rewards
+        // not yet in the hold are slashed from the staking account.
+        assert_eq!(
+            PoolState::extract::<Joining>(ACCOUNT_CANDIDATE_1, ACCOUNT_DELEGATOR_1),
+            PoolState {
+                hold: 3_600 * KILO,
+                stake: 3_600 * KILO,
+            }
+        );
+        assert_eq!(
+            PoolState::extract::<AutoCompounding<Runtime>>(
+                ACCOUNT_CANDIDATE_1,
+                ACCOUNT_DELEGATOR_1
+            ),
+            PoolState {
+                hold: 9_900 * KILO,
+                stake: 9_900 * KILO,
+            }
+        );
+        assert_eq!(
+            PoolState::extract::<ManualRewards<Runtime>>(ACCOUNT_CANDIDATE_1, ACCOUNT_DELEGATOR_2),
+            PoolState {
+                hold: 6_300 * KILO,
+                stake: 6_300 * KILO,
+            }
+        );
+        assert_eq!(
+            PoolState::extract::<Leaving>(ACCOUNT_CANDIDATE_1, ACCOUNT_DELEGATOR_2),
+            PoolState {
+                hold: 2_700 * KILO,
+                stake: 2_700 * KILO,
+            }
+        );
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_1), 13_500 * KILO);
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_2), 9 * MEGA);
+        assert_eq!(total_balance(&ACCOUNT_STAKING), DEFAULT_BALANCE);
+
+        // Slashed funds are burned by the mock.
+        assert_eq!(Balances::total_issuance(), issuance_before - 2_500 * KILO);
+
+        // Pending operations execute with the slashed values.
+        roll_to(now + BLOCKS_TO_WAIT);
+
+        assert_ok!(Staking::execute_pending_operations(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_1),
+            vec![
+                PendingOperationQuery {
+                    delegator: ACCOUNT_DELEGATOR_1,
+                    operation: PendingOperationKey::JoiningManualRewards {
+                        candidate: ACCOUNT_CANDIDATE_1,
+                        at: now,
+                    },
+                },
+                PendingOperationQuery {
+                    delegator: ACCOUNT_DELEGATOR_2,
+                    operation: PendingOperationKey::Leaving {
+                        candidate: ACCOUNT_CANDIDATE_1,
+                        at: now,
+                    },
+                },
+            ]
+        ));
+
+        assert_eq!(
+            ManualRewards::<Runtime>::computed_stake(&ACCOUNT_CANDIDATE_1, &ACCOUNT_DELEGATOR_1),
+            Ok(Stake(3_600 * KILO))
+        );
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_1), 13_500 * KILO);
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_2), 6_300 * KILO);
+        assert_eq!(
+            Candidates::<Runtime>::total_stake(&ACCOUNT_CANDIDATE_1),
+            Stake(19_800 * KILO)
+        );
+        assert_ok!(pools::check_candidate_consistency::<Runtime>(
+            &ACCOUNT_CANDIDATE_1
+        ));
+    })
+}
+
+#[test]
+fn slash_collator_without_stake_does_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        let issuance_before = Balances::total_issuance();
+
+        Staking::slash_collator(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(50));
+
+        assert_eq!(Balances::total_issuance(), issuance_before);
+        assert_eq!(events(), vec![]);
+    })
+}
+
+#[test]
+fn slash_collator_slashes_self_delegation() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_CANDIDATE_1,
+            request_amount: 20 * MEGA,
+            expected_increase: 20 * MEGA,
+            ..default()
+        }
+        .test::<ManualRewards<Runtime>>();
+
+        assert_eq!(
+            crate::SortedEligibleCandidates::<Runtime>::get().into_inner(),
+            vec![crate::EligibleCandidate {
+                candidate: ACCOUNT_CANDIDATE_1,
+                stake: 20 * MEGA,
+            }]
+        );
+
+        // Falling under the minimum self delegation makes the candidate not eligible.
+        Staking::slash_collator(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(60));
+        Staking::on_idle(block_number(), Weight::MAX);
+
+        assert_eq!(
+            PoolState::extract::<ManualRewards<Runtime>>(ACCOUNT_CANDIDATE_1, ACCOUNT_CANDIDATE_1),
+            PoolState {
+                hold: 8 * MEGA,
+                stake: 8 * MEGA,
+            }
+        );
+        assert_eq!(balance_hold(&ACCOUNT_CANDIDATE_1), 8 * MEGA);
+        assert_eq!(
+            total_balance(&ACCOUNT_CANDIDATE_1),
+            DEFAULT_BALANCE - 12 * MEGA
+        );
+        assert!(crate::SortedEligibleCandidates::<Runtime>::get().is_empty());
+    })
+}
+
+#[test]
+fn slashed_holds_are_lowered_over_several_blocks() {
+    ExtBuilder::default().build().execute_with(|| {
+        for delegator in [ACCOUNT_DELEGATOR_1, ACCOUNT_DELEGATOR_2] {
+            FullDelegation {
+                candidate: ACCOUNT_CANDIDATE_1,
+                delegator,
+                request_amount: 10 * MEGA,
+                expected_increase: 10 * MEGA,
+                ..default()
+            }
+            .test::<ManualRewards<Runtime>>();
+        }
+        let issuance_before = Balances::total_issuance();
+
+        assert_eq!(
+            Staking::slash(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(10)),
+            Ok(2 * MEGA)
+        );
+
+        // Only one `Pools` entry can be checked per block.
+        let limit = <() as WeightInfo>::settle_slashed_holds(1);
+        let mut blocks = 0;
+        while PendingSlashes::<Runtime>::contains_key(&ACCOUNT_CANDIDATE_1) {
+            assert!(Staking::on_idle(block_number(), limit).all_lte(limit));
+            blocks += 1;
+        }
+        assert!(blocks > 2);
+
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_1), 9 * MEGA);
+        assert_eq!(balance_hold(&ACCOUNT_DELEGATOR_2), 9 * MEGA);
+        assert_eq!(Balances::total_issuance(), issuance_before - 2 * MEGA);
+    })
+}
+
+#[test]
+fn operations_lower_the_slashed_hold_first() {
+    ExtBuilder::default().build().execute_with(|| {
+        FullDelegation {
+            candidate: ACCOUNT_CANDIDATE_1,
+            delegator: ACCOUNT_DELEGATOR_2,
+            request_amount: 10 * MEGA,
+            expected_increase: 10 * MEGA,
+            ..default()
+        }
+        .test::<ManualRewards<Runtime>>();
+        let issuance_before = Balances::total_issuance();
+
+        assert_eq!(
+            Staking::slash(&ACCOUNT_CANDIDATE_1, Perbill::from_percent(10)),
+            Ok(MEGA)
+        );
+
+        // Undelegating before `on_idle` doesn't release the funds to slash.
+        assert_ok!(Staking::request_undelegate(
+            RuntimeOrigin::signed(ACCOUNT_DELEGATOR_2),
+            ACCOUNT_CANDIDATE_1,
+            TargetPool::ManualRewards,
+            SharesOrStake::Stake(3 * MEGA),
+        ));
+
+        assert_eq!(
+            PendingSlashes::<Runtime>::get(&ACCOUNT_CANDIDATE_1).map(|pending| pending.collected),
+            Some(MEGA)
+        );
+        assert_eq!(Balances::total_issuance(), issuance_before - MEGA);
+        assert!(balance_hold(&ACCOUNT_DELEGATOR_2) <= 9 * MEGA);
+
+        // Nothing is left to slash once the other holds are checked.
+        Staking::on_idle(block_number(), Weight::MAX);
+        assert!(!PendingSlashes::<Runtime>::contains_key(
+            &ACCOUNT_CANDIDATE_1
+        ));
+        assert_eq!(Balances::total_issuance(), issuance_before - MEGA);
+    })
+}
@@ -60,6 +60,8 @@ pub trait WeightInfo {
 	fn update_candidate_position(b: u32, ) -> Weight;
 	fn swap_pool() -> Weight;
 	fn distribute_rewards() -> Weight;
+	fn slash() -> Weight;
+	fn settle_slashed_holds(b: u32, ) -> Weight;
 }
 
 /// Weights for pallet_pooled_staking using the Substrate node and recommended hardware.
@@ -213,6 +215,46 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(13_u64))
 			.saturating_add(T::DbWeight::get().writes(8_u64))
 	}
+	/// Storage: PooledStaking Pools (r:9 w:5)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking PendingSlashes (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking SortedEligibleCandidates (r:1 w:1)
+	/// Proof Skipped: PooledStaking SortedEligibleCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session NextKeys (r:1 w:0)
+	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+	fn slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1302`
+		//  Estimated: `24567`
+		// Minimum execution time: 98_214_000 picoseconds.
+		Weight::from_parts(112_630_000, 24567)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: PooledStaking PendingSlashes (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking Pools (r:400 w:100)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Holds (r:100 w:100)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(66), added: 2541, mode: MaxEncodedLen)
+	/// Storage: System Account (r:101 w:101)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `b` is `[0, 100]`.
+	fn settle_slashed_holds(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612 + b * (420 ±0)`
+		//  Estimated: `6196 + b * (7684 ±0)`
+		// Minimum execution time: 41_208_000 picoseconds.
+		Weight::from_parts(45_350_000, 6196)
+			// Standard Error: 88_127
+			.saturating_add(Weight::from_parts(41_870_350, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 7684).saturating_mul(b.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -365,4 +407,44 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(13_u64))
 			.saturating_add(RocksDbWeight::get().writes(8_u64))
 	}
+	/// Storage: PooledStaking Pools (r:9 w:5)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking PendingSlashes (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking SortedEligibleCandidates (r:1 w:1)
+	/// Proof Skipped: PooledStaking SortedEligibleCandidates (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session NextKeys (r:1 w:0)
+	/// Proof Skipped: Session NextKeys (max_values: None, max_size: None, mode: Measured)
+	fn slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1302`
+		//  Estimated: `24567`
+		// Minimum execution time: 98_214_000 picoseconds.
+		Weight::from_parts(112_630_000, 24567)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: PooledStaking PendingSlashes (r:1 w:1)
+	/// Proof Skipped: PooledStaking PendingSlashes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: PooledStaking Pools (r:400 w:100)
+	/// Proof Skipped: PooledStaking Pools (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Balances Holds (r:100 w:100)
+	/// Proof: Balances Holds (max_values: None, max_size: Some(66), added: 2541, mode: MaxEncodedLen)
+	/// Storage: System Account (r:101 w:101)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `b` is `[0, 100]`.
+	fn settle_slashed_holds(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612 + b * (420 ±0)`
+		//  Estimated: `6196 + b * (7684 ±0)`
+		// Minimum execution time: 41_208_000 picoseconds.
+		Weight::from_parts(45_350_000, 6196)
+			// Standard Error: 88_127
+			.saturating_add(Weight::from_parts(41_870_350, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 7684).saturating_mul(b.into()))
+	}
 }
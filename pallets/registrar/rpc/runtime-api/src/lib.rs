@@ -34,5 +34,8 @@ sp_api::decl_runtime_apis! {
 
         /// Fetch boot_nodes for this para id
         fn boot_nodes(para_id: ParaId) -> Vec<Vec<u8>>;
+
+        /// Fetch the code announced for this para id, that collators can fetch before the upgrade
+        fn pending_code(para_id: ParaId) -> Option<Vec<u8>>;
     }
 }
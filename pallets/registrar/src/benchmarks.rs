@@ -20,8 +20,9 @@
 use {
     crate::{Call, Config, DepositBalanceOf, Pallet, RegistrarHooks},
     frame_benchmarking::{account, v2::*},
-    frame_support::traits::Currency,
+    frame_support::traits::{Currency, EnsureOriginWithArg},
     frame_system::RawOrigin,
+    parity_scale_codec::Encode,
     sp_core::Get,
     sp_std::{vec, vec::Vec},
     tp_container_chain_genesis_data::{ContainerChainGenesisData, ContainerChainGenesisDataItem},
@@ -355,5 +356,75 @@ mod benchmarks {
         assert!(Pallet::<T>::registered_para_ids().contains(&ParaId::from(1000)));
     }
 
+    #[benchmark]
+    fn update_para_genesis_data(x: Linear<5, 3_000_000>, z: Linear<1, 10>) {
+        let mut data = vec![];
+        // Number of keys
+        for _i in 1..z {
+            data.push((b"code".to_vec(), vec![1; (x / z) as usize]).into())
+        }
+
+        let storage = new_genesis_data(data);
+        let para_id = ParaId::from(1000);
+
+        let (caller, _deposit_amount) = create_funded_user::<T>(
+            "caller",
+            0,
+            Pallet::<T>::required_deposit(storage.encoded_size()),
+        );
+        Pallet::<T>::register(
+            RawOrigin::Signed(caller.clone()).into(),
+            para_id,
+            new_genesis_data(vec![]),
+        )
+        .unwrap();
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        // Fund the manager again, try_successful_origin may have reset its balance
+        T::Currency::make_free_balance_be(
+            &caller,
+            Pallet::<T>::required_deposit(storage.encoded_size()) * 2u32.into(),
+        );
+
+        #[extrinsic_call]
+        Pallet::<T>::update_para_genesis_data(origin as T::RuntimeOrigin, para_id, storage.clone());
+
+        // verification code
+        assert_eq!(
+            Pallet::<T>::para_genesis_data(para_id),
+            Some(storage.clone())
+        );
+        assert_eq!(
+            Pallet::<T>::registrar_deposit(para_id).map(|info| info.deposit),
+            Some(Pallet::<T>::required_deposit(storage.encoded_size()))
+        );
+    }
+
+    #[benchmark]
+    fn announce_para_code(x: Linear<1, 3_000_000>) {
+        // Only the size of the genesis data is read, not the genesis data itself
+        let storage = new_genesis_data(vec![]);
+        let para_id = ParaId::from(1000);
+        let code = vec![1; x as usize];
+        let deposit = Pallet::<T>::required_deposit(storage.encoded_size() + code.len());
+
+        let (caller, _deposit_amount) = create_funded_user::<T>("caller", 0, deposit);
+        Pallet::<T>::register(RawOrigin::Signed(caller.clone()).into(), para_id, storage).unwrap();
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        // Fund the manager again, try_successful_origin may have reset its balance
+        T::Currency::make_free_balance_be(&caller, deposit * 2u32.into());
+
+        #[extrinsic_call]
+        Pallet::<T>::announce_para_code(origin as T::RuntimeOrigin, para_id, Some(code.clone()));
+
+        // verification code
+        assert_eq!(Pallet::<T>::para_pending_code(para_id), Some(code));
+        assert_eq!(
+            Pallet::<T>::registrar_deposit(para_id).map(|info| info.deposit),
+            Some(deposit)
+        );
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
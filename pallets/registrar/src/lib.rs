@@ -24,6 +24,13 @@
 //! Registered container chains are stored in the PendingParaIds storage item until the session
 //! in which they can be onboarded arrives, in which case they are added to the RegisteredParaIds
 //! storage item.
+//!
+//! The manager of a container chain, the account that paid its registration deposit, can replace
+//! the genesis data of a container chain that has not produced any blocks yet, and announce the
+//! `:code` that an existing container chain will upgrade to, so that collators can fetch it in
+//! advance. The deposit is adjusted to the size of the stored genesis data and announced code.
+//! The announced code is removed, and its deposit released, once the relay chain reports that the
+//! container chain runs it.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -43,14 +50,20 @@ use {
     crate::weights::WeightInfo,
     frame_support::{
         pallet_prelude::*,
-        traits::{Currency, ReservableCurrency},
+        traits::{Currency, EnsureOriginWithArg, ReservableCurrency},
         DefaultNoBound, LOG_TARGET,
     },
     frame_system::pallet_prelude::*,
-    sp_runtime::{traits::AtLeast32BitUnsigned, Saturating},
+    sp_runtime::{
+        traits::{AtLeast32BitUnsigned, Hash},
+        Saturating,
+    },
     sp_std::prelude::*,
     tp_container_chain_genesis_data::ContainerChainGenesisData,
-    tp_traits::{GetCurrentContainerChains, GetSessionContainerChains, GetSessionIndex, ParaId},
+    tp_traits::{
+        ContainerChainCodeHook, GetCurrentContainerChains, GetSessionContainerChains,
+        GetSessionIndex, ParaId, ValidationCodeHash,
+    },
 };
 
 #[frame_support::pallet]
@@ -100,6 +113,7 @@ pub mod pallet {
                     );
                 }
                 <ParaGenesisData<T>>::insert(para_id, genesis_data);
+                <ParaGenesisDataSize<T>>::insert(para_id, genesis_data_size as u32);
             }
 
             <RegisteredParaIds<T>>::put(bounded_para_ids);
@@ -115,6 +129,9 @@ pub mod pallet {
         /// Origin that is allowed to call register and deregister
         type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+        /// Origin that is allowed to update the genesis data and announce the code of a para id
+        type ManagerOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, ParaId>;
+
         /// Max length of para id list
         #[pallet::constant]
         type MaxLengthParaIds: Get<u32>;
@@ -139,6 +156,10 @@ pub mod pallet {
         #[pallet::constant]
         type DepositAmount: Get<<Self::Currency as Currency<Self::AccountId>>::Balance>;
 
+        /// Deposit reserved for each byte of genesis data and announced code, on top of `DepositAmount`
+        #[pallet::constant]
+        type DataDepositPerByte: Get<<Self::Currency as Currency<Self::AccountId>>::Balance>;
+
         type RegistrarHooks: RegistrarHooks;
 
         type WeightInfo: WeightInfo;
@@ -167,6 +188,11 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Encoded size of the genesis data in `ParaGenesisData`, so that the deposit of a para id can
+    /// be adjusted without decoding its genesis data
+    #[pallet::storage]
+    pub type ParaGenesisDataSize<T: Config> = StorageMap<_, Blake2_128Concat, ParaId, u32>;
+
     #[pallet::storage]
     #[pallet::getter(fn pending_verification)]
     pub type PendingVerification<T: Config> =
@@ -210,6 +236,18 @@ pub mod pallet {
     #[pallet::getter(fn registrar_deposit)]
     pub type RegistrarDeposit<T: Config> = StorageMap<_, Blake2_128Concat, ParaId, DepositInfo<T>>;
 
+    /// Code announced by the manager of a para id, that the container chain will upgrade to.
+    /// Collators can fetch it before the upgrade is enacted.
+    #[pallet::storage]
+    #[pallet::getter(fn para_pending_code)]
+    pub type ParaPendingCode<T: Config> = StorageMap<_, Blake2_128Concat, ParaId, Vec<u8>>;
+
+    /// Hash of the code in `ParaPendingCode`, compared with the current code of the container
+    /// chain to clear the announcement once the upgrade is enacted.
+    #[pallet::storage]
+    pub type ParaPendingCodeHash<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, T::Hash, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -223,6 +261,14 @@ pub mod pallet {
         ParaIdPaused { para_id: ParaId },
         /// A para id has been unpaused.
         ParaIdUnpaused { para_id: ParaId },
+        /// The genesis data of a para id has been replaced.
+        ParaIdGenesisDataUpdated { para_id: ParaId },
+        /// A new code has been announced for a para id.
+        ParaIdCodeAnnounced { para_id: ParaId, code_hash: T::Hash },
+        /// The announced code of a para id has been removed.
+        ParaIdCodeAnnouncementRemoved { para_id: ParaId },
+        /// A para id has upgraded to its announced code, which has been removed.
+        ParaIdCodeUpgraded { para_id: ParaId, code_hash: T::Hash },
     }
 
     #[pallet::error]
@@ -245,6 +291,12 @@ pub mod pallet {
         ParaIdNotInPendingVerification,
         /// Tried to register a ParaId with an account that did not have enough balance for the deposit
         NotSufficientDeposit,
+        /// Attempted to update the genesis data of a ParaId that has already produced blocks
+        ParaIdAlreadyProducedBlocks,
+        /// Attempted to announce a code bigger than the genesis data size limit
+        CodeTooBig,
+        /// Attempted to remove the announced code of a ParaId that has no announced code
+        NoAnnouncedCode,
     }
 
     #[pallet::hooks]
@@ -311,6 +363,49 @@ pub mod pallet {
                 );
             }
 
+            // All entries in `ParaGenesisData` have their size in `ParaGenesisDataSize`
+            for (para_id, genesis_data) in ParaGenesisData::<T>::iter() {
+                assert_eq!(
+                    ParaGenesisDataSize::<T>::get(para_id),
+                    Some(genesis_data.encoded_size() as u32),
+                    "ParaGenesisDataSize does not match ParaGenesisData for para id: {}",
+                    u32::from(para_id)
+                );
+            }
+            let entries: Vec<_> = ParaGenesisDataSize::<T>::iter().map(|(k, _v)| k).collect();
+            for para_id in entries {
+                assert!(
+                    ParaGenesisData::<T>::contains_key(para_id),
+                    "Found ParaGenesisDataSize for unknown para id: {}",
+                    u32::from(para_id)
+                );
+            }
+
+            // All entries in `ParaPendingCode` have an entry in `ParaGenesisData`
+            let entries: Vec<_> = ParaPendingCode::<T>::iter().map(|(k, _v)| k).collect();
+            for para_id in entries {
+                assert!(
+                    ParaGenesisData::<T>::contains_key(para_id),
+                    "Found ParaPendingCode for unknown para id: {}",
+                    u32::from(para_id)
+                );
+                assert_eq!(
+                    ParaPendingCodeHash::<T>::get(para_id),
+                    ParaPendingCode::<T>::get(para_id).map(|code| T::Hashing::hash(&code)),
+                    "ParaPendingCodeHash does not match ParaPendingCode for para id: {}",
+                    u32::from(para_id)
+                );
+            }
+            // All entries in `ParaPendingCodeHash` have an entry in `ParaPendingCode`
+            let entries: Vec<_> = ParaPendingCodeHash::<T>::iter().map(|(k, _v)| k).collect();
+            for para_id in entries {
+                assert!(
+                    ParaPendingCode::<T>::contains_key(para_id),
+                    "Found ParaPendingCodeHash without ParaPendingCode for para id: {}",
+                    u32::from(para_id)
+                );
+            }
+
             // Sorted storage items are sorted
             fn assert_is_sorted_and_unique<T: Ord>(x: &[T], name: &str) {
                 assert!(
@@ -364,7 +459,8 @@ pub mod pallet {
             genesis_data: ContainerChainGenesisData<T::MaxLengthTokenSymbol>,
         ) -> DispatchResult {
             let account = ensure_signed(origin)?;
-            let deposit = T::DepositAmount::get();
+            let genesis_data_size = genesis_data.encoded_size();
+            let deposit = Self::required_deposit(genesis_data_size);
 
             // Verify we can reserve
             T::Currency::can_reserve(&account, deposit)
@@ -397,7 +493,6 @@ pub mod pallet {
             // of key-values in `genesis_data.storage`, even if those key-values are empty. And we
             // won't detect that the size is too big until after iterating over all of them, so the
             // limit in that case would be the transaction size.
-            let genesis_data_size = genesis_data.encoded_size();
             if genesis_data_size > T::MaxGenesisDataSize::get() as usize {
                 return Err(Error::<T>::GenesisDataTooBig.into());
             }
@@ -414,6 +509,7 @@ pub mod pallet {
                 },
             );
             ParaGenesisData::<T>::insert(para_id, genesis_data);
+            ParaGenesisDataSize::<T>::insert(para_id, genesis_data_size as u32);
             PendingVerification::<T>::put(pending_verification);
 
             Self::deposit_event(Event::ParaIdRegistered { para_id });
@@ -581,6 +677,97 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Replace the genesis data of a container-chain.
+        /// Only container-chains that have not produced any blocks yet can be updated. The deposit of
+        /// the para id is adjusted to the size of the new genesis data.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::update_para_genesis_data(genesis_data.encoded_size() as u32, genesis_data.storage.len() as u32))]
+        pub fn update_para_genesis_data(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            genesis_data: ContainerChainGenesisData<T::MaxLengthTokenSymbol>,
+        ) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            if !ParaGenesisDataSize::<T>::contains_key(para_id) {
+                return Err(Error::<T>::ParaIdNotRegistered.into());
+            }
+
+            // Collators of a container chain that has already produced blocks would not be able to
+            // import them with a different genesis, use `announce_para_code` to upgrade it instead
+            if T::RegistrarHooks::container_chain_produced_blocks(para_id) {
+                return Err(Error::<T>::ParaIdAlreadyProducedBlocks.into());
+            }
+
+            let genesis_data_size = genesis_data.encoded_size();
+            if genesis_data_size > T::MaxGenesisDataSize::get() as usize {
+                return Err(Error::<T>::GenesisDataTooBig.into());
+            }
+
+            let code_size = ParaPendingCode::<T>::decode_len(para_id).unwrap_or_default();
+            Self::adjust_deposit(
+                para_id,
+                Self::required_deposit(genesis_data_size.saturating_add(code_size)),
+            )?;
+
+            ParaGenesisData::<T>::insert(para_id, genesis_data);
+            ParaGenesisDataSize::<T>::insert(para_id, genesis_data_size as u32);
+
+            Self::deposit_event(Event::ParaIdGenesisDataUpdated { para_id });
+
+            Ok(())
+        }
+
+        /// Announce the code that a container-chain will upgrade to, so that collators can fetch it
+        /// before the upgrade is enacted. Replaces any previously announced code, and `None` removes
+        /// the announcement. The deposit of the para id is adjusted to the size of the code.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::announce_para_code(code.as_ref().map_or(0, |code| code.len() as u32)))]
+        pub fn announce_para_code(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            code: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            let genesis_data_size = ParaGenesisDataSize::<T>::get(para_id)
+                .ok_or(Error::<T>::ParaIdNotRegistered)?
+                as usize;
+
+            match code {
+                Some(code) => {
+                    if code.len() > T::MaxGenesisDataSize::get() as usize {
+                        return Err(Error::<T>::CodeTooBig.into());
+                    }
+
+                    Self::adjust_deposit(
+                        para_id,
+                        Self::required_deposit(genesis_data_size.saturating_add(code.len())),
+                    )?;
+
+                    let code_hash = T::Hashing::hash(&code);
+                    ParaPendingCode::<T>::insert(para_id, code);
+                    ParaPendingCodeHash::<T>::insert(para_id, code_hash);
+
+                    Self::deposit_event(Event::ParaIdCodeAnnounced { para_id, code_hash });
+                }
+                None => {
+                    if !ParaPendingCode::<T>::contains_key(para_id) {
+                        return Err(Error::<T>::NoAnnouncedCode.into());
+                    }
+
+                    Self::adjust_deposit(para_id, Self::required_deposit(genesis_data_size))?;
+
+                    ParaPendingCode::<T>::remove(para_id);
+                    ParaPendingCodeHash::<T>::remove(para_id);
+
+                    Self::deposit_event(Event::ParaIdCodeAnnouncementRemoved { para_id });
+                }
+            }
+
+            Ok(())
+        }
     }
 
     pub struct SessionChangeOutcome<T: Config> {
@@ -601,6 +788,39 @@ pub mod pallet {
                 == Some(account)
         }
 
+        /// Deposit required to register a para id storing `data_size` bytes of genesis data and
+        /// announced code
+        pub fn required_deposit(data_size: usize) -> DepositBalanceOf<T> {
+            T::DepositAmount::get().saturating_add(
+                T::DataDepositPerByte::get().saturating_mul((data_size as u32).into()),
+            )
+        }
+
+        /// Reserve or unreserve the difference between the current deposit of `para_id` and
+        /// `new_deposit` from the deposit creator.
+        /// Para ids registered in genesis have no deposit and are left untouched.
+        fn adjust_deposit(para_id: ParaId, new_deposit: DepositBalanceOf<T>) -> DispatchResult {
+            RegistrarDeposit::<T>::try_mutate(para_id, |maybe_deposit_info| -> DispatchResult {
+                if let Some(deposit_info) = maybe_deposit_info {
+                    if new_deposit > deposit_info.deposit {
+                        T::Currency::reserve(
+                            &deposit_info.creator,
+                            new_deposit.saturating_sub(deposit_info.deposit),
+                        )
+                        .map_err(|_e| Error::<T>::NotSufficientDeposit)?;
+                    } else {
+                        T::Currency::unreserve(
+                            &deposit_info.creator,
+                            deposit_info.deposit.saturating_sub(new_deposit),
+                        );
+                    }
+                    deposit_info.deposit = new_deposit;
+                }
+
+                Ok(())
+            })
+        }
+
         #[cfg(feature = "runtime-benchmarks")]
         pub fn benchmarks_get_or_create_para_manager(para_id: &ParaId) -> Result<T::AccountId, ()> {
             use {
@@ -832,6 +1052,9 @@ pub mod pallet {
         /// and execute para_deregistered hook to clean up other pallets as well
         fn cleanup_deregistered_para_id(para_id: ParaId) {
             ParaGenesisData::<T>::remove(para_id);
+            ParaGenesisDataSize::<T>::remove(para_id);
+            ParaPendingCode::<T>::remove(para_id);
+            ParaPendingCodeHash::<T>::remove(para_id);
             // Get asset creator and deposit amount
             // Deposit may not exist, for example if the para id was registered on genesis
             if let Some(asset_info) = RegistrarDeposit::<T>::take(para_id) {
@@ -875,6 +1098,40 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> ContainerChainCodeHook for Pallet<T> {
+        /// Clear the code announced for a container chain, and release its deposit, once the
+        /// container chain has upgraded to it
+        fn on_container_code_noted(para_id: ParaId, code_hash: &ValidationCodeHash) -> Weight {
+            let announced_code_hash = match ParaPendingCodeHash::<T>::get(para_id) {
+                Some(announced_code_hash) if announced_code_hash.as_ref() == code_hash.as_ref() => {
+                    announced_code_hash
+                }
+                _ => return T::DbWeight::get().reads(1),
+            };
+
+            let genesis_data_size =
+                ParaGenesisDataSize::<T>::get(para_id).unwrap_or_default() as usize;
+            if let Err(e) = Self::adjust_deposit(para_id, Self::required_deposit(genesis_data_size))
+            {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Failed to release the deposit of the code announced for para id {}: {:?}",
+                    u32::from(para_id),
+                    e
+                );
+            }
+            ParaPendingCode::<T>::remove(para_id);
+            ParaPendingCodeHash::<T>::remove(para_id);
+
+            Self::deposit_event(Event::ParaIdCodeUpgraded {
+                para_id,
+                code_hash: announced_code_hash,
+            });
+
+            T::DbWeight::get().reads_writes(3, 4)
+        }
+    }
+
     impl<T: Config> GetCurrentContainerChains for Pallet<T> {
         type MaxContainerChains = T::MaxLengthParaIds;
 
@@ -928,6 +1185,9 @@ pub trait RegistrarHooks {
     fn check_valid_for_collating(_para_id: ParaId) -> DispatchResult {
         Ok(())
     }
+    fn container_chain_produced_blocks(_para_id: ParaId) -> bool {
+        false
+    }
 
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmarks_ensure_valid_for_collating(_para_id: ParaId) {}
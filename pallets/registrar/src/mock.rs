@@ -19,7 +19,7 @@ use tp_container_chain_genesis_data::ContainerChainGenesisData;
 use {
     crate::{self as pallet_registrar, RegistrarHooks},
     frame_support::{
-        traits::{ConstU16, ConstU64},
+        traits::{ConstU16, ConstU64, EitherOfDiverse},
         weights::Weight,
     },
     parity_scale_codec::{Decode, Encode},
@@ -105,10 +105,15 @@ impl tp_traits::GetSessionIndex<u32> for CurrentSessionIndexGetter {
 parameter_types! {
     pub const DepositAmount: Balance = 100;
     pub const MaxLengthTokenSymbol: u32 = 255;
+    pub static DataDepositPerByte: Balance = 0;
 }
 impl pallet_registrar::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type RegistrarOrigin = frame_system::EnsureRoot<u64>;
+    type ManagerOrigin = EitherOfDiverse<
+        pallet_registrar::EnsureSignedByManager<Test>,
+        frame_system::EnsureRoot<u64>,
+    >;
     type MaxLengthParaIds = ConstU32<1000>;
     type MaxGenesisDataSize = ConstU32<5_000_000>;
     type MaxBootNodes = ConstU32<10>;
@@ -119,6 +124,7 @@ impl pallet_registrar::Config for Test {
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type Currency = Balances;
     type DepositAmount = DepositAmount;
+    type DataDepositPerByte = DataDepositPerByte;
     type RegistrarHooks = Mock;
     type WeightInfo = ();
 }
@@ -191,6 +197,10 @@ impl<T> RegistrarHooks for mock_data::Pallet<T> {
         Ok(())
     }
 
+    fn container_chain_produced_blocks(para_id: ParaId) -> bool {
+        Mock::get().produced_blocks.contains(&para_id)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmarks_ensure_valid_for_collating(_para_id: ParaId) {}
 }
@@ -203,6 +213,7 @@ impl mock_data::Config for Test {}
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mocks {
     pub called_hooks: Vec<HookCall>,
+    pub produced_blocks: Vec<ParaId>,
 }
 
 impl Drop for Mocks {
@@ -15,17 +15,20 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{mock::*, Error, Event},
+    crate::{mock::*, Error, Event, ParaGenesisDataSize},
     frame_support::{assert_noop, assert_ok, dispatch::GetDispatchInfo, BoundedVec},
     parity_scale_codec::Encode,
     sp_core::Get,
-    sp_runtime::DispatchError,
+    sp_runtime::{
+        traits::{BlakeTwo256, Hash},
+        DispatchError,
+    },
     tp_container_chain_genesis_data::ContainerChainGenesisData,
-    tp_traits::ParaId,
+    tp_traits::{ContainerChainCodeHook, ParaId},
 };
 
 const ALICE: u64 = 1;
-//const BOB: u64 = 2;
+const BOB: u64 = 2;
 
 #[test]
 fn register_para_id_42() {
@@ -312,6 +315,7 @@ fn deregister_para_id_removes_genesis_data() {
         );
         run_to_session(4);
         assert_eq!(ParaRegistrar::para_genesis_data(ParaId::from(42)), None);
+        assert_eq!(ParaGenesisDataSize::<Test>::get(ParaId::from(42)), None);
     });
 }
 
@@ -1174,6 +1178,391 @@ fn deposit_removed_after_2_sessions_if_marked_as_valid() {
     });
 }
 
+fn genesis_data_with_code(code_len: usize) -> ContainerChainGenesisData<MaxLengthTokenSymbol> {
+    ContainerChainGenesisData {
+        storage: vec![(b":code".to_vec(), vec![1; code_len]).into()],
+        ..empty_genesis_data()
+    }
+}
+
+#[test]
+fn manager_can_update_genesis_data() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+
+        let genesis_data = genesis_data_with_code(100);
+        assert_ok!(ParaRegistrar::update_para_genesis_data(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            genesis_data.clone()
+        ));
+        System::assert_last_event(Event::ParaIdGenesisDataUpdated { para_id: 42.into() }.into());
+        assert_eq!(
+            ParaRegistrar::para_genesis_data(ParaId::from(42)),
+            Some(genesis_data)
+        );
+    });
+}
+
+#[test]
+fn update_genesis_data_bad_origin() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+
+        assert_noop!(
+            ParaRegistrar::update_para_genesis_data(
+                RuntimeOrigin::signed(BOB),
+                42.into(),
+                genesis_data_with_code(100)
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn update_genesis_data_para_id_not_registered() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_noop!(
+            ParaRegistrar::update_para_genesis_data(
+                RuntimeOrigin::root(),
+                42.into(),
+                genesis_data_with_code(100)
+            ),
+            Error::<Test>::ParaIdNotRegistered
+        );
+    });
+}
+
+#[test]
+fn cannot_update_genesis_data_after_producing_blocks() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+        Mock::mutate(|m| m.produced_blocks.push(42.into()));
+
+        assert_noop!(
+            ParaRegistrar::update_para_genesis_data(
+                RuntimeOrigin::signed(ALICE),
+                42.into(),
+                genesis_data_with_code(100)
+            ),
+            Error::<Test>::ParaIdAlreadyProducedBlocks
+        );
+    });
+}
+
+#[test]
+fn root_can_update_genesis_data_of_para_id_registered_in_genesis() {
+    new_test_ext_with_genesis(vec![(42.into(), empty_genesis_data())]).execute_with(|| {
+        run_to_block(1);
+        let genesis_data = genesis_data_with_code(100);
+        assert_ok!(ParaRegistrar::update_para_genesis_data(
+            RuntimeOrigin::root(),
+            42.into(),
+            genesis_data.clone()
+        ));
+        assert_eq!(
+            ParaRegistrar::para_genesis_data(ParaId::from(42)),
+            Some(genesis_data.clone())
+        );
+        assert_eq!(
+            ParaGenesisDataSize::<Test>::get(ParaId::from(42)),
+            Some(genesis_data.encoded_size() as u32)
+        );
+        assert_eq!(ParaRegistrar::registrar_deposit(ParaId::from(42)), None);
+    });
+}
+
+#[test]
+fn update_genesis_data_adjusts_deposit() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        DataDepositPerByte::set(1);
+        let small_genesis_data = empty_genesis_data();
+        let big_genesis_data = genesis_data_with_code(100);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            small_genesis_data.clone()
+        ));
+        assert_eq!(
+            Balances::reserved_balance(ALICE),
+            DepositAmount::get() + small_genesis_data.encoded_size() as u128
+        );
+
+        assert_ok!(ParaRegistrar::update_para_genesis_data(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            big_genesis_data.clone()
+        ));
+        assert_eq!(
+            Balances::reserved_balance(ALICE),
+            DepositAmount::get() + big_genesis_data.encoded_size() as u128
+        );
+        assert_eq!(
+            ParaGenesisDataSize::<Test>::get(ParaId::from(42)),
+            Some(big_genesis_data.encoded_size() as u32)
+        );
+
+        assert_ok!(ParaRegistrar::update_para_genesis_data(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            small_genesis_data.clone()
+        ));
+        assert_eq!(
+            Balances::reserved_balance(ALICE),
+            DepositAmount::get() + small_genesis_data.encoded_size() as u128
+        );
+        assert_eq!(
+            ParaRegistrar::registrar_deposit(ParaId::from(42)).map(|info| info.deposit),
+            Some(DepositAmount::get() + small_genesis_data.encoded_size() as u128)
+        );
+    });
+}
+
+#[test]
+fn update_genesis_data_not_sufficient_deposit() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        DataDepositPerByte::set(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+
+        // ALICE only has 1_000 balance
+        assert_noop!(
+            ParaRegistrar::update_para_genesis_data(
+                RuntimeOrigin::signed(ALICE),
+                42.into(),
+                genesis_data_with_code(1_000)
+            ),
+            Error::<Test>::NotSufficientDeposit
+        );
+    });
+}
+
+#[test]
+fn manager_can_announce_and_remove_code() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        DataDepositPerByte::set(1);
+        let genesis_data = empty_genesis_data();
+        let deposit = DepositAmount::get() + genesis_data.encoded_size() as u128;
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            genesis_data
+        ));
+        assert_ok!(ParaRegistrar::mark_valid_for_collating(
+            RuntimeOrigin::root(),
+            42.into(),
+        ));
+        Mock::mutate(|m| m.produced_blocks.push(42.into()));
+
+        let code = vec![1; 100];
+        assert_ok!(ParaRegistrar::announce_para_code(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(code.clone())
+        ));
+        System::assert_last_event(
+            Event::ParaIdCodeAnnounced {
+                para_id: 42.into(),
+                code_hash: BlakeTwo256::hash(&code),
+            }
+            .into(),
+        );
+        assert_eq!(
+            ParaRegistrar::para_pending_code(ParaId::from(42)),
+            Some(code)
+        );
+        assert_eq!(Balances::reserved_balance(ALICE), deposit + 100);
+
+        assert_ok!(ParaRegistrar::announce_para_code(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            None
+        ));
+        System::assert_last_event(
+            Event::ParaIdCodeAnnouncementRemoved { para_id: 42.into() }.into(),
+        );
+        assert_eq!(ParaRegistrar::para_pending_code(ParaId::from(42)), None);
+        assert_eq!(Balances::reserved_balance(ALICE), deposit);
+    });
+}
+
+#[test]
+fn announce_code_bad_origin() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+
+        assert_noop!(
+            ParaRegistrar::announce_para_code(RuntimeOrigin::signed(BOB), 42.into(), Some(vec![1])),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn remove_announced_code_fails_if_no_code_announced() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+
+        assert_noop!(
+            ParaRegistrar::announce_para_code(RuntimeOrigin::signed(ALICE), 42.into(), None),
+            Error::<Test>::NoAnnouncedCode
+        );
+    });
+}
+
+#[test]
+fn update_genesis_data_keeps_deposit_of_announced_code() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        DataDepositPerByte::set(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+        assert_ok!(ParaRegistrar::announce_para_code(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(vec![1; 100])
+        ));
+
+        let genesis_data = genesis_data_with_code(50);
+        assert_ok!(ParaRegistrar::update_para_genesis_data(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            genesis_data.clone()
+        ));
+        assert_eq!(
+            Balances::reserved_balance(ALICE),
+            DepositAmount::get() + genesis_data.encoded_size() as u128 + 100
+        );
+    });
+}
+
+#[test]
+fn announced_code_removed_on_deregister() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        DataDepositPerByte::set(1);
+        let balance_before = Balances::free_balance(ALICE);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+        assert_ok!(ParaRegistrar::announce_para_code(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(vec![1; 100])
+        ));
+
+        assert_ok!(ParaRegistrar::deregister(RuntimeOrigin::root(), 42.into()));
+
+        assert_eq!(ParaRegistrar::para_pending_code(ParaId::from(42)), None);
+        assert_eq!(Balances::free_balance(ALICE), balance_before);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+    });
+}
+
+#[test]
+fn announced_code_removed_once_container_chain_upgrades_to_it() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        DataDepositPerByte::set(1);
+        let genesis_data = empty_genesis_data();
+        let deposit = DepositAmount::get() + genesis_data.encoded_size() as u128;
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            genesis_data
+        ));
+        let code = vec![1; 100];
+        assert_ok!(ParaRegistrar::announce_para_code(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            Some(code.clone())
+        ));
+        assert_eq!(Balances::reserved_balance(ALICE), deposit + 100);
+
+        // The container chain is still running its previous code
+        ParaRegistrar::on_container_code_noted(42.into(), &BlakeTwo256::hash(&[2; 100]).into());
+        assert_eq!(
+            ParaRegistrar::para_pending_code(ParaId::from(42)),
+            Some(code.clone())
+        );
+        assert_eq!(Balances::reserved_balance(ALICE), deposit + 100);
+
+        ParaRegistrar::on_container_code_noted(42.into(), &BlakeTwo256::hash(&code).into());
+        System::assert_last_event(
+            Event::ParaIdCodeUpgraded {
+                para_id: 42.into(),
+                code_hash: BlakeTwo256::hash(&code),
+            }
+            .into(),
+        );
+        assert_eq!(ParaRegistrar::para_pending_code(ParaId::from(42)), None);
+        assert_eq!(Balances::reserved_balance(ALICE), deposit);
+
+        // Removing the announcement fails, it has already been removed
+        assert_noop!(
+            ParaRegistrar::announce_para_code(RuntimeOrigin::signed(ALICE), 42.into(), None),
+            Error::<Test>::NoAnnouncedCode
+        );
+    });
+}
+
+#[test]
+fn code_noted_without_announced_code_does_nothing() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ParaRegistrar::register(
+            RuntimeOrigin::signed(ALICE),
+            42.into(),
+            empty_genesis_data()
+        ));
+        let reserved_balance = Balances::reserved_balance(ALICE);
+        let events_before = System::events().len();
+
+        ParaRegistrar::on_container_code_noted(42.into(), &BlakeTwo256::hash(&[1; 100]).into());
+
+        assert_eq!(System::events().len(), events_before);
+        assert_eq!(Balances::reserved_balance(ALICE), reserved_balance);
+    });
+}
+
 #[test]
 fn weights_assigned_to_extrinsics_are_correct() {
     new_test_ext().execute_with(|| {
@@ -1231,5 +1620,28 @@ fn weights_assigned_to_extrinsics_are_correct() {
                 <Test as crate::Config>::MaxLengthParaIds::get()
             )
         );
+
+        assert_eq!(
+            crate::Call::<Test>::update_para_genesis_data {
+                para_id: 42.into(),
+                genesis_data: empty_genesis_data()
+            }
+            .get_dispatch_info()
+            .weight,
+            <() as crate::weights::WeightInfo>::update_para_genesis_data(
+                empty_genesis_data().encoded_size() as u32,
+                0
+            )
+        );
+
+        assert_eq!(
+            crate::Call::<Test>::announce_para_code {
+                para_id: 42.into(),
+                code: Some(vec![1; 100])
+            }
+            .get_dispatch_info()
+            .weight,
+            <() as crate::weights::WeightInfo>::announce_para_code(100)
+        );
     });
 }
@@ -58,6 +58,8 @@ pub trait WeightInfo {
 	fn mark_valid_for_collating(y: u32, ) -> Weight;
 	fn pause_container_chain(y: u32, ) -> Weight;
 	fn unpause_container_chain(y: u32, ) -> Weight;
+	fn update_para_genesis_data(x: u32, z: u32, ) -> Weight;
+	fn announce_para_code(x: u32, ) -> Weight;
 }
 
 /// Weights for pallet_registrar using the Substrate node and recommended hardware.
@@ -71,6 +73,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Registrar::PendingVerification` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Registrar::RegistrarDeposit` (r:0 w:1)
 	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::ParaGenesisDataSize` (r:0 w:1)
+	/// Proof: `Registrar::ParaGenesisDataSize` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
 	/// The range of component `x` is `[5, 3000000]`.
 	/// The range of component `y` is `[1, 50]`.
 	/// The range of component `z` is `[1, 10]`.
@@ -85,7 +89,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 2_644_145
 			.saturating_add(Weight::from_parts(77_565_165, 0).saturating_mul(z.into()))
 			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 			.saturating_add(Weight::from_parts(0, 12).saturating_mul(y.into()))
 			.saturating_add(Weight::from_parts(0, 2).saturating_mul(z.into()))
 	}
@@ -214,6 +218,47 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 			.saturating_add(Weight::from_parts(0, 8).saturating_mul(y.into()))
 	}
+	/// Storage: `Registrar::ParaGenesisDataSize` (r:1 w:1)
+	/// Proof: `Registrar::ParaGenesisDataSize` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
+	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaPendingCode` (r:1 w:0)
+	/// Proof: `Registrar::ParaPendingCode` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:1)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaGenesisData` (r:0 w:1)
+	/// Proof: `Registrar::ParaGenesisData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[5, 3000000]`.
+	/// The range of component `z` is `[1, 10]`.
+	fn update_para_genesis_data(x: u32, z: u32, ) -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(42_115_000, 4003)
+			.saturating_add(Weight::from_parts(618, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(78_104_393, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 2).saturating_mul(z.into()))
+	}
+	/// Storage: `Registrar::ParaGenesisDataSize` (r:1 w:0)
+	/// Proof: `Registrar::ParaGenesisDataSize` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:1)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaPendingCode` (r:0 w:1)
+	/// Proof: `Registrar::ParaPendingCode` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::ParaPendingCodeHash` (r:0 w:1)
+	/// Proof: `Registrar::ParaPendingCodeHash` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `x` is `[1, 3000000]`.
+	fn announce_para_code(x: u32, ) -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(40_000_000, 3593)
+			.saturating_add(Weight::from_parts(1_457, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -226,6 +271,8 @@ impl WeightInfo for () {
 	/// Proof: `Registrar::PendingVerification` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Registrar::RegistrarDeposit` (r:0 w:1)
 	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::ParaGenesisDataSize` (r:0 w:1)
+	/// Proof: `Registrar::ParaGenesisDataSize` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
 	/// The range of component `x` is `[5, 3000000]`.
 	/// The range of component `y` is `[1, 50]`.
 	/// The range of component `z` is `[1, 10]`.
@@ -240,7 +287,7 @@ impl WeightInfo for () {
 			// Standard Error: 2_644_145
 			.saturating_add(Weight::from_parts(77_565_165, 0).saturating_mul(z.into()))
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 			.saturating_add(Weight::from_parts(0, 12).saturating_mul(y.into()))
 			.saturating_add(Weight::from_parts(0, 2).saturating_mul(z.into()))
 	}
@@ -369,4 +416,45 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 			.saturating_add(Weight::from_parts(0, 8).saturating_mul(y.into()))
 	}
+	/// Storage: `Registrar::ParaGenesisDataSize` (r:1 w:1)
+	/// Proof: `Registrar::ParaGenesisDataSize` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
+	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaPendingCode` (r:1 w:0)
+	/// Proof: `Registrar::ParaPendingCode` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:1)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaGenesisData` (r:0 w:1)
+	/// Proof: `Registrar::ParaGenesisData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[5, 3000000]`.
+	/// The range of component `z` is `[1, 10]`.
+	fn update_para_genesis_data(x: u32, z: u32, ) -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(42_115_000, 4003)
+			.saturating_add(Weight::from_parts(618, 0).saturating_mul(x.into()))
+			.saturating_add(Weight::from_parts(78_104_393, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 2).saturating_mul(z.into()))
+	}
+	/// Storage: `Registrar::ParaGenesisDataSize` (r:1 w:0)
+	/// Proof: `Registrar::ParaGenesisDataSize` (`max_values`: None, `max_size`: Some(24), added: 2499, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:1)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Registrar::ParaPendingCode` (r:0 w:1)
+	/// Proof: `Registrar::ParaPendingCode` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Registrar::ParaPendingCodeHash` (r:0 w:1)
+	/// Proof: `Registrar::ParaPendingCodeHash` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+	/// The range of component `x` is `[1, 3000000]`.
+	fn announce_para_code(x: u32, ) -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(40_000_000, 3593)
+			.saturating_add(Weight::from_parts(1_457, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
@@ -22,9 +22,10 @@ use {
     frame_benchmarking::{account, v2::*},
     frame_support::{
         assert_ok,
-        traits::{Currency, Get},
+        traits::{Currency, EnsureOriginWithArg, Get},
     },
     frame_system::RawOrigin,
+    sp_runtime::traits::Zero,
     sp_std::prelude::*,
 };
 
@@ -125,5 +126,44 @@ mod benchmarks {
         assert!(crate::GivenFreeCredits::<T>::get(&para_id).is_some());
     }
 
+    #[benchmark]
+    fn set_collator_assignment_tip() {
+        let para_id = 1001u32.into();
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let tip = <T::Currency>::minimum_balance();
+
+        #[extrinsic_call]
+        Pallet::<T>::set_collator_assignment_tip(origin as T::RuntimeOrigin, para_id, Some(tip));
+
+        assert_eq!(crate::CollatorAssignmentTip::<T>::get(&para_id), Some(tip));
+    }
+
+    #[benchmark]
+    fn withdraw_from_tank() {
+        let para_id = 1001u32.into();
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let tank = Pallet::<T>::parachain_tank(para_id);
+        let balance = <T::Currency>::minimum_balance() * 1000u32.into();
+        let _ = <T::Currency>::make_free_balance_be(&tank, balance);
+        let dest = create_funded_user::<T>("dest", 1, 1);
+
+        // The whole balance is withdrawn, so that the tank account is removed
+        #[extrinsic_call]
+        Pallet::<T>::withdraw_from_tank(
+            origin as T::RuntimeOrigin,
+            para_id,
+            dest.clone(),
+            balance,
+        );
+
+        assert!(<T::Currency>::free_balance(&tank).is_zero());
+        assert_eq!(
+            <T::Currency>::free_balance(&dest),
+            <T::Currency>::minimum_balance() + balance
+        );
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::benchmarks::new_test_ext(), crate::mock::Test);
 }
@@ -32,6 +32,15 @@
 //!
 //! This pallet allows for block creation services to be paid for by a
 //! containerChain.
+//!
+//! The manager of a containerChain can also bid a tip to be assigned collators first when there
+//! are not enough collators for all the containerChains. The tip is charged every session the
+//! containerChain is assigned collators, from the tank account of the containerChain, which
+//! anyone can fund with a regular transfer and the manager can withdraw from.
+//!
+//! The collators a containerChain requests above `collators_per_container` are charged every
+//! session from the same tank account. A containerChain whose tank cannot pay for them is
+//! assigned `collators_per_container` collators.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -40,12 +49,15 @@ use {
     cumulus_primitives_core::ParaId,
     frame_support::{
         pallet_prelude::*,
-        sp_runtime::{traits::Zero, Saturating},
-        traits::{tokens::ExistenceRequirement, Currency, WithdrawReasons},
+        sp_runtime::{
+            traits::{Hash, TrailingZeroInput, Zero},
+            Saturating,
+        },
+        traits::{tokens::ExistenceRequirement, Currency, EnsureOriginWithArg, WithdrawReasons},
     },
     frame_system::pallet_prelude::*,
     scale_info::prelude::vec::Vec,
-    tp_traits::{AuthorNotingHook, BlockNumber},
+    tp_traits::{AuthorNotingHook, BlockNumber, CollatorAssignmentPriority},
 };
 
 #[cfg(any(test, feature = "runtime-benchmarks"))]
@@ -75,6 +87,15 @@ pub mod pallet {
         type ProvideBlockProductionCost: ProvideBlockProductionCost<Self>;
         /// The maximum number of credits that can be accumulated
         type MaxCreditsStored: Get<BlockNumberFor<Self>>;
+        /// Origin allowed to set the collator assignment tip of a container chain
+        type ManagerOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, ParaId>;
+        /// Fee paid every session for each collator assigned to a container chain above
+        /// `collators_per_container`
+        type ExtraCollatorFee: Get<BalanceOf<Self>>;
+        /// The credits of the containerChains are compared in buckets of this size when assigning
+        /// collators, so that containerChains burning their credits keep their collators against
+        /// containerChains with slightly more credits
+        type CreditsPriorityBucket: Get<BlockNumberFor<Self>>;
 
         type WeightInfo: WeightInfo;
     }
@@ -107,6 +128,24 @@ pub mod pallet {
             para_id: ParaId,
             credits: BlockNumberFor<T>,
         },
+        CollatorAssignmentTipSet {
+            para_id: ParaId,
+            tip: Option<BalanceOf<T>>,
+        },
+        CollatorAssignmentTipCharged {
+            para_id: ParaId,
+            tip: BalanceOf<T>,
+        },
+        ExtraCollatorsCharged {
+            para_id: ParaId,
+            extra_collators: u32,
+            fee: BalanceOf<T>,
+        },
+        TankFundsWithdrawn {
+            para_id: ParaId,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+        },
     }
 
     #[pallet::storage]
@@ -119,6 +158,13 @@ pub mod pallet {
     #[pallet::getter(fn given_free_credits)]
     pub type GivenFreeCredits<T: Config> = StorageMap<_, Blake2_128Concat, ParaId, (), OptionQuery>;
 
+    /// Tip paid by a para id every session it is assigned collators, to get collators before the
+    /// para ids with a lower tip
+    #[pallet::storage]
+    #[pallet::getter(fn collator_assignment_tip)]
+    pub type CollatorAssignmentTip<T: Config> =
+        StorageMap<_, Blake2_128Concat, ParaId, BalanceOf<T>, OptionQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T>
     where
@@ -213,9 +259,93 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// Set the tip this para_id pays every session it is assigned collators, or remove it.
+        /// The tip is paid from the tank account of the para_id, a para_id whose tank cannot pay
+        /// the tip is assigned collators as if it had no tip.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_collator_assignment_tip())]
+        pub fn set_collator_assignment_tip(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            tip: Option<BalanceOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            match tip {
+                Some(tip) if !tip.is_zero() => CollatorAssignmentTip::<T>::insert(para_id, tip),
+                _ => CollatorAssignmentTip::<T>::remove(para_id),
+            }
+
+            Self::deposit_event(Event::<T>::CollatorAssignmentTipSet { para_id, tip });
+
+            Ok(().into())
+        }
+
+        /// Transfer `amount` from the tank account of this para_id to `dest`.
+        /// Can only be called by the manager of the para_id.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::withdraw_from_tank())]
+        pub fn withdraw_from_tank(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            dest: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            T::Currency::transfer(
+                &Self::parachain_tank(para_id),
+                &dest,
+                amount,
+                ExistenceRequirement::AllowDeath,
+            )?;
+
+            Self::deposit_event(Event::<T>::TankFundsWithdrawn {
+                para_id,
+                dest,
+                amount,
+            });
+
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Account paying the collator assignment tip of a para_id
+        pub fn parachain_tank(para_id: ParaId) -> T::AccountId {
+            let entropy = T::Hashing::hash_of(&(PARACHAIN_TANK_SEED, para_id));
+            T::AccountId::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+                .expect("infinite length input; no invalid inputs for type; qed")
+        }
+
+        /// Tip of the para_id, if its tank can pay it
+        fn payable_tip(para_id: &ParaId) -> Option<BalanceOf<T>> {
+            CollatorAssignmentTip::<T>::get(para_id)
+                .filter(|tip| T::Currency::free_balance(&Self::parachain_tank(*para_id)) >= *tip)
+        }
+
+        /// Fee paid for `num_extra_collators` collators above `collators_per_container` during
+        /// one session
+        pub fn extra_collators_fee(num_extra_collators: u32) -> BalanceOf<T> {
+            T::ExtraCollatorFee::get().saturating_mul(num_extra_collators.into())
+        }
+
+        /// Burn `amount` from the tank account of the para_id
+        fn burn_from_tank(
+            para_id: &ParaId,
+            amount: BalanceOf<T>,
+            reasons: WithdrawReasons,
+        ) -> DispatchResult {
+            T::Currency::withdraw(
+                &Self::parachain_tank(*para_id),
+                amount,
+                reasons,
+                ExistenceRequirement::AllowDeath,
+            )
+            .map(|_| ())
+        }
+
         /// Burn a credit for the given para. Deducts one credit if possible, errors otherwise.
         pub fn burn_credit_for_para(para_id: &ParaId) -> DispatchResultWithPostInfo {
             let existing_credits =
@@ -286,6 +416,9 @@ pub mod pallet {
     }
 }
 
+/// Seed of the tank accounts of the para ids
+const PARACHAIN_TANK_SEED: &[u8] = b"modlpy/serpayment";
+
 /// Balance used by this pallet
 pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -355,3 +488,74 @@ impl<T: Config> AuthorNotingHook<T::AccountId> for Pallet<T> {
         total_weight
     }
 }
+
+/// Container chains are assigned collators first by tip, then by remaining credits, counted in
+/// buckets of `CreditsPriorityBucket` credits
+impl<T: Config> CollatorAssignmentPriority for Pallet<T> {
+    type Priority = (BalanceOf<T>, BlockNumberFor<T>);
+
+    fn para_id_priority(para_id: &ParaId) -> Self::Priority {
+        let tip = Self::payable_tip(para_id).unwrap_or_else(Zero::zero);
+        let credits = BlockProductionCredits::<T>::get(para_id).unwrap_or_else(Zero::zero);
+        let bucket = T::CreditsPriorityBucket::get();
+        let credit_buckets = if bucket.is_zero() {
+            credits
+        } else {
+            credits / bucket
+        };
+
+        (tip, credit_buckets)
+    }
+
+    fn can_pay_extra_collators(para_id: &ParaId, num_extra_collators: u32) -> bool {
+        // The tip is charged first, the extra collators are paid with what is left
+        let tip = Self::payable_tip(para_id).unwrap_or_else(Zero::zero);
+        let fee = Self::extra_collators_fee(num_extra_collators);
+
+        T::Currency::free_balance(&Self::parachain_tank(*para_id)) >= tip.saturating_add(fee)
+    }
+
+    fn on_collators_assigned(
+        para_id: &ParaId,
+        (tip, _credits): &Self::Priority,
+        num_extra_collators: u32,
+    ) {
+        // The tip and the extra collators are burned, like the fees paid for the credits
+        if !tip.is_zero() {
+            match Self::burn_from_tank(para_id, *tip, WithdrawReasons::TIP) {
+                Ok(()) => Self::deposit_event(Event::<T>::CollatorAssignmentTipCharged {
+                    para_id: *para_id,
+                    tip: *tip,
+                }),
+                Err(e) => log::warn!(
+                    "Failed to charge the collator assignment tip of container chain {}: {:?}",
+                    u32::from(*para_id),
+                    e
+                ),
+            }
+        }
+
+        if num_extra_collators > 0 {
+            let fee = Self::extra_collators_fee(num_extra_collators);
+            match Self::burn_from_tank(para_id, fee, WithdrawReasons::FEE) {
+                Ok(()) => Self::deposit_event(Event::<T>::ExtraCollatorsCharged {
+                    para_id: *para_id,
+                    extra_collators: num_extra_collators,
+                    fee,
+                }),
+                Err(e) => log::warn!(
+                    "Failed to charge the extra collators of container chain {}: {:?}",
+                    u32::from(*para_id),
+                    e
+                ),
+            }
+        }
+    }
+
+    fn weight_per_para_id() -> Weight {
+        // CollatorAssignmentTip, BlockProductionCredits and the tank account are read for the
+        // priority and for the extra collators, the tip and the extra collators are withdrawn
+        // from the tank account, updating the total issuance
+        T::DbWeight::get().reads_writes(10, 4)
+    }
+}
@@ -34,7 +34,7 @@ use {
     frame_support::{
         pallet_prelude::*,
         parameter_types,
-        traits::{ConstU32, ConstU64, Everything},
+        traits::{ConstU32, ConstU64, EnsureOriginWithArg, Everything},
     },
     sp_core::H256,
     sp_runtime::{
@@ -105,6 +105,8 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
     pub const MaxCreditsStored: u64 = 5;
+    pub const ExtraCollatorFee: Balance = 20;
+    pub const CreditsPriorityBucket: u64 = 2;
 }
 
 impl pallet_services_payment::Config for Test {
@@ -113,9 +115,31 @@ impl pallet_services_payment::Config for Test {
     type Currency = Balances;
     type ProvideBlockProductionCost = BlockProductionCost<Test>;
     type MaxCreditsStored = MaxCreditsStored;
+    type ManagerOrigin = EnsureSignedByManager;
+    type ExtraCollatorFee = ExtraCollatorFee;
+    type CreditsPriorityBucket = CreditsPriorityBucket;
     type WeightInfo = ();
 }
 
+/// The manager of a para id is the account with the same id
+pub struct EnsureSignedByManager;
+impl EnsureOriginWithArg<RuntimeOrigin, ParaId> for EnsureSignedByManager {
+    type Success = ();
+
+    fn try_origin(o: RuntimeOrigin, para_id: &ParaId) -> Result<Self::Success, RuntimeOrigin> {
+        let result: Result<frame_system::RawOrigin<AccountId>, RuntimeOrigin> = o.clone().into();
+        match result {
+            Ok(frame_system::RawOrigin::Signed(who)) if who == u32::from(*para_id) as u64 => Ok(()),
+            _ => Err(o),
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin(para_id: &ParaId) -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::signed(u32::from(*para_id) as u64))
+    }
+}
+
 pub(crate) const FIXED_BLOCK_PRODUCTION_COST: u128 = 100;
 
 pub struct BlockProductionCost<Test>(PhantomData<Test>);
@@ -29,10 +29,13 @@
 //! to that containerChain, by simply assigning the slot position.
 
 use {
-    crate::{mock::*, pallet as pallet_services_payment, BlockProductionCredits},
+    crate::{
+        mock::*, pallet as pallet_services_payment, BlockProductionCredits, CollatorAssignmentTip,
+    },
     cumulus_primitives_core::ParaId,
-    frame_support::{assert_err, assert_ok},
+    frame_support::{assert_err, assert_ok, traits::Currency},
     sp_runtime::DispatchError,
+    tp_traits::CollatorAssignmentPriority,
 };
 
 const ALICE: u64 = 1;
@@ -358,3 +361,240 @@ fn set_credits_to_zero_kills_storage() {
             assert_eq!(<BlockProductionCredits<Test>>::get(ParaId::from(1)), None,);
         });
 }
+
+#[test]
+fn set_collator_assignment_tip_works() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id = ParaId::from(1001);
+
+            // Only the manager of the para id can set its tip
+            assert_err!(
+                PaymentServices::set_collator_assignment_tip(
+                    RuntimeOrigin::signed(ALICE),
+                    para_id,
+                    Some(10),
+                ),
+                DispatchError::BadOrigin
+            );
+
+            assert_ok!(PaymentServices::set_collator_assignment_tip(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                Some(10),
+            ));
+            assert_eq!(<CollatorAssignmentTip<Test>>::get(para_id), Some(10));
+
+            assert_ok!(PaymentServices::set_collator_assignment_tip(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                None,
+            ));
+            assert_eq!(<CollatorAssignmentTip<Test>>::get(para_id), None);
+
+            assert_eq!(
+                events(),
+                vec![
+                    pallet_services_payment::Event::CollatorAssignmentTipSet {
+                        para_id,
+                        tip: Some(10),
+                    },
+                    pallet_services_payment::Event::CollatorAssignmentTipSet { para_id, tip: None },
+                ]
+            );
+        });
+}
+
+#[test]
+fn priority_is_tip_then_credits() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            let (para_a, para_b) = (ParaId::from(1001), ParaId::from(1002));
+            assert_ok!(PaymentServices::set_credits(
+                RuntimeOrigin::root(),
+                para_a,
+                2
+            ));
+            assert_ok!(PaymentServices::set_credits(
+                RuntimeOrigin::root(),
+                para_b,
+                4
+            ));
+            assert_ok!(PaymentServices::set_collator_assignment_tip(
+                RuntimeOrigin::signed(1001),
+                para_a,
+                Some(10),
+            ));
+
+            // The tank of para_a cannot pay the tip yet, the credits are counted in buckets of 2
+            assert_eq!(PaymentServices::para_id_priority(&para_a), (0, 1));
+            assert_eq!(PaymentServices::para_id_priority(&para_b), (0, 2));
+
+            Balances::make_free_balance_be(&PaymentServices::parachain_tank(para_a), 100);
+            assert_eq!(PaymentServices::para_id_priority(&para_a), (10, 1));
+            assert!(
+                PaymentServices::para_id_priority(&para_a)
+                    > PaymentServices::para_id_priority(&para_b)
+            );
+        });
+}
+
+#[test]
+fn credits_in_the_same_bucket_have_the_same_priority() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            let (para_a, para_b) = (ParaId::from(1001), ParaId::from(1002));
+            assert_ok!(PaymentServices::set_credits(
+                RuntimeOrigin::root(),
+                para_a,
+                4
+            ));
+            assert_ok!(PaymentServices::set_credits(
+                RuntimeOrigin::root(),
+                para_b,
+                5
+            ));
+
+            // para_b does not take the collators of para_a for a single credit
+            assert_eq!(
+                PaymentServices::para_id_priority(&para_a),
+                PaymentServices::para_id_priority(&para_b)
+            );
+
+            // Burning a credit moves para_a to a lower bucket
+            assert_ok!(PaymentServices::burn_credit_for_para(&para_a));
+            assert!(
+                PaymentServices::para_id_priority(&para_a)
+                    < PaymentServices::para_id_priority(&para_b)
+            );
+        });
+}
+
+#[test]
+fn manager_can_withdraw_from_tank() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id = ParaId::from(1001);
+            let tank = PaymentServices::parachain_tank(para_id);
+            Balances::make_free_balance_be(&tank, 100);
+
+            // Only the manager of the para id can withdraw from its tank
+            assert_err!(
+                PaymentServices::withdraw_from_tank(
+                    RuntimeOrigin::signed(ALICE),
+                    para_id,
+                    ALICE,
+                    40,
+                ),
+                DispatchError::BadOrigin
+            );
+
+            assert_ok!(PaymentServices::withdraw_from_tank(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                ALICE,
+                40,
+            ));
+            assert_eq!(Balances::free_balance(tank), 60);
+            assert_eq!(Balances::free_balance(ALICE), 1_040);
+            assert_eq!(
+                events().last(),
+                Some(&pallet_services_payment::Event::TankFundsWithdrawn {
+                    para_id,
+                    dest: ALICE,
+                    amount: 40,
+                })
+            );
+
+            // The tank cannot pay more than its balance
+            assert!(PaymentServices::withdraw_from_tank(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                ALICE,
+                100,
+            )
+            .is_err());
+        });
+}
+
+#[test]
+fn tip_is_charged_when_collators_are_assigned() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id = ParaId::from(1001);
+            let tank = PaymentServices::parachain_tank(para_id);
+            assert_ok!(PaymentServices::set_collator_assignment_tip(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                Some(10),
+            ));
+            Balances::make_free_balance_be(&tank, 100);
+
+            let priority = PaymentServices::para_id_priority(&para_id);
+            PaymentServices::on_collators_assigned(&para_id, &priority, 0);
+
+            assert_eq!(Balances::free_balance(tank), 90);
+            assert_eq!(
+                events().last(),
+                Some(
+                    &pallet_services_payment::Event::CollatorAssignmentTipCharged {
+                        para_id,
+                        tip: 10,
+                    }
+                )
+            );
+
+            // Nothing is charged without a tip
+            let priority = PaymentServices::para_id_priority(&ParaId::from(1002));
+            PaymentServices::on_collators_assigned(&ParaId::from(1002), &priority, 0);
+            assert_eq!(Balances::free_balance(tank), 90);
+        });
+}
+
+#[test]
+fn extra_collators_are_charged_when_collators_are_assigned() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            let para_id = ParaId::from(1001);
+            let tank = PaymentServices::parachain_tank(para_id);
+            assert_ok!(PaymentServices::set_collator_assignment_tip(
+                RuntimeOrigin::signed(1001),
+                para_id,
+                Some(10),
+            ));
+            Balances::make_free_balance_be(&tank, 100);
+
+            // The tank pays the tip first, then 20 for each extra collator
+            assert!(PaymentServices::can_pay_extra_collators(&para_id, 4));
+            assert!(!PaymentServices::can_pay_extra_collators(&para_id, 5));
+
+            let priority = PaymentServices::para_id_priority(&para_id);
+            PaymentServices::on_collators_assigned(&para_id, &priority, 2);
+
+            assert_eq!(Balances::free_balance(tank), 50);
+            assert_eq!(
+                events().last(),
+                Some(&pallet_services_payment::Event::ExtraCollatorsCharged {
+                    para_id,
+                    extra_collators: 2,
+                    fee: 40,
+                })
+            );
+        });
+}
@@ -55,6 +55,8 @@ pub trait WeightInfo {
 	fn purchase_credits() -> Weight;
 	fn set_credits() -> Weight;
 	fn set_given_free_credits() -> Weight;
+	fn set_collator_assignment_tip() -> Weight;
+	fn withdraw_from_tank() -> Weight;
 }
 
 /// Weights for pallet_services_payment using the Substrate node and recommended hardware.
@@ -93,6 +95,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(2_691_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:0)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::CollatorAssignmentTip` (r:0 w:1)
+	/// Proof: `ServicesPayment::CollatorAssignmentTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	fn set_collator_assignment_tip() -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(10_215_000, 3661)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:0)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_from_tank() -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(45_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -130,4 +152,24 @@ impl WeightInfo for () {
 		Weight::from_parts(2_691_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:0)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `ServicesPayment::CollatorAssignmentTip` (r:0 w:1)
+	/// Proof: `ServicesPayment::CollatorAssignmentTip` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	fn set_collator_assignment_tip() -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(10_215_000, 3661)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Registrar::RegistrarDeposit` (r:1 w:0)
+	/// Proof: `Registrar::RegistrarDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn withdraw_from_tank() -> Weight {
+		// Placeholder written by hand, not measured: regenerate with the benchmark CLI.
+		Weight::from_parts(45_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
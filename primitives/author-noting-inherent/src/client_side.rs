@@ -15,7 +15,7 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::OwnParachainInherentData,
+    crate::{para_id_current_code_hash, OwnParachainInherentData},
     cumulus_primitives_core::ParaId,
     cumulus_relay_chain_interface::{PHash, RelayChainInterface},
     dp_core::well_known_keys::para_id_head,
@@ -31,7 +31,7 @@ async fn collect_relay_storage_proof(
 ) -> Option<sp_state_machine::StorageProof> {
     let relevant_keys = para_ids
         .iter()
-        .map(|para_id| para_id_head(*para_id))
+        .flat_map(|para_id| [para_id_head(*para_id), para_id_current_code_hash(*para_id)])
         .collect();
 
     relay_chain_interface
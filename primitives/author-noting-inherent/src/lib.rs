@@ -41,9 +41,11 @@ pub use mock::*;
 mod tests;
 
 use {
+    cumulus_primitives_core::ParaId,
     parity_scale_codec::{Decode, Encode},
     scale_info::TypeInfo,
     sp_inherents::InherentIdentifier,
+    sp_std::vec::Vec,
 };
 
 #[derive(Encode, Decode, sp_core::RuntimeDebug, Clone, PartialEq, TypeInfo)]
@@ -53,3 +55,15 @@ pub struct OwnParachainInherentData {
 
 // Identifier of the author-noting inherent
 pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"auno1337";
+
+/// Relay chain storage key of the hash of the current code of a para, `Paras::CurrentCodeHash`
+pub fn para_id_current_code_hash(para_id: ParaId) -> Vec<u8> {
+    let encoded_para_id = para_id.encode();
+    [
+        &sp_io::hashing::twox_128(b"Paras")[..],
+        &sp_io::hashing::twox_128(b"CurrentCodeHash")[..],
+        &sp_io::hashing::twox_64(&encoded_para_id)[..],
+        &encoded_para_id[..],
+    ]
+    .concat()
+}
@@ -1,12 +1,12 @@
 use super::*;
 use frame_support::{pallet_prelude::Get, BoundedVec};
 pub type IssuanceYear = u16;
-use frame_support::pallet_prelude::DispatchResult;
+use frame_support::{pallet_prelude::DispatchResult, Parameter};
 use sp_std::{fmt::Debug, vec::Vec};
 
 /// The possible values for Registry Names
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum RegistryName {
 	BioCarbon,
 	Verra,
@@ -18,7 +18,7 @@ pub enum RegistryName {
 /// Data to represent the data of the project as recoreded by the respective Registry
 /// This might differ from the project owner's name/description and hence important to store
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegistryDetails<StringType> {
 	/// The name of registry the project belongs to
 	pub reg_name: RegistryName,
@@ -33,7 +33,7 @@ pub struct RegistryDetails<StringType> {
 /// The possible values for SDG's addressed by a project
 /// Full list here : https://sdgs.un.org/
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum SdgType {
 	NoPoverty,
 	ZeroHunger,
@@ -56,7 +56,7 @@ pub enum SdgType {
 
 /// The details of SDGs that the project addresses
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct SDGDetails<StringType> {
 	/// The `SdgType` that the project solves
 	pub sdg_type: SdgType,
@@ -69,7 +69,7 @@ pub struct SDGDetails<StringType> {
 /// Projects can have rolyalties attached to the tokens, these royalties
 /// are paid out when the token is purchased
 #[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Royalty<AccountId> {
 	/// The account_id of the royalty recipeint
 	pub account_id: AccountId,
@@ -100,7 +100,7 @@ pub struct Royalty<AccountId> {
 ///         retired : 100
 ///     }
 #[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Batch<StringType, Balance> {
 	/// Descriptive name for this batch of credits
 	pub name: StringType,
@@ -123,7 +123,7 @@ pub struct Batch<StringType, Balance> {
 
 /// The details of a retired batch of VCU
 #[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, Default, Debug, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct BatchRetireData<StringType, Balance> {
 	/// Name of the batch
 	pub name: StringType,
@@ -137,7 +137,14 @@ pub struct BatchRetireData<StringType, Balance> {
 
 /// Representation of a group of credits. Groups are collections of batches of credits
 #[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, Default, Debug, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+	feature = "std",
+	serde(bound(
+		serialize = "StringType: serde::Serialize, AssetId: serde::Serialize, Balance: serde::Serialize, Batch: serde::Serialize",
+		deserialize = "StringType: serde::Deserialize<'de>, AssetId: serde::Deserialize<'de>, Balance: serde::Deserialize<'de>, Batch: serde::Deserialize<'de>"
+	))
+)]
 pub struct BatchGroup<StringType, AssetId, Balance, Batch, MaxBatches: Get<u32>> {
 	/// Descriptive name for this batch of credits
 	pub name: StringType,
@@ -184,6 +191,13 @@ pub trait CarbonCreditsValidator {
 	/// Returns ProjectId and GroupId if the given AssetId represents a CarbonCredit Project
 	fn get_project_details(asset_id: &Self::AssetId) -> Option<(Self::ProjectId, Self::GroupId)>;
 
+	/// Returns the royalty recipients configured for the given ProjectId
+	fn get_royalties(project_id: &Self::ProjectId) -> Vec<Royalty<Self::Address>>;
+
+	/// Sets the royalty recipients of the given ProjectId, used to benchmark royalty payouts
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_royalties(_project_id: &Self::ProjectId, _royalties: Vec<Royalty<Self::Address>>) {}
+
 	/// Retires credits with given details
 	fn retire_credits(
 		sender: Self::Address,
@@ -194,10 +208,40 @@ pub trait CarbonCreditsValidator {
 	) -> DispatchResult;
 }
 
+/// Trait to list carbon credits held by an escrow account for sale on a marketplace
+pub trait MarketplaceListing<AccountId, AssetId, AssetBalance> {
+	/// Price type used by the marketplace
+	type Price: Parameter + MaxEncodedLen;
+
+	/// Moves `units` of `asset_id` out of `escrow` and lists them for sale at `price_per_unit`,
+	/// the listing is owned by `owner` and any unsold units are returned to `owner` on cancellation
+	fn list_from_escrow(
+		escrow: &AccountId,
+		owner: &AccountId,
+		asset_id: AssetId,
+		units: AssetBalance,
+		price_per_unit: Self::Price,
+	) -> DispatchResult;
+}
+
+impl<AccountId, AssetId, AssetBalance> MarketplaceListing<AccountId, AssetId, AssetBalance> for () {
+	type Price = Balance;
+
+	fn list_from_escrow(
+		_escrow: &AccountId,
+		_owner: &AccountId,
+		_asset_id: AssetId,
+		_units: AssetBalance,
+		_price_per_unit: Self::Price,
+	) -> DispatchResult {
+		Err(sp_runtime::DispatchError::Other("Marketplace not supported"))
+	}
+}
+
 /// Represents different types of projects related to environmental impact assessment.
 #[allow(non_camel_case_types)]
 #[derive(Clone, Encode, Decode, Eq, PartialEq, TypeInfo, Default, Debug, MaxEncodedLen)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProjectType {
 	/// Projects related to agriculture, forestry, and other land use.
 	#[default]
@@ -1,6 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight};
+use frame_support::{
+	traits::{Contains, Get},
+	weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
+};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
@@ -8,7 +11,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, IdentifyAccount, Verify},
 	MultiAddress, MultiSignature, Perbill, Percent, RuntimeDebug,
 };
-use sp_std::convert::TryFrom;
+use sp_std::{convert::TryFrom, marker::PhantomData};
 
 mod carbon_credits;
 pub use carbon_credits::*;
@@ -148,6 +151,8 @@ impl TryFrom<u8> for CurrencyId {
 	Decode,
 	Eq,
 	PartialEq,
+	PartialOrd,
+	Ord,
 	RuntimeDebug,
 	Default,
 	MaxEncodedLen,
@@ -166,3 +171,26 @@ pub enum UserLevel {
 	// KYC approved as accredited investor
 	KYCLevel4,
 }
+
+/// Trait to read the KYC level of an account, levels are ordered so that `KYCLevel4` is the
+/// highest verification tier
+pub trait KycLevelProvider<AccountId> {
+	/// Returns the KYC level of `who`, or None if the account has not completed KYC
+	fn kyc_level(who: &AccountId) -> Option<UserLevel>;
+
+	/// Returns true if `who` has completed KYC at `min_level` or higher
+	fn has_kyc_level(who: &AccountId, min_level: &UserLevel) -> bool {
+		Self::kyc_level(who).map_or(false, |level| &level >= min_level)
+	}
+}
+
+/// Adapter to use a `Contains` membership as a `KycLevelProvider`, every member is reported at
+/// `Level`
+pub struct KycLevelOfMembers<Members, Level>(PhantomData<(Members, Level)>);
+impl<AccountId, Members: Contains<AccountId>, Level: Get<UserLevel>> KycLevelProvider<AccountId>
+	for KycLevelOfMembers<Members, Level>
+{
+	fn kyc_level(who: &AccountId) -> Option<UserLevel> {
+		Members::contains(who).then(Level::get)
+	}
+}
@@ -20,12 +20,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use cumulus_primitives_core::{
-    relay_chain::{BlockNumber, Slot},
+    relay_chain::{BlockNumber, Slot, ValidationCodeHash},
     ParaId,
 };
 use {
     frame_support::{
         pallet_prelude::{DispatchResultWithPostInfo, Get, Weight},
+        sp_runtime::Perbill,
         BoundedVec,
     },
     sp_std::vec::Vec,
@@ -52,6 +53,21 @@ impl<AccountId> AuthorNotingHook<AccountId> for Tuple {
     }
 }
 
+/// The hook to react to the code of container chains, as stored in the relay chain.
+pub trait ContainerChainCodeHook {
+    /// This hook is called partway through the `set_latest_author_data` inherent in author-noting,
+    /// with the hash of the current `:code` of the container chain.
+    ///
+    /// The hook should never panic and is required to return the weight consumed.
+    fn on_container_code_noted(para_id: ParaId, code_hash: &ValidationCodeHash) -> Weight;
+}
+
+impl ContainerChainCodeHook for () {
+    fn on_container_code_noted(_para_id: ParaId, _code_hash: &ValidationCodeHash) -> Weight {
+        Weight::zero()
+    }
+}
+
 pub trait DistributeRewards<AccountId, Imbalance> {
     fn distribute_rewards(rewarded: AccountId, amount: Imbalance) -> DispatchResultWithPostInfo;
 }
@@ -62,6 +78,38 @@ impl<AccountId, Imbalance> DistributeRewards<AccountId, Imbalance> for () {
     }
 }
 
+/// Punish a collator for an offence, such as missing its container chain slots.
+pub trait SlashCollator<AccountId> {
+    /// Slash `fraction` of the stake backing `collator`, including the stake of its delegators.
+    ///
+    /// The hook should never panic and is required to return the weight consumed.
+    fn slash_collator(collator: &AccountId, fraction: Perbill) -> Weight;
+}
+
+impl<AccountId> SlashCollator<AccountId> for () {
+    fn slash_collator(_collator: &AccountId, _fraction: Perbill) -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Handle collators that repeatedly failed to produce the container chain blocks they were
+/// assigned to.
+pub trait HandleRepeatOffender<AccountId> {
+    /// Called when `collator` underperformed during `offences` consecutive sessions.
+    ///
+    /// The hook should never panic and is required to return the weight consumed.
+    fn handle_repeat_offender(collator: &AccountId, offences: u32) -> Weight;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<AccountId> HandleRepeatOffender<AccountId> for Tuple {
+    fn handle_repeat_offender(c: &AccountId, o: u32) -> Weight {
+        let mut weight: Weight = Default::default();
+        for_tuples!( #( weight.saturating_accrue(Tuple::handle_repeat_offender(c, o)); )* );
+        weight
+    }
+}
+
 /// Get the current list of container chains parachain ids.
 pub trait GetCurrentContainerChains {
     type MaxContainerChains: Get<u32>;
@@ -87,12 +135,20 @@ pub trait GetContainerChainAuthor<AccountId> {
     fn set_authors_for_para_id(para_id: ParaId, authors: Vec<AccountId>);
 }
 
+/// Returns the author of the latest block noted for a parachain id, if any.
+pub trait GetLatestAuthor<AccountId> {
+    fn latest_author(para_id: ParaId) -> Option<AccountId>;
+}
+
 /// Returns the host configuration composed of the amount of collators assigned
 /// to the orchestrator chain, and how many collators are assigned per container chain.
 pub trait GetHostConfiguration<SessionIndex> {
     fn min_collators_for_orchestrator(session_index: SessionIndex) -> u32;
     fn max_collators_for_orchestrator(session_index: SessionIndex) -> u32;
     fn collators_per_container(session_index: SessionIndex) -> u32;
+    /// Number of collators assigned to `para_id`, which may have requested a different number
+    /// than `collators_per_container`.
+    fn collators_for_container(session_index: SessionIndex, para_id: ParaId) -> u32;
 }
 
 /// Returns current session index.
@@ -125,3 +181,49 @@ pub trait RemoveParaIdsWithNoCredits {
     #[cfg(feature = "runtime-benchmarks")]
     fn make_valid_para_ids(para_ids: &[ParaId]);
 }
+
+/// Helper trait for pallet_collator_assignment to decide which container chains get collators
+/// first when there are not enough collators for all of them, and to charge them for the
+/// collators they get
+pub trait CollatorAssignmentPriority {
+    /// Priority of a container chain, container chains with a greater priority get collators first.
+    type Priority: Ord;
+
+    /// Priority of `para_id` in the collator assignment of the next session.
+    fn para_id_priority(para_id: &ParaId) -> Self::Priority;
+
+    /// Whether `para_id` can pay for `num_extra_collators` collators above
+    /// `collators_per_container` during the next session. A container chain that cannot pay for
+    /// them is assigned `collators_per_container` collators.
+    fn can_pay_extra_collators(para_id: &ParaId, num_extra_collators: u32) -> bool;
+
+    /// Called for every container chain that was assigned collators for the next session, with
+    /// the priority it was given and the number of collators it was assigned above
+    /// `collators_per_container`. Used for example to charge the tip the container chain bid.
+    fn on_collators_assigned(para_id: &ParaId, priority: &Self::Priority, num_extra_collators: u32);
+
+    /// Upper bound of the weight of `para_id_priority`, `can_pay_extra_collators` and
+    /// `on_collators_assigned` for one container chain.
+    fn weight_per_para_id() -> Weight;
+}
+
+impl CollatorAssignmentPriority for () {
+    type Priority = ();
+
+    fn para_id_priority(_para_id: &ParaId) -> Self::Priority {}
+
+    fn can_pay_extra_collators(_para_id: &ParaId, _num_extra_collators: u32) -> bool {
+        true
+    }
+
+    fn on_collators_assigned(
+        _para_id: &ParaId,
+        _priority: &Self::Priority,
+        _num_extra_collators: u32,
+    ) {
+    }
+
+    fn weight_per_para_id() -> Weight {
+        Weight::zero()
+    }
+}
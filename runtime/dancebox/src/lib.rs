@@ -69,6 +69,7 @@ use {
     },
     nimbus_primitives::NimbusId,
     pallet_collator_assignment::{GetRandomnessForNextBlock, RotateCollatorsEveryNSessions},
+    pallet_collator_performance::{PerformanceRewardDistribution, SlashRepeatOffenders},
     pallet_invulnerables::InvulnerableRewardDistribution,
     pallet_pooled_staking::traits::{IsCandidateEligible, Timer},
     pallet_registrar::RegistrarHooks,
@@ -538,6 +539,8 @@ impl pallet_initializer::ApplyNewSession<Runtime> for OwnApplySession {
         Registrar::initializer_on_new_session(&session_index);
         // Next: AuthorityMapping
         AuthorityMapping::initializer_on_new_session(&session_index, &all_validators);
+        // Next: CollatorPerformance, judging the collators of the session that ended
+        CollatorPerformance::initializer_on_new_session(&session_index);
 
         let next_collators = queued.iter().map(|(k, _)| k.clone()).collect();
 
@@ -750,6 +753,7 @@ impl pallet_collator_assignment::Config for Runtime {
     type GetRandomnessForNextBlock = BabeGetRandomnessForNextBlock;
     type RemoveInvulnerables = RemoveInvulnerablesImpl;
     type RemoveParaIdsWithNoCredits = RemoveParaIdsWithNoCreditsImpl;
+    type CollatorAssignmentPriority = ServicesPayment;
     type WeightInfo = pallet_collator_assignment::weights::SubstrateWeight<Runtime>;
 }
 
@@ -770,6 +774,10 @@ impl ProvideBlockProductionCost<Runtime> for BlockProductionCost<Runtime> {
 parameter_types! {
     // 60 days worth of blocks
     pub const MaxCreditsStored: BlockNumber = 60 * DAYS;
+    // Each extra collator costs as much as the blocks of a session
+    pub ExtraCollatorFee: u128 = FIXED_BLOCK_PRODUCTION_COST * u128::from(Period::get());
+    // 1 day worth of blocks
+    pub const CreditsPriorityBucket: BlockNumber = DAYS;
 }
 
 impl pallet_services_payment::Config for Runtime {
@@ -782,6 +790,12 @@ impl pallet_services_payment::Config for Runtime {
     type ProvideBlockProductionCost = BlockProductionCost<Runtime>;
     /// The maximum number of credits that can be accumulated
     type MaxCreditsStored = MaxCreditsStored;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
+    /// The fee paid every session for each collator above `collators_per_container`
+    type ExtraCollatorFee = ExtraCollatorFee;
+    /// Credits are compared in buckets of a day of blocks when assigning collators
+    type CreditsPriorityBucket = CreditsPriorityBucket;
     type WeightInfo = pallet_services_payment::weights::SubstrateWeight<Runtime>;
 }
 
@@ -801,7 +815,9 @@ impl pallet_author_noting::Config for Runtime {
     type SelfParaId = parachain_info::Pallet<Runtime>;
     type ContainerChainAuthor = CollatorAssignment;
     type RelayChainStateProvider = cumulus_pallet_parachain_system::RelaychainDataProvider<Self>;
-    type AuthorNotingHook = (InflationRewards, ServicesPayment);
+    // CollatorPerformance goes first so the rewards account for the block being noted
+    type AuthorNotingHook = (CollatorPerformance, InflationRewards, ServicesPayment);
+    type ContainerChainCodeHook = Registrar;
     type WeightInfo = pallet_author_noting::weights::SubstrateWeight<Runtime>;
 }
 
@@ -814,10 +830,20 @@ parameter_types! {
     pub const ExecutiveBody: BodyId = BodyId::Executive;
 }
 
+/// Repeat offenders keep their invulnerable status rather than leaving less invulnerables than
+/// the minimum number of orchestrator collators
+pub struct MinOrchestratorCollators;
+impl Get<u32> for MinOrchestratorCollators {
+    fn get() -> u32 {
+        Configuration::config().min_orchestrator_collators
+    }
+}
+
 impl pallet_invulnerables::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type UpdateOrigin = EnsureRoot<AccountId>;
     type MaxInvulnerables = MaxInvulnerables;
+    type MinInvulnerables = MinOrchestratorCollators;
     type CollatorId = <Self as frame_system::Config>::AccountId;
     type CollatorIdOf = pallet_invulnerables::IdentityCollator;
     type CollatorRegistration = Session;
@@ -843,10 +869,13 @@ impl tp_traits::GetSessionIndex<u32> for CurrentSessionIndexGetter {
 }
 
 impl pallet_configuration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type SessionDelay = ConstU32<2>;
     type SessionIndex = u32;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type AuthorityId = NimbusId;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
     type WeightInfo = pallet_configuration::weights::SubstrateWeight<Runtime>;
 }
 
@@ -877,6 +906,8 @@ impl RegistrarHooks for DanceboxRegistrarHooks {
         }
         // Remove bootnodes from pallet_data_preservers
         DataPreservers::para_deregistered(para_id);
+        // Remove the request of collators from pallet_configuration
+        Configuration::para_deregistered(para_id);
 
         Weight::default()
     }
@@ -886,6 +917,11 @@ impl RegistrarHooks for DanceboxRegistrarHooks {
         DataPreservers::check_valid_for_collating(para_id)
     }
 
+    fn container_chain_produced_blocks(para_id: ParaId) -> bool {
+        // The author of the latest container chain block is noted once the chain produces blocks
+        AuthorNoting::latest_author(para_id).is_some()
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmarks_ensure_valid_for_collating(para_id: ParaId) {
         use sp_runtime::BoundedVec;
@@ -904,11 +940,14 @@ impl RegistrarHooks for DanceboxRegistrarHooks {
 
 parameter_types! {
     pub const DepositAmount: Balance = 100 * UNIT;
+    pub const DataDepositPerByte: Balance = MICROUNIT;
     pub const MaxLengthTokenSymbol: u32 = 255;
 }
 impl pallet_registrar::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RegistrarOrigin = EnsureRoot<AccountId>;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
     type MaxLengthParaIds = MaxLengthParaIds;
     type MaxGenesisDataSize = MaxEncodedGenesisDataSize;
     type MaxBootNodes = MaxBootNodes;
@@ -919,6 +958,7 @@ impl pallet_registrar::Config for Runtime {
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type Currency = Balances;
     type DepositAmount = DepositAmount;
+    type DataDepositPerByte = DataDepositPerByte;
     type RegistrarHooks = DanceboxRegistrarHooks;
     type WeightInfo = pallet_registrar::weights::SubstrateWeight<Runtime>;
 }
@@ -1275,6 +1315,8 @@ impl pallet_pooled_staking::Config for Runtime {
     type LeavingRequestTimer = SessionTimer<StakingSessionDelay>;
     type EligibleCandidatesBufferSize = ConstU32<100>;
     type EligibleCandidatesFilter = CandidateHasRegisteredKeys;
+    // Slashed funds are burned
+    type OnSlash = ();
     type WeightInfo = pallet_pooled_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1323,10 +1365,47 @@ impl pallet_inflation_rewards::Config for Runtime {
     type InflationRate = InflationRate;
     type OnUnbalanced = OnUnbalancedInflation;
     type PendingRewardsAccount = PendingRewardsAccount;
-    type StakingRewardsDistributor = InvulnerableRewardDistribution<Self, Balances, PooledStaking>;
+    type StakingRewardsDistributor = PerformanceRewardDistribution<
+        Self,
+        Balances,
+        InvulnerableRewardDistribution<Self, Balances, PooledStaking>,
+        OnUnbalancedInflation,
+    >;
     type RewardsPortion = RewardsPortion;
 }
 
+parameter_types! {
+    pub const CollatorMinPerformance: Perbill = Perbill::from_percent(50);
+    pub const RepeatOffenderSlash: Perbill = Perbill::from_percent(1);
+}
+
+pub struct CurrentSlotGetter;
+impl Get<Slot> for CurrentSlotGetter {
+    fn get() -> Slot {
+        let slot = <Runtime as pallet_author_inherent::Config>::SlotBeacon::slot() as u64;
+        Slot::from(slot)
+    }
+}
+
+impl pallet_collator_performance::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type ContainerChains = Registrar;
+    type ContainerChainAuthor = CollatorAssignment;
+    type LatestAuthor = AuthorNoting;
+    type CurrentSlot = CurrentSlotGetter;
+    type MinPerformance = CollatorMinPerformance;
+    type MinExpectedBlocks = ConstU32<10>;
+    type MaxOffences = ConstU32<3>;
+    // Repeat offenders lose their invulnerable status, staked candidates are slashed
+    type RepeatOffenderHandler = (
+        Invulnerables,
+        SlashRepeatOffenders<PooledStaking, RepeatOffenderSlash>,
+    );
+    type HistoryDepth = ConstU32<10>;
+    type MaxCollators = ConstU32<1000>;
+}
+
 impl pallet_tx_pause::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RuntimeCall = RuntimeCall;
@@ -1407,6 +1486,8 @@ construct_runtime!(
         PooledStaking: pallet_pooled_staking = 34,
         // InflationRewards must be after Session and AuthorInherent
         InflationRewards: pallet_inflation_rewards = 35,
+        // CollatorPerformance must be after Session and AuthorInherent
+        CollatorPerformance: pallet_collator_performance = 36,
 
         //XCM
         XcmpQueue: cumulus_pallet_xcmp_queue::{Pallet, Call, Storage, Event<T>} = 50,
@@ -1741,6 +1822,33 @@ impl_runtime_apis! {
                 assigned_collators.container_chains.get(&para_id).cloned()
             }
         }
+
+        /// Return the container chains that will be considered in the next collator assignment,
+        /// from the highest to the lowest priority.
+        fn container_chains_by_priority() -> Vec<ParaId> {
+            // The next assignment is computed at the start of the next session, for the session
+            // after it
+            let target_session_index = Session::current_index() + 2;
+
+            CollatorAssignment::container_chains_by_priority(target_session_index)
+        }
+    }
+
+    impl pallet_collator_performance_runtime_api::CollatorPerformanceApi<Block, AccountId, u32, pallet_collator_performance::BlockProduction> for Runtime {
+        /// Return the expected and produced blocks of the given `AccountId` in `session`.
+        fn collator_performance(session: u32, account: AccountId) -> Option<pallet_collator_performance::BlockProduction> {
+            CollatorPerformance::performance(session, account)
+        }
+
+        /// Return the expected and produced blocks of all the collators in `session`.
+        fn session_performance(session: u32) -> Vec<(AccountId, pallet_collator_performance::BlockProduction)> {
+            CollatorPerformance::session_performance(session)
+        }
+
+        /// Return the number of consecutive sessions in which the given `AccountId` underperformed.
+        fn consecutive_offences(account: AccountId) -> u32 {
+            CollatorPerformance::consecutive_offences(account)
+        }
     }
 
     impl pallet_registrar_runtime_api::RegistrarApi<Block, ParaId, MaxLengthTokenSymbol> for Runtime {
@@ -1772,6 +1880,11 @@ impl_runtime_apis! {
 
             bounded_vec.into_iter().map(|x| x.into()).collect()
         }
+
+        /// Fetch the code announced for this para id
+        fn pending_code(para_id: ParaId) -> Option<Vec<u8>> {
+            Registrar::para_pending_code(para_id)
+        }
     }
 
     impl pallet_author_noting_runtime_api::AuthorNotingApi<Block, AccountId, BlockNumber, ParaId> for Runtime
@@ -53,6 +53,22 @@ struct HostConfigurationV0 {
     pub collators_per_container: u32,
 }
 
+#[derive(
+    Clone,
+    parity_scale_codec::Encode,
+    parity_scale_codec::Decode,
+    PartialEq,
+    sp_core::RuntimeDebug,
+    scale_info::TypeInfo,
+)]
+struct HostConfigurationV1 {
+    pub max_collators: u32,
+    pub min_orchestrator_collators: u32,
+    pub max_orchestrator_collators: u32,
+    pub collators_per_container: u32,
+    pub full_rotation_period: u32,
+}
+
 pub struct CollatorSelectionStorageValuePrefix;
 impl frame_support::traits::StorageInstance for CollatorSelectionStorageValuePrefix {
     const STORAGE_PREFIX: &'static str = "Invulnerables";
@@ -314,6 +330,89 @@ where
     }
 }
 
+pub struct MigrateConfigurationCollatorsPerContainerBounds<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateConfigurationCollatorsPerContainerBounds<T>
+where
+    T: pallet_configuration::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateConfigurationCollatorsPerContainerBounds"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        log::info!(target: LOG_TARGET, "migrate");
+
+        const CONFIGURATION_ACTIVE_CONFIG_KEY: &[u8] =
+            &hex_literal::hex!("06de3d8a54d27e44a9d5ce189618f22db4b49d95320d9021994c850f25b8e385");
+        const CONFIGURATION_PENDING_CONFIGS_KEY: &[u8] =
+            &hex_literal::hex!("06de3d8a54d27e44a9d5ce189618f22d53b4123b2e186e07fb7bad5dda5f55c0");
+
+        // Container chains can request between 1 and `collators_per_container` collators until
+        // the bounds are changed
+        let migrate_config = |old_config: HostConfigurationV1| HostConfiguration {
+            max_collators: old_config.max_collators,
+            min_orchestrator_collators: old_config.min_orchestrator_collators,
+            max_orchestrator_collators: old_config.max_orchestrator_collators,
+            collators_per_container: old_config.collators_per_container,
+            min_collators_per_container: 1,
+            max_collators_per_container: old_config.collators_per_container.max(1),
+            full_rotation_period: old_config.full_rotation_period,
+        };
+
+        // Modify active config
+        let old_config: HostConfigurationV1 =
+            frame_support::storage::unhashed::get(CONFIGURATION_ACTIVE_CONFIG_KEY)
+                .expect("configuration.activeConfig should have value");
+        frame_support::storage::unhashed::put(
+            CONFIGURATION_ACTIVE_CONFIG_KEY,
+            &migrate_config(old_config),
+        );
+
+        // Modify pending configs, if any
+        let old_pending_configs: Vec<(u32, HostConfigurationV1)> =
+            frame_support::storage::unhashed::get(CONFIGURATION_PENDING_CONFIGS_KEY)
+                .unwrap_or_default();
+        let new_pending_configs: Vec<(u32, HostConfiguration)> = old_pending_configs
+            .into_iter()
+            .map(|(session_index, old_config)| (session_index, migrate_config(old_config)))
+            .collect();
+
+        if !new_pending_configs.is_empty() {
+            frame_support::storage::unhashed::put(
+                CONFIGURATION_PENDING_CONFIGS_KEY,
+                &new_pending_configs,
+            );
+        }
+
+        <T as pallet_configuration::Config>::WeightInfo::set_config_with_u32()
+    }
+
+    /// Run a standard pre-runtime test. This works the same way as in a normal runtime upgrade.
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade(&self) -> Result<Vec<u8>, sp_runtime::DispatchError> {
+        const CONFIGURATION_ACTIVE_CONFIG_KEY: &[u8] =
+            &hex_literal::hex!("06de3d8a54d27e44a9d5ce189618f22db4b49d95320d9021994c850f25b8e385");
+
+        let old_config_bytes =
+            frame_support::storage::unhashed::get_raw(CONFIGURATION_ACTIVE_CONFIG_KEY)
+                .expect("configuration.activeConfig should have value");
+        assert_eq!(old_config_bytes.len(), 20);
+
+        use parity_scale_codec::Encode;
+        Ok((old_config_bytes).encode())
+    }
+
+    /// Run a standard post-runtime test. This works the same way as in a normal runtime upgrade.
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(&self, _old_config_bytes: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+        let config = crate::Configuration::config();
+        assert_eq!(config.min_collators_per_container, 1);
+        assert!(config.max_collators_per_container >= config.collators_per_container);
+
+        Ok(())
+    }
+}
+
 pub struct PolkadotXcmMigration<T>(pub PhantomData<T>);
 impl<T> Migration for PolkadotXcmMigration<T>
 where
@@ -543,6 +642,36 @@ where
     }
 }
 
+pub struct MigrateRegistrarParaGenesisDataSize<T>(pub PhantomData<T>);
+impl<T> Migration for MigrateRegistrarParaGenesisDataSize<T>
+where
+    T: pallet_registrar::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_MigrateRegistrarParaGenesisDataSize"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        use parity_scale_codec::Encode;
+
+        // Store the size of the genesis data of the para ids registered before
+        // `ParaGenesisDataSize` existed, the deposits are adjusted using that size
+        let mut len = 0;
+        for (para_id, genesis_data) in pallet_registrar::ParaGenesisData::<T>::iter() {
+            len += 1;
+            pallet_registrar::ParaGenesisDataSize::<T>::insert(
+                para_id,
+                genesis_data.encoded_size() as u32,
+            );
+        }
+
+        let db_weights = T::DbWeight::get();
+        let reads = len;
+        let writes = len;
+        db_weights.reads_writes(reads, writes)
+    }
+}
+
 pub struct DanceboxMigrations<Runtime>(PhantomData<Runtime>);
 
 impl<Runtime> GetMigrations for DanceboxMigrations<Runtime>
@@ -553,6 +682,7 @@ where
     Runtime: pallet_configuration::Config,
     Runtime: pallet_xcm::Config,
     Runtime: cumulus_pallet_xcmp_queue::Config,
+    Runtime: pallet_registrar::Config,
     <Runtime as pallet_balances::Config>::RuntimeHoldReason:
         From<pallet_pooled_staking::HoldReason>,
 {
@@ -568,6 +698,10 @@ where
 
         let migrate_hold_reason_runtime_enum =
             MigrateHoldReasonRuntimeEnum::<Runtime>(Default::default());
+        let migrate_config_collators_per_container_bounds =
+            MigrateConfigurationCollatorsPerContainerBounds::<Runtime>(Default::default());
+        let migrate_registrar_genesis_data_size =
+            MigrateRegistrarParaGenesisDataSize::<Runtime>(Default::default());
         vec![
             // Applied in runtime 200
             //Box::new(migrate_invulnerables),
@@ -582,6 +716,8 @@ where
             Box::new(migrate_services_payment),
             Box::new(migrate_hold_reason_runtime_enum),
             Box::new(migrate_boot_nodes),
+            Box::new(migrate_config_collators_per_container_bounds),
+            Box::new(migrate_registrar_genesis_data_size),
         ]
     }
 }
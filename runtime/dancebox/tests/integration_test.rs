@@ -106,6 +106,8 @@ fn default_config() -> pallet_configuration::HostConfiguration {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 2,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 24,
     }
 }
@@ -615,6 +617,8 @@ fn test_authors_paras_inserted_a_posteriori_with_collators_already_assigned() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 5,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 24,
         })
         .build()
@@ -3733,6 +3737,8 @@ fn test_pallet_session_limits_num_validators() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 24,
         })
         .build()
@@ -3821,6 +3827,8 @@ fn test_pallet_session_limits_num_validators_from_staking() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 24,
         })
         .build()
@@ -4040,6 +4048,8 @@ fn test_reward_to_staking_candidate() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 24,
         })
         .build()
@@ -4154,6 +4164,8 @@ fn test_reward_to_invulnerable() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 24,
         })
         .build()
@@ -4251,6 +4263,8 @@ fn test_reward_to_invulnerable_with_key_change() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 24,
         })
         .build()
@@ -4323,6 +4337,8 @@ fn test_migration_config_full_rotation_period() {
                 min_orchestrator_collators: 2,
                 max_orchestrator_collators: 5,
                 collators_per_container: 2,
+                min_collators_per_container: 1,
+                max_collators_per_container: 5,
                 full_rotation_period: 0,
             };
             assert_eq!(Configuration::config(), expected_active);
@@ -621,6 +621,7 @@ impl pallet_collator_assignment::Config for Runtime {
     type GetRandomnessForNextBlock = ();
     type RemoveInvulnerables = RemoveInvulnerablesImpl;
     type RemoveParaIdsWithNoCredits = RemoveParaIdsWithNoCreditsImpl;
+    type CollatorAssignmentPriority = ServicesPayment;
     type WeightInfo = pallet_collator_assignment::weights::SubstrateWeight<Runtime>;
 }
 
@@ -641,6 +642,10 @@ impl ProvideBlockProductionCost<Runtime> for BlockProductionCost<Runtime> {
 parameter_types! {
     // 60 days worth of blocks
     pub const MaxCreditsStored: BlockNumber = 60 * DAYS;
+    // Each extra collator costs as much as the blocks of a session
+    pub ExtraCollatorFee: u128 = FIXED_BLOCK_PRODUCTION_COST * u128::from(Period::get());
+    // 1 day worth of blocks
+    pub const CreditsPriorityBucket: BlockNumber = DAYS;
 }
 
 impl pallet_services_payment::Config for Runtime {
@@ -653,6 +658,12 @@ impl pallet_services_payment::Config for Runtime {
     type ProvideBlockProductionCost = BlockProductionCost<Runtime>;
     /// The maximum number of credits that can be accumulated
     type MaxCreditsStored = MaxCreditsStored;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
+    /// The fee paid every session for each collator above `collators_per_container`
+    type ExtraCollatorFee = ExtraCollatorFee;
+    /// Credits are compared in buckets of a day of blocks when assigning collators
+    type CreditsPriorityBucket = CreditsPriorityBucket;
     type WeightInfo = pallet_services_payment::weights::SubstrateWeight<Runtime>;
 }
 
@@ -673,6 +684,7 @@ impl pallet_author_noting::Config for Runtime {
     type ContainerChainAuthor = CollatorAssignment;
     type RelayChainStateProvider = cumulus_pallet_parachain_system::RelaychainDataProvider<Self>;
     type AuthorNotingHook = (InflationRewards, ServicesPayment);
+    type ContainerChainCodeHook = Registrar;
     type WeightInfo = pallet_author_noting::weights::SubstrateWeight<Runtime>;
 }
 
@@ -685,10 +697,20 @@ parameter_types! {
     pub const ExecutiveBody: BodyId = BodyId::Executive;
 }
 
+/// Repeat offenders keep their invulnerable status rather than leaving less invulnerables than
+/// the minimum number of orchestrator collators
+pub struct MinOrchestratorCollators;
+impl Get<u32> for MinOrchestratorCollators {
+    fn get() -> u32 {
+        Configuration::config().min_orchestrator_collators
+    }
+}
+
 impl pallet_invulnerables::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type UpdateOrigin = EnsureRoot<AccountId>;
     type MaxInvulnerables = MaxInvulnerables;
+    type MinInvulnerables = MinOrchestratorCollators;
     type CollatorId = <Self as frame_system::Config>::AccountId;
     type CollatorIdOf = pallet_invulnerables::IdentityCollator;
     type CollatorRegistration = Session;
@@ -714,10 +736,13 @@ impl tp_traits::GetSessionIndex<u32> for CurrentSessionIndexGetter {
 }
 
 impl pallet_configuration::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
     type SessionDelay = ConstU32<2>;
     type SessionIndex = u32;
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type AuthorityId = NimbusId;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
     type WeightInfo = pallet_configuration::weights::SubstrateWeight<Runtime>;
 }
 
@@ -748,6 +773,8 @@ impl RegistrarHooks for FlashboxRegistrarHooks {
         }
         // Remove bootnodes from pallet_data_preservers
         DataPreservers::para_deregistered(para_id);
+        // Remove the request of collators from pallet_configuration
+        Configuration::para_deregistered(para_id);
 
         Weight::default()
     }
@@ -757,6 +784,11 @@ impl RegistrarHooks for FlashboxRegistrarHooks {
         DataPreservers::check_valid_for_collating(para_id)
     }
 
+    fn container_chain_produced_blocks(para_id: ParaId) -> bool {
+        // The author of the latest container chain block is noted once the chain produces blocks
+        AuthorNoting::latest_author(para_id).is_some()
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn benchmarks_ensure_valid_for_collating(para_id: ParaId) {
         use sp_runtime::BoundedVec;
@@ -775,11 +807,14 @@ impl RegistrarHooks for FlashboxRegistrarHooks {
 
 parameter_types! {
     pub const DepositAmount: Balance = 100 * UNIT;
+    pub const DataDepositPerByte: Balance = MICROUNIT;
     pub const MaxLengthTokenSymbol: u32 = 255;
 }
 impl pallet_registrar::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RegistrarOrigin = EnsureRoot<AccountId>;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
     type MaxLengthParaIds = MaxLengthParaIds;
     type MaxGenesisDataSize = MaxEncodedGenesisDataSize;
     type MaxBootNodes = MaxBootNodes;
@@ -790,6 +825,7 @@ impl pallet_registrar::Config for Runtime {
     type CurrentSessionIndex = CurrentSessionIndexGetter;
     type Currency = Balances;
     type DepositAmount = DepositAmount;
+    type DataDepositPerByte = DataDepositPerByte;
     type RegistrarHooks = FlashboxRegistrarHooks;
     type WeightInfo = pallet_registrar::weights::SubstrateWeight<Runtime>;
 }
@@ -1419,6 +1455,16 @@ impl_runtime_apis! {
                 assigned_collators.container_chains.get(&para_id).cloned()
             }
         }
+
+        /// Return the container chains that will be considered in the next collator assignment,
+        /// from the highest to the lowest priority.
+        fn container_chains_by_priority() -> Vec<ParaId> {
+            // The next assignment is computed at the start of the next session, for the session
+            // after it
+            let target_session_index = Session::current_index() + 2;
+
+            CollatorAssignment::container_chains_by_priority(target_session_index)
+        }
     }
 
     impl pallet_registrar_runtime_api::RegistrarApi<Block, ParaId, MaxLengthTokenSymbol> for Runtime {
@@ -1450,6 +1496,11 @@ impl_runtime_apis! {
 
             bounded_vec.into_iter().map(|x| x.into()).collect()
         }
+
+        /// Fetch the code announced for this para id
+        fn pending_code(para_id: ParaId) -> Option<Vec<u8>> {
+            Registrar::para_pending_code(para_id)
+        }
     }
 
     impl pallet_author_noting_runtime_api::AuthorNotingApi<Block, AccountId, BlockNumber, ParaId> for Runtime
@@ -94,6 +94,8 @@ fn default_config() -> pallet_configuration::HostConfiguration {
         min_orchestrator_collators: 2,
         max_orchestrator_collators: 2,
         collators_per_container: 2,
+        min_collators_per_container: 1,
+        max_collators_per_container: 5,
         full_rotation_period: 0,
     }
 }
@@ -603,6 +605,8 @@ fn test_authors_paras_inserted_a_posteriori_with_collators_already_assigned() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 5,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 0,
         })
         .build()
@@ -2377,6 +2381,8 @@ fn test_reward_to_invulnerable() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 0,
         })
         .build()
@@ -2437,6 +2443,8 @@ fn test_reward_to_invulnerable_with_key_change() {
             min_orchestrator_collators: 2,
             max_orchestrator_collators: 2,
             collators_per_container: 2,
+            min_collators_per_container: 1,
+            max_collators_per_container: 5,
             full_rotation_period: 0,
         })
         .build()